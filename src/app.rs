@@ -1,33 +1,53 @@
 use std::cmp::max;
+use std::collections::HashMap;
 use std::time::Instant;
 
 use unicode_segmentation::GraphemeCursor;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
+use serde::{Deserialize, Serialize};
+
 use typed_builder::*;
 
 use data_farmer::*;
-use data_harvester::{processes, temperature};
+use data_harvester::{processes, temperature, HarvestError, HarvestSource};
+use dialog::{Dialog, DialogAction};
+use layout::{get_layout_widget, BottomLayout, WidgetDirection};
+use toast::ToastState;
 
-use crate::{canvas, constants, utils::error::Result};
+use crate::{
+    canvas, constants,
+    utils::{clipboard, error::Result, gen_util::get_exact_byte_values},
+};
 
+pub mod alerts;
 pub mod data_farmer;
 pub mod data_harvester;
+pub mod dialog;
+pub mod layout;
 mod process_killer;
+pub mod toast;
 
 const MAX_SEARCH_LENGTH: usize = 200;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WidgetPosition {
     Cpu,
     CpuLegend,
+    Gpu,
     Mem,
     Disk,
     Temp,
     Network,
     NetworkLegend,
+    Psi,
+    Ping,
+    DiskIo,
+    TempGraph,
+    Battery,
     Process,
     ProcessSearch,
+    Sockets,
     BasicCpu,
     BasicMem,
     BasicNet,
@@ -40,6 +60,7 @@ impl WidgetPosition {
             | WidgetPosition::Process
             | WidgetPosition::ProcessSearch
             | WidgetPosition::Temp
+            | WidgetPosition::Sockets
             | WidgetPosition::CpuLegend => true,
             _ => false,
         }
@@ -47,7 +68,15 @@ impl WidgetPosition {
 
     pub fn is_widget_graph(self) -> bool {
         match self {
-            WidgetPosition::Cpu | WidgetPosition::Network | WidgetPosition::Mem => true,
+            WidgetPosition::Cpu
+            | WidgetPosition::Network
+            | WidgetPosition::Mem
+            | WidgetPosition::Psi
+            | WidgetPosition::Ping
+            | WidgetPosition::DiskIo
+            | WidgetPosition::TempGraph
+            | WidgetPosition::Gpu
+            | WidgetPosition::Battery => true,
             _ => false,
         }
     }
@@ -56,11 +85,18 @@ impl WidgetPosition {
         use WidgetPosition::*;
         match self {
             Cpu | BasicCpu | CpuLegend => "CPU",
+            Gpu => "GPU",
             Mem | BasicMem => "Memory",
             Disk => "Disks",
             Temp => "Temperature",
             Network | BasicNet | NetworkLegend => "Network",
+            Psi => "Pressure",
+            Ping => "Ping",
+            DiskIo => "Disk I/O",
+            TempGraph => "Temperature History",
+            Battery => "Battery",
             Process | ProcessSearch => "Processes",
+            Sockets => "Sockets",
         }
         .to_string()
     }
@@ -85,6 +121,8 @@ pub enum CursorDirection {
 pub struct AppScrollWidgetState {
     pub current_scroll_position: u64,
     pub previous_scroll_position: u64,
+    /// How many rows fit in the table when it was last drawn, for scrolling by half a page.
+    pub num_rows: u64,
 }
 
 pub struct AppScrollState {
@@ -93,6 +131,7 @@ pub struct AppScrollState {
     pub disk_scroll_state: AppScrollWidgetState,
     pub temp_scroll_state: AppScrollWidgetState,
     pub cpu_scroll_state: AppScrollWidgetState,
+    pub socket_scroll_state: AppScrollWidgetState,
 }
 
 impl Default for AppScrollState {
@@ -103,6 +142,7 @@ impl Default for AppScrollState {
             disk_scroll_state: AppScrollWidgetState::default(),
             temp_scroll_state: AppScrollWidgetState::default(),
             cpu_scroll_state: AppScrollWidgetState::default(),
+            socket_scroll_state: AppScrollWidgetState::default(),
         }
     }
 }
@@ -149,6 +189,101 @@ impl AppSearchState {
     pub fn is_invalid_or_blank_search(&self) -> bool {
         self.is_blank_search || self.is_invalid_search
     }
+
+    /// Inserts a character at the cursor, returning whether the query changed.  Nothing is
+    /// inserted once the query is at its longest.
+    pub fn insert_char(&mut self, caught_char: char) -> bool {
+        if UnicodeWidthStr::width(self.current_search_query.as_str()) > MAX_SEARCH_LENGTH {
+            return false;
+        }
+
+        let cursor_position = self.grapheme_cursor.cur_cursor();
+        self.current_search_query
+            .insert(cursor_position, caught_char);
+
+        self.grapheme_cursor =
+            GraphemeCursor::new(cursor_position, self.current_search_query.len(), true);
+        self.grapheme_cursor
+            .next_boundary(
+                &self.current_search_query[cursor_position..],
+                cursor_position,
+            )
+            .unwrap();
+
+        self.char_cursor_position += UnicodeWidthChar::width(caught_char).unwrap_or(0);
+        self.cursor_direction = CursorDirection::RIGHT;
+
+        true
+    }
+
+    /// Removes the character behind the cursor, returning whether there was one.
+    pub fn remove_char_before_cursor(&mut self) -> bool {
+        let cursor_position = self.grapheme_cursor.cur_cursor();
+        if cursor_position == 0 {
+            return false;
+        }
+
+        self.grapheme_cursor
+            .prev_boundary(&self.current_search_query[..cursor_position], 0)
+            .unwrap();
+
+        let cursor_position = self.grapheme_cursor.cur_cursor();
+        let removed_char = self.current_search_query.remove(cursor_position);
+
+        self.grapheme_cursor =
+            GraphemeCursor::new(cursor_position, self.current_search_query.len(), true);
+
+        self.char_cursor_position -= UnicodeWidthChar::width(removed_char).unwrap_or(0);
+        self.cursor_direction = CursorDirection::LEFT;
+
+        true
+    }
+
+    /// Rebuilds the regex used to match against, after the query or options change.
+    pub fn update_regex(
+        &mut self, is_ignoring_case: bool, is_searching_whole_word: bool,
+        is_searching_with_regex: bool,
+    ) {
+        if self.current_search_query.is_empty() {
+            self.is_invalid_search = false;
+            self.is_blank_search = true;
+        } else {
+            let regex_string = &self.current_search_query;
+            let escaped_regex: String;
+            let final_regex_string = &format!(
+                "{}{}{}{}",
+                if is_searching_whole_word { "^" } else { "" },
+                if is_ignoring_case { "(?i)" } else { "" },
+                if !is_searching_with_regex {
+                    escaped_regex = regex::escape(regex_string);
+                    &escaped_regex
+                } else {
+                    regex_string
+                },
+                if is_searching_whole_word { "$" } else { "" },
+            );
+
+            let new_regex = regex::Regex::new(final_regex_string);
+            self.is_blank_search = false;
+            self.is_invalid_search = new_regex.is_err();
+
+            self.current_regex = Some(new_regex);
+        }
+    }
+
+    /// Returns the positions of the table rows with a column matching the search.  Every row
+    /// matches a blank or invalid search.
+    pub fn get_matching_rows(&self, rows: &[Vec<String>]) -> Vec<usize> {
+        match &self.current_regex {
+            Some(Ok(regex)) if !self.is_invalid_or_blank_search() => rows
+                .iter()
+                .enumerate()
+                .filter(|(_, row)| row.iter().any(|column| regex.is_match(column)))
+                .map(|(row_index, _)| row_index)
+                .collect(),
+            _ => (0..rows.len()).collect(),
+        }
+    }
 }
 
 /// ProcessSearchState only deals with process' search's current settings and state.
@@ -158,6 +293,10 @@ pub struct ProcessSearchState {
     pub is_ignoring_case: bool,
     pub is_searching_whole_word: bool,
     pub is_searching_with_regex: bool,
+    /// Previous queries, oldest first.
+    pub search_history: Vec<String>,
+    /// The history entry currently being shown, if the user is navigating through history.
+    pub search_history_position: Option<usize>,
 }
 
 impl Default for ProcessSearchState {
@@ -168,6 +307,8 @@ impl Default for ProcessSearchState {
             is_ignoring_case: true,
             is_searching_whole_word: false,
             is_searching_with_regex: false,
+            search_history: Vec::new(),
+            search_history_position: None,
         }
     }
 }
@@ -184,12 +325,144 @@ impl ProcessSearchState {
     pub fn search_toggle_regex(&mut self) {
         self.is_searching_with_regex = !self.is_searching_with_regex;
     }
+
+    /// Adds the current query to the history, skipping blank queries and immediate repeats.
+    pub fn add_current_query_to_history(&mut self) {
+        let query = &self.search_state.current_search_query;
+        if !query.is_empty() && self.search_history.last() != Some(query) {
+            self.search_history.push(query.clone());
+            if self.search_history.len() > constants::MAX_SEARCH_HISTORY_LENGTH {
+                self.search_history.remove(0);
+            }
+        }
+        self.search_history_position = None;
+    }
+}
+
+/// The parts of the view saved on exit and restored on launch, if `persist_state` is set.
+#[derive(Default, Serialize, Deserialize)]
+pub struct SavedState {
+    pub sorting: Option<processes::ProcessSorting>,
+    pub sorting_reverse: Option<bool>,
+    pub search_query: Option<String>,
+    pub is_ignoring_case: Option<bool>,
+    pub is_searching_whole_word: Option<bool>,
+    pub is_searching_with_regex: Option<bool>,
+    pub is_grouped: Option<bool>,
+    pub is_grouped_by_user: Option<bool>,
+    pub widget: Option<WidgetPosition>,
+    pub layout: Option<String>,
+}
+
+/// A collection error that has happened at least once, and when it was last seen.
+pub struct LoggedHarvestError {
+    pub error: HarvestError,
+    pub count: u64,
+    pub last_seen: chrono::DateTime<chrono::Local>,
+}
+
+/// The errors from the latest harvest, for marking the widgets they affect, and a log of recent
+/// ones for the error dialog.
+#[derive(Default)]
+pub struct HarvestErrorState {
+    pub current_errors: Vec<HarvestError>,
+    /// Oldest first, with repeats of the same error merged into one entry.
+    pub error_log: Vec<LoggedHarvestError>,
+}
+
+impl HarvestErrorState {
+    pub fn update(&mut self, errors: &[HarvestError]) {
+        let now = chrono::Local::now();
+        for error in errors {
+            let count = match self
+                .error_log
+                .iter()
+                .position(|logged_error| logged_error.error == *error)
+            {
+                Some(index) => self.error_log.remove(index).count + 1,
+                None => 1,
+            };
+            self.error_log.push(LoggedHarvestError {
+                error: error.clone(),
+                count,
+                last_seen: now,
+            });
+        }
+        if self.error_log.len() > constants::MAX_LOGGED_HARVEST_ERRORS {
+            let num_to_remove = self.error_log.len() - constants::MAX_LOGGED_HARVEST_ERRORS;
+            self.error_log.drain(..num_to_remove);
+        }
+        self.current_errors = errors.to_vec();
+    }
+
+    /// Gets an error from the latest harvest of the data a widget shows, if there was one.
+    pub fn get_widget_error(&self, widget: WidgetPosition) -> Option<&HarvestError> {
+        self.current_errors.iter().find(|error| match error.source {
+            HarvestSource::Processes => match widget {
+                WidgetPosition::Process | WidgetPosition::ProcessSearch => true,
+                _ => false,
+            },
+            HarvestSource::Memory | HarvestSource::Swap => match widget {
+                WidgetPosition::Mem | WidgetPosition::BasicMem => true,
+                _ => false,
+            },
+            HarvestSource::Disks => widget == WidgetPosition::Disk,
+            HarvestSource::DiskIo => widget == WidgetPosition::DiskIo,
+            HarvestSource::Temperature => match widget {
+                WidgetPosition::Temp | WidgetPosition::TempGraph => true,
+                _ => false,
+            },
+            HarvestSource::Fans => widget == WidgetPosition::Temp,
+            HarvestSource::Gpu => widget == WidgetPosition::Gpu,
+            HarvestSource::Battery => widget == WidgetPosition::Battery,
+        })
+    }
+}
+
+/// AppEnvDialogState represents the state of the environment variable viewer for a process.
+#[derive(Default)]
+pub struct AppEnvDialogState {
+    pub is_showing_env: bool,
+    pub pid: u32,
+    pub name: String,
+    pub env_vars: Vec<(String, String)>,
+    pub env_err: Option<String>,
+    pub filter_query: String,
+    pub current_scroll_position: u64,
+}
+
+impl AppEnvDialogState {
+    /// Returns the variables whose key or value contain the filter query, ignoring case.
+    pub fn get_filtered_env_vars(&self) -> Vec<&(String, String)> {
+        let lowercase_query = self.filter_query.to_lowercase();
+        self.env_vars
+            .iter()
+            .filter(|(key, value)| {
+                lowercase_query.is_empty()
+                    || key.to_lowercase().contains(&lowercase_query)
+                    || value.to_lowercase().contains(&lowercase_query)
+            })
+            .collect()
+    }
+
+    fn change_position(&mut self, num_to_change_by: i64) {
+        let num_entries = self.get_filtered_env_vars().len() as i64;
+        let new_position = self.current_scroll_position as i64 + num_to_change_by;
+
+        if new_position >= 0 && new_position < num_entries {
+            self.current_scroll_position = new_position as u64;
+        }
+    }
 }
 
+/// The state of the detail dialog for a disk, opened with Enter in the disk widget.
 #[derive(Default)]
-pub struct AppDeleteDialogState {
-    pub is_showing_dd: bool,
-    pub is_on_yes: bool, // Defaults to "No"
+pub struct AppDiskDetailDialogState {
+    pub is_showing_disk_detail: bool,
+    pub name: String,
+    pub mount_point: String,
+    pub smart: Option<data_harvester::smart::SmartHarvest>,
+    pub smart_err: Option<String>,
 }
 
 pub enum AppHelpCategory {
@@ -201,6 +474,10 @@ pub enum AppHelpCategory {
 pub struct AppHelpDialogState {
     pub is_showing_help: bool,
     pub current_category: AppHelpCategory,
+    /// The first line shown, as the help may not fit in the dialog.
+    pub scroll_position: u64,
+    pub is_searching: bool,
+    pub search_query: String,
 }
 
 impl Default for AppHelpDialogState {
@@ -208,7 +485,102 @@ impl Default for AppHelpDialogState {
         AppHelpDialogState {
             is_showing_help: false,
             current_category: AppHelpCategory::General,
+            scroll_position: 0,
+            is_searching: false,
+            search_query: String::new(),
+        }
+    }
+}
+
+impl AppHelpDialogState {
+    /// Returns the lines of the help, paired with whether each is a header.  Keys are shown as
+    /// what they're bound to, and a search looks through every category, not just the current
+    /// one, matching whole widgets by name as well as single lines.
+    pub fn get_help_lines(&self, keybinds: &HashMap<char, char>) -> Vec<(String, bool)> {
+        let sections: Vec<&constants::HelpSection> = if self.search_query.is_empty() {
+            match self.current_category {
+                AppHelpCategory::General => constants::GENERAL_HELP_TEXT.iter().collect(),
+                AppHelpCategory::Process => constants::PROCESS_HELP_TEXT.iter().collect(),
+                AppHelpCategory::Search => constants::SEARCH_HELP_TEXT.iter().collect(),
+            }
+        } else {
+            constants::GENERAL_HELP_TEXT
+                .iter()
+                .chain(constants::PROCESS_HELP_TEXT.iter())
+                .chain(constants::SEARCH_HELP_TEXT.iter())
+                .collect()
+        };
+
+        let lowercase_query = self.search_query.to_lowercase();
+        let mut help_lines = Vec::new();
+        for (section_name, section_lines) in sections {
+            let is_section_matching = section_name.to_lowercase().contains(&lowercase_query);
+            let matching_lines: Vec<(String, bool)> = section_lines
+                .iter()
+                .map(|(keys, description)| {
+                    format!(
+                        "{:<width$} {}",
+                        get_help_keys(keys, keybinds),
+                        description,
+                        width = HELP_KEY_COLUMN_WIDTH
+                    )
+                })
+                .filter(|line| {
+                    is_section_matching || line.to_lowercase().contains(&lowercase_query)
+                })
+                .map(|line| (line, false))
+                .collect();
+
+            if !matching_lines.is_empty() {
+                if !help_lines.is_empty() {
+                    help_lines.push((String::new(), false));
+                }
+                help_lines.push((section_name.to_string(), true));
+                help_lines.extend(matching_lines);
+            }
         }
+
+        help_lines
+    }
+}
+
+const HELP_KEY_COLUMN_WIDTH: usize = 14;
+
+/// Fills in the keys of the actions named in braces in a help line's keys, as they're bound.
+fn get_help_keys(keys: &str, keybinds: &HashMap<char, char>) -> String {
+    let mut help_keys = String::new();
+    let mut rest = keys;
+    while let (Some(start), Some(end)) = (rest.find('{'), rest.find('}')) {
+        help_keys.push_str(&rest[..start]);
+        help_keys.push_str(&get_action_key(&rest[start + 1..end], keybinds));
+        rest = &rest[end + 1..];
+    }
+    help_keys.push_str(rest);
+
+    help_keys
+}
+
+/// The key an action is bound to, which is its default key unless it was remapped.
+fn get_action_key(action: &str, keybinds: &HashMap<char, char>) -> String {
+    let default_key = constants::DEFAULT_KEYBINDS
+        .iter()
+        .find(|(name, _)| *name == action)
+        .map(|(_, default_key)| *default_key);
+    let key = if keybinds.is_empty() {
+        default_key
+    } else {
+        default_key.and_then(|default_key| {
+            keybinds
+                .iter()
+                .find(|(_, bound_default_key)| **bound_default_key == default_key)
+                .map(|(key, _)| *key)
+        })
+    };
+
+    match key {
+        Some(' ') => "Space".to_string(),
+        Some(key) => key.to_string(),
+        None => action.to_string(),
     }
 }
 
@@ -218,22 +590,230 @@ pub struct AppConfigFields {
     pub update_rate_in_milliseconds: u64,
     pub temperature_type: temperature::TemperatureType,
     pub use_dot: bool,
+    /// Graph widgets whose marker differs from the one set by `use_dot`.
+    pub widget_use_dot: HashMap<WidgetPosition, bool>,
+    /// The parts of each graph widget that are hidden, keyed by the graph (never its legend).
+    pub hidden_graph_parts: HashMap<WidgetPosition, HiddenGraphParts>,
+    /// Whether to only draw ASCII and the 8 standard colours, for limited terminals.
+    pub use_ascii: bool,
     pub left_legend: bool,
-    pub show_average_cpu: bool,
+    pub cpu_graph_lines: CpuGraphLines,
     pub use_current_cpu_total: bool,
     pub show_disabled_data: bool,
     pub use_basic_mode: bool,
     pub default_time_value: u64,
+    pub cpu_default_time_value: u64,
+    pub mem_default_time_value: u64,
+    pub net_default_time_value: u64,
+    /// How long data is kept for, which is also the furthest a graph can be zoomed out.
+    pub retention_time: u64,
     pub time_interval: u64,
     pub hide_time: bool,
     pub autohide_time: bool,
+    pub show_cpu_frequency: bool,
+    pub show_cpu_temp: bool,
+    pub show_cpu_min_max: bool,
+    pub subtract_arc: bool,
+    pub mem_as_value: bool,
+    pub show_psi: bool,
+    pub show_sockets: bool,
+    /// Hosts graphed in the ping widget, which is hidden if there are none.
+    pub ping_hosts: Vec<String>,
+    pub show_disk_io_graph: bool,
+    pub show_temp_graph: bool,
+    pub show_battery: bool,
+    pub show_gpu: bool,
+    pub show_disk_temp: bool,
+    pub show_disk_inodes: bool,
+    pub hide_network_disks: bool,
+    /// How long in milliseconds a network mount's stat may take before it's left out.
+    pub network_disk_timeout: u64,
+    pub network_use_bits: bool,
+    pub show_process_swap: bool,
+    pub show_process_affinity: bool,
+    /// Whether copying a process only copies its PID, rather than the whole row.
+    pub copy_pid_only: bool,
+    /// Whether to show the one-line summary above the widgets.
+    pub show_header: bool,
+    /// Whether every other table row has a background.
+    pub table_stripes: bool,
+    /// How many spaces are between table columns.
+    pub table_gap: u16,
+    /// A file to append a record of the metrics to every update.
+    pub metrics_log_path: Option<std::path::PathBuf>,
+    /// How big the metrics log may get before it's moved aside for a new one.
+    pub metrics_log_max_size_bytes: u64,
+    /// The port to serve metrics for Prometheus on.
+    pub prometheus_port: Option<u16>,
+    /// The address to serve metrics for Prometheus on.
+    pub prometheus_address: std::net::IpAddr,
+    /// Hosts whose metrics can be shown instead of this machine's, switched between with Ctrl-n.
+    pub remote_hosts: Vec<String>,
+    /// What runs bottom on remote hosts, if it isn't `btm` on their path.
+    pub remote_command: String,
+    pub alert_rules: Vec<alerts::AlertRule>,
+    /// The local port to serve metrics as JSON on.
+    pub api_port: Option<u16>,
+    /// Where to send metrics in InfluxDB's line protocol.
+    pub line_protocol_target: Option<crate::metrics::line_protocol::LineProtocolTarget>,
+    pub line_protocol_interval: std::time::Duration,
+    /// Whether lines in the same graph alternate between braille and dot markers.
+    pub vary_series_markers: bool,
+    pub secondary_sort_keys: Vec<processes::ProcessSorting>,
+    pub persist_search_history: bool,
+    pub persist_state: bool,
+    pub hide_kernel_threads: bool,
+    pub group_ignore_case: bool,
+    pub group_by_command: bool,
+    pub network_scale: NetworkScale,
+    /// A fixed maximum for the network graph's y-axis in bytes per second, rather than fitting
+    /// the rates in view.
+    pub network_y_max: Option<f64>,
+    pub network_filter: data_harvester::network::InterfaceFilter,
+    pub disk_filter: data_harvester::disks::DiskFilter,
+    pub sensor_filter: data_harvester::temperature::SensorFilter,
+    /// Keyed by sensor name, as shown in the temperature widget.
+    pub temp_thresholds: HashMap<String, data_harvester::temperature::TempThresholds>,
+    /// A layout from the config file, used in place of the default one.
+    pub custom_layout: Option<BottomLayout>,
+    /// The layouts that can be switched between, starting with the main one.  Empty unless
+    /// the config file has named layouts.
+    pub layouts: Vec<(String, BottomLayout)>,
+    /// Widgets taken out of the layouts with the hide flags, which can't be selected.
+    pub hidden_widgets: Vec<WidgetPosition>,
+    /// Maps each key to the default key of the action bound to it, if any were remapped.
+    pub keybinds: HashMap<char, char>,
+}
+
+impl AppConfigFields {
+    /// Whether a graph widget draws with dots rather than braille.
+    pub fn is_using_dot(&self, widget: WidgetPosition) -> bool {
+        // Braille can't be drawn without unicode
+        self.use_ascii
+            || self
+                .widget_use_dot
+                .get(&widget)
+                .copied()
+                .unwrap_or(self.use_dot)
+    }
+
+    /// The parts of a graph widget that are hidden, with a legend counting as its graph.
+    pub fn get_hidden_graph_parts(&self, widget: WidgetPosition) -> HiddenGraphParts {
+        self.hidden_graph_parts
+            .get(&get_graph_widget(widget))
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+/// Parts of a graph widget that can be hidden to leave more room for the plot, which helps in
+/// small layouts.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct HiddenGraphParts {
+    pub legend: bool,
+    pub y_labels: bool,
+    pub title: bool,
+}
+
+impl HiddenGraphParts {
+    /// Hides one more part, the legend first and the title last, and then shows them all again.
+    pub fn next(self) -> Self {
+        match (self.legend, self.y_labels, self.title) {
+            (false, _, _) => HiddenGraphParts {
+                legend: true,
+                ..HiddenGraphParts::default()
+            },
+            (true, false, _) => HiddenGraphParts {
+                legend: true,
+                y_labels: true,
+                title: false,
+            },
+            (true, true, false) => HiddenGraphParts {
+                legend: true,
+                y_labels: true,
+                title: true,
+            },
+            (true, true, true) => HiddenGraphParts::default(),
+        }
+    }
+}
+
+/// The graph a widget belongs to, so a legend shares its graph's hidden parts.
+pub fn get_graph_widget(widget: WidgetPosition) -> WidgetPosition {
+    match widget {
+        WidgetPosition::CpuLegend => WidgetPosition::Cpu,
+        WidgetPosition::NetworkLegend => WidgetPosition::Network,
+        _ => widget,
+    }
+}
+
+/// How the network graph's y-axis is scaled.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum NetworkScale {
+    /// A fixed log2 scale from 1 byte to 1 GiB per second.
+    Log,
+    /// A linear scale that fits the highest rate currently in view.
+    Linear,
+}
+
+/// Which lines are drawn in the CPU graph.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CpuGraphLines {
+    All,
+    AverageOnly,
+    CoresOnly,
+    /// The average of each core type on hybrid CPUs.
+    CoreTypes,
+}
+
+impl CpuGraphLines {
+    pub fn shows_average(self) -> bool {
+        match self {
+            CpuGraphLines::All | CpuGraphLines::AverageOnly => true,
+            CpuGraphLines::CoresOnly | CpuGraphLines::CoreTypes => false,
+        }
+    }
+
+    pub fn shows_cores(self) -> bool {
+        match self {
+            CpuGraphLines::All | CpuGraphLines::CoresOnly => true,
+            CpuGraphLines::AverageOnly | CpuGraphLines::CoreTypes => false,
+        }
+    }
+
+    pub fn shows_core_types(self) -> bool {
+        match self {
+            CpuGraphLines::CoreTypes => true,
+            _ => false,
+        }
+    }
+
+    fn next(self, has_core_types: bool) -> Self {
+        match self {
+            CpuGraphLines::All => CpuGraphLines::AverageOnly,
+            CpuGraphLines::AverageOnly => CpuGraphLines::CoresOnly,
+            CpuGraphLines::CoresOnly if has_core_types => CpuGraphLines::CoreTypes,
+            CpuGraphLines::CoresOnly | CpuGraphLines::CoreTypes => CpuGraphLines::All,
+        }
+    }
 }
 
 /// Network specific
+/// Which interfaces the network graph plots.
+#[derive(Clone, Debug, PartialEq)]
+pub enum NetInterfaceSelection {
+    /// The combined usage of every interface.
+    Total,
+    /// Each interface as its own line.
+    All,
+    Single(String),
+}
+
 pub struct NetState {
     pub is_showing_tray: bool,
     pub is_showing_rx: bool,
     pub is_showing_tx: bool,
+    pub interface_selection: NetInterfaceSelection,
     pub zoom_level: f64,
     pub current_display_time: u64,
     pub force_update: bool,
@@ -246,6 +826,7 @@ impl NetState {
             is_showing_tray: false,
             is_showing_rx: true,
             is_showing_tx: true,
+            interface_selection: NetInterfaceSelection::Total,
             zoom_level: 100.0,
             current_display_time,
             force_update: false,
@@ -257,6 +838,7 @@ impl NetState {
 /// CPU specific
 pub struct CpuState {
     pub is_showing_tray: bool,
+    pub is_showing_bars: bool,
     pub zoom_level: f64,
     pub core_show_vec: Vec<bool>,
     pub num_cpus_shown: u64,
@@ -269,6 +851,7 @@ impl CpuState {
     pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
         CpuState {
             is_showing_tray: false,
+            is_showing_bars: false,
             zoom_level: 100.0,
             core_show_vec: Vec::new(),
             num_cpus_shown: 0,
@@ -304,17 +887,80 @@ impl MemState {
     }
 }
 
+/// The zoom of a graph that has nothing else to track, like the pressure, ping, disk I/O,
+/// temperature, battery, and GPU graphs.
+pub struct GraphState {
+    pub current_display_time: u64,
+    /// What the zoom is reset to.
+    pub default_display_time: u64,
+    pub force_update: bool,
+    pub autohide_timer: Option<Instant>,
+}
+
+impl GraphState {
+    pub fn init(current_display_time: u64, autohide_timer: Option<Instant>) -> Self {
+        GraphState {
+            current_display_time,
+            default_display_time: current_display_time,
+            force_update: false,
+            autohide_timer,
+        }
+    }
+
+    pub fn zoom_out(&mut self, time_interval: u64, retention_time: u64, autohide_time: bool) {
+        let new_time = std::cmp::min(self.current_display_time + time_interval, retention_time);
+        if new_time != self.current_display_time {
+            self.set_display_time(new_time, autohide_time);
+        }
+    }
+
+    pub fn zoom_in(&mut self, time_interval: u64, autohide_time: bool) {
+        let new_time = std::cmp::max(
+            self.current_display_time.saturating_sub(time_interval),
+            constants::STALE_MIN_MILLISECONDS,
+        );
+        if new_time != self.current_display_time {
+            self.set_display_time(new_time, autohide_time);
+        }
+    }
+
+    pub fn reset_zoom(&mut self, autohide_time: bool) {
+        self.set_display_time(self.default_display_time, autohide_time);
+    }
+
+    fn set_display_time(&mut self, display_time: u64, autohide_time: bool) {
+        self.current_display_time = display_time;
+        self.force_update = true;
+        if autohide_time {
+            self.autohide_timer = Some(Instant::now());
+        }
+    }
+}
+
 #[derive(TypedBuilder)]
 pub struct App {
-    #[builder(default=processes::ProcessSorting::CPU, setter(skip))]
+    #[builder(default=processes::ProcessSorting::CPU)]
     pub process_sorting_type: processes::ProcessSorting,
 
-    #[builder(default = true, setter(skip))]
+    #[builder(default = true)]
     pub process_sorting_reverse: bool,
 
+    /// How many columns of the process table are scrolled past, if they don't all fit.
+    #[builder(default, setter(skip))]
+    pub process_column_offset: usize,
+
     #[builder(default = false, setter(skip))]
     pub force_update_processes: bool,
 
+    #[builder(default, setter(skip))]
+    pub disk_sorting_type: data_harvester::disks::DiskSorting,
+
+    #[builder(default = false, setter(skip))]
+    pub disk_sorting_reverse: bool,
+
+    #[builder(default = false, setter(skip))]
+    pub force_update_disks: bool,
+
     #[builder(default, setter(skip))]
     pub app_scroll_positions: AppScrollState,
 
@@ -324,8 +970,9 @@ pub struct App {
     #[builder(default, setter(skip))]
     second_char: Option<char>,
 
+    /// Why the config file couldn't be reloaded, if it couldn't.
     #[builder(default, setter(skip))]
-    pub dd_err: Option<String>,
+    pub config_err: Option<String>,
 
     #[builder(default, setter(skip))]
     to_delete_process_list: Option<(String, Vec<u32>)>,
@@ -333,6 +980,9 @@ pub struct App {
     #[builder(default = false, setter(skip))]
     pub is_frozen: bool,
 
+    #[builder(default = false, setter(skip))]
+    pub force_update_time_travel: bool,
+
     #[builder(default = Instant::now(), setter(skip))]
     last_key_press: Instant,
 
@@ -342,27 +992,78 @@ pub struct App {
     #[builder(default = false)]
     enable_grouping: bool,
 
+    #[builder(default = false, setter(skip))]
+    enable_user_grouping: bool,
+
     #[builder(default, setter(skip))]
     pub data_collection: DataCollection,
 
     #[builder(default, setter(skip))]
     pub process_search_state: ProcessSearchState,
 
+    /// A prompt, like the confirmation to kill a process, shown over everything else.
+    #[builder(default, setter(skip))]
+    pub dialog: Option<Dialog>,
+
+    /// Messages about what actions did, dismissed after a few seconds.
+    #[builder(default, setter(skip))]
+    pub toast_state: ToastState,
+
     #[builder(default, setter(skip))]
-    pub delete_dialog_state: AppDeleteDialogState,
+    pub harvest_error_state: HarvestErrorState,
+
+    #[builder(default, setter(skip))]
+    pub alert_state: alerts::AlertState,
 
     #[builder(default, setter(skip))]
     pub help_dialog_state: AppHelpDialogState,
 
-    #[builder(default = false, setter(skip))]
+    #[builder(default, setter(skip))]
+    pub env_dialog_state: AppEnvDialogState,
+
+    #[builder(default, setter(skip))]
+    pub disk_detail_dialog_state: AppDiskDetailDialogState,
+
+    /// The search of the disk table, filtering it to the matching rows.
+    #[builder(default, setter(skip))]
+    pub disk_search_state: AppSearchState,
+
+    /// The search of the temperature table, filtering it to the matching rows.
+    #[builder(default, setter(skip))]
+    pub temp_search_state: AppSearchState,
+
+    /// The search of the sockets table, filtering it to the matching rows.
+    #[builder(default, setter(skip))]
+    pub socket_search_state: AppSearchState,
+
+    #[builder(default = false)]
     pub is_expanded: bool,
 
     #[builder(default = false, setter(skip))]
     pub is_resized: bool,
 
+    /// The index of the layout in use out of the switchable ones.
+    #[builder(default, setter(skip))]
+    pub current_layout: usize,
+
+    /// The index of the remote host being shown, or `None` for this machine.
+    #[builder(default)]
+    pub remote_host_index: Option<usize>,
+
+    /// The index of the only GPU graphed, or `None` to graph every GPU.
+    #[builder(default, setter(skip))]
+    pub selected_gpu: Option<usize>,
+
     pub cpu_state: CpuState,
     pub mem_state: MemState,
     pub net_state: NetState,
+    pub psi_state: GraphState,
+    pub ping_state: GraphState,
+    pub disk_io_state: GraphState,
+    pub temp_graph_state: GraphState,
+    /// For the power draw graph.
+    pub battery_state: GraphState,
+    pub gpu_state: GraphState,
 
     pub app_config_fields: AppConfigFields,
     pub current_widget_selected: WidgetPosition,
@@ -375,8 +1076,10 @@ impl App {
         self.reset_multi_tap_keys();
 
         // Reset dialog state
-        self.help_dialog_state.is_showing_help = false;
-        self.delete_dialog_state.is_showing_dd = false;
+        self.help_dialog_state = AppHelpDialogState::default();
+        self.dialog = None;
+        self.env_dialog_state = AppEnvDialogState::default();
+        self.disk_detail_dialog_state = AppDiskDetailDialogState::default();
 
         // Close search and reset it
         self.process_search_state.search_state.reset();
@@ -384,7 +1087,6 @@ impl App {
 
         // Clear current delete list
         self.to_delete_process_list = None;
-        self.dd_err = None;
 
         // Unfreeze.
         self.is_frozen = false;
@@ -400,13 +1102,17 @@ impl App {
 
     pub fn on_esc(&mut self) {
         self.reset_multi_tap_keys();
-        if self.is_in_dialog() {
-            self.help_dialog_state.is_showing_help = false;
-            self.help_dialog_state.current_category = AppHelpCategory::General;
-            self.delete_dialog_state.is_showing_dd = false;
-            self.delete_dialog_state.is_on_yes = false;
+        if self.help_dialog_state.is_searching {
+            self.help_dialog_state.is_searching = false;
+            self.help_dialog_state.search_query.clear();
+            self.help_dialog_state.scroll_position = 0;
+        } else if self.is_in_dialog() {
+            self.help_dialog_state = AppHelpDialogState::default();
+            self.dialog = None;
             self.to_delete_process_list = None;
-            self.dd_err = None;
+            self.config_err = None;
+            self.env_dialog_state = AppEnvDialogState::default();
+            self.disk_detail_dialog_state = AppDiskDetailDialogState::default();
         } else if self.is_filtering_or_searching() {
             match self.current_widget_selected {
                 WidgetPosition::Cpu | WidgetPosition::CpuLegend => {
@@ -428,6 +1134,7 @@ impl App {
                 }
                 WidgetPosition::Process | WidgetPosition::ProcessSearch => {
                     if self.process_search_state.search_state.is_enabled {
+                        self.process_search_state.add_current_query_to_history();
                         self.current_widget_selected = WidgetPosition::Process;
                         self.process_search_state.search_state.is_enabled = false;
                     }
@@ -438,22 +1145,88 @@ impl App {
                 WidgetPosition::Network => {
                     self.net_state.is_showing_tray = false;
                 }
-                _ => {}
+                _ => {
+                    if let Some(search_state) = self.get_table_search_state_mut() {
+                        *search_state = AppSearchState::default();
+                        self.update_table_search();
+                    }
+                }
             }
+        } else if self.is_time_travelling() {
+            self.stop_time_travel();
         } else if self.is_expanded {
-            self.is_expanded = false;
-            self.is_resized = true;
-            if self.app_config_fields.use_basic_mode {
-                self.current_widget_selected = match self.current_widget_selected {
-                    WidgetPosition::Cpu | WidgetPosition::CpuLegend => WidgetPosition::BasicCpu,
-                    WidgetPosition::Mem => WidgetPosition::BasicMem,
-                    WidgetPosition::Network => WidgetPosition::BasicNet,
-                    _ => self.current_widget_selected,
+            self.restore_current_widget();
+        }
+    }
+
+    pub fn is_time_travelling(&self) -> bool {
+        self.data_collection.time_travel_instant.is_some()
+    }
+
+    /// Pauses the display at the latest point in the history so it can be stepped through, or
+    /// goes back to the live data.
+    pub fn toggle_time_travel(&mut self) {
+        if self.is_time_travelling() {
+            self.stop_time_travel();
+        } else if let Some((latest_instant, _)) = self.data_collection.timed_data_vec.last() {
+            let latest_instant = *latest_instant;
+            self.is_frozen = true;
+            self.data_collection.frozen_instant = Some(latest_instant);
+            self.data_collection
+                .set_time_travel_instant(Some(latest_instant));
+            self.force_update_time_travel = true;
+        }
+    }
+
+    fn stop_time_travel(&mut self) {
+        self.data_collection.set_time_travel_instant(None);
+        self.is_frozen = false;
+    }
+
+    /// Moves one entry back or forward through the history while time travelling.
+    fn step_time_travel(&mut self, is_forward: bool) {
+        if let Some(time_travel_instant) = self.data_collection.time_travel_instant {
+            let timed_data_vec = &self.data_collection.timed_data_vec;
+            if let Some(position) = timed_data_vec
+                .iter()
+                .position(|(time, _)| *time == time_travel_instant)
+            {
+                // Only entries that still have a snapshot can be stepped to
+                let new_instant = if is_forward {
+                    timed_data_vec[position + 1..]
+                        .iter()
+                        .find(|(_, entry)| entry.snapshot.is_some())
+                } else {
+                    timed_data_vec[..position]
+                        .iter()
+                        .rev()
+                        .find(|(_, entry)| entry.snapshot.is_some())
+                }
+                .map(|(time, _)| *time);
+
+                if let Some(new_instant) = new_instant {
+                    self.data_collection.frozen_instant = Some(new_instant);
+                    self.data_collection
+                        .set_time_travel_instant(Some(new_instant));
+                    self.force_update_time_travel = true;
                 }
             }
         }
     }
 
+    /// How far back in seconds the shown data is from the latest, while time travelling.
+    pub fn get_time_travel_offset(&self) -> Option<u64> {
+        match (
+            self.data_collection.time_travel_instant,
+            self.data_collection.timed_data_vec.last(),
+        ) {
+            (Some(time_travel_instant), Some((latest_instant, _))) => {
+                Some(latest_instant.duration_since(time_travel_instant).as_secs())
+            }
+            _ => None,
+        }
+    }
+
     fn is_filtering_or_searching(&self) -> bool {
         match self.current_widget_selected {
             WidgetPosition::Cpu | WidgetPosition::CpuLegend => self.cpu_state.is_showing_tray,
@@ -462,54 +1235,312 @@ impl App {
             WidgetPosition::Process | WidgetPosition::ProcessSearch => {
                 self.process_search_state.search_state.is_enabled
             }
-            _ => false,
+            _ => self
+                .get_table_search_state()
+                .map(|search_state| search_state.is_enabled || !search_state.is_blank_search)
+                .unwrap_or(false),
+        }
+    }
+
+    /// The search of the selected table, for the tables other than processes that can be
+    /// searched.
+    fn get_table_search_state(&self) -> Option<&AppSearchState> {
+        match self.current_widget_selected {
+            WidgetPosition::Disk => Some(&self.disk_search_state),
+            WidgetPosition::Temp => Some(&self.temp_search_state),
+            WidgetPosition::Sockets => Some(&self.socket_search_state),
+            _ => None,
+        }
+    }
+
+    fn get_table_search_state_mut(&mut self) -> Option<&mut AppSearchState> {
+        match self.current_widget_selected {
+            WidgetPosition::Disk => Some(&mut self.disk_search_state),
+            WidgetPosition::Temp => Some(&mut self.temp_search_state),
+            WidgetPosition::Sockets => Some(&mut self.socket_search_state),
+            _ => None,
         }
     }
 
+    /// Whether key presses are being typed into the search of the selected table.
+    pub fn is_typing_in_table_search(&self) -> bool {
+        self.get_table_search_state()
+            .map(|search_state| search_state.is_enabled)
+            .unwrap_or(false)
+    }
+
+    /// Rebuilds the selected table's search after its query changes, and goes back to the first
+    /// row, as the rows shown have changed.
+    fn update_table_search(&mut self) {
+        let scroll_state = match self.current_widget_selected {
+            WidgetPosition::Disk => {
+                self.disk_search_state.update_regex(true, false, false);
+                &mut self.app_scroll_positions.disk_scroll_state
+            }
+            WidgetPosition::Temp => {
+                self.temp_search_state.update_regex(true, false, false);
+                &mut self.app_scroll_positions.temp_scroll_state
+            }
+            WidgetPosition::Sockets => {
+                self.socket_search_state.update_regex(true, false, false);
+                &mut self.app_scroll_positions.socket_scroll_state
+            }
+            _ => return,
+        };
+        scroll_state.current_scroll_position = 0;
+        scroll_state.previous_scroll_position = 0;
+    }
+
+    /// Returns the positions of the disks matching the disk table's search.
+    pub fn get_filtered_disk_rows(&self) -> Vec<usize> {
+        self.disk_search_state
+            .get_matching_rows(&self.canvas_data.disk_data)
+    }
+
+    /// Returns the positions of the sensors matching the temperature table's search.
+    pub fn get_filtered_temp_rows(&self) -> Vec<usize> {
+        self.temp_search_state
+            .get_matching_rows(&self.canvas_data.temp_sensor_data)
+    }
+
+    /// Returns the positions of the sockets matching the sockets table's search.
+    pub fn get_filtered_socket_rows(&self) -> Vec<usize> {
+        self.socket_search_state
+            .get_matching_rows(&self.canvas_data.socket_data)
+    }
+
     fn reset_multi_tap_keys(&mut self) {
         self.awaiting_second_char = false;
         self.second_char = None;
     }
 
-    fn is_in_dialog(&self) -> bool {
-        self.help_dialog_state.is_showing_help || self.delete_dialog_state.is_showing_dd
+    pub fn is_in_dialog(&self) -> bool {
+        self.help_dialog_state.is_showing_help
+            || self.dialog.is_some()
+            || self.env_dialog_state.is_showing_env
+            || self.disk_detail_dialog_state.is_showing_disk_detail
+            || self.config_err.is_some()
     }
 
-    pub fn toggle_grouping(&mut self) {
-        // Disallow usage whilst in a dialog and only in processes
-        if !self.is_in_dialog() {
-            if let WidgetPosition::Process = self.current_widget_selected {
-                self.enable_grouping = !(self.enable_grouping);
-                self.force_update_processes = true;
-            }
+    /// Takes the layout and filters from a reloaded config file.  Other settings only change
+    /// on a restart.
+    pub fn update_config_fields(&mut self, new_fields: AppConfigFields) {
+        // Stay on the same switchable layout if it's still there
+        if self.current_layout >= new_fields.layouts.len() {
+            self.current_layout = 0;
         }
-    }
-
-    pub fn on_tab(&mut self) {
-        match self.current_widget_selected {
-            WidgetPosition::Process => {
-                self.toggle_grouping();
-                if self.is_grouped() {
-                    self.search_with_name();
-                } else {
-                    self.force_update_processes = true;
-                }
-            }
-            WidgetPosition::ProcessSearch => {
-                if !self.is_grouped() {
-                    if self.process_search_state.is_searching_with_pid {
-                        self.search_with_name();
-                    } else {
-                        self.search_with_pid();
-                    }
+        self.app_config_fields.custom_layout = match new_fields.layouts.get(self.current_layout) {
+            Some((_, layout)) => Some(layout.clone()),
+            None => new_fields.custom_layout,
+        };
+        self.app_config_fields.layouts = new_fields.layouts;
+        self.app_config_fields.hidden_widgets = new_fields.hidden_widgets;
+        self.app_config_fields.show_gpu = new_fields.show_gpu;
+        self.app_config_fields.show_psi = new_fields.show_psi;
+        self.app_config_fields.show_sockets = new_fields.show_sockets;
+        self.app_config_fields.show_disk_io_graph = new_fields.show_disk_io_graph;
+        self.app_config_fields.show_temp_graph = new_fields.show_temp_graph;
+        self.app_config_fields.show_battery = new_fields.show_battery;
+        self.app_config_fields.network_filter = new_fields.network_filter;
+        self.app_config_fields.disk_filter = new_fields.disk_filter;
+        self.app_config_fields.sensor_filter = new_fields.sensor_filter;
+        self.app_config_fields.widget_use_dot = new_fields.widget_use_dot;
+        self.app_config_fields.hidden_graph_parts = new_fields.hidden_graph_parts;
+        self.app_config_fields.table_stripes = new_fields.table_stripes;
+        self.app_config_fields.table_gap = new_fields.table_gap;
+        self.app_config_fields.alert_rules = new_fields.alert_rules;
+        self.alert_state.forget_rules();
+        self.move_off_hidden_cpu_legend();
+
+        // Move off of any widget the new layout removed
+        if !self.app_config_fields.use_basic_mode {
+            let layout = match &self.app_config_fields.custom_layout {
+                Some(layout) => layout.clone(),
+                None => BottomLayout::default_layout(&self.app_config_fields),
+            };
+            if !layout.contains(self.current_widget_selected) {
+                if let Some(first_widget) = layout.get_first_widget() {
+                    self.current_widget_selected = first_widget;
+                    self.is_expanded = false;
                 }
             }
-            _ => {}
         }
-    }
 
-    pub fn is_grouped(&self) -> bool {
-        self.enable_grouping
+        self.is_resized = true;
+    }
+
+    pub fn get_saved_state(&self) -> SavedState {
+        let search_query = &self.process_search_state.search_state.current_search_query;
+        SavedState {
+            sorting: Some(self.process_sorting_type.clone()),
+            sorting_reverse: Some(self.process_sorting_reverse),
+            search_query: if search_query.is_empty() {
+                None
+            } else {
+                Some(search_query.clone())
+            },
+            is_ignoring_case: Some(self.process_search_state.is_ignoring_case),
+            is_searching_whole_word: Some(self.process_search_state.is_searching_whole_word),
+            is_searching_with_regex: Some(self.process_search_state.is_searching_with_regex),
+            is_grouped: Some(self.enable_grouping),
+            is_grouped_by_user: Some(self.enable_user_grouping),
+            widget: Some(self.current_widget_selected),
+            layout: self
+                .app_config_fields
+                .layouts
+                .get(self.current_layout)
+                .map(|(name, _)| name.clone()),
+        }
+    }
+
+    /// Restores a saved view over the one set by flags and the config file.  A saved widget is
+    /// skipped if the current layout doesn't have it.
+    pub fn restore_saved_state(&mut self, saved_state: SavedState) {
+        if let Some(sorting) = saved_state.sorting {
+            self.process_sorting_type = sorting;
+        }
+        if let Some(sorting_reverse) = saved_state.sorting_reverse {
+            self.process_sorting_reverse = sorting_reverse;
+        }
+        if let Some(is_ignoring_case) = saved_state.is_ignoring_case {
+            self.process_search_state.is_ignoring_case = is_ignoring_case;
+        }
+        if let Some(is_searching_whole_word) = saved_state.is_searching_whole_word {
+            self.process_search_state.is_searching_whole_word = is_searching_whole_word;
+        }
+        if let Some(is_searching_with_regex) = saved_state.is_searching_with_regex {
+            self.process_search_state.is_searching_with_regex = is_searching_with_regex;
+        }
+        if let Some(is_grouped) = saved_state.is_grouped {
+            self.enable_grouping = is_grouped;
+        }
+        if let Some(is_grouped_by_user) = saved_state.is_grouped_by_user {
+            self.enable_user_grouping = is_grouped_by_user;
+            if is_grouped_by_user {
+                self.enable_grouping = false;
+            }
+        }
+
+        // Keep the search bar open so it's clear why processes are missing
+        if let Some(search_query) = saved_state.search_query {
+            self.process_search_state.search_state.is_enabled = true;
+            self.set_search_query(search_query);
+        }
+
+        if let Some(saved_layout) = saved_state.layout {
+            if let Some(index) = self
+                .app_config_fields
+                .layouts
+                .iter()
+                .position(|(name, _)| *name == saved_layout)
+            {
+                self.current_layout = index;
+                self.app_config_fields.custom_layout =
+                    Some(self.app_config_fields.layouts[index].1.clone());
+            }
+        }
+
+        if let Some(widget) = saved_state.widget {
+            if !self.app_config_fields.use_basic_mode {
+                let layout = match &self.app_config_fields.custom_layout {
+                    Some(layout) => layout.clone(),
+                    None => BottomLayout::default_layout(&self.app_config_fields),
+                };
+                if layout.contains(widget) {
+                    self.current_widget_selected = match widget {
+                        WidgetPosition::CpuLegend => WidgetPosition::Cpu,
+                        WidgetPosition::NetworkLegend => WidgetPosition::Network,
+                        _ => widget,
+                    };
+                }
+            }
+        }
+
+        self.force_update_processes = true;
+    }
+
+    /// Switches to the next or previous named layout from the config file, if there are any.
+    pub fn cycle_layouts(&mut self, is_forward: bool) {
+        let num_layouts = self.app_config_fields.layouts.len();
+        if num_layouts > 1 && !self.is_in_dialog() && !self.app_config_fields.use_basic_mode {
+            self.current_layout = if is_forward {
+                (self.current_layout + 1) % num_layouts
+            } else {
+                (self.current_layout + num_layouts - 1) % num_layouts
+            };
+
+            let layout = self.app_config_fields.layouts[self.current_layout]
+                .1
+                .clone();
+            if !layout.contains(self.current_widget_selected) {
+                if let Some(first_widget) = layout.get_first_widget() {
+                    self.current_widget_selected = first_widget;
+                }
+            }
+            self.app_config_fields.custom_layout = Some(layout);
+            self.is_expanded = false;
+            self.is_resized = true;
+        }
+    }
+
+    pub fn toggle_grouping(&mut self) {
+        // Disallow usage whilst in a dialog and only in processes
+        if !self.is_in_dialog() {
+            if let WidgetPosition::Process = self.current_widget_selected {
+                // This leaves the per-user view rather than switching to name grouping
+                if self.enable_user_grouping {
+                    self.enable_user_grouping = false;
+                } else {
+                    self.enable_grouping = !(self.enable_grouping);
+                }
+                if self.is_grouped() {
+                    self.search_with_name();
+                }
+                self.force_update_processes = true;
+            }
+        }
+    }
+
+    /// Toggles the per-user view, which shows one row per user with the number of processes
+    /// and their total usage.
+    pub fn toggle_user_grouping(&mut self) {
+        if !self.is_in_dialog() {
+            if let WidgetPosition::Process = self.current_widget_selected {
+                self.enable_user_grouping = !(self.enable_user_grouping);
+                self.enable_grouping = false;
+                if self.enable_user_grouping {
+                    self.search_with_name();
+                }
+                self.force_update_processes = true;
+                self.app_scroll_positions
+                    .process_scroll_state
+                    .current_scroll_position = 0;
+            }
+        }
+    }
+
+    pub fn on_tab(&mut self) {
+        if let Some(dialog) = &mut self.dialog {
+            dialog.focus_next();
+        } else if !self.is_in_dialog() {
+            self.cycle_widget_selection(true);
+        }
+    }
+
+    pub fn on_back_tab(&mut self) {
+        if let Some(dialog) = &mut self.dialog {
+            dialog.focus_previous();
+        } else if !self.is_in_dialog() {
+            self.cycle_widget_selection(false);
+        }
+    }
+
+    pub fn is_grouped(&self) -> bool {
+        self.enable_grouping || self.enable_user_grouping
+    }
+
+    pub fn is_grouped_by_user(&self) -> bool {
+        self.enable_user_grouping
     }
 
     pub fn on_space(&mut self) {
@@ -539,6 +1570,247 @@ impl App {
         }
     }
 
+    /// Toggles every core's line in the CPU graph at once while the CPU filter tray is open.
+    /// If all lines are currently shown they are all hidden, otherwise they are all shown.
+    pub fn toggle_all_cpu_cores(&mut self) {
+        if let WidgetPosition::CpuLegend = self.current_widget_selected {
+            if self.cpu_state.is_showing_tray {
+                let show_all = !self
+                    .cpu_state
+                    .core_show_vec
+                    .iter()
+                    .all(|is_shown| *is_shown);
+                for is_shown in self.cpu_state.core_show_vec.iter_mut() {
+                    *is_shown = show_all;
+                }
+
+                if !self.app_config_fields.show_disabled_data {
+                    self.cpu_state.num_cpus_shown = if show_all {
+                        self.cpu_state.core_show_vec.len() as u64
+                    } else {
+                        0
+                    };
+                }
+            }
+        }
+    }
+
+    /// Cycles the CPU graph between showing every line, only the average, and only the cores.
+    pub fn toggle_cpu_graph_lines(&mut self) {
+        match self.current_widget_selected {
+            WidgetPosition::Cpu | WidgetPosition::CpuLegend if !self.cpu_state.is_showing_tray => {
+                self.app_config_fields.cpu_graph_lines = self
+                    .app_config_fields
+                    .cpu_graph_lines
+                    .next(self.has_core_types());
+                self.reset_cpu_core_visibility();
+            }
+            _ => {}
+        }
+    }
+
+    /// Switches the CPU widget between the graph and per-core usage bars.
+    pub fn toggle_cpu_bars(&mut self) {
+        match self.current_widget_selected {
+            WidgetPosition::Cpu | WidgetPosition::CpuLegend if !self.cpu_state.is_showing_tray => {
+                self.cpu_state.is_showing_bars = !self.cpu_state.is_showing_bars;
+            }
+            _ => {}
+        }
+    }
+
+    /// Shows or hides the swap line in the memory graph.
+    pub fn toggle_swap(&mut self) {
+        if let WidgetPosition::Mem = self.current_widget_selected {
+            self.mem_state.is_showing_swap = !self.mem_state.is_showing_swap;
+        }
+    }
+
+    /// Sorts the disk table by the next shown column.  Text columns are sorted in ascending
+    /// order, and numeric ones in descending order.
+    pub fn cycle_disk_sorting(&mut self) {
+        use data_harvester::disks::DiskSorting;
+
+        let show_disk_inodes = self.app_config_fields.show_disk_inodes;
+        let show_disk_temp = self.app_config_fields.show_disk_temp;
+        self.disk_sorting_type = match self.disk_sorting_type {
+            DiskSorting::Name => DiskSorting::Mount,
+            DiskSorting::Mount => DiskSorting::Used,
+            DiskSorting::Used => DiskSorting::Free,
+            DiskSorting::Free => DiskSorting::Total,
+            DiskSorting::Total => DiskSorting::Read,
+            DiskSorting::Read => DiskSorting::Write,
+            DiskSorting::Write => DiskSorting::Util,
+            DiskSorting::Util if show_disk_inodes => DiskSorting::Inodes,
+            DiskSorting::Util | DiskSorting::Inodes if show_disk_temp => DiskSorting::Temp,
+            DiskSorting::Util | DiskSorting::Inodes | DiskSorting::Temp => DiskSorting::Name,
+        };
+        self.disk_sorting_reverse = match self.disk_sorting_type {
+            DiskSorting::Name | DiskSorting::Mount => false,
+            _ => true,
+        };
+
+        self.force_update_disks = true;
+        self.app_scroll_positions
+            .disk_scroll_state
+            .current_scroll_position = 0;
+    }
+
+    pub fn reverse_disk_sorting(&mut self) {
+        self.disk_sorting_reverse = !self.disk_sorting_reverse;
+        self.force_update_disks = true;
+        self.app_scroll_positions
+            .disk_scroll_state
+            .current_scroll_position = 0;
+    }
+
+    /// Cycles the network graph between the total, every interface, and each interface alone.
+    pub fn cycle_net_interfaces(&mut self) {
+        if let WidgetPosition::Network = self.current_widget_selected {
+            let interfaces = &self.data_collection.network_harvest.interfaces;
+            self.net_state.interface_selection = match &self.net_state.interface_selection {
+                NetInterfaceSelection::Total if !interfaces.is_empty() => {
+                    NetInterfaceSelection::All
+                }
+                NetInterfaceSelection::All if !interfaces.is_empty() => {
+                    NetInterfaceSelection::Single(interfaces[0].name.clone())
+                }
+                NetInterfaceSelection::Single(name) => {
+                    match interfaces
+                        .iter()
+                        .position(|interface| &interface.name == name)
+                        .and_then(|itx| interfaces.get(itx + 1))
+                    {
+                        Some(next_interface) => {
+                            NetInterfaceSelection::Single(next_interface.name.clone())
+                        }
+                        None => NetInterfaceSelection::Total,
+                    }
+                }
+                _ => NetInterfaceSelection::Total,
+            };
+        }
+    }
+
+    /// Cycles the GPU graph between every GPU and each GPU alone.
+    pub fn cycle_gpus(&mut self) {
+        if let WidgetPosition::Gpu = self.current_widget_selected {
+            let num_gpus = self.data_collection.gpu_harvest.len();
+            self.selected_gpu = match self.selected_gpu {
+                None if num_gpus > 0 => Some(0),
+                Some(itx) if itx + 1 < num_gpus => Some(itx + 1),
+                _ => None,
+            };
+        }
+    }
+
+    /// Switches network throughput between bits and bytes per second.
+    pub fn toggle_network_bits(&mut self) {
+        if let WidgetPosition::Network = self.current_widget_selected {
+            self.app_config_fields.network_use_bits = !self.app_config_fields.network_use_bits;
+        }
+    }
+
+    /// Returns the index into the CPU data of the entry highlighted in the CPU legend.
+    pub fn get_selected_cpu_index(&self) -> Option<usize> {
+        let selected_position = self
+            .app_scroll_positions
+            .cpu_scroll_state
+            .current_scroll_position as usize;
+
+        // Hidden entries aren't in the legend unless we're filtering or showing disabled data
+        if self.cpu_state.is_showing_tray || self.app_config_fields.show_disabled_data {
+            Some(selected_position)
+        } else {
+            self.cpu_state
+                .core_show_vec
+                .iter()
+                .enumerate()
+                .filter(|(_itx, is_shown)| **is_shown)
+                .nth(selected_position)
+                .map(|(itx, _is_shown)| itx)
+        }
+    }
+
+    /// Returns the name of the core highlighted in the CPU legend, along with the name and usage
+    /// of the process using the most CPU out of those that last ran on it.
+    pub fn get_top_process_of_selected_core(&self) -> Option<(&str, &str, f64)> {
+        let cpu_harvest = &self.data_collection.cpu_harvest;
+        let selected_index = self.get_selected_cpu_index()?;
+
+        // The average is always the first entry, and isn't a core
+        let selected_cpu = cpu_harvest.get(selected_index)?;
+        if selected_index == 0 || selected_cpu.is_core_type_average {
+            return None;
+        }
+        let core = selected_index - 1;
+
+        self.canvas_data
+            .process_data
+            .values()
+            .filter(|process| process.last_cpu == Some(core))
+            .max_by(|a, b| {
+                a.cpu_usage_percent
+                    .partial_cmp(&b.cpu_usage_percent)
+                    .unwrap_or(std::cmp::Ordering::Equal)
+            })
+            .map(|process| {
+                (
+                    selected_cpu.cpu_name.as_str(),
+                    process.name.as_str(),
+                    process.cpu_usage_percent,
+                )
+            })
+    }
+
+    fn has_core_types(&self) -> bool {
+        self.data_collection
+            .cpu_harvest
+            .iter()
+            .any(|cpu| cpu.is_core_type_average)
+    }
+
+    /// Shows or hides each CPU line based on the current [`CpuGraphLines`].  Note the average is
+    /// always the first entry.
+    pub fn reset_cpu_core_visibility(&mut self) {
+        // Not a hybrid CPU, so there is nothing to show for core types
+        if self.app_config_fields.cpu_graph_lines.shows_core_types() && !self.has_core_types() {
+            self.app_config_fields.cpu_graph_lines = CpuGraphLines::All;
+        }
+
+        let cpu_graph_lines = self.app_config_fields.cpu_graph_lines;
+        let cpu_harvest = &self.data_collection.cpu_harvest;
+        for (itx, is_shown) in self.cpu_state.core_show_vec.iter_mut().enumerate() {
+            *is_shown = if itx == 0 {
+                cpu_graph_lines.shows_average()
+            } else if cpu_harvest
+                .get(itx)
+                .map(|cpu| cpu.is_core_type_average)
+                .unwrap_or(false)
+            {
+                cpu_graph_lines.shows_core_types()
+            } else {
+                cpu_graph_lines.shows_cores()
+            };
+        }
+
+        self.cpu_state.num_cpus_shown = if self.app_config_fields.show_disabled_data {
+            self.cpu_state.core_show_vec.len() as u64
+        } else {
+            self.cpu_state
+                .core_show_vec
+                .iter()
+                .filter(|is_shown| **is_shown)
+                .count() as u64
+        };
+        self.app_scroll_positions
+            .cpu_scroll_state
+            .current_scroll_position = 0;
+        self.app_scroll_positions
+            .cpu_scroll_state
+            .previous_scroll_position = 0;
+    }
+
     pub fn on_slash(&mut self) {
         if !self.is_in_dialog() {
             match self.current_widget_selected {
@@ -564,7 +1836,11 @@ impl App {
                 // WidgetPosition::Network => {
                 // 	self.net_state.is_showing_tray = true;
                 // }
-                _ => {}
+                _ => {
+                    if let Some(search_state) = self.get_table_search_state_mut() {
+                        search_state.is_enabled = true;
+                    }
+                }
             }
         }
     }
@@ -573,6 +1849,17 @@ impl App {
         self.process_search_state.search_state.is_enabled
     }
 
+    /// Whether key presses are being typed into a dialog, rather than acted on.
+    pub fn is_typing_in_dialog(&self) -> bool {
+        self.env_dialog_state.is_showing_env
+            || self.help_dialog_state.is_searching
+            || self
+                .dialog
+                .as_ref()
+                .map(|dialog| dialog.is_input_focused())
+                .unwrap_or(false)
+    }
+
     pub fn is_in_search_widget(&self) -> bool {
         if let WidgetPosition::ProcessSearch = self.current_widget_selected {
             true
@@ -595,6 +1882,18 @@ impl App {
         }
     }
 
+    /// Switches between searching for PIDs and names, unless grouped, where only names are
+    /// searched.
+    pub fn toggle_search_pid(&mut self) {
+        if !self.is_grouped() {
+            if self.process_search_state.is_searching_with_pid {
+                self.search_with_name();
+            } else {
+                self.search_with_pid();
+            }
+        }
+    }
+
     pub fn get_current_search_query(&self) -> &String {
         &self.process_search_state.search_state.current_search_query
     }
@@ -618,48 +1917,11 @@ impl App {
     }
 
     pub fn update_regex(&mut self) {
-        if self
-            .process_search_state
-            .search_state
-            .current_search_query
-            .is_empty()
-        {
-            self.process_search_state.search_state.is_invalid_search = false;
-            self.process_search_state.search_state.is_blank_search = true;
-        } else {
-            let regex_string = &self.process_search_state.search_state.current_search_query;
-            let escaped_regex: String;
-            let final_regex_string = &format!(
-                "{}{}{}{}",
-                if self.process_search_state.is_searching_whole_word {
-                    "^"
-                } else {
-                    ""
-                },
-                if self.process_search_state.is_ignoring_case {
-                    "(?i)"
-                } else {
-                    ""
-                },
-                if !self.process_search_state.is_searching_with_regex {
-                    escaped_regex = regex::escape(regex_string);
-                    &escaped_regex
-                } else {
-                    regex_string
-                },
-                if self.process_search_state.is_searching_whole_word {
-                    "$"
-                } else {
-                    ""
-                },
-            );
-
-            let new_regex = regex::Regex::new(final_regex_string);
-            self.process_search_state.search_state.is_blank_search = false;
-            self.process_search_state.search_state.is_invalid_search = new_regex.is_err();
-
-            self.process_search_state.search_state.current_regex = Some(new_regex);
-        }
+        self.process_search_state.search_state.update_regex(
+            self.process_search_state.is_ignoring_case,
+            self.process_search_state.is_searching_whole_word,
+            self.process_search_state.is_searching_with_regex,
+        );
         self.app_scroll_positions
             .process_scroll_state
             .previous_scroll_position = 0;
@@ -681,42 +1943,67 @@ impl App {
 
     /// One of two functions allowed to run while in a dialog...
     pub fn on_enter(&mut self) {
-        if self.delete_dialog_state.is_showing_dd {
-            if self.delete_dialog_state.is_on_yes {
-                // If within dd...
-                if self.dd_err.is_none() {
-                    // Also ensure that we didn't just fail a dd...
-                    let dd_result = self.kill_highlighted_process();
-                    self.delete_dialog_state.is_on_yes = false;
-
-                    // Check if there was an issue... if so, inform the user.
-                    if let Err(dd_err) = dd_result {
-                        self.dd_err = Some(dd_err.to_string());
-                    } else {
-                        self.delete_dialog_state.is_showing_dd = false;
-                    }
-                }
-            } else {
-                self.delete_dialog_state.is_showing_dd = false;
+        if let Some(dialog) = &mut self.dialog {
+            // Enter in the text input moves on to the buttons
+            if dialog.is_input_focused() {
+                dialog.focus_next();
+            } else if let Some((_, button)) = dialog.get_focused_button() {
+                let action = button.action;
+                self.dialog = None;
+                self.run_dialog_action(action);
             }
         } else if !self.is_in_dialog() {
             // Pop-out mode.  We ignore if in process search.
 
             match self.current_widget_selected {
-                WidgetPosition::ProcessSearch => {}
-                _ => {
-                    self.is_expanded = true;
-                    self.is_resized = true;
+                WidgetPosition::ProcessSearch => {
+                    self.process_search_state.add_current_query_to_history();
+                }
+                _ if self.is_typing_in_table_search() => {
+                    if let Some(search_state) = self.get_table_search_state_mut() {
+                        search_state.is_enabled = false;
+                    }
                 }
+                WidgetPosition::Disk => self.start_disk_detail_dialog(),
+                _ => self.expand_current_widget(),
             }
+        }
+    }
 
-            if self.app_config_fields.use_basic_mode {
-                self.current_widget_selected = match self.current_widget_selected {
-                    WidgetPosition::BasicCpu => WidgetPosition::Cpu,
-                    WidgetPosition::BasicMem => WidgetPosition::Mem,
-                    WidgetPosition::BasicNet => WidgetPosition::Network,
-                    _ => self.current_widget_selected,
-                }
+    /// Maximizes the selected widget, or restores the layout if it already is.  Unlike Enter,
+    /// this works the same on every widget, including the disk widget.
+    pub fn toggle_expanded(&mut self) {
+        if !self.is_in_dialog() {
+            if self.is_expanded {
+                self.restore_current_widget();
+            } else {
+                self.expand_current_widget();
+            }
+        }
+    }
+
+    fn expand_current_widget(&mut self) {
+        self.is_expanded = true;
+        self.is_resized = true;
+        if self.app_config_fields.use_basic_mode {
+            self.current_widget_selected = match self.current_widget_selected {
+                WidgetPosition::BasicCpu => WidgetPosition::Cpu,
+                WidgetPosition::BasicMem => WidgetPosition::Mem,
+                WidgetPosition::BasicNet => WidgetPosition::Network,
+                _ => self.current_widget_selected,
+            }
+        }
+    }
+
+    fn restore_current_widget(&mut self) {
+        self.is_expanded = false;
+        self.is_resized = true;
+        if self.app_config_fields.use_basic_mode {
+            self.current_widget_selected = match self.current_widget_selected {
+                WidgetPosition::Cpu | WidgetPosition::CpuLegend => WidgetPosition::BasicCpu,
+                WidgetPosition::Mem => WidgetPosition::BasicMem,
+                WidgetPosition::Network => WidgetPosition::BasicNet,
+                _ => self.current_widget_selected,
             }
         }
     }
@@ -793,29 +2080,29 @@ impl App {
     }
 
     pub fn on_backspace(&mut self) {
-        if let WidgetPosition::ProcessSearch = self.current_widget_selected {
-            if self.process_search_state.search_state.is_enabled && self.get_cursor_position() > 0 {
-                self.search_walk_back(self.get_cursor_position());
-
-                let removed_char = self
+        if let Some(dialog) = &mut self.dialog {
+            dialog.on_backspace();
+        } else if self.env_dialog_state.is_showing_env {
+            self.env_dialog_state.filter_query.pop();
+            self.env_dialog_state.current_scroll_position = 0;
+        } else if self.help_dialog_state.is_searching {
+            self.help_dialog_state.search_query.pop();
+            self.help_dialog_state.scroll_position = 0;
+        } else if self.is_typing_in_table_search() {
+            let is_changed = self
+                .get_table_search_state_mut()
+                .map(|search_state| search_state.remove_char_before_cursor())
+                .unwrap_or(false);
+            if is_changed {
+                self.update_table_search();
+            }
+        } else if let WidgetPosition::ProcessSearch = self.current_widget_selected {
+            if self.process_search_state.search_state.is_enabled
+                && self
                     .process_search_state
                     .search_state
-                    .current_search_query
-                    .remove(self.get_cursor_position());
-
-                self.process_search_state.search_state.grapheme_cursor = GraphemeCursor::new(
-                    self.get_cursor_position(),
-                    self.process_search_state
-                        .search_state
-                        .current_search_query
-                        .len(),
-                    true,
-                );
-
-                self.process_search_state.search_state.char_cursor_position -=
-                    UnicodeWidthChar::width(removed_char).unwrap_or(0);
-                self.process_search_state.search_state.cursor_direction = CursorDirection::LEFT;
-
+                    .remove_char_before_cursor()
+            {
                 self.update_regex();
                 self.force_update_processes = true;
             }
@@ -831,24 +2118,84 @@ impl App {
     pub fn on_up_key(&mut self) {
         if !self.is_in_dialog() {
             if let WidgetPosition::ProcessSearch = self.current_widget_selected {
+                self.search_history_previous();
             } else {
                 self.decrement_position_count();
             }
+        } else if self.env_dialog_state.is_showing_env {
+            self.env_dialog_state.change_position(-1);
+        } else if self.help_dialog_state.is_showing_help {
+            self.change_help_position(-1);
+        } else if let Some(dialog) = &mut self.dialog {
+            dialog.focus_previous();
         }
     }
 
     pub fn on_down_key(&mut self) {
         if !self.is_in_dialog() {
             if let WidgetPosition::ProcessSearch = self.current_widget_selected {
+                self.search_history_next();
             } else {
                 self.increment_position_count();
             }
+        } else if self.env_dialog_state.is_showing_env {
+            self.env_dialog_state.change_position(1);
+        } else if self.help_dialog_state.is_showing_help {
+            self.change_help_position(1);
+        } else if let Some(dialog) = &mut self.dialog {
+            dialog.focus_next();
         }
     }
 
+    /// Replaces the current search query with an older one from the history.
+    fn search_history_previous(&mut self) {
+        let history_len = self.process_search_state.search_history.len();
+        if history_len > 0 {
+            let new_position = match self.process_search_state.search_history_position {
+                Some(position) if position > 0 => position - 1,
+                Some(position) => position,
+                None => history_len - 1,
+            };
+            self.process_search_state.search_history_position = Some(new_position);
+            let query = self.process_search_state.search_history[new_position].clone();
+            self.set_search_query(query);
+        }
+    }
+
+    /// Replaces the current search query with a newer one from the history, or clears it
+    /// if already at the newest entry.
+    fn search_history_next(&mut self) {
+        if let Some(position) = self.process_search_state.search_history_position {
+            if position + 1 < self.process_search_state.search_history.len() {
+                self.process_search_state.search_history_position = Some(position + 1);
+                let query = self.process_search_state.search_history[position + 1].clone();
+                self.set_search_query(query);
+            } else {
+                self.process_search_state.search_history_position = None;
+                self.set_search_query(String::default());
+            }
+        }
+    }
+
+    fn set_search_query(&mut self, query: String) {
+        let query_len = query.len();
+        self.process_search_state.search_state.char_cursor_position =
+            UnicodeWidthStr::width(query.as_str());
+        self.process_search_state.search_state.current_search_query = query;
+        self.process_search_state.search_state.grapheme_cursor =
+            GraphemeCursor::new(query_len, query_len, true);
+        self.process_search_state.search_state.cursor_direction = CursorDirection::RIGHT;
+        self.update_regex();
+        self.force_update_processes = true;
+    }
+
     pub fn on_left_key(&mut self) {
         if !self.is_in_dialog() {
-            if let WidgetPosition::ProcessSearch = self.current_widget_selected {
+            if self.is_time_travelling() && !self.is_in_search_widget() {
+                self.step_time_travel(false);
+            } else if let WidgetPosition::Process = self.current_widget_selected {
+                self.process_column_offset = self.process_column_offset.saturating_sub(1);
+            } else if let WidgetPosition::ProcessSearch = self.current_widget_selected {
                 let prev_cursor = self.get_cursor_position();
                 self.search_walk_back(self.get_cursor_position());
                 if self.get_cursor_position() < prev_cursor {
@@ -859,14 +2206,21 @@ impl App {
                     self.process_search_state.search_state.cursor_direction = CursorDirection::LEFT;
                 }
             }
-        } else if self.delete_dialog_state.is_showing_dd && !self.delete_dialog_state.is_on_yes {
-            self.delete_dialog_state.is_on_yes = true;
+        } else if let Some(dialog) = &mut self.dialog {
+            if !dialog.is_input_focused() {
+                dialog.focus_previous();
+            }
         }
     }
 
     pub fn on_right_key(&mut self) {
         if !self.is_in_dialog() {
-            if let WidgetPosition::ProcessSearch = self.current_widget_selected {
+            if self.is_time_travelling() && !self.is_in_search_widget() {
+                self.step_time_travel(true);
+            } else if let WidgetPosition::Process = self.current_widget_selected {
+                // Bounded by what fits once drawn
+                self.process_column_offset += 1;
+            } else if let WidgetPosition::ProcessSearch = self.current_widget_selected {
                 let prev_cursor = self.get_cursor_position();
                 self.search_walk_forward(self.get_cursor_position());
                 if self.get_cursor_position() > prev_cursor {
@@ -878,8 +2232,10 @@ impl App {
                         CursorDirection::RIGHT;
                 }
             }
-        } else if self.delete_dialog_state.is_showing_dd && self.delete_dialog_state.is_on_yes {
-            self.delete_dialog_state.is_on_yes = false;
+        } else if let Some(dialog) = &mut self.dialog {
+            if !dialog.is_input_focused() {
+                dialog.focus_next();
+            }
         }
     }
 
@@ -927,11 +2283,13 @@ impl App {
     }
 
     pub fn start_dd(&mut self) {
-        if self
-            .app_scroll_positions
-            .process_scroll_state
-            .current_scroll_position
-            < self.canvas_data.finalized_process_data.len() as u64
+        // Killing every process of a user is a bit too much for a single keypress
+        if !self.is_grouped_by_user()
+            && self
+                .app_scroll_positions
+                .process_scroll_state
+                .current_scroll_position
+                < self.canvas_data.finalized_process_data.len() as u64
         {
             let current_process = if self.is_grouped() {
                 let group_pids = &self.canvas_data.finalized_process_data[self
@@ -960,13 +2318,236 @@ impl App {
                 (process.name.clone(), vec![process.pid])
             };
 
-            self.to_delete_process_list = Some(current_process);
-            self.delete_dialog_state.is_showing_dd = true;
+            let message = if self.is_grouped() {
+                if current_process.1.len() != 1 {
+                    format!(
+                        "Kill {} processes with the name {}?",
+                        current_process.1.len(),
+                        current_process.0
+                    )
+                } else {
+                    format!(
+                        "Kill {} process with the name {}?",
+                        current_process.1.len(),
+                        current_process.0
+                    )
+                }
+            } else {
+                format!(
+                    "Kill process {} with PID {}?",
+                    current_process.0,
+                    current_process.1.first().copied().unwrap_or_default()
+                )
+            };
+
+            self.to_delete_process_list = Some(current_process);
+            self.dialog = Some(
+                Dialog::new("Confirm Kill Process", &message)
+                    .button("Yes", DialogAction::KillProcesses)
+                    .button("No", DialogAction::Close)
+                    .focus_button(1),
+            );
+        }
+
+        self.reset_multi_tap_keys();
+    }
+
+    /// Copies the highlighted process' row, as shown, or just its PID to the clipboard.  For a
+    /// group, the PID is every PID in it, separated by spaces.
+    pub fn copy_highlighted_process(&mut self) {
+        let process = match self.canvas_data.finalized_process_data.get(
+            self.app_scroll_positions
+                .process_scroll_state
+                .current_scroll_position as usize,
+        ) {
+            Some(process) => process,
+            None => return,
+        };
+
+        let pid_text = if self.is_grouped() {
+            process
+                .group_pids
+                .iter()
+                .map(|pid| pid.to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        } else {
+            process.pid.to_string()
+        };
+
+        let copied_text = if self.app_config_fields.copy_pid_only {
+            pid_text
+        } else {
+            let mut row = vec![
+                if self.is_grouped() {
+                    process.group_pids.len().to_string()
+                } else {
+                    pid_text
+                },
+                process.name.clone(),
+                format!("{:.1}%", process.cpu_usage),
+                format!("{:.1}%", process.mem_usage),
+            ];
+            if self.app_config_fields.show_process_swap {
+                let (swap_value, swap_unit) = get_exact_byte_values(process.swap_usage, false);
+                row.push(format!("{:.1}{}", swap_value, swap_unit));
+            }
+            if self.app_config_fields.show_process_affinity {
+                row.push(process.cpu_affinity.clone());
+            }
+            row.join("\t")
+        };
+
+        match clipboard::copy_to_clipboard(&copied_text) {
+            Ok(clipboard::ClipboardMethod::Command) => self.notify("Copied to the clipboard"),
+            Ok(clipboard::ClipboardMethod::Osc52) => {
+                self.notify("Sent to the terminal clipboard (OSC 52)")
+            }
+            Err(err) => self.notify_error(&format!("Unable to copy to the clipboard - {}", err)),
+        }
+    }
+
+    /// Hides the next part of the selected graph widget, or shows them all again.
+    pub fn cycle_hidden_graph_parts(&mut self) {
+        let widget = get_graph_widget(self.current_widget_selected);
+        match widget {
+            WidgetPosition::Cpu
+            | WidgetPosition::Mem
+            | WidgetPosition::Network
+            | WidgetPosition::TempGraph
+            | WidgetPosition::DiskIo
+            | WidgetPosition::Gpu
+            | WidgetPosition::Psi
+            | WidgetPosition::Ping
+            | WidgetPosition::Battery => {}
+            _ => return,
+        }
+
+        let hidden_parts = self.app_config_fields.get_hidden_graph_parts(widget).next();
+        self.app_config_fields
+            .hidden_graph_parts
+            .insert(widget, hidden_parts);
+        self.move_off_hidden_cpu_legend();
+    }
+
+    /// Whether the CPU legend is drawn, and so can be selected.
+    fn is_showing_cpu_legend(&self) -> bool {
+        !self
+            .app_config_fields
+            .get_hidden_graph_parts(WidgetPosition::Cpu)
+            .legend
+    }
+
+    fn move_off_hidden_cpu_legend(&mut self) {
+        if let WidgetPosition::CpuLegend = self.current_widget_selected {
+            if !self.is_showing_cpu_legend() {
+                self.current_widget_selected = WidgetPosition::Cpu;
+            }
+        }
+    }
+
+    /// Lists the recent data collection errors, newest first.
+    pub fn show_harvest_errors(&mut self) {
+        let message = if self.harvest_error_state.error_log.is_empty() {
+            "No data has failed to be collected.".to_string()
+        } else {
+            self.harvest_error_state
+                .error_log
+                .iter()
+                .rev()
+                .map(|logged_error| {
+                    let mut line = format!(
+                        "{} {}: {}",
+                        logged_error.last_seen.format("%H:%M:%S"),
+                        logged_error.error.source.get_name(),
+                        logged_error.error.message
+                    );
+                    if logged_error.count > 1 {
+                        line.push_str(&format!(" (x{})", logged_error.count));
+                    }
+                    line
+                })
+                .collect::<Vec<_>>()
+                .join("\n")
+        };
+
+        self.dialog =
+            Some(Dialog::new("Collection Errors", &message).button("Ok", DialogAction::Close));
+        self.reset_multi_tap_keys();
+    }
+
+    /// Opens the environment variable viewer for the currently highlighted process.
+    /// Disabled while grouping, as there is no single process to show.
+    pub fn start_env_dialog(&mut self) {
+        if !self.is_grouped()
+            && self
+                .app_scroll_positions
+                .process_scroll_state
+                .current_scroll_position
+                < self.canvas_data.finalized_process_data.len() as u64
+        {
+            let process = &self.canvas_data.finalized_process_data[self
+                .app_scroll_positions
+                .process_scroll_state
+                .current_scroll_position
+                as usize];
+
+            let (env_vars, env_err) = match processes::get_process_environment(process.pid) {
+                Ok(env_vars) => (env_vars, None),
+                Err(err) => (Vec::new(), Some(err.to_string())),
+            };
+
+            self.env_dialog_state = AppEnvDialogState {
+                is_showing_env: true,
+                pid: process.pid,
+                name: process.name.clone(),
+                env_vars,
+                env_err,
+                ..AppEnvDialogState::default()
+            };
+        }
+
+        self.reset_multi_tap_keys();
+    }
+
+    /// Opens the detail dialog for the currently highlighted disk, reading its S.M.A.R.T. data.
+    pub fn start_disk_detail_dialog(&mut self) {
+        let disk_index = self
+            .get_filtered_disk_rows()
+            .get(
+                self.app_scroll_positions
+                    .disk_scroll_state
+                    .current_scroll_position as usize,
+            )
+            .copied();
+        if let Some(disk_row) = disk_index.and_then(|index| self.canvas_data.disk_data.get(index)) {
+            let (smart, smart_err) = match data_harvester::smart::get_smart_data(&disk_row[0]) {
+                Ok(smart) => (Some(smart), None),
+                Err(err) => (None, Some(err.to_string())),
+            };
+
+            self.disk_detail_dialog_state = AppDiskDetailDialogState {
+                is_showing_disk_detail: true,
+                name: disk_row[0].clone(),
+                mount_point: disk_row[1].clone(),
+                smart,
+                smart_err,
+            };
         }
 
         self.reset_multi_tap_keys();
     }
 
+    /// Gets the default key of the action a key is bound to, so that remapped keys can be
+    /// handled as their defaults.
+    pub fn get_default_key(&self, key: char) -> Option<char> {
+        if self.app_config_fields.keybinds.is_empty() {
+            Some(key)
+        } else {
+            self.app_config_fields.keybinds.get(&key).copied()
+        }
+    }
+
     pub fn on_char_key(&mut self, caught_char: char) {
         // Skip control code chars
         if caught_char.is_control() {
@@ -985,38 +2566,28 @@ impl App {
             }
             self.last_key_press = current_key_press_inst;
 
-            if let WidgetPosition::ProcessSearch = self.current_widget_selected {
-                if UnicodeWidthStr::width(
-                    self.process_search_state
-                        .search_state
-                        .current_search_query
-                        .as_str(),
-                ) <= MAX_SEARCH_LENGTH
+            if self.is_typing_in_table_search() {
+                let is_changed = self
+                    .get_table_search_state_mut()
+                    .map(|search_state| search_state.insert_char(caught_char))
+                    .unwrap_or(false);
+                if is_changed {
+                    self.update_table_search();
+                }
+            } else if let WidgetPosition::ProcessSearch = self.current_widget_selected {
+                if self
+                    .process_search_state
+                    .search_state
+                    .insert_char(caught_char)
                 {
-                    self.process_search_state
-                        .search_state
-                        .current_search_query
-                        .insert(self.get_cursor_position(), caught_char);
-
-                    self.process_search_state.search_state.grapheme_cursor = GraphemeCursor::new(
-                        self.get_cursor_position(),
-                        self.process_search_state
-                            .search_state
-                            .current_search_query
-                            .len(),
-                        true,
-                    );
-                    self.search_walk_forward(self.get_cursor_position());
-
-                    self.process_search_state.search_state.char_cursor_position +=
-                        UnicodeWidthChar::width(caught_char).unwrap_or(0);
-
                     self.update_regex();
                     self.force_update_processes = true;
-                    self.process_search_state.search_state.cursor_direction =
-                        CursorDirection::RIGHT;
                 }
-            } else {
+            } else if ('1'..='9').contains(&caught_char)
+                && !self.app_config_fields.keybinds.contains_key(&caught_char)
+            {
+                self.select_widget_by_number(caught_char as usize - '0' as usize);
+            } else if let Some(caught_char) = self.get_default_key(caught_char) {
                 match caught_char {
                     '/' => {
                         self.on_slash();
@@ -1059,10 +2630,27 @@ impl App {
                     'G' => self.skip_to_last(),
                     'k' => self.decrement_position_count(),
                     'j' => self.increment_position_count(),
+                    'e' => {
+                        if let WidgetPosition::Process = self.current_widget_selected {
+                            self.start_env_dialog();
+                        }
+                    }
                     'f' => {
-                        self.is_frozen = !self.is_frozen;
-                        if self.is_frozen {
-                            self.data_collection.set_frozen_time();
+                        if self.is_time_travelling() {
+                            self.stop_time_travel();
+                        } else {
+                            self.is_frozen = !self.is_frozen;
+                            if self.is_frozen {
+                                self.data_collection.set_frozen_time();
+                            }
+                        }
+                    }
+                    't' => self.toggle_time_travel(),
+                    '!' => self.show_harvest_errors(),
+                    'v' => self.cycle_hidden_graph_parts(),
+                    'y' => {
+                        if let WidgetPosition::Process = self.current_widget_selected {
+                            self.copy_highlighted_process();
                         }
                     }
                     'c' => {
@@ -1097,7 +2685,7 @@ impl App {
                     }
                     'p' => {
                         // Disable if grouping
-                        if !self.enable_grouping {
+                        if !self.is_grouped() {
                             match self.process_sorting_type {
                                 processes::ProcessSorting::PID => {
                                     self.process_sorting_reverse = !self.process_sorting_reverse
@@ -1128,17 +2716,79 @@ impl App {
                             .process_scroll_state
                             .current_scroll_position = 0;
                     }
+                    's' => {
+                        // Only sortable if the column is actually shown
+                        if let WidgetPosition::Mem = self.current_widget_selected {
+                            self.toggle_swap();
+                        } else if let WidgetPosition::Disk = self.current_widget_selected {
+                            self.cycle_disk_sorting();
+                        } else if self.app_config_fields.show_process_swap {
+                            match self.process_sorting_type {
+                                processes::ProcessSorting::SWAP => {
+                                    self.process_sorting_reverse = !self.process_sorting_reverse
+                                }
+                                _ => {
+                                    self.process_sorting_type = processes::ProcessSorting::SWAP;
+                                    self.process_sorting_reverse = true;
+                                }
+                            }
+                            self.force_update_processes = true;
+                            self.app_scroll_positions
+                                .process_scroll_state
+                                .current_scroll_position = 0;
+                        }
+                    }
+                    'S' => {
+                        if let WidgetPosition::Disk = self.current_widget_selected {
+                            self.reverse_disk_sorting();
+                        }
+                    }
+                    'i' => {
+                        if let WidgetPosition::Gpu = self.current_widget_selected {
+                            self.cycle_gpus();
+                        } else {
+                            self.cycle_net_interfaces();
+                        }
+                    }
+                    'u' => self.toggle_user_grouping(),
+                    'N' => self.toggle_grouping(),
+                    'z' => self.toggle_expanded(),
+                    'w' => self.cycle_layouts(true),
+                    'W' => self.cycle_layouts(false),
+                    'a' => self.toggle_all_cpu_cores(),
+                    'A' => self.toggle_cpu_graph_lines(),
+                    'b' => {
+                        if let WidgetPosition::Network = self.current_widget_selected {
+                            self.toggle_network_bits();
+                        } else {
+                            self.toggle_cpu_bars();
+                        }
+                    }
+                    'T' => {
+                        if let WidgetPosition::Process = self.current_widget_selected {
+                            self.app_config_fields.hide_kernel_threads =
+                                !self.app_config_fields.hide_kernel_threads;
+                            self.force_update_processes = true;
+                            self.app_scroll_positions
+                                .process_scroll_state
+                                .current_scroll_position = 0;
+                        }
+                    }
                     '?' => {
                         self.help_dialog_state.is_showing_help = true;
                     }
-                    'H' => self.move_widget_selection_left(),
-                    'L' => self.move_widget_selection_right(),
+                    'H' | 'h' => self.move_widget_selection_left(),
+                    'L' | 'l' => self.move_widget_selection_right(),
                     'K' => self.move_widget_selection_up(),
                     'J' => self.move_widget_selection_down(),
                     ' ' => self.on_space(),
                     '+' => self.zoom_in(),
                     '-' => self.zoom_out(),
                     '=' => self.reset_zoom(),
+                    '<' => self.resize_current_widget(true, false),
+                    '>' => self.resize_current_widget(true, true),
+                    '[' => self.resize_current_widget(false, false),
+                    ']' => self.resize_current_widget(false, true),
                     _ => {}
                 }
 
@@ -1148,31 +2798,207 @@ impl App {
                     }
                 }
             }
+        } else if let Some(dialog) = &mut self.dialog {
+            dialog.on_char(caught_char);
+        } else if self.env_dialog_state.is_showing_env {
+            if UnicodeWidthStr::width(self.env_dialog_state.filter_query.as_str())
+                <= MAX_SEARCH_LENGTH
+            {
+                self.env_dialog_state.filter_query.push(caught_char);
+                self.env_dialog_state.current_scroll_position = 0;
+            }
         } else if self.help_dialog_state.is_showing_help {
-            match caught_char {
-                '1' => self.help_dialog_state.current_category = AppHelpCategory::General,
-                '2' => self.help_dialog_state.current_category = AppHelpCategory::Process,
-                '3' => self.help_dialog_state.current_category = AppHelpCategory::Search,
-                _ => {}
+            if self.help_dialog_state.is_searching {
+                if UnicodeWidthStr::width(self.help_dialog_state.search_query.as_str())
+                    <= MAX_SEARCH_LENGTH
+                {
+                    self.help_dialog_state.search_query.push(caught_char);
+                    self.help_dialog_state.scroll_position = 0;
+                }
+            } else {
+                match caught_char {
+                    '1' => self.change_help_category(AppHelpCategory::General),
+                    '2' => self.change_help_category(AppHelpCategory::Process),
+                    '3' => self.change_help_category(AppHelpCategory::Search),
+                    _ => match self.get_default_key(caught_char) {
+                        Some('/') => self.help_dialog_state.is_searching = true,
+                        Some('k') => self.change_help_position(-1),
+                        Some('j') => self.change_help_position(1),
+                        Some('g') => self.skip_to_first(),
+                        Some('G') => self.skip_to_last(),
+                        _ => {}
+                    },
+                }
             }
         }
     }
 
+    fn change_help_category(&mut self, category: AppHelpCategory) {
+        self.help_dialog_state.current_category = category;
+        self.help_dialog_state.scroll_position = 0;
+    }
+
+    fn change_help_position(&mut self, num_to_change_by: i64) {
+        let num_lines = self
+            .help_dialog_state
+            .get_help_lines(&self.app_config_fields.keybinds)
+            .len() as i64;
+        let new_position = self.help_dialog_state.scroll_position as i64 + num_to_change_by;
+
+        if new_position >= 0 && new_position < num_lines {
+            self.help_dialog_state.scroll_position = new_position as u64;
+        }
+    }
+
     pub fn kill_highlighted_process(&mut self) -> Result<()> {
         // Technically unnecessary but this is a good check...
         if let WidgetPosition::Process = self.current_widget_selected {
-            if let Some(current_selected_processes) = &self.to_delete_process_list {
-                for pid in &current_selected_processes.1 {
+            if let Some((name, pids)) = &self.to_delete_process_list {
+                for pid in pids {
                     process_killer::kill_process_given_pid(*pid)?;
                 }
+                let message = match pids.as_slice() {
+                    [pid] => format!("Killed {} (PID {})", name, pid),
+                    _ => format!("Killed {} processes named {}", pids.len(), name),
+                };
+                self.notify(&message);
             }
             self.to_delete_process_list = None;
         }
         Ok(())
     }
 
-    pub fn get_to_delete_processes(&self) -> Option<(String, Vec<u32>)> {
-        self.to_delete_process_list.clone()
+    /// Briefly shows a message about what an action did.
+    pub fn notify(&mut self, message: &str) {
+        self.toast_state.push(message, false);
+    }
+
+    pub fn get_remote_host(&self) -> Option<&str> {
+        self.remote_host_index
+            .and_then(|index| self.app_config_fields.remote_hosts.get(index))
+            .map(|host| host.as_str())
+    }
+
+    /// Moves on to the next remote host, and after the last, back to this machine.  Gives the
+    /// host switched to, with `None` for this machine, or nothing if there are no remote hosts.
+    pub fn next_remote_host(&mut self) -> Option<Option<String>> {
+        if self.app_config_fields.remote_hosts.is_empty() {
+            self.notify_error("No remote hosts are set, see remote_hosts in the config");
+            return None;
+        }
+
+        self.remote_host_index = match self.remote_host_index {
+            Some(index) if index + 1 < self.app_config_fields.remote_hosts.len() => Some(index + 1),
+            Some(_) => None,
+            None => Some(0),
+        };
+
+        Some(self.get_remote_host().map(|host| host.to_string()))
+    }
+
+    /// Briefly shows why an action failed, without interrupting like a dialog would.
+    pub fn notify_error(&mut self, message: &str) {
+        self.toast_state.push(message, true);
+    }
+
+    fn run_dialog_action(&mut self, action: DialogAction) {
+        match action {
+            DialogAction::Close => {}
+            DialogAction::KillProcesses => {
+                if let Err(dd_err) = self.kill_highlighted_process() {
+                    self.notify_error(&format!(
+                        "Failure to properly kill the process - {}",
+                        dd_err
+                    ));
+                }
+                self.to_delete_process_list = None;
+            }
+        }
+    }
+
+    /// Grows or shrinks the selected widget's width or height.  The new sizes last for the rest
+    /// of the session, or until the config file is reloaded.
+    pub fn resize_current_widget(&mut self, is_horizontal: bool, is_growing: bool) {
+        if !self.is_in_dialog() && !self.is_expanded && !self.app_config_fields.use_basic_mode {
+            let mut layout = match &self.app_config_fields.custom_layout {
+                Some(layout) => layout.clone(),
+                None => BottomLayout::default_layout(&self.app_config_fields),
+            };
+            if layout.resize_widget(self.current_widget_selected, is_horizontal, is_growing) {
+                // Keeps the sizes when switching away from a named layout and back
+                if let Some((_, named_layout)) =
+                    self.app_config_fields.layouts.get_mut(self.current_layout)
+                {
+                    *named_layout = layout.clone();
+                }
+                self.app_config_fields.custom_layout = Some(layout);
+                self.is_resized = true;
+            }
+        }
+
+        self.reset_multi_tap_keys();
+    }
+
+    /// The widgets that can be selected, in the order they're laid out.
+    fn get_widgets_in_layout_order(&self) -> Vec<WidgetPosition> {
+        if self.app_config_fields.use_basic_mode {
+            vec![
+                WidgetPosition::BasicCpu,
+                WidgetPosition::BasicMem,
+                WidgetPosition::BasicNet,
+                WidgetPosition::Temp,
+                WidgetPosition::Disk,
+                WidgetPosition::Process,
+            ]
+        } else {
+            let widgets = match &self.app_config_fields.custom_layout {
+                Some(layout) => layout.get_widgets(),
+                None => BottomLayout::default_layout(&self.app_config_fields).get_widgets(),
+            };
+            widgets
+                .into_iter()
+                .filter(|widget| !self.app_config_fields.hidden_widgets.contains(widget))
+                .collect()
+        }
+    }
+
+    /// Selects the next or previous widget in layout order, wrapping around at either end.
+    /// Unlike moving in a direction, this reaches every widget of any layout.
+    pub fn cycle_widget_selection(&mut self, is_forward: bool) {
+        if !self.is_in_dialog() && !self.is_expanded {
+            let widgets = self.get_widgets_in_layout_order();
+            let current_widget = get_layout_widget(self.current_widget_selected);
+            if let Some(position) = widgets
+                .iter()
+                .position(|widget| get_layout_widget(*widget) == current_widget)
+            {
+                let new_position = if is_forward {
+                    (position + 1) % widgets.len()
+                } else {
+                    (position + widgets.len() - 1) % widgets.len()
+                };
+                self.current_widget_selected = widgets[new_position];
+            } else if let Some(first_widget) = widgets.first() {
+                self.current_widget_selected = *first_widget;
+            }
+        }
+
+        self.reset_multi_tap_keys();
+    }
+
+    /// Selects a widget by its place in layout order, counting from 1.
+    pub fn select_widget_by_number(&mut self, widget_number: usize) {
+        if !self.is_in_dialog() && !self.is_expanded {
+            let widgets = self.get_widgets_in_layout_order();
+            if let Some(widget) = widget_number
+                .checked_sub(1)
+                .and_then(|position| widgets.get(position))
+            {
+                self.current_widget_selected = *widget;
+            }
+        }
+
+        self.reset_multi_tap_keys();
     }
 
     // TODO: [MODULARITY] Do NOT hard code this in thu future!
@@ -1196,24 +3022,66 @@ impl App {
                     WidgetPosition::Temp => WidgetPosition::Process,
                     _ => self.current_widget_selected,
                 };
+            } else if let Some(layout) = &self.app_config_fields.custom_layout {
+                self.current_widget_selected = match self.current_widget_selected {
+                    WidgetPosition::Cpu
+                        if self.app_config_fields.left_legend && self.is_showing_cpu_legend() =>
+                    {
+                        WidgetPosition::CpuLegend
+                    }
+                    WidgetPosition::CpuLegend if !self.app_config_fields.left_legend => {
+                        WidgetPosition::Cpu
+                    }
+                    current_widget => layout
+                        .get_neighbour(current_widget, WidgetDirection::Left)
+                        .unwrap_or(current_widget),
+                };
             } else {
                 self.current_widget_selected = match self.current_widget_selected {
-                    WidgetPosition::Cpu if self.app_config_fields.left_legend => {
+                    WidgetPosition::Cpu
+                        if self.app_config_fields.left_legend && self.is_showing_cpu_legend() =>
+                    {
                         WidgetPosition::CpuLegend
                     }
                     WidgetPosition::CpuLegend if !self.app_config_fields.left_legend => {
                         WidgetPosition::Cpu
                     }
+                    WidgetPosition::Process
+                    | WidgetPosition::ProcessSearch
+                    | WidgetPosition::Sockets
+                        if !self.app_config_fields.ping_hosts.is_empty() =>
+                    {
+                        WidgetPosition::Ping
+                    }
                     WidgetPosition::Process => WidgetPosition::Network,
                     WidgetPosition::ProcessSearch => WidgetPosition::Network,
+                    WidgetPosition::Ping => WidgetPosition::Network,
+                    WidgetPosition::Disk | WidgetPosition::DiskIo | WidgetPosition::Temp
+                        if self.app_config_fields.show_psi =>
+                    {
+                        WidgetPosition::Psi
+                    }
+                    WidgetPosition::TempGraph => WidgetPosition::Temp,
+                    WidgetPosition::Battery => WidgetPosition::Disk,
+                    WidgetPosition::Gpu
+                        if self.app_config_fields.left_legend || !self.is_showing_cpu_legend() =>
+                    {
+                        WidgetPosition::Cpu
+                    }
+                    WidgetPosition::Gpu => WidgetPosition::CpuLegend,
                     WidgetPosition::Disk => WidgetPosition::Mem,
+                    WidgetPosition::DiskIo => WidgetPosition::Mem,
                     WidgetPosition::Temp => WidgetPosition::Mem,
+                    WidgetPosition::Psi => WidgetPosition::Mem,
+                    WidgetPosition::Sockets => WidgetPosition::Network,
                     _ => self.current_widget_selected,
                 };
             }
         } else if self.is_expanded {
             self.current_widget_selected = match self.current_widget_selected {
-                WidgetPosition::Cpu if self.app_config_fields.left_legend => {
+                WidgetPosition::Cpu
+                    if self.app_config_fields.left_legend && self.is_showing_cpu_legend() =>
+                {
                     WidgetPosition::CpuLegend
                 }
                 WidgetPosition::CpuLegend if !self.app_config_fields.left_legend => {
@@ -1237,22 +3105,57 @@ impl App {
                     WidgetPosition::Temp => WidgetPosition::Disk,
                     _ => self.current_widget_selected,
                 };
+            } else if let Some(layout) = &self.app_config_fields.custom_layout {
+                self.current_widget_selected = match self.current_widget_selected {
+                    WidgetPosition::Cpu
+                        if !self.app_config_fields.left_legend && self.is_showing_cpu_legend() =>
+                    {
+                        WidgetPosition::CpuLegend
+                    }
+                    WidgetPosition::CpuLegend if self.app_config_fields.left_legend => {
+                        WidgetPosition::Cpu
+                    }
+                    current_widget => layout
+                        .get_neighbour(current_widget, WidgetDirection::Right)
+                        .unwrap_or(current_widget),
+                };
             } else {
                 self.current_widget_selected = match self.current_widget_selected {
-                    WidgetPosition::Cpu if !self.app_config_fields.left_legend => {
+                    WidgetPosition::Cpu
+                        if !self.app_config_fields.left_legend && self.is_showing_cpu_legend() =>
+                    {
                         WidgetPosition::CpuLegend
                     }
                     WidgetPosition::CpuLegend if self.app_config_fields.left_legend => {
                         WidgetPosition::Cpu
                     }
+                    WidgetPosition::Cpu | WidgetPosition::CpuLegend
+                        if self.app_config_fields.show_gpu =>
+                    {
+                        WidgetPosition::Gpu
+                    }
+                    WidgetPosition::Mem if self.app_config_fields.show_psi => WidgetPosition::Psi,
                     WidgetPosition::Mem => WidgetPosition::Temp,
+                    WidgetPosition::Psi => WidgetPosition::Temp,
+                    WidgetPosition::Temp if self.app_config_fields.show_temp_graph => {
+                        WidgetPosition::TempGraph
+                    }
+                    WidgetPosition::Disk if self.app_config_fields.show_battery => {
+                        WidgetPosition::Battery
+                    }
+                    WidgetPosition::Network if !self.app_config_fields.ping_hosts.is_empty() => {
+                        WidgetPosition::Ping
+                    }
                     WidgetPosition::Network => WidgetPosition::Process,
+                    WidgetPosition::Ping => WidgetPosition::Process,
                     _ => self.current_widget_selected,
                 };
             }
         } else if self.is_expanded {
             self.current_widget_selected = match self.current_widget_selected {
-                WidgetPosition::Cpu if !self.app_config_fields.left_legend => {
+                WidgetPosition::Cpu
+                    if !self.app_config_fields.left_legend && self.is_showing_cpu_legend() =>
+                {
                     WidgetPosition::CpuLegend
                 }
                 WidgetPosition::CpuLegend if self.app_config_fields.left_legend => {
@@ -1280,14 +3183,44 @@ impl App {
                     WidgetPosition::Disk => WidgetPosition::BasicMem,
                     _ => self.current_widget_selected,
                 };
+            } else if let Some(layout) = &self.app_config_fields.custom_layout {
+                self.current_widget_selected = match self.current_widget_selected {
+                    WidgetPosition::ProcessSearch => WidgetPosition::Process,
+                    current_widget => layout
+                        .get_neighbour(current_widget, WidgetDirection::Up)
+                        .unwrap_or(current_widget),
+                };
             } else {
                 self.current_widget_selected = match self.current_widget_selected {
                     WidgetPosition::Mem => WidgetPosition::Cpu,
+                    WidgetPosition::Psi => WidgetPosition::Cpu,
                     WidgetPosition::Network => WidgetPosition::Mem,
+                    WidgetPosition::Ping => WidgetPosition::Mem,
+                    WidgetPosition::Process if self.app_config_fields.show_disk_io_graph => {
+                        WidgetPosition::DiskIo
+                    }
                     WidgetPosition::Process => WidgetPosition::Disk,
+                    WidgetPosition::DiskIo => WidgetPosition::Disk,
                     WidgetPosition::ProcessSearch => WidgetPosition::Process,
+                    WidgetPosition::Temp | WidgetPosition::TempGraph
+                        if self.app_config_fields.show_gpu =>
+                    {
+                        WidgetPosition::Gpu
+                    }
                     WidgetPosition::Temp => WidgetPosition::Cpu,
+                    WidgetPosition::TempGraph => WidgetPosition::Cpu,
                     WidgetPosition::Disk => WidgetPosition::Temp,
+                    WidgetPosition::Battery if self.app_config_fields.show_temp_graph => {
+                        WidgetPosition::TempGraph
+                    }
+                    WidgetPosition::Battery => WidgetPosition::Temp,
+                    WidgetPosition::Sockets => {
+                        if self.is_searching() {
+                            WidgetPosition::ProcessSearch
+                        } else {
+                            WidgetPosition::Process
+                        }
+                    }
                     _ => self.current_widget_selected,
                 };
             }
@@ -1317,19 +3250,42 @@ impl App {
                     }
                     _ => self.current_widget_selected,
                 };
+            } else if let Some(layout) = &self.app_config_fields.custom_layout {
+                self.current_widget_selected = match self.current_widget_selected {
+                    WidgetPosition::Process if self.is_searching() => WidgetPosition::ProcessSearch,
+                    current_widget => layout
+                        .get_neighbour(current_widget, WidgetDirection::Down)
+                        .unwrap_or(current_widget),
+                };
             } else {
                 self.current_widget_selected = match self.current_widget_selected {
                     WidgetPosition::Cpu | WidgetPosition::CpuLegend => WidgetPosition::Mem,
+                    WidgetPosition::Gpu => WidgetPosition::Temp,
                     WidgetPosition::Mem => WidgetPosition::Network,
+                    WidgetPosition::Psi => WidgetPosition::Network,
                     WidgetPosition::Temp => WidgetPosition::Disk,
+                    WidgetPosition::TempGraph => WidgetPosition::Disk,
+                    WidgetPosition::Disk if self.app_config_fields.show_disk_io_graph => {
+                        WidgetPosition::DiskIo
+                    }
                     WidgetPosition::Disk => WidgetPosition::Process,
+                    WidgetPosition::Battery if self.app_config_fields.show_disk_io_graph => {
+                        WidgetPosition::DiskIo
+                    }
+                    WidgetPosition::Battery => WidgetPosition::Process,
+                    WidgetPosition::DiskIo => WidgetPosition::Process,
                     WidgetPosition::Process => {
                         if self.is_searching() {
                             WidgetPosition::ProcessSearch
+                        } else if self.app_config_fields.show_sockets {
+                            WidgetPosition::Sockets
                         } else {
                             WidgetPosition::Process
                         }
                     }
+                    WidgetPosition::ProcessSearch if self.app_config_fields.show_sockets => {
+                        WidgetPosition::Sockets
+                    }
                     _ => self.current_widget_selected,
                 };
             }
@@ -1350,7 +3306,11 @@ impl App {
     }
 
     pub fn skip_to_first(&mut self) {
-        if !self.is_in_dialog() {
+        if self.env_dialog_state.is_showing_env {
+            self.env_dialog_state.current_scroll_position = 0;
+        } else if self.help_dialog_state.is_showing_help {
+            self.help_dialog_state.scroll_position = 0;
+        } else if !self.is_in_dialog() {
             match self.current_widget_selected {
                 WidgetPosition::Process => {
                     self.app_scroll_positions
@@ -1372,6 +3332,11 @@ impl App {
                         .cpu_scroll_state
                         .current_scroll_position = 0
                 }
+                WidgetPosition::Sockets => {
+                    self.app_scroll_positions
+                        .socket_scroll_state
+                        .current_scroll_position = 0
+                }
 
                 _ => {}
             }
@@ -1381,29 +3346,50 @@ impl App {
     }
 
     pub fn skip_to_last(&mut self) {
-        if !self.is_in_dialog() {
+        if self.env_dialog_state.is_showing_env {
+            self.env_dialog_state.current_scroll_position = max(
+                0,
+                self.env_dialog_state.get_filtered_env_vars().len() as i64 - 1,
+            ) as u64;
+        } else if self.help_dialog_state.is_showing_help {
+            self.help_dialog_state.scroll_position = max(
+                0,
+                self.help_dialog_state
+                    .get_help_lines(&self.app_config_fields.keybinds)
+                    .len() as i64
+                    - 1,
+            ) as u64;
+        } else if !self.is_in_dialog() {
             match self.current_widget_selected {
                 WidgetPosition::Process => {
                     self.app_scroll_positions
                         .process_scroll_state
                         .current_scroll_position =
-                        self.canvas_data.finalized_process_data.len() as u64 - 1
+                        (self.canvas_data.finalized_process_data.len() as u64).saturating_sub(1)
                 }
                 WidgetPosition::Temp => {
                     self.app_scroll_positions
                         .temp_scroll_state
                         .current_scroll_position =
-                        self.canvas_data.temp_sensor_data.len() as u64 - 1
+                        max(0, self.get_filtered_temp_rows().len() as i64 - 1) as u64
                 }
                 WidgetPosition::Disk => {
                     self.app_scroll_positions
                         .disk_scroll_state
-                        .current_scroll_position = self.canvas_data.disk_data.len() as u64 - 1
+                        .current_scroll_position =
+                        max(0, self.get_filtered_disk_rows().len() as i64 - 1) as u64
                 }
                 WidgetPosition::CpuLegend => {
                     self.app_scroll_positions
                         .cpu_scroll_state
-                        .current_scroll_position = self.canvas_data.cpu_data.len() as u64 - 1;
+                        .current_scroll_position =
+                        (self.canvas_data.cpu_data.len() as u64).saturating_sub(1);
+                }
+                WidgetPosition::Sockets => {
+                    self.app_scroll_positions
+                        .socket_scroll_state
+                        .current_scroll_position =
+                        max(0, self.get_filtered_socket_rows().len() as i64 - 1) as u64;
                 }
                 _ => {}
             }
@@ -1419,6 +3405,8 @@ impl App {
                 WidgetPosition::Temp => self.change_temp_position(-1),
                 WidgetPosition::Disk => self.change_disk_position(-1),
                 WidgetPosition::CpuLegend => self.change_cpu_table_position(-1),
+                WidgetPosition::Sockets => self.change_socket_position(-1),
+                WidgetPosition::Gpu => self.change_gpu_position(-1),
                 _ => {}
             }
             self.app_scroll_positions.scroll_direction = ScrollDirection::UP;
@@ -1433,6 +3421,8 @@ impl App {
                 WidgetPosition::Temp => self.change_temp_position(1),
                 WidgetPosition::Disk => self.change_disk_position(1),
                 WidgetPosition::CpuLegend => self.change_cpu_table_position(1),
+                WidgetPosition::Sockets => self.change_socket_position(1),
+                WidgetPosition::Gpu => self.change_gpu_position(1),
                 _ => {}
             }
             self.app_scroll_positions.scroll_direction = ScrollDirection::DOWN;
@@ -1440,6 +3430,56 @@ impl App {
         }
     }
 
+    /// Moves the selection of the current table by the rows that fit in it, or half of them,
+    /// stopping at the first or last entry.
+    pub fn scroll_page(&mut self, is_down: bool, is_half_page: bool) {
+        if self.is_in_dialog() {
+            return;
+        }
+
+        let num_entries = match self.current_widget_selected {
+            WidgetPosition::Process => self.canvas_data.finalized_process_data.len() as u64,
+            WidgetPosition::Temp => self.get_filtered_temp_rows().len() as u64,
+            WidgetPosition::Disk => self.get_filtered_disk_rows().len() as u64,
+            WidgetPosition::CpuLegend => {
+                if self.is_filtering_or_searching() {
+                    self.canvas_data.cpu_data.len() as u64
+                } else {
+                    self.cpu_state.num_cpus_shown
+                }
+            }
+            WidgetPosition::Sockets => self.get_filtered_socket_rows().len() as u64,
+            _ => return,
+        };
+
+        let scroll_state = match self.current_widget_selected {
+            WidgetPosition::Process => &mut self.app_scroll_positions.process_scroll_state,
+            WidgetPosition::Temp => &mut self.app_scroll_positions.temp_scroll_state,
+            WidgetPosition::Disk => &mut self.app_scroll_positions.disk_scroll_state,
+            WidgetPosition::CpuLegend => &mut self.app_scroll_positions.cpu_scroll_state,
+            WidgetPosition::Sockets => &mut self.app_scroll_positions.socket_scroll_state,
+            _ => return,
+        };
+
+        let page = if is_half_page {
+            max(1, scroll_state.num_rows / 2)
+        } else {
+            max(1, scroll_state.num_rows)
+        };
+        if is_down {
+            scroll_state.current_scroll_position = std::cmp::min(
+                scroll_state.current_scroll_position + page,
+                num_entries.saturating_sub(1),
+            );
+            self.app_scroll_positions.scroll_direction = ScrollDirection::DOWN;
+        } else {
+            scroll_state.current_scroll_position =
+                scroll_state.current_scroll_position.saturating_sub(page);
+            self.app_scroll_positions.scroll_direction = ScrollDirection::UP;
+        }
+        self.reset_multi_tap_keys();
+    }
+
     fn change_cpu_table_position(&mut self, num_to_change_by: i64) {
         let current_posn = self
             .app_scroll_positions
@@ -1461,6 +3501,18 @@ impl App {
         }
     }
 
+    /// Moves to the previous or next GPU while a single GPU is graphed.
+    fn change_gpu_position(&mut self, num_to_change_by: i64) {
+        if let Some(current_posn) = self.selected_gpu {
+            if current_posn as i64 + num_to_change_by >= 0
+                && current_posn as i64 + num_to_change_by
+                    < self.data_collection.gpu_harvest.len() as i64
+            {
+                self.selected_gpu = Some((current_posn as i64 + num_to_change_by) as usize);
+            }
+        }
+    }
+
     fn change_process_position(&mut self, num_to_change_by: i64) {
         let current_posn = self
             .app_scroll_positions
@@ -1484,8 +3536,7 @@ impl App {
             .current_scroll_position;
 
         if current_posn as i64 + num_to_change_by >= 0
-            && current_posn as i64 + num_to_change_by
-                < self.canvas_data.temp_sensor_data.len() as i64
+            && current_posn as i64 + num_to_change_by < self.get_filtered_temp_rows().len() as i64
         {
             self.app_scroll_positions
                 .temp_scroll_state
@@ -1500,7 +3551,7 @@ impl App {
             .current_scroll_position;
 
         if current_posn as i64 + num_to_change_by >= 0
-            && current_posn as i64 + num_to_change_by < self.canvas_data.disk_data.len() as i64
+            && current_posn as i64 + num_to_change_by < self.get_filtered_disk_rows().len() as i64
         {
             self.app_scroll_positions
                 .disk_scroll_state
@@ -1508,8 +3559,27 @@ impl App {
         }
     }
 
+    fn change_socket_position(&mut self, num_to_change_by: i64) {
+        let current_posn = self
+            .app_scroll_positions
+            .socket_scroll_state
+            .current_scroll_position;
+
+        if current_posn as i64 + num_to_change_by >= 0
+            && current_posn as i64 + num_to_change_by < self.get_filtered_socket_rows().len() as i64
+        {
+            self.app_scroll_positions
+                .socket_scroll_state
+                .current_scroll_position = (current_posn as i64 + num_to_change_by) as u64;
+        }
+    }
+
     pub fn handle_scroll_up(&mut self) {
-        if self.current_widget_selected.is_widget_graph() {
+        if self.env_dialog_state.is_showing_env {
+            self.env_dialog_state.change_position(-1);
+        } else if self.help_dialog_state.is_showing_help {
+            self.change_help_position(-1);
+        } else if self.current_widget_selected.is_widget_graph() {
             self.zoom_in();
         } else if self.current_widget_selected.is_widget_table() {
             self.decrement_position_count();
@@ -1517,7 +3587,11 @@ impl App {
     }
 
     pub fn handle_scroll_down(&mut self) {
-        if self.current_widget_selected.is_widget_graph() {
+        if self.env_dialog_state.is_showing_env {
+            self.env_dialog_state.change_position(1);
+        } else if self.help_dialog_state.is_showing_help {
+            self.change_help_position(1);
+        } else if self.current_widget_selected.is_widget_graph() {
             self.zoom_out();
         } else if self.current_widget_selected.is_widget_table() {
             self.increment_position_count();
@@ -1529,14 +3603,16 @@ impl App {
             WidgetPosition::Cpu => {
                 let new_time =
                     self.cpu_state.current_display_time + self.app_config_fields.time_interval;
-                if new_time <= constants::STALE_MAX_MILLISECONDS {
+                if new_time <= self.app_config_fields.retention_time {
                     self.cpu_state.current_display_time = new_time;
                     self.cpu_state.force_update = true;
                     if self.app_config_fields.autohide_time {
                         self.cpu_state.autohide_timer = Some(Instant::now());
                     }
-                } else if self.cpu_state.current_display_time != constants::STALE_MAX_MILLISECONDS {
-                    self.cpu_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
+                } else if self.cpu_state.current_display_time
+                    != self.app_config_fields.retention_time
+                {
+                    self.cpu_state.current_display_time = self.app_config_fields.retention_time;
                     self.cpu_state.force_update = true;
                     if self.app_config_fields.autohide_time {
                         self.cpu_state.autohide_timer = Some(Instant::now());
@@ -1546,14 +3622,16 @@ impl App {
             WidgetPosition::Mem => {
                 let new_time =
                     self.mem_state.current_display_time + self.app_config_fields.time_interval;
-                if new_time <= constants::STALE_MAX_MILLISECONDS {
+                if new_time <= self.app_config_fields.retention_time {
                     self.mem_state.current_display_time = new_time;
                     self.mem_state.force_update = true;
                     if self.app_config_fields.autohide_time {
                         self.mem_state.autohide_timer = Some(Instant::now());
                     }
-                } else if self.mem_state.current_display_time != constants::STALE_MAX_MILLISECONDS {
-                    self.mem_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
+                } else if self.mem_state.current_display_time
+                    != self.app_config_fields.retention_time
+                {
+                    self.mem_state.current_display_time = self.app_config_fields.retention_time;
                     self.mem_state.force_update = true;
                     if self.app_config_fields.autohide_time {
                         self.mem_state.autohide_timer = Some(Instant::now());
@@ -1563,21 +3641,30 @@ impl App {
             WidgetPosition::Network => {
                 let new_time =
                     self.net_state.current_display_time + self.app_config_fields.time_interval;
-                if new_time <= constants::STALE_MAX_MILLISECONDS {
+                if new_time <= self.app_config_fields.retention_time {
                     self.net_state.current_display_time = new_time;
                     self.net_state.force_update = true;
                     if self.app_config_fields.autohide_time {
                         self.net_state.autohide_timer = Some(Instant::now());
                     }
-                } else if self.net_state.current_display_time != constants::STALE_MAX_MILLISECONDS {
-                    self.net_state.current_display_time = constants::STALE_MAX_MILLISECONDS;
+                } else if self.net_state.current_display_time
+                    != self.app_config_fields.retention_time
+                {
+                    self.net_state.current_display_time = self.app_config_fields.retention_time;
                     self.net_state.force_update = true;
                     if self.app_config_fields.autohide_time {
                         self.net_state.autohide_timer = Some(Instant::now());
                     }
                 }
             }
-            _ => {}
+            widget => {
+                let time_interval = self.app_config_fields.time_interval;
+                let retention_time = self.app_config_fields.retention_time;
+                let autohide_time = self.app_config_fields.autohide_time;
+                if let Some(graph_state) = self.get_graph_state_mut(widget) {
+                    graph_state.zoom_out(time_interval, retention_time, autohide_time);
+                }
+            }
         }
     }
 
@@ -1634,12 +3721,31 @@ impl App {
                     }
                 }
             }
-            _ => {}
+            widget => {
+                let time_interval = self.app_config_fields.time_interval;
+                let autohide_time = self.app_config_fields.autohide_time;
+                if let Some(graph_state) = self.get_graph_state_mut(widget) {
+                    graph_state.zoom_in(time_interval, autohide_time);
+                }
+            }
+        }
+    }
+
+    /// The state of a graph that only tracks its zoom, so they can all be zoomed the same way.
+    fn get_graph_state_mut(&mut self, widget: WidgetPosition) -> Option<&mut GraphState> {
+        match widget {
+            WidgetPosition::Psi => Some(&mut self.psi_state),
+            WidgetPosition::Ping => Some(&mut self.ping_state),
+            WidgetPosition::DiskIo => Some(&mut self.disk_io_state),
+            WidgetPosition::TempGraph => Some(&mut self.temp_graph_state),
+            WidgetPosition::Battery => Some(&mut self.battery_state),
+            WidgetPosition::Gpu => Some(&mut self.gpu_state),
+            _ => None,
         }
     }
 
     fn reset_cpu_zoom(&mut self) {
-        self.cpu_state.current_display_time = self.app_config_fields.default_time_value;
+        self.cpu_state.current_display_time = self.app_config_fields.cpu_default_time_value;
         self.cpu_state.force_update = true;
         if self.app_config_fields.autohide_time {
             self.cpu_state.autohide_timer = Some(Instant::now());
@@ -1647,7 +3753,7 @@ impl App {
     }
 
     fn reset_mem_zoom(&mut self) {
-        self.mem_state.current_display_time = self.app_config_fields.default_time_value;
+        self.mem_state.current_display_time = self.app_config_fields.mem_default_time_value;
         self.mem_state.force_update = true;
         if self.app_config_fields.autohide_time {
             self.mem_state.autohide_timer = Some(Instant::now());
@@ -1655,7 +3761,7 @@ impl App {
     }
 
     fn reset_net_zoom(&mut self) {
-        self.net_state.current_display_time = self.app_config_fields.default_time_value;
+        self.net_state.current_display_time = self.app_config_fields.net_default_time_value;
         self.net_state.force_update = true;
         if self.app_config_fields.autohide_time {
             self.net_state.autohide_timer = Some(Instant::now());
@@ -1667,7 +3773,12 @@ impl App {
             WidgetPosition::Cpu => self.reset_cpu_zoom(),
             WidgetPosition::Mem => self.reset_mem_zoom(),
             WidgetPosition::Network => self.reset_net_zoom(),
-            _ => {}
+            widget => {
+                let autohide_time = self.app_config_fields.autohide_time;
+                if let Some(graph_state) = self.get_graph_state_mut(widget) {
+                    graph_state.reset_zoom(autohide_time);
+                }
+            }
         }
     }
 }
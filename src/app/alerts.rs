@@ -0,0 +1,516 @@
+//! Rules from the config file checked against every harvest, like `cpu_total > 90 for 30s`.
+//! Once one has held for long enough, it fires: the widget it's about is marked, and it can run
+//! a command or send a desktop notification.  It fires again only after it stops holding.
+//!
+//! A rule is a metric, a comparison, and a threshold, with an optional `for` and a duration:
+//!
+//! - `cpu_total`, the average CPU usage as a percentage.
+//! - `mem used` and `swap used`, as a percentage of the total.
+//! - `disk "<mount point>" used`, as a percentage of the disk's size.
+//! - `temp "<sensor>"`, in the temperature type shown.
+//! - `load`, the 1 minute load average.
+//!
+//! Commands are arbitrary: they're taken as written from the config file and run through
+//! `sh -c`, or `cmd /C` on Windows, as the user running bottom.  Nothing here checks or escapes
+//! them, so the config file is trusted as much as the user's shell.
+
+use std::{
+    process::{Child, Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    app::{data_harvester::Data, WidgetPosition},
+    utils::error::{self, BottomError},
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum AlertMetric {
+    CpuTotal,
+    MemUsed,
+    SwapUsed,
+    /// By mount point.
+    DiskUsed(String),
+    /// By sensor name.
+    Temperature(String),
+    Load,
+}
+
+impl AlertMetric {
+    fn is_percentage(&self) -> bool {
+        match self {
+            AlertMetric::Temperature(_) | AlertMetric::Load => false,
+            _ => true,
+        }
+    }
+
+    fn get_value(&self, data: &Data) -> Option<f64> {
+        match self {
+            // The average is always the first entry
+            AlertMetric::CpuTotal => data.cpu.first().map(|cpu| cpu.cpu_usage),
+            AlertMetric::MemUsed => {
+                get_percentage(data.memory.mem_used_in_mb, data.memory.mem_total_in_mb)
+            }
+            AlertMetric::SwapUsed => {
+                get_percentage(data.swap.mem_used_in_mb, data.swap.mem_total_in_mb)
+            }
+            AlertMetric::DiskUsed(mount_point) => data
+                .disks
+                .iter()
+                .find(|disk| disk.mount_point == *mount_point)
+                .and_then(|disk| get_percentage(disk.used_space, disk.total_space)),
+            AlertMetric::Temperature(sensor_name) => data
+                .temperature_sensors
+                .iter()
+                .find(|sensor| sensor.component_name == *sensor_name)
+                .map(|sensor| f64::from(sensor.temperature)),
+            AlertMetric::Load => {
+                if cfg!(unix) {
+                    Some(data.load_avg[0])
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    /// Whether the widget shows this metric, and so should be marked when it fires.
+    fn is_shown_in(&self, widget: WidgetPosition) -> bool {
+        match self {
+            AlertMetric::CpuTotal | AlertMetric::Load => match widget {
+                WidgetPosition::Cpu | WidgetPosition::CpuLegend | WidgetPosition::BasicCpu => true,
+                _ => false,
+            },
+            AlertMetric::MemUsed | AlertMetric::SwapUsed => match widget {
+                WidgetPosition::Mem | WidgetPosition::BasicMem => true,
+                _ => false,
+            },
+            AlertMetric::DiskUsed(_) => widget == WidgetPosition::Disk,
+            AlertMetric::Temperature(_) => match widget {
+                WidgetPosition::Temp | WidgetPosition::TempGraph => true,
+                _ => false,
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AlertComparison {
+    Above,
+    AtLeast,
+    Below,
+    AtMost,
+}
+
+impl AlertComparison {
+    fn holds(self, value: f64, threshold: f64) -> bool {
+        match self {
+            AlertComparison::Above => value > threshold,
+            AlertComparison::AtLeast => value >= threshold,
+            AlertComparison::Below => value < threshold,
+            AlertComparison::AtMost => value <= threshold,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AlertRule {
+    /// As written in the config file, for showing when it fires.
+    pub rule: String,
+    pub metric: AlertMetric,
+    pub comparison: AlertComparison,
+    pub threshold: f64,
+    /// How long it has to hold before firing.
+    pub duration: Duration,
+    /// Run through the shell as written when it fires, so this is any command at all.
+    pub command: Option<String>,
+    pub desktop_notification: bool,
+}
+
+impl AlertRule {
+    pub fn parse(
+        rule: &str, command: Option<String>, desktop_notification: bool,
+    ) -> error::Result<Self> {
+        let invalid_rule = |reason: &str| {
+            BottomError::ConfigError(format!("Invalid alert rule \"{}\": {}", rule, reason))
+        };
+
+        let tokens = tokenize(rule).ok_or_else(|| invalid_rule("a quote is never closed"))?;
+        let mut tokens = tokens.iter().map(|token| token.as_str());
+        let expect_used = |tokens: &mut dyn Iterator<Item = &str>| match tokens.next() {
+            Some("used") => Ok(()),
+            _ => Err(invalid_rule("expected \"used\" after the metric")),
+        };
+        let metric = match tokens.next() {
+            Some("cpu_total") => AlertMetric::CpuTotal,
+            Some("load") => AlertMetric::Load,
+            Some("mem") => {
+                expect_used(&mut tokens)?;
+                AlertMetric::MemUsed
+            }
+            Some("swap") => {
+                expect_used(&mut tokens)?;
+                AlertMetric::SwapUsed
+            }
+            Some("disk") => {
+                let mount_point = tokens
+                    .next()
+                    .ok_or_else(|| invalid_rule("expected a quoted mount point after \"disk\""))?;
+                expect_used(&mut tokens)?;
+                AlertMetric::DiskUsed(mount_point.to_string())
+            }
+            Some("temp") => {
+                let sensor_name = tokens
+                    .next()
+                    .ok_or_else(|| invalid_rule("expected a quoted sensor name after \"temp\""))?;
+                AlertMetric::Temperature(sensor_name.to_string())
+            }
+            _ => {
+                return Err(invalid_rule(
+                    "expected one of cpu_total, mem, swap, disk, temp, or load",
+                ))
+            }
+        };
+
+        let comparison = match tokens.next() {
+            Some(">") => AlertComparison::Above,
+            Some(">=") => AlertComparison::AtLeast,
+            Some("<") => AlertComparison::Below,
+            Some("<=") => AlertComparison::AtMost,
+            _ => return Err(invalid_rule("expected one of >, >=, <, or <=")),
+        };
+
+        let threshold_text = tokens
+            .next()
+            .ok_or_else(|| invalid_rule("expected a threshold"))?;
+        let is_percentage = threshold_text.ends_with('%');
+        let threshold_text = threshold_text.trim_end_matches('%');
+        if is_percentage && !metric.is_percentage() {
+            return Err(invalid_rule(
+                "only cpu_total, mem, swap, and disk are percentages",
+            ));
+        }
+        let threshold = threshold_text
+            .parse::<f64>()
+            .map_err(|_| invalid_rule("the threshold isn't a number"))?;
+
+        let duration = match tokens.next() {
+            Some("for") => {
+                let duration_text = tokens
+                    .next()
+                    .ok_or_else(|| invalid_rule("expected a duration after \"for\""))?;
+                parse_duration(duration_text).ok_or_else(|| {
+                    invalid_rule("the duration should be a number of seconds, minutes, or hours, like 30s, 5m, or 1h")
+                })?
+            }
+            Some(_) => return Err(invalid_rule("expected \"for\" after the threshold")),
+            None => Duration::from_secs(0),
+        };
+        if tokens.next().is_some() {
+            return Err(invalid_rule("expected nothing after the duration"));
+        }
+
+        Ok(AlertRule {
+            rule: rule.to_string(),
+            metric,
+            comparison,
+            threshold,
+            duration,
+            command,
+            desktop_notification,
+        })
+    }
+}
+
+/// Something to tell the user about an alert.
+pub struct AlertEvent {
+    pub message: String,
+    pub is_error: bool,
+}
+
+/// Where each rule is at, by the rule's index.
+#[derive(Default)]
+pub struct AlertState {
+    /// When each rule started holding, if it is.
+    holding_since: Vec<Option<Instant>>,
+    is_firing: Vec<bool>,
+    /// Commands and notifications still running, kept to be reaped once done.
+    running_actions: Vec<Child>,
+}
+
+impl AlertState {
+    /// Checks every rule against a harvest, running the actions of those that fire.
+    pub fn update(&mut self, rules: &[AlertRule], data: &Data) -> Vec<AlertEvent> {
+        if self.holding_since.len() != rules.len() {
+            self.holding_since = vec![None; rules.len()];
+            self.is_firing = vec![false; rules.len()];
+        }
+        self.running_actions = self
+            .running_actions
+            .drain(..)
+            .filter_map(|mut child| match child.try_wait() {
+                Ok(None) => Some(child),
+                Ok(Some(_)) | Err(_) => None,
+            })
+            .collect();
+
+        let mut events = Vec::new();
+        let now = Instant::now();
+        for (index, rule) in rules.iter().enumerate() {
+            let value = rule.metric.get_value(data);
+            let holds = value.map_or(false, |value| rule.comparison.holds(value, rule.threshold));
+            if !holds {
+                self.holding_since[index] = None;
+                if self.is_firing[index] {
+                    self.is_firing[index] = false;
+                    events.push(AlertEvent {
+                        message: format!("Alert cleared: {}", rule.rule),
+                        is_error: false,
+                    });
+                }
+                continue;
+            }
+
+            let holding_since = *self.holding_since[index].get_or_insert(now);
+            if self.is_firing[index] || now.duration_since(holding_since) < rule.duration {
+                continue;
+            }
+
+            self.is_firing[index] = true;
+            let message = format!("Alert: {}", rule.rule);
+            let value = value.unwrap_or_default();
+            if let Some(command) = &rule.command {
+                match run_command(command, rule, value) {
+                    Ok(child) => self.running_actions.push(child),
+                    Err(err) => events.push(AlertEvent {
+                        message: format!(
+                            "Unable to run the command for \"{}\" - {}",
+                            rule.rule, err
+                        ),
+                        is_error: true,
+                    }),
+                }
+            }
+            if rule.desktop_notification {
+                match send_desktop_notification(&message) {
+                    Ok(child) => self.running_actions.push(child),
+                    Err(err) => events.push(AlertEvent {
+                        message: format!("Unable to send a desktop notification - {}", err),
+                        is_error: true,
+                    }),
+                }
+            }
+            events.push(AlertEvent {
+                message,
+                is_error: true,
+            });
+        }
+
+        events
+    }
+
+    /// Starts every rule over, for when they've changed.  Rules are tracked by index, so one
+    /// could otherwise carry over another's state.
+    pub fn forget_rules(&mut self) {
+        self.holding_since.clear();
+        self.is_firing.clear();
+    }
+
+    /// Gets a firing rule about what the widget shows, if there is one.
+    pub fn get_widget_alert<'a>(
+        &self, rules: &'a [AlertRule], widget: WidgetPosition,
+    ) -> Option<&'a AlertRule> {
+        rules
+            .iter()
+            .zip(&self.is_firing)
+            .find(|(rule, is_firing)| **is_firing && rule.metric.is_shown_in(widget))
+            .map(|(rule, _is_firing)| rule)
+    }
+}
+
+fn get_percentage(used: u64, total: u64) -> Option<f64> {
+    if total == 0 {
+        None
+    } else {
+        Some(used as f64 / total as f64 * 100.0)
+    }
+}
+
+/// Splits on whitespace, keeping quoted text together without its quotes.  Gives `None` if a
+/// quote isn't closed.
+fn tokenize(rule: &str) -> Option<Vec<String>> {
+    let mut tokens = Vec::new();
+    let mut chars = rule.chars().peekable();
+    while let Some(&next_char) = chars.peek() {
+        if next_char.is_whitespace() {
+            chars.next();
+        } else if next_char == '"' {
+            chars.next();
+            let mut token = String::new();
+            loop {
+                match chars.next()? {
+                    '"' => break,
+                    quoted_char => token.push(quoted_char),
+                }
+            }
+            tokens.push(token);
+        } else {
+            let mut token = String::new();
+            while let Some(&token_char) = chars.peek() {
+                if token_char.is_whitespace() {
+                    break;
+                }
+                token.push(token_char);
+                chars.next();
+            }
+            tokens.push(token);
+        }
+    }
+
+    Some(tokens)
+}
+
+fn parse_duration(duration_text: &str) -> Option<Duration> {
+    let (unit_start, _unit) = duration_text.char_indices().last()?;
+    let (amount, unit) = duration_text.split_at(unit_start);
+    let amount = amount.parse::<u64>().ok()?;
+    match unit {
+        "s" => Some(Duration::from_secs(amount)),
+        "m" => Some(Duration::from_secs(amount * 60)),
+        "h" => Some(Duration::from_secs(amount * 60 * 60)),
+        _ => None,
+    }
+}
+
+/// Runs the command through `sh -c` or `cmd /C`, with the rule and the value that set it off in
+/// `BOTTOM_ALERT` and `BOTTOM_ALERT_VALUE`.  The command isn't checked or escaped in any way.
+fn run_command(command: &str, rule: &AlertRule, value: f64) -> std::io::Result<Child> {
+    let mut shell = if cfg!(target_os = "windows") {
+        let mut shell = Command::new("cmd");
+        shell.arg("/C");
+        shell
+    } else {
+        let mut shell = Command::new("sh");
+        shell.arg("-c");
+        shell
+    };
+
+    shell
+        .arg(command)
+        .env("BOTTOM_ALERT", &rule.rule)
+        .env("BOTTOM_ALERT_VALUE", format!("{:.1}", value))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+/// Sends a notification through `notify-send`, or on macOS, `osascript`.
+fn send_desktop_notification(message: &str) -> std::io::Result<Child> {
+    let mut notifier = if cfg!(target_os = "macos") {
+        let mut notifier = Command::new("osascript");
+        notifier.arg("-e").arg(format!(
+            "display notification \"{}\" with title \"bottom\"",
+            message.replace('\\', "\\\\").replace('"', "\\\"")
+        ));
+        notifier
+    } else {
+        let mut notifier = Command::new("notify-send");
+        notifier.arg("bottom").arg(message);
+        notifier
+    };
+
+    notifier
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(rule: &str) -> error::Result<AlertRule> {
+        AlertRule::parse(rule, None, false)
+    }
+
+    fn get_error(rule: &str) -> String {
+        match parse(rule) {
+            Ok(_) => panic!("\"{}\" should be invalid", rule),
+            Err(err) => err.to_string(),
+        }
+    }
+
+    #[test]
+    fn test_valid_rules() {
+        let rule = parse("cpu_total > 90 for 30s").unwrap();
+        assert_eq!(rule.metric, AlertMetric::CpuTotal);
+        assert_eq!(rule.comparison, AlertComparison::Above);
+        assert_eq!(rule.threshold, 90.0);
+        assert_eq!(rule.duration, Duration::from_secs(30));
+
+        let rule = parse("mem used >= 80% for 5m").unwrap();
+        assert_eq!(rule.metric, AlertMetric::MemUsed);
+        assert_eq!(rule.comparison, AlertComparison::AtLeast);
+        assert_eq!(rule.threshold, 80.0);
+        assert_eq!(rule.duration, Duration::from_secs(5 * 60));
+
+        let rule = parse("swap used < 10").unwrap();
+        assert_eq!(rule.metric, AlertMetric::SwapUsed);
+        assert_eq!(rule.comparison, AlertComparison::Below);
+        assert_eq!(rule.duration, Duration::from_secs(0));
+
+        let rule = parse(r#"disk "/mnt/my disk" used > 95% for 1h"#).unwrap();
+        assert_eq!(
+            rule.metric,
+            AlertMetric::DiskUsed("/mnt/my disk".to_string())
+        );
+        assert_eq!(rule.duration, Duration::from_secs(60 * 60));
+
+        let rule = parse(r#"temp "CPU Package" <= 85.5"#).unwrap();
+        assert_eq!(
+            rule.metric,
+            AlertMetric::Temperature("CPU Package".to_string())
+        );
+        assert_eq!(rule.comparison, AlertComparison::AtMost);
+        assert_eq!(rule.threshold, 85.5);
+
+        let rule = parse("  load  >  4  ").unwrap();
+        assert_eq!(rule.metric, AlertMetric::Load);
+        assert_eq!(rule.threshold, 4.0);
+        assert_eq!(rule.rule, "  load  >  4  ");
+    }
+
+    #[test]
+    fn test_unknown_metric() {
+        assert!(get_error("gpu_total > 90").contains("expected one of cpu_total"));
+        assert!(get_error("").contains("expected one of cpu_total"));
+        assert!(get_error("mem > 90").contains("expected \"used\" after the metric"));
+        assert!(get_error("disk").contains("expected a quoted mount point"));
+        assert!(get_error("temp").contains("expected a quoted sensor name"));
+        assert!(get_error(r#"temp "CPU > 90"#).contains("a quote is never closed"));
+    }
+
+    #[test]
+    fn test_bad_comparator() {
+        assert!(get_error("cpu_total => 90").contains("expected one of >, >=, <, or <="));
+        assert!(get_error("cpu_total == 90").contains("expected one of >, >=, <, or <="));
+        assert!(get_error("cpu_total").contains("expected one of >, >=, <, or <="));
+    }
+
+    #[test]
+    fn test_missing_threshold() {
+        assert!(get_error("cpu_total >").contains("expected a threshold"));
+        assert!(get_error("cpu_total > ninety").contains("the threshold isn't a number"));
+        assert!(get_error("load > 4%").contains("only cpu_total, mem, swap, and disk"));
+    }
+
+    #[test]
+    fn test_trailing_tokens() {
+        assert!(get_error("cpu_total > 90 30s").contains("expected \"for\" after the threshold"));
+        assert!(get_error("cpu_total > 90 for 30s please").contains("expected nothing after"));
+        assert!(get_error("cpu_total > 90 for").contains("expected a duration"));
+        assert!(get_error("cpu_total > 90 for 30d").contains("the duration should be"));
+    }
+}
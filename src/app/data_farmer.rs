@@ -12,25 +12,79 @@
 /// call the purging function.  Failure to do so *will* result in a growing
 /// memory usage and higher CPU usage - you will be trying to process more and
 /// more points as this is used!
+use std::collections::HashMap;
+use std::rc::Rc;
 use std::time::Instant;
 use std::vec::Vec;
 
-use crate::data_harvester::{cpu, disks, mem, network, processes, temperature, Data};
+use crate::constants::MAX_TIME_TRAVEL_SNAPSHOTS;
+use crate::data_harvester::{
+    batteries, cpu, disks, gpu, mem, network, ping, processes,
+    psi::{self, PSI_SERIES_NAMES},
+    sockets, temperature, Data,
+};
 
 pub type TimeOffset = f64;
 pub type Value = f64;
 pub type JoinedDataPoints = (Value, Vec<(TimeOffset, Value)>);
+/// Points keyed by what they're from, like a sensor or host, so each line follows it even when
+/// the list it's in changes.  The keys come from `get_series_keys`.
+pub type NamedDataPoints = HashMap<String, JoinedDataPoints>;
 
 #[derive(Debug, Default)]
 pub struct TimedData {
     pub rx_data: JoinedDataPoints,
     pub tx_data: JoinedDataPoints,
+    pub interface_rx_data: Vec<JoinedDataPoints>,
+    pub interface_tx_data: Vec<JoinedDataPoints>,
     pub cpu_data: Vec<JoinedDataPoints>,
     pub mem_data: JoinedDataPoints,
     pub swap_data: JoinedDataPoints,
-    // Unused for now
-    // pub io_data : JoinedDataPoints
-    // pub temp_data: JoinedDataPoints,
+    /// The VRAM usage of each GPU.
+    pub vram_data: NamedDataPoints,
+    pub numa_data: Vec<JoinedDataPoints>,
+    pub commit_data: Vec<JoinedDataPoints>,
+    pub psi_data: NamedDataPoints,
+    pub ping_data: NamedDataPoints,
+    /// The read and write rates summed over every disk.
+    pub io_data: Vec<JoinedDataPoints>,
+    pub temp_data: NamedDataPoints,
+    /// The utilization of each GPU.
+    pub gpu_data: NamedDataPoints,
+    /// The power drawn from, or charging, every battery combined.
+    pub power_data: Vec<JoinedDataPoints>,
+    /// The tables and labels as of this entry, for time travel.  Only the latest entries
+    /// (and the one being looked at) keep one.
+    pub snapshot: Option<Box<HarvestSnapshot>>,
+}
+
+/// The latest harvests of a `DataCollection` at some point, which are swapped in to show the
+/// tables and labels of that point while time travelling.
+#[derive(Debug, Default, Clone)]
+pub struct HarvestSnapshot {
+    pub network_harvest: network::NetworkHarvest,
+    pub memory_harvest: mem::MemHarvest,
+    pub swap_harvest: mem::MemHarvest,
+    pub compressed_mem_harvest: Vec<mem::CompressedMemHarvest>,
+    pub arc_harvest: Option<u64>,
+    pub huge_pages_harvest: Option<mem::HugePagesHarvest>,
+    pub gpu_harvest: Vec<gpu::GpuHarvest>,
+    pub numa_harvest: Vec<mem::MemHarvest>,
+    pub commit_harvest: Option<mem::MemHarvest>,
+    pub psi_harvest: Option<psi::PsiHarvest>,
+    pub ping_harvest: Vec<ping::PingHarvest>,
+    pub cpu_harvest: cpu::CPUHarvest,
+    pub load_avg_harvest: cpu::LoadAvgHarvest,
+    /// In seconds.
+    pub uptime_harvest: u64,
+    pub process_harvest: Rc<Vec<processes::ProcessHarvest>>,
+    pub socket_harvest: Rc<Vec<sockets::SocketHarvest>>,
+    pub disk_harvest: Vec<disks::DiskHarvest>,
+    pub io_labels: HashMap<String, (u64, u64)>,
+    pub io_utilization: HashMap<String, f64>,
+    pub temp_harvest: Vec<temperature::TempHarvest>,
+    pub fan_harvest: Vec<temperature::FanHarvest>,
+    pub battery_harvest: Vec<batteries::BatteryHarvest>,
 }
 
 /// AppCollection represents the pooled data stored within the main app
@@ -46,17 +100,38 @@ pub struct TimedData {
 pub struct DataCollection {
     pub current_instant: Instant,
     pub frozen_instant: Option<Instant>,
+    /// The entry whose snapshot is swapped in while time travelling.
+    pub time_travel_instant: Option<Instant>,
     pub timed_data_vec: Vec<(Instant, TimedData)>,
     pub network_harvest: network::NetworkHarvest,
     pub memory_harvest: mem::MemHarvest,
     pub swap_harvest: mem::MemHarvest,
+    pub compressed_mem_harvest: Vec<mem::CompressedMemHarvest>,
+    pub arc_harvest: Option<u64>,
+    pub huge_pages_harvest: Option<mem::HugePagesHarvest>,
+    pub gpu_harvest: Vec<gpu::GpuHarvest>,
+    pub numa_harvest: Vec<mem::MemHarvest>,
+    pub commit_harvest: Option<mem::MemHarvest>,
+    pub psi_harvest: Option<psi::PsiHarvest>,
+    pub ping_harvest: Vec<ping::PingHarvest>,
     pub cpu_harvest: cpu::CPUHarvest,
-    pub process_harvest: Vec<processes::ProcessHarvest>,
+    pub load_avg_harvest: cpu::LoadAvgHarvest,
+    /// In seconds.
+    pub uptime_harvest: u64,
+    /// Shared with the snapshots taken for time travel, rather than copied into each one.
+    pub process_harvest: Rc<Vec<processes::ProcessHarvest>>,
+    pub socket_harvest: Rc<Vec<sockets::SocketHarvest>>,
     pub disk_harvest: Vec<disks::DiskHarvest>,
     pub io_harvest: disks::IOHarvest,
-    pub io_labels: Vec<(u64, u64)>,
-    io_prev: Vec<(u64, u64)>,
+    /// The read and write bytes per second of each device, keyed by its name without the path.
+    pub io_labels: HashMap<String, (u64, u64)>,
+    /// The percentage of time each device was busy doing IO since the last harvest, keyed like
+    /// `io_labels`.  Only found within Linux.
+    pub io_utilization: HashMap<String, f64>,
+    io_prev: HashMap<String, (u64, u64, Option<u64>)>,
     pub temp_harvest: Vec<temperature::TempHarvest>,
+    pub fan_harvest: Vec<temperature::FanHarvest>,
+    pub battery_harvest: Vec<batteries::BatteryHarvest>,
 }
 
 impl Default for DataCollection {
@@ -64,34 +139,64 @@ impl Default for DataCollection {
         DataCollection {
             current_instant: Instant::now(),
             frozen_instant: None,
+            time_travel_instant: None,
             timed_data_vec: Vec::default(),
             network_harvest: network::NetworkHarvest::default(),
             memory_harvest: mem::MemHarvest::default(),
             swap_harvest: mem::MemHarvest::default(),
+            compressed_mem_harvest: Vec::default(),
+            arc_harvest: None,
+            huge_pages_harvest: None,
+            gpu_harvest: Vec::default(),
+            numa_harvest: Vec::default(),
+            commit_harvest: None,
+            psi_harvest: None,
+            ping_harvest: Vec::default(),
             cpu_harvest: cpu::CPUHarvest::default(),
-            process_harvest: Vec::default(),
+            load_avg_harvest: cpu::LoadAvgHarvest::default(),
+            uptime_harvest: 0,
+            process_harvest: Rc::default(),
+            socket_harvest: Rc::default(),
             disk_harvest: Vec::default(),
             io_harvest: disks::IOHarvest::default(),
-            io_labels: Vec::default(),
-            io_prev: Vec::default(),
+            io_labels: HashMap::default(),
+            io_utilization: HashMap::default(),
+            io_prev: HashMap::default(),
             temp_harvest: Vec::default(),
+            fan_harvest: Vec::default(),
+            battery_harvest: Vec::default(),
         }
     }
 }
 
 impl DataCollection {
     pub fn reset(&mut self) {
+        self.time_travel_instant = None;
         self.timed_data_vec = Vec::default();
         self.network_harvest = network::NetworkHarvest::default();
         self.memory_harvest = mem::MemHarvest::default();
         self.swap_harvest = mem::MemHarvest::default();
+        self.compressed_mem_harvest = Vec::default();
+        self.arc_harvest = None;
+        self.huge_pages_harvest = None;
+        self.gpu_harvest = Vec::default();
+        self.numa_harvest = Vec::default();
+        self.commit_harvest = None;
+        self.psi_harvest = None;
+        self.ping_harvest = Vec::default();
         self.cpu_harvest = cpu::CPUHarvest::default();
-        self.process_harvest = Vec::default();
+        self.load_avg_harvest = cpu::LoadAvgHarvest::default();
+        self.uptime_harvest = 0;
+        self.process_harvest = Rc::default();
+        self.socket_harvest = Rc::default();
         self.disk_harvest = Vec::default();
         self.io_harvest = disks::IOHarvest::default();
-        self.io_labels = Vec::default();
-        self.io_prev = Vec::default();
+        self.io_labels = HashMap::default();
+        self.io_utilization = HashMap::default();
+        self.io_prev = HashMap::default();
         self.temp_harvest = Vec::default();
+        self.fan_harvest = Vec::default();
+        self.battery_harvest = Vec::default();
     }
 
     pub fn set_frozen_time(&mut self) {
@@ -103,7 +208,10 @@ impl DataCollection {
 
         let mut remove_index = 0;
         for entry in &self.timed_data_vec {
-            if current_time.duration_since(entry.0).as_millis() >= max_time_millis as u128 {
+            // The entry being looked at while time travelling, and everything after it, stays
+            if current_time.duration_since(entry.0).as_millis() >= max_time_millis as u128
+                && Some(entry.0) != self.time_travel_instant
+            {
                 remove_index += 1;
             } else {
                 break;
@@ -113,7 +221,92 @@ impl DataCollection {
         self.timed_data_vec.drain(0..remove_index);
     }
 
-    pub fn eat_data(&mut self, harvested_data: &Data) {
+    /// Shows the tables and labels as of an entry in the history, or the latest ones again if
+    /// given `None`.  The latest ones are kept in that entry's place in the meantime.
+    pub fn set_time_travel_instant(&mut self, time_travel_instant: Option<Instant>) {
+        if let Some(previous_instant) = self.time_travel_instant {
+            self.swap_snapshot(previous_instant);
+        }
+        if let Some(new_instant) = time_travel_instant {
+            self.swap_snapshot(new_instant);
+        }
+        self.time_travel_instant = time_travel_instant;
+    }
+
+    fn swap_snapshot(&mut self, instant: Instant) {
+        use std::mem::swap;
+
+        if let Some(snapshot) = self
+            .timed_data_vec
+            .iter_mut()
+            .find(|(time, _)| *time == instant)
+            .and_then(|(_, entry)| entry.snapshot.as_mut())
+        {
+            swap(&mut self.network_harvest, &mut snapshot.network_harvest);
+            swap(&mut self.memory_harvest, &mut snapshot.memory_harvest);
+            swap(&mut self.swap_harvest, &mut snapshot.swap_harvest);
+            swap(
+                &mut self.compressed_mem_harvest,
+                &mut snapshot.compressed_mem_harvest,
+            );
+            swap(&mut self.arc_harvest, &mut snapshot.arc_harvest);
+            swap(
+                &mut self.huge_pages_harvest,
+                &mut snapshot.huge_pages_harvest,
+            );
+            swap(&mut self.gpu_harvest, &mut snapshot.gpu_harvest);
+            swap(&mut self.numa_harvest, &mut snapshot.numa_harvest);
+            swap(&mut self.commit_harvest, &mut snapshot.commit_harvest);
+            swap(&mut self.psi_harvest, &mut snapshot.psi_harvest);
+            swap(&mut self.ping_harvest, &mut snapshot.ping_harvest);
+            swap(&mut self.cpu_harvest, &mut snapshot.cpu_harvest);
+            swap(&mut self.load_avg_harvest, &mut snapshot.load_avg_harvest);
+            swap(&mut self.uptime_harvest, &mut snapshot.uptime_harvest);
+            swap(&mut self.process_harvest, &mut snapshot.process_harvest);
+            swap(&mut self.socket_harvest, &mut snapshot.socket_harvest);
+            swap(&mut self.disk_harvest, &mut snapshot.disk_harvest);
+            swap(&mut self.io_labels, &mut snapshot.io_labels);
+            swap(&mut self.io_utilization, &mut snapshot.io_utilization);
+            swap(&mut self.temp_harvest, &mut snapshot.temp_harvest);
+            swap(&mut self.fan_harvest, &mut snapshot.fan_harvest);
+            swap(&mut self.battery_harvest, &mut snapshot.battery_harvest);
+        }
+    }
+
+    fn get_snapshot(&self) -> HarvestSnapshot {
+        HarvestSnapshot {
+            network_harvest: self.network_harvest.clone(),
+            memory_harvest: self.memory_harvest.clone(),
+            swap_harvest: self.swap_harvest.clone(),
+            compressed_mem_harvest: self.compressed_mem_harvest.clone(),
+            arc_harvest: self.arc_harvest,
+            huge_pages_harvest: self.huge_pages_harvest.clone(),
+            gpu_harvest: self.gpu_harvest.clone(),
+            numa_harvest: self.numa_harvest.clone(),
+            commit_harvest: self.commit_harvest.clone(),
+            psi_harvest: self.psi_harvest.clone(),
+            ping_harvest: self.ping_harvest.clone(),
+            cpu_harvest: self.cpu_harvest.clone(),
+            load_avg_harvest: self.load_avg_harvest,
+            uptime_harvest: self.uptime_harvest,
+            process_harvest: self.process_harvest.clone(),
+            socket_harvest: self.socket_harvest.clone(),
+            disk_harvest: self.disk_harvest.clone(),
+            io_labels: self.io_labels.clone(),
+            io_utilization: self.io_utilization.clone(),
+            temp_harvest: self.temp_harvest.clone(),
+            fan_harvest: self.fan_harvest.clone(),
+            battery_harvest: self.battery_harvest.clone(),
+        }
+    }
+
+    /// Adds a harvest to the history.  `max_time_millis` is how long the history is kept for, which
+    /// time travel snapshots are spread across.
+    pub fn eat_data(&mut self, harvested_data: &Data, max_time_millis: u64) {
+        // New data is eaten on top of the latest harvests, not a snapshot being looked at
+        let time_travel_instant = self.time_travel_instant;
+        self.set_time_travel_instant(None);
+
         let harvested_time = harvested_data.last_collection_time;
         let mut new_entry = TimedData::default();
 
@@ -126,18 +319,55 @@ impl DataCollection {
         // CPU
         self.eat_cpu(&harvested_data, harvested_time, &mut new_entry);
 
+        // Pressure
+        self.eat_psi(&harvested_data, harvested_time, &mut new_entry);
+
+        // Ping
+        self.eat_ping(&harvested_data, harvested_time, &mut new_entry);
+
         // Temp
-        self.eat_temp(&harvested_data);
+        self.eat_temp(&harvested_data, harvested_time, &mut new_entry);
+
+        // GPUs
+        self.eat_gpu(&harvested_data, harvested_time, &mut new_entry);
+
+        // Batteries
+        self.eat_batteries(&harvested_data, harvested_time, &mut new_entry);
 
         // Disks
-        self.eat_disks(&harvested_data, harvested_time);
+        self.eat_disks(&harvested_data, harvested_time, &mut new_entry);
 
         // Processes
         self.eat_proc(&harvested_data);
 
+        // Sockets
+        self.eat_sockets(&harvested_data);
+
         // And we're done eating.  Update time and push the new entry!
+        new_entry.snapshot = Some(Box::new(self.get_snapshot()));
         self.current_instant = harvested_time;
         self.timed_data_vec.push((harvested_time, new_entry));
+
+        // The latest entry always keeps its snapshot, so time travel can start from it, but the
+        // one before is only kept if it's far enough from the last one kept.  This spreads them
+        // through the whole retained history without keeping one for every update.
+        let snapshot_spacing_millis = max_time_millis / MAX_TIME_TRAVEL_SNAPSHOTS as u64;
+        if let Some(previous_index) = self.timed_data_vec.len().checked_sub(2) {
+            let previous_time = self.timed_data_vec[previous_index].0;
+            let is_too_close = self.timed_data_vec[..previous_index]
+                .iter()
+                .rev()
+                .find(|(_, entry)| entry.snapshot.is_some())
+                .map_or(false, |(last_kept_time, _)| {
+                    previous_time.duration_since(*last_kept_time).as_millis()
+                        < u128::from(snapshot_spacing_millis)
+                });
+            if is_too_close && Some(previous_time) != time_travel_instant {
+                self.timed_data_vec[previous_index].1.snapshot = None;
+            }
+        }
+
+        self.set_time_travel_instant(time_travel_instant);
     }
 
     fn eat_memory_and_swap(
@@ -171,44 +401,148 @@ impl DataCollection {
             new_entry.swap_data = swap_pt;
         }
 
+        // VRAM
+        let gpu_keys = get_series_keys(harvested_data.gpu.iter().map(|gpu| gpu.name.as_str()));
+        for (gpu_key, gpu) in gpu_keys.into_iter().zip(&harvested_data.gpu) {
+            let vram_percent = match gpu.memory.mem_total_in_mb {
+                0 => 0f64,
+                total => (gpu.memory.mem_used_in_mb as f64) / (total as f64) * 100.0,
+            };
+            let vram_pt =
+                self.get_named_points(&gpu_key, vram_percent, harvested_time, |last_pt| {
+                    &last_pt.vram_data
+                });
+            new_entry.vram_data.insert(gpu_key, vram_pt);
+        }
+
+        // Commit charge
+        new_entry.commit_data =
+            self.get_mem_list_points(harvested_data.commit.iter(), harvested_time, |last_pt| {
+                &last_pt.commit_data
+            });
+
+        // NUMA nodes
+        new_entry.numa_data = self.get_mem_list_points(
+            harvested_data.numa_nodes.iter(),
+            harvested_time,
+            |last_pt| &last_pt.numa_data,
+        );
+
         // In addition copy over latest data for easy reference
         self.memory_harvest = harvested_data.memory.clone();
         self.swap_harvest = harvested_data.swap.clone();
+        self.compressed_mem_harvest = harvested_data.compressed_memory.clone();
+        self.arc_harvest = harvested_data.arc_size_in_mb;
+        self.huge_pages_harvest = harvested_data.huge_pages.clone();
+        self.gpu_harvest = harvested_data.gpu.clone();
+        self.numa_harvest = harvested_data.numa_nodes.clone();
+        self.commit_harvest = harvested_data.commit.clone();
+    }
+
+    /// Joins a value to the last point with the same key, if there was one.
+    fn get_named_points(
+        &self, key: &str, value: Value, harvested_time: Instant,
+        get_last_points: fn(&TimedData) -> &NamedDataPoints,
+    ) -> JoinedDataPoints {
+        let joining_pts = match self.timed_data_vec.last() {
+            Some((time, last_pt)) => match get_last_points(last_pt).get(key) {
+                Some(last_named_pt) => {
+                    generate_joining_points(*time, last_named_pt.0, harvested_time, value)
+                }
+                None => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+        (value, joining_pts)
+    }
+
+    /// Calculates the usage percentage and joining points of each of a list of memory pools.
+    fn get_mem_list_points<'a>(
+        &self, mem_list: impl Iterator<Item = &'a mem::MemHarvest>, harvested_time: Instant,
+        get_last_points: fn(&TimedData) -> &Vec<JoinedDataPoints>,
+    ) -> Vec<JoinedDataPoints> {
+        mem_list
+            .enumerate()
+            .map(|(itx, memory)| {
+                let mem_percent = match memory.mem_total_in_mb {
+                    0 => 0f64,
+                    total => (memory.mem_used_in_mb as f64) / (total as f64) * 100.0,
+                };
+                let mem_joining_pts = if let Some((time, last_pt)) = self.timed_data_vec.last() {
+                    if let Some(last_mem_pt) = get_last_points(last_pt).get(itx) {
+                        generate_joining_points(*time, last_mem_pt.0, harvested_time, mem_percent)
+                    } else {
+                        Vec::new()
+                    }
+                } else {
+                    Vec::new()
+                };
+                (mem_percent, mem_joining_pts)
+            })
+            .collect()
     }
 
     fn eat_network(
         &mut self, harvested_data: &Data, harvested_time: Instant, new_entry: &mut TimedData,
     ) {
         // RX
-        let logged_rx_val = if harvested_data.network.rx as f64 > 0.0 {
-            (harvested_data.network.rx as f64).log(2.0)
-        } else {
-            0.0
-        };
+        let rx_val = harvested_data.network.rx as f64;
 
         let rx_joining_pts = if let Some((time, last_pt)) = self.timed_data_vec.last() {
-            generate_joining_points(*time, last_pt.rx_data.0, harvested_time, logged_rx_val)
+            generate_joining_points(*time, last_pt.rx_data.0, harvested_time, rx_val)
         } else {
             Vec::new()
         };
-        let rx_pt = (logged_rx_val, rx_joining_pts);
+        let rx_pt = (rx_val, rx_joining_pts);
         new_entry.rx_data = rx_pt;
 
         // TX
-        let logged_tx_val = if harvested_data.network.tx as f64 > 0.0 {
-            (harvested_data.network.tx as f64).log(2.0)
-        } else {
-            0.0
-        };
+        let tx_val = harvested_data.network.tx as f64;
 
         let tx_joining_pts = if let Some((time, last_pt)) = self.timed_data_vec.last() {
-            generate_joining_points(*time, last_pt.tx_data.0, harvested_time, logged_tx_val)
+            generate_joining_points(*time, last_pt.tx_data.0, harvested_time, tx_val)
         } else {
             Vec::new()
         };
-        let tx_pt = (logged_tx_val, tx_joining_pts);
+        let tx_pt = (tx_val, tx_joining_pts);
         new_entry.tx_data = tx_pt;
 
+        // Per interface, joined to the last point of the same interface in case the list changed
+        let last_entry = self.timed_data_vec.last();
+        let prev_interfaces = &self.network_harvest.interfaces;
+        for interface in &harvested_data.network.interfaces {
+            let rx_val = interface.rx as f64;
+            let tx_val = interface.tx as f64;
+
+            let (rx_joining_pts, tx_joining_pts) = match (
+                last_entry,
+                prev_interfaces
+                    .iter()
+                    .position(|prev_interface| prev_interface.name == interface.name),
+            ) {
+                (Some((time, last_pt)), Some(prev_itx)) => (
+                    last_pt
+                        .interface_rx_data
+                        .get(prev_itx)
+                        .map(|last_rx_pt| {
+                            generate_joining_points(*time, last_rx_pt.0, harvested_time, rx_val)
+                        })
+                        .unwrap_or_default(),
+                    last_pt
+                        .interface_tx_data
+                        .get(prev_itx)
+                        .map(|last_tx_pt| {
+                            generate_joining_points(*time, last_tx_pt.0, harvested_time, tx_val)
+                        })
+                        .unwrap_or_default(),
+                ),
+                _ => (Vec::new(), Vec::new()),
+            };
+
+            new_entry.interface_rx_data.push((rx_val, rx_joining_pts));
+            new_entry.interface_tx_data.push((tx_val, tx_joining_pts));
+        }
+
         // In addition copy over latest data for easy reference
         self.network_harvest = harvested_data.network.clone();
     }
@@ -236,52 +570,212 @@ impl DataCollection {
         }
 
         self.cpu_harvest = harvested_data.cpu.clone();
+        self.load_avg_harvest = harvested_data.load_avg;
+        self.uptime_harvest = harvested_data.uptime;
     }
 
-    fn eat_temp(&mut self, harvested_data: &Data) {
-        // TODO: [PO] To implement
+    fn eat_psi(
+        &mut self, harvested_data: &Data, harvested_time: Instant, new_entry: &mut TimedData,
+    ) {
+        if let Some(psi) = &harvested_data.psi {
+            // Only the 10 second averages are graphed, as they react the fastest
+            for (series_name, (avg10, _avg60)) in PSI_SERIES_NAMES.iter().zip(psi.averages().iter())
+            {
+                let psi_pt =
+                    self.get_named_points(series_name, *avg10, harvested_time, |last_pt| {
+                        &last_pt.psi_data
+                    });
+                new_entry.psi_data.insert(series_name.to_string(), psi_pt);
+            }
+        }
+
+        self.psi_harvest = harvested_data.psi.clone();
+    }
+
+    fn eat_ping(
+        &mut self, harvested_data: &Data, harvested_time: Instant, new_entry: &mut TimedData,
+    ) {
+        let ping_keys = get_series_keys(harvested_data.ping.iter().map(|ping| ping.host.as_str()));
+        for (ping_key, ping) in ping_keys.into_iter().zip(&harvested_data.ping) {
+            let ping_pt =
+                self.get_named_points(&ping_key, ping.latency_ms, harvested_time, |last_pt| {
+                    &last_pt.ping_data
+                });
+            new_entry.ping_data.insert(ping_key, ping_pt);
+        }
+
+        self.ping_harvest = harvested_data.ping.clone();
+    }
+
+    fn eat_temp(
+        &mut self, harvested_data: &Data, harvested_time: Instant, new_entry: &mut TimedData,
+    ) {
+        let sensor_keys = get_series_keys(
+            harvested_data
+                .temperature_sensors
+                .iter()
+                .map(|sensor| sensor.component_name.as_str()),
+        );
+        for (sensor_key, sensor) in sensor_keys
+            .into_iter()
+            .zip(&harvested_data.temperature_sensors)
+        {
+            let temp_pt = self.get_named_points(
+                &sensor_key,
+                f64::from(sensor.temperature),
+                harvested_time,
+                |last_pt| &last_pt.temp_data,
+            );
+            new_entry.temp_data.insert(sensor_key, temp_pt);
+        }
+
         self.temp_harvest = harvested_data.temperature_sensors.clone();
+        self.fan_harvest = harvested_data.fans.clone();
     }
 
-    fn eat_disks(&mut self, harvested_data: &Data, harvested_time: Instant) {
+    fn eat_gpu(
+        &mut self, harvested_data: &Data, harvested_time: Instant, new_entry: &mut TimedData,
+    ) {
+        let gpu_keys = get_series_keys(harvested_data.gpu.iter().map(|gpu| gpu.name.as_str()));
+        for (gpu_key, gpu) in gpu_keys.into_iter().zip(&harvested_data.gpu) {
+            let utilization = gpu.utilization_percent.unwrap_or(0.0);
+            let gpu_pt = self.get_named_points(&gpu_key, utilization, harvested_time, |last_pt| {
+                &last_pt.gpu_data
+            });
+            new_entry.gpu_data.insert(gpu_key, gpu_pt);
+        }
+    }
+
+    fn eat_batteries(
+        &mut self, harvested_data: &Data, harvested_time: Instant, new_entry: &mut TimedData,
+    ) {
+        if !harvested_data.batteries.is_empty() {
+            let power_watts: f64 = harvested_data
+                .batteries
+                .iter()
+                .map(|battery| battery.power_watts)
+                .sum();
+            let power_joining_pts = if let Some((time, last_pt)) = self.timed_data_vec.last() {
+                if let Some(last_power_pt) = last_pt.power_data.first() {
+                    generate_joining_points(*time, last_power_pt.0, harvested_time, power_watts)
+                } else {
+                    Vec::new()
+                }
+            } else {
+                Vec::new()
+            };
+            new_entry.power_data.push((power_watts, power_joining_pts));
+        }
+
+        self.battery_harvest = harvested_data.batteries.clone();
+    }
+
+    fn eat_disks(
+        &mut self, harvested_data: &Data, harvested_time: Instant, new_entry: &mut TimedData,
+    ) {
         // TODO: [PO] To implement
 
         let time_since_last_harvest = harvested_time
             .duration_since(self.current_instant)
             .as_secs_f64();
 
-        for (itx, device) in harvested_data.disks.iter().enumerate() {
+        // Keyed by name, so that disks being added or removed don't shift the rates of the others
+        let mut io_labels = HashMap::new();
+        let mut io_utilization = HashMap::new();
+        let mut io_prev = HashMap::new();
+        for device in &harvested_data.disks {
             if let Some(trim) = device.name.split('/').last() {
-                let io_device = harvested_data.io.get(trim);
-                if let Some(io) = io_device {
+                if let Some(io) = harvested_data.io.get(trim) {
                     let io_r_pt = io.read_bytes;
                     let io_w_pt = io.write_bytes;
 
-                    if self.io_labels.len() <= itx {
-                        self.io_prev.push((io_r_pt, io_w_pt));
-                        self.io_labels.push((0, 0));
-                    } else {
-                        let r_rate = ((io_r_pt - self.io_prev[itx].0) as f64
-                            / time_since_last_harvest)
-                            .round() as u64;
-                        let w_rate = ((io_w_pt - self.io_prev[itx].1) as f64
-                            / time_since_last_harvest)
-                            .round() as u64;
-
-                        self.io_labels[itx] = (r_rate, w_rate);
-                        self.io_prev[itx] = (io_r_pt, io_w_pt);
-                    }
+                    // Devices that weren't seen before start off at zero
+                    let rates = match self.io_prev.get(trim) {
+                        Some((prev_r_pt, prev_w_pt, prev_busy_time_ms))
+                            if time_since_last_harvest > 0.0 =>
+                        {
+                            // Counters can go backwards if a device is reattached
+                            let get_rate = |curr_pt: u64, prev_pt: u64| {
+                                (curr_pt.saturating_sub(prev_pt) as f64 / time_since_last_harvest)
+                                    .round() as u64
+                            };
+
+                            if let (Some(busy_time_ms), Some(prev_busy_time_ms)) =
+                                (io.busy_time_ms, prev_busy_time_ms)
+                            {
+                                let busy_seconds =
+                                    busy_time_ms.saturating_sub(*prev_busy_time_ms) as f64 / 1000.0;
+                                io_utilization.insert(
+                                    trim.to_string(),
+                                    (busy_seconds / time_since_last_harvest * 100.0).min(100.0),
+                                );
+                            }
+
+                            (get_rate(io_r_pt, *prev_r_pt), get_rate(io_w_pt, *prev_w_pt))
+                        }
+                        _ => (0, 0),
+                    };
+
+                    io_labels.insert(trim.to_string(), rates);
+                    io_prev.insert(trim.to_string(), (io_r_pt, io_w_pt, io.busy_time_ms));
                 }
             }
         }
+        self.io_labels = io_labels;
+        self.io_utilization = io_utilization;
+        self.io_prev = io_prev;
+
+        let total_read: u64 = self
+            .io_labels
+            .values()
+            .map(|(read_rate, _)| read_rate)
+            .sum();
+        let total_write: u64 = self
+            .io_labels
+            .values()
+            .map(|(_, write_rate)| write_rate)
+            .sum();
+        for (itx, io_val) in [total_read as f64, total_write as f64].iter().enumerate() {
+            let io_joining_pts = if let Some((time, last_pt)) = self.timed_data_vec.last() {
+                if let Some(last_io_pt) = last_pt.io_data.get(itx) {
+                    generate_joining_points(*time, last_io_pt.0, harvested_time, *io_val)
+                } else {
+                    Vec::new()
+                }
+            } else {
+                Vec::new()
+            };
+            new_entry.io_data.push((*io_val, io_joining_pts));
+        }
 
         self.disk_harvest = harvested_data.disks.clone();
         self.io_harvest = harvested_data.io.clone();
     }
 
     fn eat_proc(&mut self, harvested_data: &Data) {
-        self.process_harvest = harvested_data.list_of_processes.clone();
+        self.process_harvest = Rc::new(harvested_data.list_of_processes.clone());
     }
+
+    fn eat_sockets(&mut self, harvested_data: &Data) {
+        self.socket_harvest = Rc::new(harvested_data.sockets.clone());
+    }
+}
+
+/// The keys of a list's points in `NamedDataPoints`, which are their names, with repeats
+/// numbered so that two identical GPUs or sensors still get a line each.
+pub fn get_series_keys<'a>(names: impl Iterator<Item = &'a str>) -> Vec<String> {
+    let mut name_counts: HashMap<&str, usize> = HashMap::new();
+    names
+        .map(|name| {
+            let count = name_counts.entry(name).or_insert(0);
+            *count += 1;
+            if *count == 1 {
+                name.to_string()
+            } else {
+                format!("{} #{}", name, count)
+            }
+        })
+        .collect()
 }
 
 pub fn generate_joining_points(
@@ -2,27 +2,92 @@
 
 use std::{collections::HashMap, time::Instant};
 
+use serde::{Deserialize, Serialize};
 use sysinfo::{System, SystemExt};
 
 use futures::join;
 
+use crate::constants::DEFAULT_NETWORK_DISK_TIMEOUT_MILLISECONDS;
+
+pub mod batteries;
 pub mod cpu;
 pub mod disks;
+pub mod gpu;
 pub mod mem;
 pub mod network;
+pub mod ping;
 pub mod processes;
+pub mod psi;
+pub mod remote;
+pub mod smart;
+pub mod sockets;
 pub mod temperature;
 
-#[derive(Clone, Debug)]
+/// Which collector failed, for showing the error on the widgets it feeds.
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
+pub enum HarvestSource {
+    Processes,
+    Memory,
+    Swap,
+    Disks,
+    DiskIo,
+    Temperature,
+    Fans,
+    Gpu,
+    Battery,
+}
+
+impl HarvestSource {
+    pub fn get_name(self) -> &'static str {
+        match self {
+            HarvestSource::Processes => "Processes",
+            HarvestSource::Memory => "Memory",
+            HarvestSource::Swap => "Swap",
+            HarvestSource::Disks => "Disks",
+            HarvestSource::DiskIo => "Disk I/O",
+            HarvestSource::Temperature => "Temperature",
+            HarvestSource::Fans => "Fans",
+            HarvestSource::Gpu => "GPU",
+            HarvestSource::Battery => "Battery",
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct HarvestError {
+    pub source: HarvestSource,
+    pub message: String,
+}
+
+/// Serialized for remote hosts, which send one over SSH each harvest.
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Data {
     pub cpu: cpu::CPUHarvest,
+    pub load_avg: cpu::LoadAvgHarvest,
+    /// In seconds.
+    pub uptime: u64,
     pub memory: mem::MemHarvest,
     pub swap: mem::MemHarvest,
+    pub compressed_memory: Vec<mem::CompressedMemHarvest>,
+    pub arc_size_in_mb: Option<u64>,
+    pub huge_pages: Option<mem::HugePagesHarvest>,
+    pub gpu: Vec<gpu::GpuHarvest>,
+    pub numa_nodes: Vec<mem::MemHarvest>,
+    pub commit: Option<mem::MemHarvest>,
+    pub psi: Option<psi::PsiHarvest>,
+    pub ping: Vec<ping::PingHarvest>,
     pub temperature_sensors: Vec<temperature::TempHarvest>,
+    pub fans: Vec<temperature::FanHarvest>,
+    pub batteries: Vec<batteries::BatteryHarvest>,
     pub network: network::NetworkHarvest,
     pub list_of_processes: Vec<processes::ProcessHarvest>,
+    pub sockets: Vec<sockets::SocketHarvest>,
     pub disks: Vec<disks::DiskHarvest>,
     pub io: disks::IOHarvest,
+    /// What failed in the latest harvest.  The failed parts keep their previous values.
+    pub errors: Vec<HarvestError>,
+    /// Not sent by remote hosts, as it's only meaningful on one machine, and is set on arrival.
+    #[serde(skip, default = "Instant::now")]
     pub last_collection_time: Instant,
 }
 
@@ -30,13 +95,27 @@ impl Default for Data {
     fn default() -> Self {
         Data {
             cpu: cpu::CPUHarvest::default(),
+            load_avg: cpu::LoadAvgHarvest::default(),
+            uptime: 0,
             memory: mem::MemHarvest::default(),
             swap: mem::MemHarvest::default(),
+            compressed_memory: Vec::default(),
+            arc_size_in_mb: None,
+            huge_pages: None,
+            gpu: Vec::default(),
+            numa_nodes: Vec::default(),
+            commit: None,
+            psi: None,
+            ping: Vec::default(),
             temperature_sensors: Vec::default(),
+            fans: Vec::default(),
+            batteries: Vec::default(),
             list_of_processes: Vec::default(),
+            sockets: Vec::default(),
             disks: Vec::default(),
             io: disks::IOHarvest::default(),
             network: network::NetworkHarvest::default(),
+            errors: Vec::default(),
             last_collection_time: Instant::now(),
         }
     }
@@ -46,6 +125,7 @@ impl Data {
     pub fn first_run_cleanup(&mut self) {
         self.io = disks::IOHarvest::default();
         self.temperature_sensors = Vec::new();
+        self.fans = Vec::new();
         self.list_of_processes = Vec::new();
         self.disks = Vec::new();
 
@@ -54,6 +134,33 @@ impl Data {
         self.swap = mem::MemHarvest::default();
         self.cpu = cpu::CPUHarvest::default();
     }
+
+    fn add_error(&mut self, source: HarvestSource, err: crate::utils::error::BottomError) {
+        self.errors.push(HarvestError {
+            source,
+            message: err.to_string(),
+        });
+    }
+}
+
+/// What to collect and how, as set in the config.
+#[derive(Clone, Debug)]
+pub struct HarvesterOptions {
+    pub temperature_type: temperature::TemperatureType,
+    pub process_options: processes::ProcessOptions,
+    pub get_cpu_frequency: bool,
+    pub subtract_arc: bool,
+    pub get_psi: bool,
+    pub get_sockets: bool,
+    pub get_disk_temp: bool,
+    pub get_disk_inodes: bool,
+    pub get_network_disks: bool,
+    pub network_disk_timeout: u64,
+    pub ping_hosts: Vec<String>,
+    pub network_filter: network::InterfaceFilter,
+    pub disk_filter: disks::DiskFilter,
+    pub sensor_filter: temperature::SensorFilter,
+    pub get_battery: bool,
 }
 
 pub struct DataState {
@@ -64,11 +171,29 @@ pub struct DataState {
     prev_non_idle: f64,
     mem_total_kb: u64,
     temperature_type: temperature::TemperatureType,
-    use_current_cpu_total: bool,
+    process_options: processes::ProcessOptions,
+    get_cpu_frequency: bool,
+    subtract_arc: bool,
+    get_psi: bool,
+    get_sockets: bool,
+    get_disk_temp: bool,
+    get_disk_inodes: bool,
+    get_network_disks: bool,
+    network_disks: disks::NetworkDiskStatter,
+    pinger: ping::Pinger,
+    battery_manager: Option<battery::Manager>,
+    battery_list: Vec<battery::Battery>,
+    /// Why the batteries couldn't be found, which is reported with every harvest.
+    battery_error: Option<String>,
+    core_types: Vec<Option<cpu::CoreType>>,
+    gpu_backends: Vec<Box<dyn gpu::GpuBackend>>,
+    /// Why NVML couldn't be loaded for an NVIDIA GPU, which is reported with every harvest.
+    nvml_error: Option<String>,
     last_collection_time: Instant,
-    total_rx: u64,
-    total_tx: u64,
-    show_average_cpu: bool,
+    network_totals: network::NetworkTotals,
+    network_filter: network::InterfaceFilter,
+    disk_filter: disks::DiskFilter,
+    sensor_filter: temperature::SensorFilter,
 }
 
 impl Default for DataState {
@@ -81,30 +206,134 @@ impl Default for DataState {
             prev_non_idle: 0_f64,
             mem_total_kb: 0,
             temperature_type: temperature::TemperatureType::Celsius,
-            use_current_cpu_total: false,
+            process_options: processes::ProcessOptions::default(),
+            get_cpu_frequency: false,
+            subtract_arc: false,
+            get_psi: false,
+            get_sockets: false,
+            get_disk_temp: false,
+            get_disk_inodes: false,
+            get_network_disks: true,
+            network_disks: disks::NetworkDiskStatter::new(
+                DEFAULT_NETWORK_DISK_TIMEOUT_MILLISECONDS,
+            ),
+            pinger: ping::Pinger::default(),
+            battery_manager: None,
+            battery_list: Vec::new(),
+            battery_error: None,
+            core_types: Vec::new(),
+            gpu_backends: Vec::new(),
+            nvml_error: None,
             last_collection_time: Instant::now(),
-            total_rx: 0,
-            total_tx: 0,
-            show_average_cpu: false,
+            network_totals: network::NetworkTotals::default(),
+            network_filter: network::InterfaceFilter::default(),
+            disk_filter: disks::DiskFilter::default(),
+            sensor_filter: temperature::SensorFilter::default(),
         }
     }
 }
 
 impl DataState {
+    pub fn set_options(&mut self, options: HarvesterOptions) {
+        self.set_temperature_type(options.temperature_type);
+        self.process_options = options.process_options;
+        self.set_get_cpu_frequency(options.get_cpu_frequency);
+        self.set_subtract_arc(options.subtract_arc);
+        self.set_get_psi(options.get_psi);
+        self.set_get_sockets(options.get_sockets);
+        self.set_get_disk_temp(options.get_disk_temp);
+        self.set_get_disk_inodes(options.get_disk_inodes);
+        self.set_get_network_disks(options.get_network_disks);
+        self.set_network_disk_timeout(options.network_disk_timeout);
+        self.set_ping_hosts(&options.ping_hosts);
+        self.set_network_filter(options.network_filter);
+        self.set_disk_filter(options.disk_filter);
+        self.set_sensor_filter(options.sensor_filter);
+        self.set_get_battery(options.get_battery);
+    }
+
     pub fn set_temperature_type(&mut self, temperature_type: temperature::TemperatureType) {
         self.temperature_type = temperature_type;
     }
 
-    pub fn set_use_current_cpu_total(&mut self, use_current_cpu_total: bool) {
-        self.use_current_cpu_total = use_current_cpu_total;
+    pub fn set_get_cpu_frequency(&mut self, get_cpu_frequency: bool) {
+        self.get_cpu_frequency = get_cpu_frequency;
+    }
+
+    pub fn set_subtract_arc(&mut self, subtract_arc: bool) {
+        self.subtract_arc = subtract_arc;
+    }
+
+    pub fn set_get_psi(&mut self, get_psi: bool) {
+        self.get_psi = get_psi;
+    }
+
+    pub fn set_get_sockets(&mut self, get_sockets: bool) {
+        self.get_sockets = get_sockets;
+    }
+
+    pub fn set_get_disk_temp(&mut self, get_disk_temp: bool) {
+        self.get_disk_temp = get_disk_temp;
+    }
+
+    pub fn set_get_disk_inodes(&mut self, get_disk_inodes: bool) {
+        self.get_disk_inodes = get_disk_inodes;
+    }
+
+    pub fn set_get_network_disks(&mut self, get_network_disks: bool) {
+        self.get_network_disks = get_network_disks;
+    }
+
+    pub fn set_network_disk_timeout(&mut self, timeout_in_milliseconds: u64) {
+        self.network_disks = disks::NetworkDiskStatter::new(timeout_in_milliseconds);
+    }
+
+    pub fn set_ping_hosts(&mut self, ping_hosts: &[String]) {
+        self.pinger = ping::Pinger::new(ping_hosts);
+    }
+
+    pub fn set_get_battery(&mut self, get_battery: bool) {
+        self.battery_manager = None;
+        self.battery_list = Vec::new();
+        self.battery_error = None;
+        if get_battery {
+            let found_batteries = battery::Manager::new().and_then(|battery_manager| {
+                let battery_list = batteries::get_batteries(&battery_manager)?;
+                Ok((battery_manager, battery_list))
+            });
+            match found_batteries {
+                Ok((battery_manager, battery_list)) => {
+                    self.battery_manager = Some(battery_manager);
+                    self.battery_list = battery_list;
+                }
+                Err(err) => {
+                    self.battery_error = Some(format!("Couldn't find the batteries: {}", err));
+                }
+            }
+        }
+    }
+
+    pub fn set_network_filter(&mut self, network_filter: network::InterfaceFilter) {
+        self.network_filter = network_filter;
     }
 
-    pub fn set_show_average_cpu(&mut self, show_average_cpu: bool) {
-        self.show_average_cpu = show_average_cpu;
+    pub fn set_disk_filter(&mut self, disk_filter: disks::DiskFilter) {
+        self.disk_filter = disk_filter;
+    }
+
+    pub fn set_sensor_filter(&mut self, sensor_filter: temperature::SensorFilter) {
+        self.sensor_filter = sensor_filter;
     }
 
     pub fn init(&mut self) {
         self.mem_total_kb = self.sys.get_total_memory();
+        self.core_types = cpu::get_core_types(self.sys.get_processors().len());
+        #[cfg(feature = "gpu")]
+        {
+            let (gpu_backends, nvml_error) = gpu::get_gpu_backends();
+            self.gpu_backends = gpu_backends;
+            self.nvml_error = nvml_error;
+        }
         futures::executor::block_on(self.update_data());
         std::thread::sleep(std::time::Duration::from_millis(250));
         self.data.first_run_cleanup();
@@ -122,39 +351,71 @@ impl DataState {
         }
 
         let current_instant = std::time::Instant::now();
+        self.data.errors.clear();
 
         // CPU
-        self.data.cpu = cpu::get_cpu_data_list(&self.sys, self.show_average_cpu);
+        self.data.cpu = cpu::get_cpu_data_list(&self.sys, self.get_cpu_frequency, &self.core_types);
+
+        // Load averages are only a thing on Unix
+        if cfg!(unix) {
+            self.data.load_avg = cpu::get_load_avg(&self.sys);
+        }
+        self.data.uptime = self.sys.get_uptime();
 
         // Processes.  This is the longest part of the harvesting process... changing this might be
         // good in the future.  What was tried already:
         // * Splitting the internal part into multiple scoped threads (dropped by ~.01 seconds, but upped usage)
-        if let Ok(process_list) = processes::get_sorted_processes_list(
+        match processes::get_sorted_processes_list(
             &self.sys,
             &mut self.prev_idle,
             &mut self.prev_non_idle,
             &mut self.prev_pid_stats,
-            self.use_current_cpu_total,
+            self.process_options,
             self.mem_total_kb,
             current_instant,
         ) {
-            self.data.list_of_processes = process_list;
+            Ok(process_list) => self.data.list_of_processes = process_list,
+            Err(err) => self.data.add_error(HarvestSource::Processes, err),
         }
 
+        // Sockets.  Finding the owner of each requires reading every process' file descriptors.
+        self.data.sockets = if self.get_sockets {
+            sockets::get_socket_list()
+        } else {
+            Vec::new()
+        };
+
         // ASYNC
         let network_data_fut = network::get_network_data(
             &self.sys,
             self.last_collection_time,
-            &mut self.total_rx,
-            &mut self.total_tx,
+            &mut self.network_totals,
+            &self.network_filter,
             current_instant,
         );
 
         let mem_data_fut = mem::get_mem_data_list();
         let swap_data_fut = mem::get_swap_data_list();
-        let disk_data_fut = disks::get_disk_usage_list();
+        let disk_data_fut = disks::get_disk_usage_list(
+            &self.disk_filter,
+            if self.get_network_disks {
+                Some(&mut self.network_disks)
+            } else {
+                None
+            },
+        );
         let disk_io_usage_fut = disks::get_io_usage_list(false);
-        let temp_data_fut = temperature::get_temperature_data(&self.sys, &self.temperature_type);
+        let gpu_temperatures: Vec<(String, f32)> = self
+            .gpu_backends
+            .iter_mut()
+            .flat_map(|gpu_backend| gpu_backend.get_temperature_data())
+            .collect();
+        let temp_data_fut = temperature::get_temperature_data(
+            &self.sys,
+            &self.temperature_type,
+            &self.sensor_filter,
+            gpu_temperatures,
+        );
 
         let (net_data, mem_res, swap_res, disk_res, io_res, temp_res) = join!(
             network_data_fut,
@@ -167,26 +428,123 @@ impl DataState {
 
         // After async
         self.data.network = net_data;
-        self.total_rx = self.data.network.total_rx;
-        self.total_tx = self.data.network.total_tx;
 
-        if let Ok(memory) = mem_res {
-            self.data.memory = memory;
+        self.data.arc_size_in_mb = mem::get_arc_size_in_mb();
+        match mem_res {
+            Ok(mut memory) => {
+                // The ARC is reclaimable, so optionally treat it as free rather than used
+                if self.subtract_arc {
+                    if let Some(arc_size_in_mb) = self.data.arc_size_in_mb {
+                        memory.mem_used_in_mb =
+                            memory.mem_used_in_mb.saturating_sub(arc_size_in_mb);
+                    }
+                }
+                self.data.memory = memory;
+            }
+            Err(err) => self.data.add_error(HarvestSource::Memory, err),
+        }
+
+        match swap_res {
+            Ok(swap) => self.data.swap = swap,
+            Err(err) => self.data.add_error(HarvestSource::Swap, err),
         }
+        #[cfg(target_os = "windows")]
+        {
+            // Report the pagefile as swap, rather than an estimate
+            if let Some((commit, pagefile)) = mem::get_commit_and_pagefile_data() {
+                self.data.commit = Some(commit);
+                self.data.swap = pagefile;
+            }
+        }
+        self.data.compressed_memory = mem::get_compressed_mem_data();
+        self.data.huge_pages = mem::get_huge_pages_data();
+        self.data.numa_nodes = mem::get_numa_mem_data();
+        self.data.psi = if self.get_psi {
+            psi::get_psi_data()
+        } else {
+            None
+        };
+        self.data.ping = self.pinger.get_ping_data_list();
 
-        if let Ok(swap) = swap_res {
-            self.data.swap = swap;
+        if let Some(battery_error) = &self.battery_error {
+            self.data.errors.push(HarvestError {
+                source: HarvestSource::Battery,
+                message: battery_error.clone(),
+            });
+        }
+        match &self.battery_manager {
+            Some(battery_manager) => {
+                match batteries::get_battery_data_list(battery_manager, &mut self.battery_list) {
+                    Ok(battery_data) => self.data.batteries = battery_data,
+                    Err(err) => self.data.errors.push(HarvestError {
+                        source: HarvestSource::Battery,
+                        message: format!("Couldn't refresh the batteries: {}", err),
+                    }),
+                }
+            }
+            None => self.data.batteries = Vec::new(),
         }
 
-        if let Ok(disks) = disk_res {
-            self.data.disks = disks;
+        // GPUs.  If a backend fails, every GPU keeps its previous readings, as they're one list.
+        if let Some(nvml_error) = &self.nvml_error {
+            self.data.errors.push(HarvestError {
+                source: HarvestSource::Gpu,
+                message: nvml_error.clone(),
+            });
         }
-        if let Ok(io) = io_res {
-            self.data.io = io;
+        let mut gpu_data = Vec::new();
+        let mut is_gpu_data_complete = true;
+        for gpu_backend in &mut self.gpu_backends {
+            match gpu_backend.get_gpu_data_list() {
+                Ok(gpu_list) => gpu_data.extend(gpu_list),
+                Err(err) => {
+                    is_gpu_data_complete = false;
+                    self.data.add_error(HarvestSource::Gpu, err);
+                }
+            }
+        }
+        if is_gpu_data_complete {
+            self.data.gpu = gpu_data;
         }
 
-        if let Ok(temp) = temp_res {
-            self.data.temperature_sensors = temp;
+        match disk_res {
+            Ok(mut disks) => {
+                if self.get_disk_temp {
+                    let disk_temperatures = disks::get_disk_temperatures();
+                    for disk in &mut disks {
+                        let partition_name = disk.name.split('/').last().unwrap_or_default();
+                        disk.temperature = disk_temperatures
+                            .iter()
+                            .find(|(device_name, _)| {
+                                disks::is_partition_of(partition_name, device_name)
+                            })
+                            .map(|(_, celsius)| {
+                                temperature::convert_celsius(*celsius, &self.temperature_type)
+                            });
+                    }
+                }
+                if self.get_disk_inodes {
+                    // Stat'ing a network mount could hang
+                    for disk in disks.iter_mut().filter(|disk| !disk.is_network) {
+                        disk.inodes = disks::get_inode_usage(&disk.mount_point);
+                    }
+                }
+                self.data.disks = disks;
+            }
+            Err(err) => self.data.add_error(HarvestSource::Disks, err),
+        }
+        match io_res {
+            Ok(io) => self.data.io = io,
+            Err(err) => self.data.add_error(HarvestSource::DiskIo, err),
+        }
+
+        match temp_res {
+            Ok(temp) => self.data.temperature_sensors = temp,
+            Err(err) => self.data.add_error(HarvestSource::Temperature, err),
+        }
+        match temperature::get_fan_data(&self.sensor_filter) {
+            Ok(fans) => self.data.fans = fans,
+            Err(err) => self.data.add_error(HarvestSource::Fans, err),
         }
 
         // Update time
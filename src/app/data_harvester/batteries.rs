@@ -0,0 +1,62 @@
+//! Data collection for batteries, through the battery crate.
+
+use battery::{
+    units::{energy::watt_hour, power::watt, ratio::percent},
+    Battery, Manager, State,
+};
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BatteryHarvest {
+    pub name: String,
+    pub charge_percent: f64,
+    pub state: String,
+    pub energy_wh: f64,
+    pub energy_full_wh: f64,
+    /// How fast the battery is charging or discharging.
+    pub power_watts: f64,
+    /// The capacity the battery was designed to hold, which it loses as it wears.
+    pub energy_full_design_wh: f64,
+    pub cycle_count: Option<u32>,
+}
+
+/// Finds every battery once, so they can be refreshed in place on each update.
+pub fn get_batteries(manager: &Manager) -> battery::Result<Vec<Battery>> {
+    Ok(manager.batteries()?.flatten().collect())
+}
+
+/// Fails if any battery couldn't be refreshed, so the previous readings can be kept.
+pub fn get_battery_data_list(
+    manager: &Manager, batteries: &mut [Battery],
+) -> battery::Result<Vec<BatteryHarvest>> {
+    batteries
+        .iter_mut()
+        .enumerate()
+        .map(|(itx, battery)| {
+            manager.refresh(battery)?;
+
+            Ok(BatteryHarvest {
+                name: match battery.model() {
+                    Some(model) if !model.trim().is_empty() => {
+                        format!("BAT{} {}", itx, model.trim())
+                    }
+                    _ => format!("BAT{}", itx),
+                },
+                charge_percent: f64::from(battery.state_of_charge().get::<percent>()),
+                state: match battery.state() {
+                    State::Charging => "Charging",
+                    State::Discharging => "Discharging",
+                    State::Full => "Full",
+                    State::Empty => "Empty",
+                    _ => "Unknown",
+                }
+                .to_string(),
+                energy_wh: f64::from(battery.energy().get::<watt_hour>()),
+                energy_full_wh: f64::from(battery.energy_full().get::<watt_hour>()),
+                power_watts: f64::from(battery.energy_rate().get::<watt>()),
+                energy_full_design_wh: f64::from(battery.energy_full_design().get::<watt_hour>()),
+                cycle_count: battery.cycle_count(),
+            })
+        })
+        .collect()
+}
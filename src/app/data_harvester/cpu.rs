@@ -1,31 +1,203 @@
-use sysinfo::{ProcessorExt, System, SystemExt};
+use serde::{Deserialize, Serialize};
+use sysinfo::{Processor, ProcessorExt, System, SystemExt};
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct CPUData {
     pub cpu_name: String,
     pub cpu_usage: f64,
+    /// In MHz.  Only gathered if enabled, and is 0 if unavailable.
+    pub frequency_mhz: u64,
+    /// In MHz.  Only gathered within Linux.
+    pub max_frequency_mhz: Option<u64>,
+    /// Whether this is the average of every core of one type on a hybrid CPU.
+    pub is_core_type_average: bool,
+}
+
+/// The type of a core on hybrid CPUs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CoreType {
+    Performance,
+    Efficiency,
+}
+
+impl CoreType {
+    fn short_name(self) -> &'static str {
+        match self {
+            CoreType::Performance => "P",
+            CoreType::Efficiency => "E",
+        }
+    }
 }
 
 pub type CPUHarvest = Vec<CPUData>;
 
-pub fn get_cpu_data_list(sys: &System, show_average_cpu: bool) -> CPUHarvest {
+/// The 1, 5, and 15 minute load averages.
+pub type LoadAvgHarvest = [f64; 3];
+
+/// Note the average is always the first entry, as whether it is shown can be toggled at runtime.
+/// If `core_types` is not empty, cores are labelled by their type and the average of each type is
+/// appended after the cores.
+pub fn get_cpu_data_list(
+    sys: &System, get_frequency: bool, core_types: &[Option<CoreType>],
+) -> CPUHarvest {
     let cpu_data = sys.get_processors();
     let avg_cpu_usage = sys.get_global_processor_info().get_cpu_usage();
     let mut cpu_vec = vec![];
 
-    if show_average_cpu {
-        cpu_vec.push(CPUData {
-            cpu_name: "AVG".to_string(),
-            cpu_usage: avg_cpu_usage as f64,
-        });
-    }
+    for (itx, cpu) in cpu_data.iter().enumerate() {
+        let (frequency_mhz, max_frequency_mhz) = if get_frequency {
+            get_core_frequency(itx, cpu)
+        } else {
+            (0, None)
+        };
+
+        let cpu_name = if let Some(Some(core_type)) = core_types.get(itx) {
+            format!(
+                "{} {}",
+                cpu.get_name().to_uppercase(),
+                core_type.short_name()
+            )
+        } else {
+            cpu.get_name().to_uppercase()
+        };
 
-    for cpu in cpu_data {
         cpu_vec.push(CPUData {
-            cpu_name: cpu.get_name().to_uppercase(),
+            cpu_name,
             cpu_usage: f64::from(cpu.get_cpu_usage()),
+            frequency_mhz,
+            max_frequency_mhz,
+            is_core_type_average: false,
         });
     }
 
+    // Averages across performance and efficiency cores are misleading, so also give each their own
+    let core_type_averages = if core_types.is_empty() {
+        Vec::new()
+    } else {
+        [CoreType::Performance, CoreType::Efficiency]
+            .iter()
+            .map(|core_type| {
+                let cores_of_type = cpu_vec
+                    .iter()
+                    .zip(core_types)
+                    .filter(|(_cpu, cpu_core_type)| **cpu_core_type == Some(*core_type))
+                    .map(|(cpu, _cpu_core_type)| cpu)
+                    .collect::<Vec<_>>();
+                let num_cores = std::cmp::max(cores_of_type.len(), 1);
+
+                CPUData {
+                    cpu_name: format!("{}-AVG", core_type.short_name()),
+                    cpu_usage: cores_of_type.iter().map(|cpu| cpu.cpu_usage).sum::<f64>()
+                        / num_cores as f64,
+                    frequency_mhz: cores_of_type
+                        .iter()
+                        .map(|cpu| cpu.frequency_mhz)
+                        .sum::<u64>()
+                        / num_cores as u64,
+                    max_frequency_mhz: cores_of_type
+                        .iter()
+                        .filter_map(|cpu| cpu.max_frequency_mhz)
+                        .max(),
+                    is_core_type_average: true,
+                }
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let (frequency_mhz, max_frequency_mhz) = if get_frequency && !cpu_vec.is_empty() {
+        (
+            cpu_vec.iter().map(|cpu| cpu.frequency_mhz).sum::<u64>() / cpu_vec.len() as u64,
+            cpu_vec.iter().filter_map(|cpu| cpu.max_frequency_mhz).max(),
+        )
+    } else {
+        (0, None)
+    };
+
+    cpu_vec.insert(
+        0,
+        CPUData {
+            cpu_name: "AVG".to_string(),
+            cpu_usage: avg_cpu_usage as f64,
+            frequency_mhz,
+            max_frequency_mhz,
+            is_core_type_average: false,
+        },
+    );
+    cpu_vec.extend(core_type_averages);
+
     cpu_vec
 }
+
+/// Returns the type of each core if this is a hybrid CPU, otherwise an empty list.  Only
+/// supported within Linux, using the lists exposed by the hybrid PMUs (ie: Intel 12th gen+).
+pub fn get_core_types(num_cores: usize) -> Vec<Option<CoreType>> {
+    if cfg!(target_os = "linux") {
+        if let (Some(performance_cores), Some(efficiency_cores)) = (
+            read_cpu_list("/sys/devices/cpu_core/cpus"),
+            read_cpu_list("/sys/devices/cpu_atom/cpus"),
+        ) {
+            return (0..num_cores)
+                .map(|core| {
+                    if performance_cores.contains(&core) {
+                        Some(CoreType::Performance)
+                    } else if efficiency_cores.contains(&core) {
+                        Some(CoreType::Efficiency)
+                    } else {
+                        None
+                    }
+                })
+                .collect();
+        }
+    }
+
+    Vec::new()
+}
+
+/// Reads a CPU list of the form "0-3,8,10-11".
+fn read_cpu_list(path: &str) -> Option<Vec<usize>> {
+    let cpu_list = std::fs::read_to_string(path).ok()?;
+    let mut cpus = Vec::new();
+
+    for range in cpu_list.trim().split(',').filter(|range| !range.is_empty()) {
+        let mut bounds = range.splitn(2, '-');
+        let start = bounds.next()?.parse::<usize>().ok()?;
+        let end = match bounds.next() {
+            Some(end) => end.parse::<usize>().ok()?,
+            None => start,
+        };
+        cpus.extend(start..=end);
+    }
+
+    Some(cpus)
+}
+
+pub fn get_load_avg(sys: &System) -> LoadAvgHarvest {
+    let load_avg = sys.get_load_average();
+
+    [load_avg.one, load_avg.five, load_avg.fifteen]
+}
+
+/// Returns the current and max frequency of a core in MHz.  Within Linux these are read
+/// from cpufreq, as it also exposes the max; otherwise we fall back to sysinfo for the current.
+fn get_core_frequency(core: usize, cpu: &Processor) -> (u64, Option<u64>) {
+    if cfg!(target_os = "linux") {
+        (
+            read_cpufreq_mhz(core, "scaling_cur_freq").unwrap_or_else(|| cpu.get_frequency()),
+            read_cpufreq_mhz(core, "cpuinfo_max_freq"),
+        )
+    } else {
+        (cpu.get_frequency(), None)
+    }
+}
+
+fn read_cpufreq_mhz(core: usize, file_name: &str) -> Option<u64> {
+    let path = format!("/sys/devices/system/cpu/cpu{}/cpufreq/{}", core, file_name);
+
+    // Values are in kHz
+    std::fs::read_to_string(path)
+        .ok()?
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .map(|khz| khz / 1000)
+}
@@ -1,19 +1,189 @@
+use std::{
+    collections::HashMap,
+    sync::mpsc::{self, Receiver, TryRecvError},
+    time::{Duration, Instant},
+};
+
 use futures::stream::StreamExt;
 use heim::units::information;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// Filesystems mounted over the network, whose stat calls can hang if the server stops responding.
+const NETWORK_FILE_SYSTEMS: [&str; 8] = [
+    "nfs",
+    "nfs4",
+    "cifs",
+    "smb3",
+    "smbfs",
+    "sshfs",
+    "fuse.sshfs",
+    "9p",
+];
 
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct DiskHarvest {
     pub name: String,
     pub mount_point: String,
     pub free_space: u64,
     pub used_space: u64,
     pub total_space: u64,
+    /// In the configured temperature unit, if the drive reports one and it was asked for.
+    pub temperature: Option<f32>,
+    /// The used and total inodes, if the filesystem has a fixed number of them and they were
+    /// asked for.
+    pub inodes: Option<(u64, u64)>,
+    pub is_network: bool,
 }
 
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct IOData {
     pub read_bytes: u64,
     pub write_bytes: u64,
+    /// The total time the device has spent doing IO.  Only read within Linux.
+    pub busy_time_ms: Option<u64>,
+}
+
+/// The columns of the disk table that it can be sorted by.  Inodes and temperatures can only be
+/// sorted by if they're shown.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DiskSorting {
+    Name,
+    Mount,
+    Used,
+    Free,
+    Total,
+    Read,
+    Write,
+    Util,
+    Inodes,
+    Temp,
+}
+
+impl Default for DiskSorting {
+    fn default() -> Self {
+        DiskSorting::Name
+    }
+}
+
+/// Regexes matched against disk names and mount points, set under `[disk_filter]` in the config.
+#[derive(Default, Clone, Debug)]
+pub struct DiskFilter {
+    /// If not empty, only disks with a name or mount point matching one of these are included.
+    pub allow: Vec<Regex>,
+    pub deny: Vec<Regex>,
+}
+
+impl DiskFilter {
+    pub fn is_included(&self, name: &str, mount_point: &str) -> bool {
+        let is_match = |regex: &Regex| regex.is_match(name) || regex.is_match(mount_point);
+        (self.allow.is_empty() || self.allow.iter().any(is_match))
+            && !self.deny.iter().any(is_match)
+    }
+}
+
+/// The free, used, and total bytes of a disk.
+type DiskUsage = (u64, u64, u64);
+
+struct NetworkMount {
+    in_flight: Option<(Instant, Receiver<Option<DiskUsage>>)>,
+    last_usage: Option<DiskUsage>,
+}
+
+/// Stats network mounts on their own threads, so that a hung server never blocks harvesting.  A
+/// mount whose latest stat has taken longer than the timeout is left out until it responds.
+#[derive(Default)]
+pub struct NetworkDiskStatter {
+    timeout: Duration,
+    mounts: HashMap<String, NetworkMount>,
+}
+
+impl NetworkDiskStatter {
+    pub fn new(timeout_in_milliseconds: u64) -> Self {
+        NetworkDiskStatter {
+            timeout: Duration::from_millis(timeout_in_milliseconds),
+            mounts: HashMap::new(),
+        }
+    }
+
+    /// Returns the usage from the last stat that finished, and sends a new one if none are in
+    /// flight.
+    fn get_usage(&mut self, mount_point: &str) -> Option<DiskUsage> {
+        let mount = self
+            .mounts
+            .entry(mount_point.to_string())
+            .or_insert_with(|| NetworkMount {
+                in_flight: None,
+                last_usage: None,
+            });
+
+        let is_hung = if let Some((sent_instant, receiver)) = &mount.in_flight {
+            match receiver.try_recv() {
+                Ok(usage) => {
+                    mount.last_usage = usage;
+                    mount.in_flight = None;
+                    false
+                }
+                Err(TryRecvError::Empty) => sent_instant.elapsed() > self.timeout,
+                Err(TryRecvError::Disconnected) => {
+                    mount.in_flight = None;
+                    false
+                }
+            }
+        } else {
+            false
+        };
+
+        if mount.in_flight.is_none() {
+            mount.in_flight = Some((Instant::now(), spawn_usage_stat(mount_point)));
+        }
+
+        if is_hung {
+            None
+        } else {
+            mount.last_usage
+        }
+    }
+}
+
+fn spawn_usage_stat(mount_point: &str) -> Receiver<Option<DiskUsage>> {
+    let (sender, receiver) = mpsc::channel();
+    let mount_point = mount_point.to_string();
+    std::thread::spawn(move || {
+        let usage = futures::executor::block_on(heim::disk::usage(mount_point))
+            .ok()
+            .map(|usage| {
+                (
+                    usage.free().get::<information::byte>(),
+                    usage.used().get::<information::byte>(),
+                    usage.total().get::<information::byte>(),
+                )
+            });
+        let _ = sender.send(usage);
+    });
+
+    receiver
+}
+
+pub fn is_network_file_system(file_system: &str) -> bool {
+    NETWORK_FILE_SYSTEMS.contains(&file_system)
+}
+
+/// The device name and mount point of a partition.
+fn get_partition_names(partition: &heim::disk::Partition) -> (String, String) {
+    let name = partition
+        .device()
+        .unwrap_or_else(|| std::ffi::OsStr::new("Name Unavailable"))
+        .to_str()
+        .unwrap_or("Name Unavailable")
+        .to_string();
+    let mount_point = partition
+        .mount_point()
+        .to_str()
+        .unwrap_or("Name Unavailable")
+        .to_string();
+
+    (name, mount_point)
 }
 
 pub type IOHarvest = std::collections::HashMap<String, IOData>;
@@ -30,6 +200,7 @@ pub async fn get_io_usage_list(get_physical: bool) -> crate::utils::error::Resul
                 IOData {
                     read_bytes: io.read_bytes().get::<information::megabyte>(),
                     write_bytes: io.write_bytes().get::<information::megabyte>(),
+                    busy_time_ms: None,
                 },
             );
         }
@@ -43,15 +214,46 @@ pub async fn get_io_usage_list(get_physical: bool) -> crate::utils::error::Resul
                 IOData {
                     read_bytes: io.read_bytes().get::<information::byte>(),
                     write_bytes: io.write_bytes().get::<information::byte>(),
+                    busy_time_ms: None,
                 },
             );
         }
     }
 
+    if cfg!(target_os = "linux") {
+        let busy_times = get_busy_times();
+        for (device_name, io) in io_hash.iter_mut() {
+            io.busy_time_ms = busy_times.get(device_name).cloned();
+        }
+    }
+
     Ok(io_hash)
 }
 
-pub async fn get_disk_usage_list() -> crate::utils::error::Result<Vec<DiskHarvest>> {
+/// Reads the milliseconds each device has spent doing IO from /proc/diskstats, keyed by device
+/// name.  Only works within Linux.
+fn get_busy_times() -> HashMap<String, u64> {
+    let mut busy_times = HashMap::new();
+    if let Ok(diskstats) = std::fs::read_to_string("/proc/diskstats") {
+        for line in diskstats.lines() {
+            // The major and minor numbers, the name, and then the IO time is the tenth stat
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if let (Some(device_name), Some(busy_time_ms)) = (
+                fields.get(2),
+                fields.get(12).and_then(|field| field.parse::<u64>().ok()),
+            ) {
+                busy_times.insert(device_name.to_string(), busy_time_ms);
+            }
+        }
+    }
+
+    busy_times
+}
+
+/// Network mounts are only included if `network_disks` is given, and are stat'd through it.
+pub async fn get_disk_usage_list(
+    filter: &DiskFilter, network_disks: Option<&mut NetworkDiskStatter>,
+) -> crate::utils::error::Result<Vec<DiskHarvest>> {
     let mut vec_disks: Vec<DiskHarvest> = Vec::new();
     let mut partitions_stream = heim::disk::partitions_physical();
 
@@ -59,27 +261,151 @@ pub async fn get_disk_usage_list() -> crate::utils::error::Result<Vec<DiskHarves
         if let Ok(part) = part {
             let partition = part;
             let usage = heim::disk::usage(partition.mount_point().to_path_buf()).await?;
+            let (name, mount_point) = get_partition_names(&partition);
 
             vec_disks.push(DiskHarvest {
                 free_space: usage.free().get::<information::byte>(),
                 used_space: usage.used().get::<information::byte>(),
                 total_space: usage.total().get::<information::byte>(),
-                mount_point: (partition
-                    .mount_point()
-                    .to_str()
-                    .unwrap_or("Name Unavailable"))
-                .to_string(),
-                name: (partition
-                    .device()
-                    .unwrap_or_else(|| std::ffi::OsStr::new("Name Unavailable"))
-                    .to_str()
-                    .unwrap_or("Name Unavailable"))
-                .to_string(),
+                temperature: None,
+                inodes: None,
+                is_network: false,
+                mount_point,
+                name,
             });
         }
     }
 
+    if let Some(network_disks) = network_disks {
+        // Physical partitions exclude network mounts, so look through every partition for them
+        let mut network_mount_points: Vec<String> = Vec::new();
+        let mut all_partitions_stream = heim::disk::partitions();
+        while let Some(partition) = all_partitions_stream.next().await {
+            if let Ok(partition) = partition {
+                if !is_network_file_system(partition.file_system().as_str()) {
+                    continue;
+                }
+
+                let (name, mount_point) = get_partition_names(&partition);
+                // Check the filter first, so excluded mounts are never stat'd
+                if !filter.is_included(&name, &mount_point) {
+                    continue;
+                }
+
+                if let Some((free_space, used_space, total_space)) =
+                    network_disks.get_usage(&mount_point)
+                {
+                    vec_disks.push(DiskHarvest {
+                        free_space,
+                        used_space,
+                        total_space,
+                        temperature: None,
+                        inodes: None,
+                        is_network: true,
+                        mount_point: mount_point.clone(),
+                        name,
+                    });
+                }
+                network_mount_points.push(mount_point);
+            }
+        }
+
+        // Forget unmounted ones
+        network_disks
+            .mounts
+            .retain(|mount_point, _| network_mount_points.contains(mount_point));
+    }
+
+    vec_disks.retain(|disk| disk.is_network || filter.is_included(&disk.name, &disk.mount_point));
     vec_disks.sort_by(|a, b| a.name.cmp(&b.name));
 
     Ok(vec_disks)
 }
+
+/// Returns the used and total inodes of the filesystem at a mount point.  Filesystems that
+/// allocate inodes dynamically, like btrfs, report none in total.  Only works on Unix.
+#[cfg(unix)]
+pub fn get_inode_usage(mount_point: &str) -> Option<(u64, u64)> {
+    let mount_point = std::ffi::CString::new(mount_point).ok()?;
+    let mut stats: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(mount_point.as_ptr(), &mut stats) } != 0 || stats.f_files == 0 {
+        return None;
+    }
+
+    let total = stats.f_files as u64;
+    Some((total.saturating_sub(stats.f_ffree as u64), total))
+}
+
+#[cfg(not(unix))]
+pub fn get_inode_usage(_mount_point: &str) -> Option<(u64, u64)> {
+    None
+}
+
+/// Reads drive temperatures in celsius from hwmon, which NVMe drives and SATA drives using the
+/// drivetemp module expose.  Keyed by block device name, like nvme0n1 or sda.  Only works within
+/// Linux.
+pub fn get_disk_temperatures() -> std::collections::HashMap<String, f32> {
+    let mut disk_temperatures = std::collections::HashMap::new();
+    if !cfg!(target_os = "linux") {
+        return disk_temperatures;
+    }
+
+    let hwmons = match std::fs::read_dir("/sys/class/hwmon") {
+        Ok(hwmons) => hwmons,
+        Err(_) => return disk_temperatures,
+    };
+
+    for hwmon in hwmons.flatten() {
+        let hwmon = hwmon.path();
+        let read_trimmed = |file_name: &str| {
+            std::fs::read_to_string(hwmon.join(file_name))
+                .ok()
+                .map(|contents| contents.trim().to_string())
+        };
+
+        // The drive's block devices are found under the hwmon's parent device
+        let block_device_dir = match read_trimmed("name").as_ref().map(String::as_str) {
+            Some("drivetemp") => hwmon.join("device").join("block"),
+            Some("nvme") => hwmon.join("device"),
+            _ => continue,
+        };
+        let temperature = match read_trimmed("temp1_input")
+            .and_then(|millidegrees| millidegrees.parse::<f32>().ok())
+        {
+            Some(millidegrees) => millidegrees / 1000.0,
+            None => continue,
+        };
+
+        if let Ok(entries) = std::fs::read_dir(block_device_dir) {
+            for entry in entries.flatten() {
+                let device_name = entry.file_name().to_string_lossy().to_string();
+                // NVMe controllers also hold non-namespace entries, like their hwmon
+                let is_block_device = if device_name.starts_with("nvme") {
+                    device_name["nvme".len()..].contains('n')
+                } else {
+                    true
+                };
+                if is_block_device {
+                    disk_temperatures.insert(device_name, temperature);
+                }
+            }
+        }
+    }
+
+    disk_temperatures
+}
+
+/// Whether a partition, like nvme0n1p2 or sda1, is on the given block device, like nvme0n1 or sda.
+pub fn is_partition_of(partition_name: &str, device_name: &str) -> bool {
+    if !partition_name.starts_with(device_name) {
+        return false;
+    }
+
+    let partition_suffix = &partition_name[device_name.len()..];
+    let partition_number = if partition_suffix.starts_with('p') {
+        &partition_suffix[1..]
+    } else {
+        partition_suffix
+    };
+    partition_number.chars().all(|c| c.is_ascii_digit())
+}
@@ -0,0 +1,335 @@
+//! Data collection for GPUs.  Harvesting is only done when built with the `gpu` feature.
+//!
+//! Each vendor is read through its own backend, which are all found on startup and feed into the
+//! same [`GpuHarvest`] list.
+
+#[cfg(feature = "gpu")]
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::data_harvester::mem::MemHarvest;
+use crate::utils::error::Result;
+
+#[cfg(feature = "gpu")]
+use crate::utils::error::BottomError;
+
+#[cfg(feature = "gpu")]
+use nvml_wrapper::{enum_wrappers::device::TemperatureSensor, error::NvmlError};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct GpuHarvest {
+    pub name: String,
+    pub memory: MemHarvest,
+    pub utilization_percent: Option<f64>,
+    pub temperature_celsius: Option<f32>,
+    pub power_watts: Option<f64>,
+}
+
+/// A source of GPU data, such as a vendor's driver.
+pub trait GpuBackend {
+    /// Fails if the backend can't be read at all, rather than just some values of a GPU.
+    fn get_gpu_data_list(&mut self) -> Result<Vec<GpuHarvest>>;
+
+    /// Temperatures in celsius for the temperature widget, paired with their sensor names.  GPUs
+    /// with hwmon sensors are already read by the temperature harvester, so this is only needed
+    /// for those without.
+    fn get_temperature_data(&mut self) -> Vec<(String, f32)> {
+        Vec::new()
+    }
+}
+
+/// Finds a backend for each GPU vendor present, along with why NVML couldn't be loaded if there
+/// seems to be an NVIDIA GPU that needs it.
+#[cfg(feature = "gpu")]
+pub fn get_gpu_backends() -> (Vec<Box<dyn GpuBackend>>, Option<String>) {
+    let mut backends: Vec<Box<dyn GpuBackend>> = Vec::new();
+    let mut nvml_error = None;
+    let cards = if cfg!(target_os = "linux") {
+        get_drm_cards()
+    } else {
+        Vec::new()
+    };
+
+    match nvml_wrapper::Nvml::init() {
+        Ok(nvml) => backends.push(Box::new(NvidiaBackend { nvml })),
+        Err(err) => {
+            // Not having the library without an NVIDIA card just means there's no NVIDIA GPU to
+            // read from
+            let is_library_missing = match err {
+                NvmlError::LibloadingError(_) | NvmlError::LibraryNotFound => true,
+                _ => false,
+            };
+            let has_nvidia_card = cards.iter().any(|(_, driver)| driver == "nvidia");
+            if has_nvidia_card || !is_library_missing {
+                nvml_error = Some(format!("Couldn't load NVML: {}", err));
+            }
+        }
+    }
+
+    if cfg!(target_os = "linux") {
+        let amd_cards: Vec<std::path::PathBuf> = cards
+            .iter()
+            .filter(|(_, driver)| driver == "amdgpu")
+            .map(|(card, _)| card.clone())
+            .collect();
+        if !amd_cards.is_empty() {
+            backends.push(Box::new(AmdBackend { cards: amd_cards }));
+        }
+
+        let intel_cards: Vec<IntelCard> = cards
+            .iter()
+            .filter(|(_, driver)| driver == "i915" || driver == "xe")
+            .map(|(card, _)| IntelCard {
+                card: card.clone(),
+                last_sample: None,
+            })
+            .collect();
+        if !intel_cards.is_empty() {
+            backends.push(Box::new(IntelBackend { cards: intel_cards }));
+        }
+    }
+
+    (backends, nvml_error)
+}
+
+/// Every card under /sys/class/drm, paired with the name of its driver.
+#[cfg(feature = "gpu")]
+fn get_drm_cards() -> Vec<(std::path::PathBuf, String)> {
+    let mut cards: Vec<(std::path::PathBuf, String)> = match std::fs::read_dir("/sys/class/drm") {
+        Ok(entries) => entries
+            .flatten()
+            .filter(|entry| {
+                // Skip connectors like card0-DP-1
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                file_name.starts_with("card") && !file_name.contains('-')
+            })
+            .filter_map(|entry| {
+                let card = entry.path();
+                let driver = std::fs::read_link(card.join("device/driver")).ok()?;
+                let driver = driver.file_name()?.to_string_lossy().to_string();
+                Some((card, driver))
+            })
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    cards.sort();
+    cards
+}
+
+/// Reads a single number from a sysfs file.
+#[cfg(feature = "gpu")]
+fn read_sysfs_value(path: &std::path::Path) -> Option<u64> {
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|contents| contents.trim().parse::<u64>().ok())
+}
+
+/// A card's hwmon directory, where its temperature and power are reported.
+#[cfg(feature = "gpu")]
+fn get_card_hwmon(card: &std::path::Path) -> Option<std::path::PathBuf> {
+    std::fs::read_dir(card.join("device/hwmon"))
+        .ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .next()
+}
+
+#[cfg(feature = "gpu")]
+struct NvidiaBackend {
+    nvml: nvml_wrapper::Nvml,
+}
+
+#[cfg(feature = "gpu")]
+impl GpuBackend for NvidiaBackend {
+    fn get_gpu_data_list(&mut self) -> Result<Vec<GpuHarvest>> {
+        let mut gpu_vector = Vec::new();
+
+        let num_devices = self.nvml.device_count().map_err(|err| {
+            BottomError::GenericError(format!("NVML couldn't count the GPUs: {}", err))
+        })?;
+        for index in 0..num_devices {
+            if let Ok(device) = self.nvml.device_by_index(index) {
+                if let Ok(memory) = device.memory_info() {
+                    gpu_vector.push(GpuHarvest {
+                        name: device.name().unwrap_or_else(|_| format!("GPU {}", index)),
+                        memory: MemHarvest {
+                            mem_total_in_mb: memory.total / 1_048_576,
+                            mem_used_in_mb: memory.used / 1_048_576,
+                        },
+                        utilization_percent: device
+                            .utilization_rates()
+                            .ok()
+                            .map(|utilization| f64::from(utilization.gpu)),
+                        temperature_celsius: device
+                            .temperature(TemperatureSensor::Gpu)
+                            .ok()
+                            .map(|celsius| celsius as f32),
+                        // NVML reports power in milliwatts
+                        power_watts: device
+                            .power_usage()
+                            .ok()
+                            .map(|milliwatts| f64::from(milliwatts) / 1000.0),
+                    });
+                }
+            }
+        }
+
+        Ok(gpu_vector)
+    }
+
+    fn get_temperature_data(&mut self) -> Vec<(String, f32)> {
+        let mut temperature_vec = Vec::new();
+
+        if let Ok(num_devices) = self.nvml.device_count() {
+            for index in 0..num_devices {
+                if let Ok(celsius) = self
+                    .nvml
+                    .device_by_index(index)
+                    .and_then(|device| device.temperature(TemperatureSensor::Gpu))
+                {
+                    temperature_vec.push((format!("nvidia gpu{}", index), celsius as f32));
+                }
+            }
+        }
+
+        temperature_vec
+    }
+}
+
+/// amdgpu exposes VRAM usage and how busy the GPU is under each card's device directory.
+#[cfg(feature = "gpu")]
+struct AmdBackend {
+    cards: Vec<std::path::PathBuf>,
+}
+
+#[cfg(feature = "gpu")]
+impl GpuBackend for AmdBackend {
+    fn get_gpu_data_list(&mut self) -> Result<Vec<GpuHarvest>> {
+        let mut gpu_vector = Vec::new();
+
+        for card in &self.cards {
+            let device = card.join("device");
+            let hwmon = get_card_hwmon(card);
+            let read_hwmon = |file_name: &str| {
+                hwmon
+                    .as_ref()
+                    .and_then(|hwmon| read_sysfs_value(&hwmon.join(file_name)))
+            };
+
+            let name = std::fs::read_to_string(device.join("product_name"))
+                .ok()
+                .map(|product_name| product_name.trim().to_string())
+                .filter(|product_name| !product_name.is_empty())
+                .unwrap_or_else(|| {
+                    card.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .to_string()
+                });
+
+            gpu_vector.push(GpuHarvest {
+                name,
+                memory: MemHarvest {
+                    mem_total_in_mb: read_sysfs_value(&device.join("mem_info_vram_total"))
+                        .unwrap_or(0)
+                        / 1_048_576,
+                    mem_used_in_mb: read_sysfs_value(&device.join("mem_info_vram_used"))
+                        .unwrap_or(0)
+                        / 1_048_576,
+                },
+                utilization_percent: read_sysfs_value(&device.join("gpu_busy_percent"))
+                    .map(|busy_percent| busy_percent as f64),
+                // The edge temperature, in millidegrees
+                temperature_celsius: read_hwmon("temp1_input")
+                    .map(|millidegrees| millidegrees as f32 / 1000.0),
+                // Older kernels only have the average, and newer ones only the current, power
+                power_watts: read_hwmon("power1_average")
+                    .or_else(|| read_hwmon("power1_input"))
+                    .map(|microwatts| microwatts as f64 / 1_000_000.0),
+            });
+        }
+
+        Ok(gpu_vector)
+    }
+}
+
+/// i915 and xe don't report how busy the GPU is directly, so it's worked out from how long the
+/// GPU spent idling in RC6 since the last sample.  Energy is only reported by discrete cards.
+#[cfg(feature = "gpu")]
+struct IntelBackend {
+    cards: Vec<IntelCard>,
+}
+
+#[cfg(feature = "gpu")]
+struct IntelCard {
+    card: std::path::PathBuf,
+    /// When the last sample was taken, with the RC6 residency in milliseconds and the energy used
+    /// in microjoules up to then.
+    last_sample: Option<(Instant, Option<u64>, Option<u64>)>,
+}
+
+#[cfg(feature = "gpu")]
+impl GpuBackend for IntelBackend {
+    fn get_gpu_data_list(&mut self) -> Result<Vec<GpuHarvest>> {
+        let mut gpu_vector = Vec::new();
+
+        for intel_card in &mut self.cards {
+            let card = &intel_card.card;
+            let hwmon = get_card_hwmon(card);
+            let read_hwmon = |file_name: &str| {
+                hwmon
+                    .as_ref()
+                    .and_then(|hwmon| read_sysfs_value(&hwmon.join(file_name)))
+            };
+
+            let current_instant = Instant::now();
+            let rc6_residency_ms = read_sysfs_value(&card.join("gt/gt0/rc6_residency_ms"))
+                .or_else(|| read_sysfs_value(&card.join("power/rc6_residency_ms")));
+            let energy_uj = read_hwmon("energy1_input");
+
+            let (utilization_percent, power_watts) = match intel_card.last_sample {
+                Some((last_instant, last_rc6_residency_ms, last_energy_uj)) => {
+                    let elapsed_secs = current_instant
+                        .duration_since(last_instant)
+                        .as_secs_f64()
+                        .max(0.001);
+                    let utilization_percent = match (rc6_residency_ms, last_rc6_residency_ms) {
+                        (Some(rc6), Some(last_rc6)) => {
+                            let idle_secs = rc6.saturating_sub(last_rc6) as f64 / 1000.0;
+                            Some(
+                                (100.0 - idle_secs / elapsed_secs * 100.0)
+                                    .max(0.0)
+                                    .min(100.0),
+                            )
+                        }
+                        _ => None,
+                    };
+                    let power_watts = match (energy_uj, last_energy_uj) {
+                        (Some(energy), Some(last_energy)) => Some(
+                            energy.saturating_sub(last_energy) as f64 / 1_000_000.0 / elapsed_secs,
+                        ),
+                        _ => None,
+                    };
+                    (utilization_percent, power_watts)
+                }
+                None => (None, None),
+            };
+            intel_card.last_sample = Some((current_instant, rc6_residency_ms, energy_uj));
+
+            gpu_vector.push(GpuHarvest {
+                name: format!(
+                    "Intel {}",
+                    card.file_name().unwrap_or_default().to_string_lossy()
+                ),
+                memory: MemHarvest::default(),
+                utilization_percent,
+                temperature_celsius: read_hwmon("temp1_input")
+                    .map(|millidegrees| millidegrees as f32 / 1000.0),
+                power_watts,
+            });
+        }
+
+        Ok(gpu_vector)
+    }
+}
@@ -1,6 +1,7 @@
 use heim::units::information;
+use serde::{Deserialize, Serialize};
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemHarvest {
     pub mem_total_in_mb: u64,
     pub mem_used_in_mb: u64,
@@ -33,3 +34,235 @@ pub async fn get_swap_data_list() -> crate::utils::error::Result<MemHarvest> {
         mem_used_in_mb: memory.used().get::<information::megabyte>(),
     })
 }
+
+/// Memory held in a compressed pool, such as zram or zswap.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CompressedMemHarvest {
+    pub name: String,
+    pub original_bytes: u64,
+    pub compressed_bytes: u64,
+}
+
+/// Gathers the sizes of any zram devices and the zswap pool.  Only works within Linux.
+pub fn get_compressed_mem_data() -> Vec<CompressedMemHarvest> {
+    let mut compressed_mem = Vec::new();
+    if !cfg!(target_os = "linux") {
+        return compressed_mem;
+    }
+
+    // Each zram device's mm_stat starts with its original and compressed data sizes
+    if let Ok(block_devices) = std::fs::read_dir("/sys/block") {
+        let mut zram = CompressedMemHarvest {
+            name: "zram".to_string(),
+            ..CompressedMemHarvest::default()
+        };
+        for device in block_devices.flatten() {
+            if !device.file_name().to_string_lossy().starts_with("zram") {
+                continue;
+            }
+            if let Ok(mm_stat) = std::fs::read_to_string(device.path().join("mm_stat")) {
+                let mut fields = mm_stat
+                    .split_whitespace()
+                    .map(|field| field.parse::<u64>().unwrap_or(0));
+                zram.original_bytes += fields.next().unwrap_or(0);
+                zram.compressed_bytes += fields.next().unwrap_or(0);
+            }
+        }
+        if zram.original_bytes > 0 {
+            compressed_mem.push(zram);
+        }
+    }
+
+    // Newer kernels report zswap's pool in meminfo, which unlike debugfs doesn't need root
+    if let Ok(meminfo) = std::fs::read_to_string("/proc/meminfo") {
+        let mut zswap = CompressedMemHarvest {
+            name: "zswap".to_string(),
+            ..CompressedMemHarvest::default()
+        };
+        for line in meminfo.lines() {
+            let mut fields = line.split_whitespace();
+            let key = fields.next();
+            let kilobytes = fields
+                .next()
+                .and_then(|value| value.parse::<u64>().ok())
+                .unwrap_or(0);
+            match key {
+                Some("Zswap:") => zswap.compressed_bytes = kilobytes * 1024,
+                Some("Zswapped:") => zswap.original_bytes = kilobytes * 1024,
+                _ => {}
+            }
+        }
+        if zswap.original_bytes > 0 {
+            compressed_mem.push(zswap);
+        }
+    }
+
+    compressed_mem
+}
+
+/// Reads the size of the ZFS ARC, if ZFS is loaded.  Only works within Linux.
+pub fn get_arc_size_in_mb() -> Option<u64> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    let arcstats = std::fs::read_to_string("/proc/spl/kstat/zfs/arcstats").ok()?;
+    arcstats.lines().find_map(|line| {
+        // Each line is the name, type, and then the value
+        let fields: Vec<&str> = line.split_whitespace().collect();
+        if fields.len() == 3 && fields[0] == "size" {
+            fields[2].parse::<u64>().ok().map(|bytes| bytes / 1_048_576)
+        } else {
+            None
+        }
+    })
+}
+
+/// Huge page counts, along with the size of each page.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct HugePagesHarvest {
+    pub total: u64,
+    pub free: u64,
+    pub reserved: u64,
+    pub page_size_in_kb: u64,
+}
+
+/// Reads huge page counts from meminfo, if any are configured.  Only works within Linux.
+pub fn get_huge_pages_data() -> Option<HugePagesHarvest> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    let meminfo = std::fs::read_to_string("/proc/meminfo").ok()?;
+    let mut huge_pages = HugePagesHarvest::default();
+    for line in meminfo.lines() {
+        let mut fields = line.split_whitespace();
+        let key = fields.next();
+        let value = fields
+            .next()
+            .and_then(|value| value.parse::<u64>().ok())
+            .unwrap_or(0);
+        match key {
+            Some("HugePages_Total:") => huge_pages.total = value,
+            Some("HugePages_Free:") => huge_pages.free = value,
+            Some("HugePages_Rsvd:") => huge_pages.reserved = value,
+            Some("Hugepagesize:") => huge_pages.page_size_in_kb = value,
+            _ => {}
+        }
+    }
+
+    if huge_pages.total > 0 {
+        Some(huge_pages)
+    } else {
+        None
+    }
+}
+
+/// Gathers the memory usage of each NUMA node, if there's more than one.  Only works within Linux.
+pub fn get_numa_mem_data() -> Vec<MemHarvest> {
+    let mut numa_nodes = Vec::new();
+    if !cfg!(target_os = "linux") {
+        return numa_nodes;
+    }
+
+    let mut node_paths: Vec<std::path::PathBuf> =
+        match std::fs::read_dir("/sys/devices/system/node") {
+            Ok(entries) => entries
+                .flatten()
+                .filter(|entry| {
+                    let file_name = entry.file_name().to_string_lossy().to_string();
+                    file_name.starts_with("node") && file_name[4..].parse::<u64>().is_ok()
+                })
+                .map(|entry| entry.path())
+                .collect(),
+            Err(_) => return numa_nodes,
+        };
+    if node_paths.len() < 2 {
+        return numa_nodes;
+    }
+    // Sort numerically so node10 doesn't come before node2
+    node_paths.sort_by_key(|path| {
+        path.file_name()
+            .and_then(|file_name| file_name.to_string_lossy()[4..].parse::<u64>().ok())
+            .unwrap_or(0)
+    });
+
+    for node_path in node_paths {
+        // Each line looks like "Node 0 MemTotal:       32768000 kB"
+        if let Ok(meminfo) = std::fs::read_to_string(node_path.join("meminfo")) {
+            let mut node = MemHarvest::default();
+            for line in meminfo.lines() {
+                let mut fields = line.split_whitespace().skip(2);
+                let key = fields.next();
+                let kilobytes = fields
+                    .next()
+                    .and_then(|value| value.parse::<u64>().ok())
+                    .unwrap_or(0);
+                match key {
+                    Some("MemTotal:") => node.mem_total_in_mb = kilobytes / 1024,
+                    Some("MemUsed:") => node.mem_used_in_mb = kilobytes / 1024,
+                    _ => {}
+                }
+            }
+            numa_nodes.push(node);
+        }
+    }
+
+    numa_nodes
+}
+
+/// Gathers the commit charge against its limit, along with the size and usage of all pagefiles.
+/// This is how Windows accounts for memory, as seen in Task Manager.
+#[cfg(target_os = "windows")]
+pub fn get_commit_and_pagefile_data() -> Option<(MemHarvest, MemHarvest)> {
+    use winapi::{
+        shared::minwindef::{BOOL, LPVOID, TRUE},
+        um::{
+            psapi::{
+                K32EnumPageFilesW, K32GetPerformanceInfo, PENUM_PAGE_FILE_INFORMATION,
+                PERFORMANCE_INFORMATION,
+            },
+            winnt::LPCWSTR,
+        },
+    };
+
+    // Sums up each pagefile's size and usage, in pages
+    unsafe extern "system" fn add_pagefile(
+        context: LPVOID, pagefile_info: PENUM_PAGE_FILE_INFORMATION, _file_name: LPCWSTR,
+    ) -> BOOL {
+        let pagefile_totals = &mut *(context as *mut (u64, u64));
+        pagefile_totals.0 += (*pagefile_info).TotalSize as u64;
+        pagefile_totals.1 += (*pagefile_info).TotalInUse as u64;
+        TRUE
+    }
+
+    let mut performance_info: PERFORMANCE_INFORMATION = unsafe { std::mem::zeroed() };
+    let performance_info_size = std::mem::size_of::<PERFORMANCE_INFORMATION>() as u32;
+    performance_info.cb = performance_info_size;
+    if unsafe { K32GetPerformanceInfo(&mut performance_info, performance_info_size) } == 0 {
+        return None;
+    }
+    let page_size = performance_info.PageSize as u64;
+
+    let mut pagefile_totals: (u64, u64) = (0, 0);
+    if unsafe {
+        K32EnumPageFilesW(
+            Some(add_pagefile),
+            &mut pagefile_totals as *mut (u64, u64) as LPVOID,
+        )
+    } == 0
+    {
+        return None;
+    }
+
+    Some((
+        MemHarvest {
+            mem_total_in_mb: performance_info.CommitLimit as u64 * page_size / 1_048_576,
+            mem_used_in_mb: performance_info.CommitTotal as u64 * page_size / 1_048_576,
+        },
+        MemHarvest {
+            mem_total_in_mb: pagefile_totals.0 * page_size / 1_048_576,
+            mem_used_in_mb: pagefile_totals.1 * page_size / 1_048_576,
+        },
+    ))
+}
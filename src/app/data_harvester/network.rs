@@ -1,65 +1,245 @@
+use std::collections::HashMap;
 use std::time::Instant;
 
 use futures::StreamExt;
 use heim::net;
 use heim::units::information::byte;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use sysinfo::{NetworkExt, System, SystemExt};
 
-#[derive(Default, Clone, Debug)]
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
 pub struct NetworkHarvest {
     pub rx: u64,
     pub tx: u64,
     pub total_rx: u64,
     pub total_tx: u64,
+    /// Transferred since bottom started, rather than since boot.
+    pub session_rx: u64,
+    pub session_tx: u64,
+    /// Sorted by name.
+    pub interfaces: Vec<InterfaceHarvest>,
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+pub struct InterfaceHarvest {
+    pub name: String,
+    pub rx: u64,
+    pub tx: u64,
+    pub total_rx: u64,
+    pub total_tx: u64,
+    pub session_rx: u64,
+    pub session_tx: u64,
+    /// Only set for wireless interfaces within Linux.
+    pub wireless: Option<WirelessHarvest>,
+}
+
+#[derive(Default, Clone, Debug, Serialize, Deserialize)]
+pub struct WirelessHarvest {
+    /// The link quality as a percentage of its maximum of 70.
+    pub quality: f64,
+    pub signal_dbm: i64,
+    /// The negotiated transmit rate, which is only available if `iw` is installed.
+    pub link_rate_mbps: Option<f64>,
+}
+
+/// The totals from the last harvest, which rates are measured against.
+#[derive(Default, Clone, Debug)]
+pub struct NetworkTotals {
+    pub rx: u64,
+    pub tx: u64,
+    pub interfaces: HashMap<String, (u64, u64)>,
+    /// Each interface's totals when it was first seen, which session totals are measured from.
+    pub initial_interfaces: HashMap<String, (u64, u64)>,
+}
+
+/// Regexes matched against interface names, set under `[network_filter]` in the config.
+#[derive(Default, Clone, Debug)]
+pub struct InterfaceFilter {
+    /// If not empty, only interfaces matching one of these are included.
+    pub allow: Vec<Regex>,
+    pub deny: Vec<Regex>,
+}
+
+impl InterfaceFilter {
+    pub fn is_included(&self, name: &str) -> bool {
+        (self.allow.is_empty() || self.allow.iter().any(|regex| regex.is_match(name)))
+            && !self.deny.iter().any(|regex| regex.is_match(name))
+    }
 }
 
 impl NetworkHarvest {
     pub fn first_run_cleanup(&mut self) {
         self.rx = 0;
         self.tx = 0;
+        for interface in &mut self.interfaces {
+            interface.rx = 0;
+            interface.tx = 0;
+        }
     }
 }
 
 pub async fn get_network_data(
-    sys: &System, prev_net_access_time: Instant, prev_net_rx: &mut u64, prev_net_tx: &mut u64,
-    curr_time: Instant,
+    sys: &System, prev_net_access_time: Instant, prev_totals: &mut NetworkTotals,
+    filter: &InterfaceFilter, curr_time: Instant,
 ) -> NetworkHarvest {
     let mut io_data = net::io_counters();
-    let mut total_rx: u64 = 0;
-    let mut total_tx: u64 = 0;
+    let mut interface_totals: Vec<(String, u64, u64)> = Vec::new();
 
     if cfg!(target_os = "windows") {
         let networks = sys.get_networks();
-        for (_, network) in networks {
-            total_rx += network.get_total_income();
-            total_tx += network.get_total_outcome();
+        for (name, network) in networks {
+            interface_totals.push((
+                name.to_string(),
+                network.get_total_income(),
+                network.get_total_outcome(),
+            ));
         }
     } else {
         while let Some(io) = io_data.next().await {
             if let Ok(io) = io {
-                total_rx += io.bytes_recv().get::<byte>();
-                total_tx += io.bytes_sent().get::<byte>();
+                interface_totals.push((
+                    io.interface().to_string(),
+                    io.bytes_recv().get::<byte>(),
+                    io.bytes_sent().get::<byte>(),
+                ));
             }
         }
     }
 
-    let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
+    interface_totals.retain(|(name, _, _)| filter.is_included(name));
+    interface_totals.sort_by(|a, b| a.0.cmp(&b.0));
+    let total_rx: u64 = interface_totals.iter().map(|(_, rx, _)| rx).sum();
+    let total_tx: u64 = interface_totals.iter().map(|(_, _, tx)| tx).sum();
 
-    let (rx, tx) = if elapsed_time == 0.0 {
-        (0, 0)
-    } else {
-        (
-            ((total_rx - *prev_net_rx) as f64 / elapsed_time) as u64,
-            ((total_tx - *prev_net_tx) as f64 / elapsed_time) as u64,
-        )
+    let elapsed_time = curr_time.duration_since(prev_net_access_time).as_secs_f64();
+    let get_rate = |curr_total: u64, prev_total: u64| {
+        if elapsed_time == 0.0 {
+            0
+        } else {
+            // Counters can go backwards if an interface is reset
+            (curr_total.saturating_sub(prev_total) as f64 / elapsed_time) as u64
+        }
     };
 
-    *prev_net_rx = total_rx;
-    *prev_net_tx = total_tx;
+    let (rx, tx) = (
+        get_rate(total_rx, prev_totals.rx),
+        get_rate(total_tx, prev_totals.tx),
+    );
+
+    let mut wireless_data = get_wireless_data();
+
+    // Interfaces that weren't seen before start off at zero, rather than at their entire total
+    let interfaces: Vec<InterfaceHarvest> = interface_totals
+        .iter()
+        .map(|(name, interface_rx, interface_tx)| {
+            let (prev_rx, prev_tx) = prev_totals
+                .interfaces
+                .get(name)
+                .cloned()
+                .unwrap_or((*interface_rx, *interface_tx));
+            let (initial_rx, initial_tx) = *prev_totals
+                .initial_interfaces
+                .entry(name.clone())
+                .or_insert((*interface_rx, *interface_tx));
+            InterfaceHarvest {
+                name: name.clone(),
+                rx: get_rate(*interface_rx, prev_rx),
+                tx: get_rate(*interface_tx, prev_tx),
+                total_rx: *interface_rx,
+                total_tx: *interface_tx,
+                session_rx: interface_rx.saturating_sub(initial_rx),
+                session_tx: interface_tx.saturating_sub(initial_tx),
+                wireless: wireless_data.remove(name),
+            }
+        })
+        .collect();
+
+    // Summed per interface so that interfaces coming and going don't skew the result
+    let session_rx = interfaces
+        .iter()
+        .map(|interface| interface.session_rx)
+        .sum();
+    let session_tx = interfaces
+        .iter()
+        .map(|interface| interface.session_tx)
+        .sum();
+
+    prev_totals.rx = total_rx;
+    prev_totals.tx = total_tx;
+    prev_totals.interfaces = interface_totals
+        .into_iter()
+        .map(|(name, interface_rx, interface_tx)| (name, (interface_rx, interface_tx)))
+        .collect();
     NetworkHarvest {
         rx,
         tx,
         total_rx,
         total_tx,
+        session_rx,
+        session_tx,
+        interfaces,
+    }
+}
+
+/// Reads the signal of each wireless interface from /proc/net/wireless.  Only works within Linux.
+fn get_wireless_data() -> HashMap<String, WirelessHarvest> {
+    let mut wireless_data = HashMap::new();
+    if !cfg!(target_os = "linux") {
+        return wireless_data;
     }
+
+    let wireless = match std::fs::read_to_string("/proc/net/wireless") {
+        Ok(wireless) => wireless,
+        Err(_) => return wireless_data,
+    };
+
+    // After two header lines, each line looks like
+    // "wlan0: 0000   54.  -56.  -256        0      0      0      0      0        0"
+    for line in wireless.lines().skip(2) {
+        let mut name_and_fields = line.splitn(2, ':');
+        let name = match name_and_fields.next() {
+            Some(name) => name.trim().to_string(),
+            None => continue,
+        };
+        let fields: Vec<f64> = name_and_fields
+            .next()
+            .unwrap_or_default()
+            .split_whitespace()
+            .skip(1)
+            .take(2)
+            .filter_map(|field| field.trim_end_matches('.').parse::<f64>().ok())
+            .collect();
+        if fields.len() < 2 {
+            continue;
+        }
+
+        let link_rate_mbps = get_link_rate(&name);
+        wireless_data.insert(
+            name,
+            WirelessHarvest {
+                quality: (fields[0] / 70.0 * 100.0).min(100.0),
+                signal_dbm: fields[1] as i64,
+                link_rate_mbps,
+            },
+        );
+    }
+
+    wireless_data
+}
+
+/// The kernel doesn't expose the bitrate outside of nl80211, so rely on `iw` for it.
+fn get_link_rate(name: &str) -> Option<f64> {
+    let output = std::process::Command::new("iw")
+        .args(&["dev", name, "link"])
+        .output()
+        .ok()?;
+
+    // Looks like "\ttx bitrate: 866.7 MBit/s VHT-MCS 9 80MHz short GI VHT-NSS 2"
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::trim)
+        .find(|line| line.starts_with("tx bitrate:"))
+        .and_then(|line| line["tx bitrate:".len()..].split_whitespace().next())
+        .and_then(|rate| rate.parse::<f64>().ok())
 }
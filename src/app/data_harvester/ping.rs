@@ -0,0 +1,141 @@
+//! Data collection for the latency of configured hosts, using the system's `ping` command.
+
+use std::collections::VecDeque;
+use std::process::{Child, Command, Stdio};
+
+use serde::{Deserialize, Serialize};
+
+/// How many of the most recent pings are used to calculate packet loss.
+const LOSS_WINDOW: usize = 60;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PingHarvest {
+    pub host: String,
+    /// The round trip time of the last reply.  Carried over if the latest ping was lost.
+    pub latency_ms: f64,
+    /// Whether the latest ping got a reply.
+    pub is_reachable: bool,
+    /// The percentage of the last `LOSS_WINDOW` pings without a reply.
+    pub loss_percentage: f64,
+}
+
+struct PingTarget {
+    host: String,
+    in_flight: Option<Child>,
+    recent_results: VecDeque<bool>,
+    last_harvest: PingHarvest,
+}
+
+/// Keeps one ping per host in flight, so that slow or unreachable hosts never block harvesting.
+#[derive(Default)]
+pub struct Pinger {
+    targets: Vec<PingTarget>,
+}
+
+impl Pinger {
+    pub fn new(hosts: &[String]) -> Self {
+        Pinger {
+            targets: hosts
+                .iter()
+                .map(|host| PingTarget {
+                    host: host.clone(),
+                    in_flight: None,
+                    recent_results: VecDeque::with_capacity(LOSS_WINDOW),
+                    last_harvest: PingHarvest {
+                        host: host.clone(),
+                        ..PingHarvest::default()
+                    },
+                })
+                .collect(),
+        }
+    }
+
+    /// Collects the pings that have finished since the last call, and sends new ones in their place.
+    pub fn get_ping_data_list(&mut self) -> Vec<PingHarvest> {
+        for target in &mut self.targets {
+            if let Some(mut child) = target.in_flight.take() {
+                match child.try_wait() {
+                    Ok(Some(_)) => {
+                        let latency_ms = child.wait_with_output().ok().and_then(|output| {
+                            parse_latency(&String::from_utf8_lossy(&output.stdout))
+                        });
+                        target.record(latency_ms);
+                    }
+                    Ok(None) => target.in_flight = Some(child),
+                    Err(_) => {}
+                }
+            }
+
+            if target.in_flight.is_none() {
+                target.in_flight = spawn_ping(&target.host);
+            }
+        }
+
+        self.targets
+            .iter()
+            .map(|target| target.last_harvest.clone())
+            .collect()
+    }
+}
+
+impl Drop for Pinger {
+    fn drop(&mut self) {
+        for target in &mut self.targets {
+            if let Some(child) = &mut target.in_flight {
+                let _ = child.kill();
+                let _ = child.wait();
+            }
+        }
+    }
+}
+
+impl PingTarget {
+    fn record(&mut self, latency_ms: Option<f64>) {
+        if self.recent_results.len() == LOSS_WINDOW {
+            self.recent_results.pop_front();
+        }
+        self.recent_results.push_back(latency_ms.is_some());
+
+        let lost = self
+            .recent_results
+            .iter()
+            .filter(|is_reply| !**is_reply)
+            .count();
+        self.last_harvest.loss_percentage = lost as f64 / self.recent_results.len() as f64 * 100.0;
+        self.last_harvest.is_reachable = latency_ms.is_some();
+        if let Some(latency_ms) = latency_ms {
+            self.last_harvest.latency_ms = latency_ms;
+        }
+    }
+}
+
+/// Sends a single ping that gives up after a second.
+fn spawn_ping(host: &str) -> Option<Child> {
+    let args: &[&str] = if cfg!(target_os = "windows") {
+        &["-n", "1", "-w", "1000"]
+    } else if cfg!(target_os = "macos") {
+        &["-c", "1", "-t", "1"]
+    } else {
+        &["-c", "1", "-W", "1"]
+    };
+
+    Command::new("ping")
+        .args(args)
+        .arg(host)
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()
+}
+
+/// Replies look like "time=12.3 ms" on Unix, and "time=12ms" or "time<1ms" within Windows.
+fn parse_latency(output: &str) -> Option<f64> {
+    let time_start = output.find("time=").or_else(|| output.find("time<"))? + "time=".len();
+    let latency: String = output[time_start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit() || *c == '.')
+        .collect();
+
+    latency.parse::<f64>().ok()
+}
@@ -4,16 +4,27 @@ use std::{
     time::Instant,
 };
 
+use serde::{Deserialize, Serialize};
+
 use sysinfo::{ProcessExt, ProcessorExt, System, SystemExt};
 
 use crate::utils::error;
 
-#[derive(Clone)]
+#[derive(Clone, Serialize, Deserialize)]
 pub enum ProcessSorting {
     CPU,
     MEM,
     PID,
     NAME,
+    SWAP,
+}
+
+/// What to gather for each process, besides the basics.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ProcessOptions {
+    pub use_current_cpu_total: bool,
+    pub get_process_swap: bool,
+    pub get_process_affinity: bool,
 }
 
 impl Default for ProcessSorting {
@@ -22,12 +33,34 @@ impl Default for ProcessSorting {
     }
 }
 
-#[derive(Debug, Clone, Default)]
+impl ProcessSorting {
+    /// Usage-based keys are sorted in descending order by default, everything else ascending.
+    pub fn is_reverse_by_default(&self) -> bool {
+        match self {
+            ProcessSorting::CPU | ProcessSorting::MEM | ProcessSorting::SWAP => true,
+            ProcessSorting::PID | ProcessSorting::NAME => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ProcessHarvest {
     pub pid: u32,
     pub cpu_usage_percent: f64,
     pub mem_usage_percent: f64,
     pub name: String,
+    pub command: String,
+    /// Only gathered on Linux, and only if the swap column is enabled.
+    pub swap_usage_bytes: u64,
+    /// Only detected on Linux.
+    pub is_kernel_thread: bool,
+    /// Only gathered on Linux.
+    pub user: String,
+    /// The list of CPUs the process may run on (ie: "0-3,6").  Only gathered on Linux, and
+    /// only if the affinity column is enabled.
+    pub cpu_affinity: String,
+    /// The core the process last ran on.  Only gathered on Linux.
+    pub last_cpu: Option<usize>,
 }
 
 fn cpu_usage_calculation(
@@ -100,7 +133,8 @@ fn cpu_usage_calculation(
     Ok((result, cpu_percentage))
 }
 
-fn get_process_cpu_stats(pid: u32) -> std::io::Result<f64> {
+/// Returns the total CPU time of the process, along with the core it last ran on.
+fn get_process_cpu_stats(pid: u32) -> std::io::Result<(f64, Option<usize>)> {
     let mut path = std::path::PathBuf::new();
     path.push("/proc");
     path.push(&pid.to_string());
@@ -111,9 +145,61 @@ fn get_process_cpu_stats(pid: u32) -> std::io::Result<f64> {
     let utime = val[13].parse::<f64>().unwrap_or(0_f64);
     let stime = val[14].parse::<f64>().unwrap_or(0_f64);
 
+    // The name may have spaces, so count the processor field (the 39th) from the end of the name
+    let last_cpu = stat_results
+        .rsplit(')')
+        .next()
+        .and_then(|after_name| after_name.split_whitespace().nth(36))
+        .and_then(|processor| processor.parse::<usize>().ok());
+
     //debug!("PID: {}, utime: {}, stime: {}", pid, utime, stime);
 
-    Ok(utime + stime) // This seems to match top...
+    Ok((utime + stime, last_cpu)) // This seems to match top...
+}
+
+fn get_process_swap_usage(pid: u32) -> std::io::Result<u64> {
+    let mut path = std::path::PathBuf::new();
+    path.push("/proc");
+    path.push(&pid.to_string());
+    path.push("status");
+
+    let status_results = std::fs::read_to_string(path)?;
+
+    // The line is of the form "VmSwap:       1234 kB"; kernel threads do not have one at all.
+    for line in status_results.lines() {
+        if line.starts_with("VmSwap:") {
+            let swap_kb = line
+                .split_whitespace()
+                .nth(1)
+                .unwrap_or("0")
+                .parse::<u64>()
+                .unwrap_or(0);
+            return Ok(swap_kb * 1024);
+        }
+    }
+
+    Ok(0)
+}
+
+fn get_process_cpu_affinity(pid: u32) -> std::io::Result<String> {
+    let mut path = std::path::PathBuf::new();
+    path.push("/proc");
+    path.push(&pid.to_string());
+    path.push("status");
+
+    let status_results = std::fs::read_to_string(path)?;
+
+    // The line is of the form "Cpus_allowed_list:      0-3,6"
+    for line in status_results.lines() {
+        if line.starts_with("Cpus_allowed_list:") {
+            return Ok(line
+                .trim_start_matches("Cpus_allowed_list:")
+                .trim()
+                .to_string());
+        }
+    }
+
+    Ok("".to_string())
 }
 
 /// Note that cpu_fraction should be represented WITHOUT the \times 100 factor!
@@ -122,7 +208,7 @@ fn linux_cpu_usage<S: core::hash::BuildHasher>(
     prev_pid_stats: &HashMap<String, (f64, Instant), S>,
     new_pid_stats: &mut HashMap<String, (f64, Instant), S>, use_current_cpu_total: bool,
     curr_time: Instant,
-) -> std::io::Result<f64> {
+) -> std::io::Result<(f64, Option<usize>)> {
     // Based heavily on https://stackoverflow.com/a/23376195 and https://stackoverflow.com/a/1424556
     let before_proc_val: f64 = if prev_pid_stats.contains_key(&pid.to_string()) {
         prev_pid_stats
@@ -132,7 +218,7 @@ fn linux_cpu_usage<S: core::hash::BuildHasher>(
     } else {
         0_f64
     };
-    let after_proc_val = get_process_cpu_stats(pid)?;
+    let (after_proc_val, last_cpu) = get_process_cpu_stats(pid)?;
 
     /*debug!(
         "PID - {} - Before: {}, After: {}, CPU: {}, Percentage: {}",
@@ -146,24 +232,36 @@ fn linux_cpu_usage<S: core::hash::BuildHasher>(
     new_pid_stats.insert(pid.to_string(), (after_proc_val, curr_time));
 
     if use_current_cpu_total {
-        Ok((after_proc_val - before_proc_val) / cpu_usage * 100_f64)
+        Ok((
+            (after_proc_val - before_proc_val) / cpu_usage * 100_f64,
+            last_cpu,
+        ))
     } else {
-        Ok((after_proc_val - before_proc_val) / cpu_usage * 100_f64 * cpu_fraction)
+        Ok((
+            (after_proc_val - before_proc_val) / cpu_usage * 100_f64 * cpu_fraction,
+            last_cpu,
+        ))
     }
 }
 
 fn convert_ps<S: core::hash::BuildHasher>(
     process: &str, cpu_usage: f64, cpu_fraction: f64,
     prev_pid_stats: &HashMap<String, (f64, Instant), S>,
-    new_pid_stats: &mut HashMap<String, (f64, Instant), S>, use_current_cpu_total: bool,
+    new_pid_stats: &mut HashMap<String, (f64, Instant), S>, options: ProcessOptions,
     curr_time: Instant,
 ) -> std::io::Result<ProcessHarvest> {
     if process.trim().to_string().is_empty() {
         return Ok(ProcessHarvest {
             pid: 0,
             name: "".to_string(),
+            command: "".to_string(),
             mem_usage_percent: 0.0,
             cpu_usage_percent: 0.0,
+            swap_usage_bytes: 0,
+            is_kernel_thread: false,
+            user: "".to_string(),
+            cpu_affinity: "".to_string(),
+            last_cpu: None,
         });
     }
 
@@ -173,39 +271,117 @@ fn convert_ps<S: core::hash::BuildHasher>(
         .parse::<u32>()
         .unwrap_or(0);
     let name = (&process[11..61]).trim().to_string();
-    let mem_usage_percent = (&process[62..])
+    let mem_usage_percent = process
+        .get(62..67)
+        .unwrap_or("")
         .trim()
         .to_string()
         .parse::<f64>()
         .unwrap_or(0_f64);
+    let user = process.get(68..100).unwrap_or("").trim().to_string();
+    let command = process.get(101..).unwrap_or("").trim().to_string();
+
+    // Kernel threads have no cmdline, which ps shows as the bracketed name (ie: "[kthreadd]").
+    let is_kernel_thread = command.starts_with('[') && command.ends_with(']');
 
-    let cpu_usage_percent = linux_cpu_usage(
+    let (cpu_usage_percent, last_cpu) = linux_cpu_usage(
         pid,
         cpu_usage,
         cpu_fraction,
         prev_pid_stats,
         new_pid_stats,
-        use_current_cpu_total,
+        options.use_current_cpu_total,
         curr_time,
     )?;
+
+    // The process may have died in between; we don't want to toss the entry for that.
+    let swap_usage_bytes = if options.get_process_swap {
+        get_process_swap_usage(pid).unwrap_or(0)
+    } else {
+        0
+    };
+
+    let cpu_affinity = if options.get_process_affinity {
+        get_process_cpu_affinity(pid).unwrap_or_default()
+    } else {
+        "".to_string()
+    };
+
     Ok(ProcessHarvest {
         pid,
         name,
+        command,
         mem_usage_percent,
         cpu_usage_percent,
+        swap_usage_bytes,
+        is_kernel_thread,
+        user,
+        cpu_affinity,
+        last_cpu,
     })
 }
 
+/// Returns the environment variables of a process as key-value pairs.  Note this will
+/// generally fail due to a lack of permissions for processes not owned by the user.
+pub fn get_process_environment(pid: u32) -> error::Result<Vec<(String, String)>> {
+    let env_vars: Vec<String> = if cfg!(target_os = "linux") {
+        let mut path = std::path::PathBuf::new();
+        path.push("/proc");
+        path.push(&pid.to_string());
+        path.push("environ");
+
+        let environ = std::fs::read(path).map_err(|err| {
+            if let std::io::ErrorKind::PermissionDenied = err.kind() {
+                error::BottomError::GenericError(
+                    "Permission denied - try running bottom with elevated privileges.".to_string(),
+                )
+            } else {
+                error::BottomError::from(err)
+            }
+        })?;
+
+        String::from_utf8_lossy(&environ)
+            .split('\0')
+            .filter(|entry| !entry.is_empty())
+            .map(|entry| entry.to_string())
+            .collect()
+    } else {
+        let mut sys = System::new();
+        if !sys.refresh_process(pid as sysinfo::Pid) {
+            return Err(error::BottomError::GenericError(format!(
+                "Unable to find a process with PID {}.",
+                pid
+            )));
+        }
+
+        match sys.get_process(pid as sysinfo::Pid) {
+            Some(process) => process.environ().to_vec(),
+            None => Vec::new(),
+        }
+    };
+
+    Ok(env_vars
+        .iter()
+        .map(|entry| {
+            let mut split_entry = entry.splitn(2, '=');
+            (
+                split_entry.next().unwrap_or("").to_string(),
+                split_entry.next().unwrap_or("").to_string(),
+            )
+        })
+        .collect())
+}
+
 pub fn get_sorted_processes_list(
     sys: &System, prev_idle: &mut f64, prev_non_idle: &mut f64,
-    prev_pid_stats: &mut HashMap<String, (f64, Instant), RandomState>, use_current_cpu_total: bool,
+    prev_pid_stats: &mut HashMap<String, (f64, Instant), RandomState>, options: ProcessOptions,
     mem_total_kb: u64, curr_time: Instant,
 ) -> crate::utils::error::Result<Vec<ProcessHarvest>> {
     let mut process_vector: Vec<ProcessHarvest> = Vec::new();
 
     if cfg!(target_os = "linux") {
         let ps_result = Command::new("ps")
-            .args(&["-axo", "pid:10,comm:50,%mem:5", "--noheader"])
+            .args(&["-axo", "pid:10,comm:50,%mem:5,user:32,args", "--noheader"])
             .output()?;
         let ps_stdout = String::from_utf8_lossy(&ps_result.stdout);
         let split_string = ps_stdout.split('\n');
@@ -222,7 +398,7 @@ pub fn get_sorted_processes_list(
                     cpu_fraction,
                     &prev_pid_stats,
                     &mut new_pid_stats,
-                    use_current_cpu_total,
+                    options,
                     curr_time,
                 ) {
                     if !process_object.name.is_empty() {
@@ -264,7 +440,7 @@ pub fn get_sorted_processes_list(
             } else {
                 process_val.cpu_usage() as f64 / num_cpus
             };
-            let process_cpu_usage = if use_current_cpu_total {
+            let process_cpu_usage = if options.use_current_cpu_total {
                 pcu / cpu_usage
             } else {
                 pcu
@@ -273,8 +449,14 @@ pub fn get_sorted_processes_list(
             process_vector.push(ProcessHarvest {
                 pid: process_val.pid() as u32,
                 name,
+                command: process_val.cmd().join(" "),
                 mem_usage_percent: process_val.memory() as f64 * 100.0 / mem_total_kb as f64,
                 cpu_usage_percent: process_cpu_usage,
+                swap_usage_bytes: 0,
+                is_kernel_thread: false,
+                user: "".to_string(),
+                cpu_affinity: "".to_string(),
+                last_cpu: None,
             });
         }
     }
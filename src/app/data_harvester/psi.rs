@@ -0,0 +1,80 @@
+//! Data collection for pressure stall information (PSI).  Only works within Linux.
+
+use serde::{Deserialize, Serialize};
+
+/// The names of each series of pressure data, in the order returned by `PsiHarvest::averages`.
+pub const PSI_SERIES_NAMES: [&str; 6] = [
+    "CPU some", "CPU full", "MEM some", "MEM full", "IO some", "IO full",
+];
+
+/// The percentage of time tasks were stalled on a resource, averaged over 10 and 60 seconds.
+/// "some" is when at least one task was stalled, and "full" is when all non-idle tasks were.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PsiResourceHarvest {
+    pub some_avg10: f64,
+    pub some_avg60: f64,
+    pub full_avg10: f64,
+    pub full_avg60: f64,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct PsiHarvest {
+    pub cpu: PsiResourceHarvest,
+    pub memory: PsiResourceHarvest,
+    pub io: PsiResourceHarvest,
+}
+
+impl PsiHarvest {
+    /// Returns the 10 and 60 second averages of each series, matching `PSI_SERIES_NAMES`.
+    pub fn averages(&self) -> [(f64, f64); 6] {
+        [
+            (self.cpu.some_avg10, self.cpu.some_avg60),
+            (self.cpu.full_avg10, self.cpu.full_avg60),
+            (self.memory.some_avg10, self.memory.some_avg60),
+            (self.memory.full_avg10, self.memory.full_avg60),
+            (self.io.some_avg10, self.io.some_avg60),
+            (self.io.full_avg10, self.io.full_avg60),
+        ]
+    }
+}
+
+/// Reads /proc/pressure, which only exists on kernels built with PSI support.
+pub fn get_psi_data() -> Option<PsiHarvest> {
+    if !cfg!(target_os = "linux") {
+        return None;
+    }
+
+    Some(PsiHarvest {
+        cpu: get_psi_resource_data("cpu")?,
+        memory: get_psi_resource_data("memory")?,
+        io: get_psi_resource_data("io")?,
+    })
+}
+
+fn get_psi_resource_data(resource: &str) -> Option<PsiResourceHarvest> {
+    let pressure = std::fs::read_to_string(format!("/proc/pressure/{}", resource)).ok()?;
+    let mut resource_harvest = PsiResourceHarvest::default();
+
+    // Each line looks like "some avg10=0.00 avg60=0.00 avg300=0.00 total=0"
+    for line in pressure.lines() {
+        let mut fields = line.split_whitespace();
+        let kind = fields.next();
+        for field in fields {
+            let mut key_and_value = field.splitn(2, '=');
+            let key = key_and_value.next();
+            let value = key_and_value
+                .next()
+                .and_then(|value| value.parse::<f64>().ok())
+                .unwrap_or(0.0);
+            match (kind, key) {
+                (Some("some"), Some("avg10")) => resource_harvest.some_avg10 = value,
+                (Some("some"), Some("avg60")) => resource_harvest.some_avg60 = value,
+                (Some("full"), Some("avg10")) => resource_harvest.full_avg10 = value,
+                (Some("full"), Some("avg60")) => resource_harvest.full_avg60 = value,
+                _ => {}
+            }
+        }
+    }
+
+    Some(resource_harvest)
+}
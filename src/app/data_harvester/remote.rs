@@ -0,0 +1,114 @@
+//! Data collection from another machine, by running `btm --agent` on it over SSH and reading the
+//! harvest it prints as a line of JSON each update.  SSH's own config and keys are used, so any
+//! host `ssh <host>` can log into works, with nothing listening on the remote end.
+
+use std::{
+    io::{BufRead, BufReader},
+    process::{Child, Command, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    thread,
+};
+
+use super::Data;
+
+pub enum RemoteEvent {
+    Harvest(Box<Data>),
+    /// The connection ended, with why if SSH or the agent said.
+    Disconnected(String),
+}
+
+/// A connection to a host's agent, which is closed when dropped.
+pub struct RemoteHarvester {
+    child: Child,
+    is_closed: Arc<AtomicBool>,
+}
+
+impl RemoteHarvester {
+    /// Runs `agent_command` on the host, calling `on_event` from another thread with each harvest
+    /// it prints, and once more when it stops.  Passwords can't be typed while the interface is
+    /// up, so SSH is run in batch mode and needs a key or agent to log in.
+    pub fn connect<F>(host: &str, agent_command: &str, on_event: F) -> std::io::Result<Self>
+    where
+        F: Fn(RemoteEvent) + Send + 'static,
+    {
+        let mut child = Command::new("ssh")
+            .args(&["-T", "-o", "BatchMode=yes", host, agent_command])
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        let stdout = child.stdout.take();
+        let stderr = child.stderr.take();
+
+        // SSH and the agent explain themselves on stderr, with the last line the most relevant.
+        // It's read as it comes rather than once stdout closes, as otherwise enough of it would
+        // fill the pipe and stall the agent while it's still harvesting.
+        let stderr_reader = thread::spawn(move || {
+            let mut last_line = None;
+            if let Some(stderr) = stderr {
+                for line in BufReader::new(stderr).lines() {
+                    match line {
+                        Ok(line) if !line.trim().is_empty() => {
+                            last_line = Some(line.trim().to_string())
+                        }
+                        Ok(_) => {}
+                        Err(_) => break,
+                    }
+                }
+            }
+            last_line
+        });
+
+        let is_closed = Arc::new(AtomicBool::new(false));
+        let reader_is_closed = is_closed.clone();
+        thread::spawn(move || {
+            let mut reason = None;
+            if let Some(stdout) = stdout {
+                for line in BufReader::new(stdout).lines() {
+                    // Nothing is sent once closed, so a harvest from the old host can't land
+                    // after switching
+                    if reader_is_closed.load(Ordering::SeqCst) {
+                        return;
+                    }
+                    let parsed_line = line.map_err(|err| err.to_string()).and_then(|line| {
+                        serde_json::from_str::<Data>(&line).map_err(|err| {
+                            format!(
+                                "couldn't read what the agent sent, which may be from a \
+                                     different version of bottom - {}",
+                                err
+                            )
+                        })
+                    });
+                    match parsed_line {
+                        Ok(data) => on_event(RemoteEvent::Harvest(Box::new(data))),
+                        Err(err) => {
+                            reason = Some(err);
+                            break;
+                        }
+                    }
+                }
+            }
+            if reader_is_closed.load(Ordering::SeqCst) {
+                return;
+            }
+
+            let reason = reason
+                .or_else(|| stderr_reader.join().ok().flatten())
+                .unwrap_or_else(|| "the connection closed".to_string());
+            on_event(RemoteEvent::Disconnected(reason));
+        });
+
+        Ok(RemoteHarvester { child, is_closed })
+    }
+}
+
+impl Drop for RemoteHarvester {
+    fn drop(&mut self) {
+        self.is_closed.store(true, Ordering::SeqCst);
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
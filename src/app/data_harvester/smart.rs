@@ -0,0 +1,166 @@
+//! S.M.A.R.T. data for the disk detail dialog, read with `smartctl`.  Only available when built
+//! with the `smart` feature.
+
+use crate::utils::error;
+
+/// ATA attributes whose raw value is shown, along with their display names.
+#[cfg(feature = "smart")]
+const ATA_RAW_ATTRIBUTES: [(&str, &str); 5] = [
+    ("Reallocated_Sector_Ct", "Reallocated sectors"),
+    ("Current_Pending_Sector", "Pending sectors"),
+    ("Offline_Uncorrectable", "Uncorrectable sectors"),
+    ("Reported_Uncorrect", "Reported uncorrectable errors"),
+    ("Power_On_Hours", "Power on hours"),
+];
+
+/// ATA attributes that SSD vendors use for wear, where the normalized value is the life left.
+#[cfg(feature = "smart")]
+const ATA_WEAR_ATTRIBUTES: [&str; 4] = [
+    "Wear_Leveling_Count",
+    "Media_Wearout_Indicator",
+    "SSD_Life_Left",
+    "Percent_Lifetime_Remain",
+];
+
+/// NVMe health log entries that are shown, along with their display names.
+#[cfg(feature = "smart")]
+const NVME_ATTRIBUTES: [(&str, &str); 4] = [
+    ("Percentage Used", "Percentage used"),
+    ("Available Spare", "Available spare"),
+    ("Media and Data Integrity Errors", "Media errors"),
+    ("Power On Hours", "Power on hours"),
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct SmartHarvest {
+    pub model: Option<String>,
+    pub serial: Option<String>,
+    /// The overall health assessment, like "PASSED" or "FAILED!".
+    pub health: Option<String>,
+    /// Key attributes, like reallocated sectors and SSD wear, paired with their values.
+    pub attributes: Vec<(String, String)>,
+}
+
+impl SmartHarvest {
+    pub fn is_healthy(&self) -> bool {
+        match &self.health {
+            Some(health) => health == "PASSED" || health == "OK",
+            None => true,
+        }
+    }
+}
+
+/// Runs `smartctl` on the drive a partition, like /dev/sda1, is on.
+#[cfg(feature = "smart")]
+pub fn get_smart_data(partition: &str) -> error::Result<SmartHarvest> {
+    let output = std::process::Command::new("smartctl")
+        .args(&["-i", "-H", "-A"])
+        .arg(get_parent_device(partition))
+        .output()
+        .map_err(|err| {
+            error::BottomError::GenericError(format!("Unable to run smartctl - {}", err))
+        })?;
+
+    // The lowest two bits of the exit status are set if the device couldn't be read at all
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if output.status.code().unwrap_or(1) & 0b11 != 0 {
+        let reason = stdout
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .last()
+            .unwrap_or("smartctl failed")
+            .to_string();
+        return Err(error::BottomError::GenericError(reason));
+    }
+
+    Ok(parse_smartctl_output(&stdout))
+}
+
+#[cfg(not(feature = "smart"))]
+pub fn get_smart_data(_partition: &str) -> error::Result<SmartHarvest> {
+    Err(error::BottomError::GenericError(
+        "bottom was built without S.M.A.R.T. support - rebuild it with the smart feature."
+            .to_string(),
+    ))
+}
+
+/// Finds the whole drive a partition is on through sysfs, as `smartctl` may not read partitions.
+/// Anything else, or anything outside of Linux, is returned as is.
+#[cfg(feature = "smart")]
+fn get_parent_device(partition: &str) -> String {
+    let partition_name = partition.split('/').last().unwrap_or_default();
+    let sys_path = std::path::Path::new("/sys/class/block").join(partition_name);
+    if sys_path.join("partition").exists() {
+        if let Some(parent_name) = std::fs::canonicalize(&sys_path)
+            .ok()
+            .as_ref()
+            .and_then(|path| path.parent())
+            .and_then(|parent| parent.file_name())
+        {
+            return format!("/dev/{}", parent_name.to_string_lossy());
+        }
+    }
+
+    partition.to_string()
+}
+
+#[cfg(feature = "smart")]
+fn parse_smartctl_output(output: &str) -> SmartHarvest {
+    let mut smart = SmartHarvest::default();
+    let mut is_in_attribute_table = false;
+    let mut has_wear = false;
+
+    for line in output.lines() {
+        if line.starts_with("ID# ATTRIBUTE_NAME") {
+            is_in_attribute_table = true;
+            continue;
+        } else if line.trim().is_empty() {
+            is_in_attribute_table = false;
+            continue;
+        }
+
+        if is_in_attribute_table {
+            // ID, name, flag, value, worst, threshold, type, updated, when failed, and raw value
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                continue;
+            }
+
+            if let Some((_, display_name)) = ATA_RAW_ATTRIBUTES
+                .iter()
+                .find(|(attribute_name, _)| *attribute_name == fields[1])
+            {
+                smart
+                    .attributes
+                    .push((display_name.to_string(), fields[9..].join(" ")));
+            } else if !has_wear && ATA_WEAR_ATTRIBUTES.contains(&fields[1]) {
+                if let Ok(life_left) = fields[3].parse::<u64>() {
+                    smart
+                        .attributes
+                        .push(("SSD life left".to_string(), format!("{}%", life_left)));
+                    has_wear = true;
+                }
+            }
+        } else if let Some(separator) = line.find(':') {
+            let key = line[..separator].trim();
+            let value = line[separator + 1..].trim().to_string();
+            match key {
+                "Device Model" | "Model Number" | "Product" => smart.model = Some(value),
+                "Serial Number" | "Serial number" => smart.serial = Some(value),
+                "SMART overall-health self-assessment test result" | "SMART Health Status" => {
+                    smart.health = Some(value)
+                }
+                _ => {
+                    if let Some((_, display_name)) = NVME_ATTRIBUTES
+                        .iter()
+                        .find(|(attribute_name, _)| *attribute_name == key)
+                    {
+                        smart.attributes.push((display_name.to_string(), value));
+                    }
+                }
+            }
+        }
+    }
+
+    smart
+}
@@ -0,0 +1,158 @@
+//! Data collection for open TCP and UDP sockets.  Only works within Linux.
+
+use std::collections::HashMap;
+use std::net::{Ipv4Addr, Ipv6Addr};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct SocketHarvest {
+    pub protocol: String,
+    pub local_address: String,
+    pub remote_address: String,
+    /// Empty for UDP sockets that aren't connected.
+    pub state: String,
+    /// None if the owning process couldn't be found, which is usually a permissions issue.
+    pub pid: Option<u32>,
+    pub process_name: String,
+}
+
+/// Reads the sockets from /proc/net, and matches each socket's inode to the process holding it.
+pub fn get_socket_list() -> Vec<SocketHarvest> {
+    let mut socket_vector = Vec::new();
+    if !cfg!(target_os = "linux") {
+        return socket_vector;
+    }
+
+    let inode_owners = get_socket_inode_owners();
+    for (protocol, file_name, is_tcp) in &[
+        ("TCP", "tcp", true),
+        ("TCP6", "tcp6", true),
+        ("UDP", "udp", false),
+        ("UDP6", "udp6", false),
+    ] {
+        let sockets = match std::fs::read_to_string(format!("/proc/net/{}", file_name)) {
+            Ok(sockets) => sockets,
+            Err(_) => continue,
+        };
+
+        // Each line after the header looks like
+        // "0: 0100007F:0277 00000000:0000 0A 00000000:00000000 00:00000000 00000000 0 0 12345 ..."
+        for line in sockets.lines().skip(1) {
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            if fields.len() < 10 {
+                continue;
+            }
+
+            let (local_address, remote_address) =
+                match (parse_address(fields[1]), parse_address(fields[2])) {
+                    (Some(local_address), Some(remote_address)) => (local_address, remote_address),
+                    _ => continue,
+                };
+            let owner = fields[9]
+                .parse::<u64>()
+                .ok()
+                .and_then(|inode| inode_owners.get(&inode));
+
+            socket_vector.push(SocketHarvest {
+                protocol: protocol.to_string(),
+                local_address,
+                remote_address,
+                state: get_state_name(fields[3], *is_tcp).to_string(),
+                pid: owner.map(|(pid, _)| *pid),
+                process_name: owner.map(|(_, name)| name.clone()).unwrap_or_default(),
+            });
+        }
+    }
+
+    socket_vector
+}
+
+/// Addresses are hex, with each 32 bit word in host byte order, followed by a hex port.
+fn parse_address(address: &str) -> Option<String> {
+    let mut address_and_port = address.splitn(2, ':');
+    let address = address_and_port.next()?;
+    let port = u16::from_str_radix(address_and_port.next()?, 16).ok()?;
+
+    let mut bytes = Vec::with_capacity(16);
+    for itx in (0..address.len()).step_by(8) {
+        let word = u32::from_str_radix(address.get(itx..itx + 8)?, 16).ok()?;
+        bytes.extend_from_slice(&word.to_ne_bytes());
+    }
+
+    match bytes.len() {
+        4 => Some(format!(
+            "{}:{}",
+            Ipv4Addr::new(bytes[0], bytes[1], bytes[2], bytes[3]),
+            port
+        )),
+        16 => {
+            let mut octets = [0; 16];
+            octets.copy_from_slice(&bytes);
+            Some(format!("[{}]:{}", Ipv6Addr::from(octets), port))
+        }
+        _ => None,
+    }
+}
+
+fn get_state_name(state: &str, is_tcp: bool) -> &'static str {
+    if !is_tcp {
+        // UDP sockets only use the established state, once connected
+        return if state == "01" { "ESTABLISHED" } else { "" };
+    }
+
+    match state {
+        "01" => "ESTABLISHED",
+        "02" => "SYN_SENT",
+        "03" => "SYN_RECV",
+        "04" => "FIN_WAIT1",
+        "05" => "FIN_WAIT2",
+        "06" => "TIME_WAIT",
+        "07" => "CLOSE",
+        "08" => "CLOSE_WAIT",
+        "09" => "LAST_ACK",
+        "0A" => "LISTEN",
+        "0B" => "CLOSING",
+        _ => "UNKNOWN",
+    }
+}
+
+/// Maps socket inodes to the PID and name of the process with a file descriptor for them.
+fn get_socket_inode_owners() -> HashMap<u64, (u32, String)> {
+    let mut inode_owners = HashMap::new();
+
+    let entries = match std::fs::read_dir("/proc") {
+        Ok(entries) => entries,
+        Err(_) => return inode_owners,
+    };
+
+    for entry in entries.flatten() {
+        let pid = match entry.file_name().to_string_lossy().parse::<u32>() {
+            Ok(pid) => pid,
+            Err(_) => continue,
+        };
+
+        // Other users' file descriptors can't be read without root
+        let fds = match std::fs::read_dir(entry.path().join("fd")) {
+            Ok(fds) => fds,
+            Err(_) => continue,
+        };
+        let name = std::fs::read_to_string(entry.path().join("comm"))
+            .map(|comm| comm.trim().to_string())
+            .unwrap_or_default();
+
+        for fd in fds.flatten() {
+            if let Ok(link) = std::fs::read_link(fd.path()) {
+                // Socket links look like "socket:[12345]"
+                let link = link.to_string_lossy();
+                if link.starts_with("socket:[") && link.ends_with(']') {
+                    if let Ok(inode) = link["socket:[".len()..link.len() - 1].parse::<u64>() {
+                        inode_owners.entry(inode).or_insert((pid, name.clone()));
+                    }
+                }
+            }
+        }
+    }
+
+    inode_owners
+}
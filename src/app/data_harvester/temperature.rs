@@ -1,13 +1,58 @@
-use std::cmp::Ordering;
+use std::{cmp::Ordering, collections::HashMap};
 
 use futures::StreamExt;
 use heim::units::thermodynamic_temperature;
+use regex::Regex;
+use serde::{Deserialize, Serialize};
 use sysinfo::{ComponentExt, System, SystemExt};
 
-#[derive(Default, Debug, Clone)]
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct TempHarvest {
     pub component_name: String,
     pub temperature: f32,
+    /// The sensor's own warning trip point, if it reports one.
+    pub high: Option<f32>,
+    /// The sensor's own critical trip point, if it reports one.
+    pub critical: Option<f32>,
+}
+
+#[derive(Default, Debug, Clone, Serialize, Deserialize)]
+pub struct FanHarvest {
+    pub component_name: String,
+    pub rpm: u64,
+}
+
+/// Picks which temperature and fan sensors are shown and what they're called, set under
+/// `[temp_filter]` in the config.  Regexes are matched against the original sensor names.
+#[derive(Default, Clone, Debug)]
+pub struct SensorFilter {
+    /// If not empty, only sensors matching one of these are included.
+    pub allow: Vec<Regex>,
+    pub deny: Vec<Regex>,
+    /// Names to show in place of the original ones.
+    pub rename: HashMap<String, String>,
+}
+
+/// Temperatures in celsius past which a sensor is highlighted, set under `[temp_thresholds]` in
+/// the config.  These take precedence over the sensor's own trip points.
+#[derive(Default, Clone, Copy, Debug)]
+pub struct TempThresholds {
+    pub warning: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+impl SensorFilter {
+    pub fn is_included(&self, name: &str) -> bool {
+        (self.allow.is_empty() || self.allow.iter().any(|regex| regex.is_match(name)))
+            && !self.deny.iter().any(|regex| regex.is_match(name))
+    }
+
+    pub fn get_display_name(&self, name: String) -> String {
+        match self.rename.get(&name) {
+            Some(display_name) => display_name.clone(),
+            None => name,
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -23,51 +68,79 @@ impl Default for TemperatureType {
     }
 }
 
+/// hwmon chips of GPU drivers, which are read per card by `get_gpu_temperature_data` instead, so
+/// that each GPU's sensors can be told apart.
+const GPU_HWMON_NAMES: [&str; 5] = ["amdgpu", "radeon", "nouveau", "i915", "xe"];
+
+/// Gathers every temperature sensor, along with GPU temperatures that aren't read from hwmon,
+/// like those from NVML.
 pub async fn get_temperature_data(
-    sys: &System, temp_type: &TemperatureType,
+    sys: &System, temp_type: &TemperatureType, filter: &SensorFilter,
+    gpu_temperatures: Vec<(String, f32)>,
 ) -> crate::utils::error::Result<Vec<TempHarvest>> {
     let mut temperature_vec: Vec<TempHarvest> = Vec::new();
 
     if cfg!(target_os = "linux") {
+        let get_temperature = |temperature: heim::units::ThermodynamicTemperature| {
+            convert_celsius(
+                temperature.get::<thermodynamic_temperature::degree_celsius>(),
+                temp_type,
+            )
+        };
+
         let mut sensor_data = heim::sensors::temperatures();
         while let Some(sensor) = sensor_data.next().await {
             if let Ok(sensor) = sensor {
+                if GPU_HWMON_NAMES.contains(&sensor.unit()) {
+                    continue;
+                }
+
+                // Chips often have several sensors, like k10temp's Tctl and Tdie
+                let component_name = match sensor.label() {
+                    Some(label) => format!("{} {}", sensor.unit(), label),
+                    None => sensor.unit().to_string(),
+                };
                 temperature_vec.push(TempHarvest {
-                    component_name: sensor.unit().to_string(),
-                    temperature: match temp_type {
-                        TemperatureType::Celsius => sensor
-                            .current()
-                            .get::<thermodynamic_temperature::degree_celsius>(
-                        ),
-                        TemperatureType::Kelvin => {
-                            sensor.current().get::<thermodynamic_temperature::kelvin>()
-                        }
-                        TemperatureType::Fahrenheit => sensor
-                            .current()
-                            .get::<thermodynamic_temperature::degree_fahrenheit>(
-                        ),
-                    },
+                    component_name,
+                    temperature: get_temperature(sensor.current()),
+                    high: sensor.high().map(get_temperature),
+                    critical: sensor.critical().map(get_temperature),
                 });
             }
         }
+
+        temperature_vec.extend(get_gpu_temperature_data(temp_type));
+    } else if cfg!(target_os = "windows") {
+        temperature_vec = get_windows_temperature_data(temp_type);
     } else {
         let sensor_data = sys.get_components();
         for component in sensor_data {
             temperature_vec.push(TempHarvest {
                 component_name: component.get_label().to_string(),
-                temperature: match temp_type {
-                    TemperatureType::Celsius => component.get_temperature(),
-                    TemperatureType::Kelvin => {
-                        convert_celsius_to_kelvin(component.get_temperature())
-                    }
-                    TemperatureType::Fahrenheit => {
-                        convert_celsius_to_fahrenheit(component.get_temperature())
-                    }
-                },
+                temperature: convert_celsius(component.get_temperature(), temp_type),
+                high: None,
+                critical: component
+                    .get_critical()
+                    .map(|critical| convert_celsius(critical, temp_type)),
             });
         }
     }
 
+    temperature_vec.extend(
+        gpu_temperatures
+            .into_iter()
+            .map(|(component_name, celsius)| TempHarvest {
+                component_name,
+                temperature: convert_celsius(celsius, temp_type),
+                ..TempHarvest::default()
+            }),
+    );
+
+    temperature_vec.retain(|sensor| filter.is_included(&sensor.component_name));
+    for sensor in &mut temperature_vec {
+        sensor.component_name = filter.get_display_name(sensor.component_name.clone());
+    }
+
     // By default, sort temperature, then by alphabetically!
 
     // Note we sort in reverse here; we want greater temps to be higher priority.
@@ -89,6 +162,257 @@ pub async fn get_temperature_data(
     Ok(temperature_vec)
 }
 
+/// Reads the hwmon sensors of each GPU through its DRM card, named after the driver and card, like
+/// "amdgpu card0 edge", so that several GPUs with the same driver can be told apart.  Only works
+/// within Linux.
+fn get_gpu_temperature_data(temp_type: &TemperatureType) -> Vec<TempHarvest> {
+    let mut temperature_vec: Vec<TempHarvest> = Vec::new();
+
+    let cards = match std::fs::read_dir("/sys/class/drm") {
+        Ok(cards) => cards,
+        Err(_) => return temperature_vec,
+    };
+
+    for card in cards.flatten() {
+        // Skip connectors like card0-DP-1
+        let card_name = card.file_name().to_string_lossy().to_string();
+        if !card_name.starts_with("card") || card_name.contains('-') {
+            continue;
+        }
+
+        let device = card.path().join("device");
+        let driver_name = match std::fs::read_link(device.join("driver")) {
+            Ok(driver) => driver
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            Err(_) => continue,
+        };
+        let hwmons = match std::fs::read_dir(device.join("hwmon")) {
+            Ok(hwmons) => hwmons,
+            Err(_) => continue,
+        };
+
+        for hwmon in hwmons.flatten() {
+            let hwmon = hwmon.path();
+            let read_trimmed = |file_name: &str| {
+                std::fs::read_to_string(hwmon.join(file_name))
+                    .ok()
+                    .map(|contents| contents.trim().to_string())
+            };
+            // Readings are in millidegrees
+            let read_celsius = |file_name: &str| {
+                read_trimmed(file_name)
+                    .and_then(|millidegrees| millidegrees.parse::<f32>().ok())
+                    .map(|millidegrees| convert_celsius(millidegrees / 1000.0, temp_type))
+            };
+
+            if let Ok(entries) = std::fs::read_dir(&hwmon) {
+                for entry in entries.flatten() {
+                    let file_name = entry.file_name().to_string_lossy().to_string();
+                    if !(file_name.starts_with("temp") && file_name.ends_with("_input")) {
+                        continue;
+                    }
+
+                    let sensor = &file_name[..file_name.len() - "_input".len()];
+                    if let Some(temperature) = read_celsius(&file_name) {
+                        temperature_vec.push(TempHarvest {
+                            component_name: match read_trimmed(&format!("{}_label", sensor)) {
+                                Some(label) => {
+                                    format!("{} {} {}", driver_name, card_name, label)
+                                }
+                                None => format!("{} {}", driver_name, card_name),
+                            },
+                            temperature,
+                            high: read_celsius(&format!("{}_max", sensor)),
+                            critical: read_celsius(&format!("{}_crit", sensor)),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    temperature_vec
+}
+
+/// Windows has no common sensor interface that sysinfo reads, so this asks WMI through `wmic`
+/// instead.  Sensors from LibreHardwareMonitor or OpenHardwareMonitor are used if either is
+/// running, along with the ACPI thermal zones, which usually need administrator rights.
+fn get_windows_temperature_data(temp_type: &TemperatureType) -> Vec<TempHarvest> {
+    let mut temperature_vec: Vec<TempHarvest> = Vec::new();
+
+    for namespace in &["LibreHardwareMonitor", "OpenHardwareMonitor"] {
+        for sensor in query_wmi(
+            namespace,
+            "Sensor",
+            Some("SensorType='Temperature'"),
+            &["Name", "Parent", "Value"],
+        ) {
+            // Parents are hardware identifiers like /amdcpu/0 or /nvidiagpu/0
+            let hardware = sensor["Parent"].split('/').find(|part| !part.is_empty());
+            if let Ok(celsius) = sensor["Value"].parse::<f32>() {
+                temperature_vec.push(TempHarvest {
+                    component_name: match hardware {
+                        Some(hardware) => format!("{} {}", hardware, sensor["Name"]),
+                        None => sensor["Name"].clone(),
+                    },
+                    temperature: convert_celsius(celsius, temp_type),
+                    ..TempHarvest::default()
+                });
+            }
+        }
+
+        // Both expose the same sensors, so there's no need to check the second if the first ran
+        if !temperature_vec.is_empty() {
+            break;
+        }
+    }
+
+    // Thermal zones are reported in tenths of a kelvin
+    let get_temperature = |decikelvin: &str| {
+        decikelvin
+            .parse::<f32>()
+            .ok()
+            .map(|decikelvin| convert_celsius(decikelvin / 10.0 - 273.15, temp_type))
+    };
+    for zone in query_wmi(
+        "wmi",
+        "MSAcpi_ThermalZoneTemperature",
+        None,
+        &["CriticalTripPoint", "CurrentTemperature", "InstanceName"],
+    ) {
+        if let Some(temperature) = get_temperature(&zone["CurrentTemperature"]) {
+            // Instance names look like ACPI\ThermalZone\TZ00_0
+            let zone_name = zone["InstanceName"]
+                .rsplit('\\')
+                .next()
+                .unwrap_or_default()
+                .trim_end_matches("_0");
+            temperature_vec.push(TempHarvest {
+                component_name: format!("acpitz {}", zone_name),
+                temperature,
+                high: None,
+                critical: get_temperature(&zone["CriticalTripPoint"]),
+            });
+        }
+    }
+
+    temperature_vec
+}
+
+/// Runs a WMI query with `wmic`, returning each instance's requested properties by name.  Nothing
+/// is returned if the namespace doesn't exist or the query is denied.
+fn query_wmi(
+    namespace: &str, class: &str, condition: Option<&str>, properties: &[&str],
+) -> Vec<HashMap<String, String>> {
+    let mut command = std::process::Command::new("wmic");
+    command
+        .arg(format!("/namespace:\\\\root\\{}", namespace))
+        .arg("path")
+        .arg(class);
+    if let Some(condition) = condition {
+        command.arg("where").arg(condition);
+    }
+    command
+        .arg("get")
+        .arg(properties.join(","))
+        .arg("/format:csv");
+
+    let output = match command.output() {
+        Ok(output) if output.status.success() => output,
+        _ => return Vec::new(),
+    };
+
+    // The first non-empty line is a header, starting with the machine's name as "Node"
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty());
+    let header: Vec<&str> = match lines.next() {
+        Some(header) => header.split(',').collect(),
+        None => return Vec::new(),
+    };
+
+    lines
+        .map(|line| {
+            header
+                .iter()
+                .zip(line.split(','))
+                .map(|(property, value)| (property.to_string(), value.to_string()))
+                .collect::<HashMap<String, String>>()
+        })
+        .filter(|instance| {
+            properties
+                .iter()
+                .all(|property| instance.contains_key(*property))
+        })
+        .collect()
+}
+
+/// Reads fan speeds from hwmon, named after their chip and label.  Only works within Linux.
+pub fn get_fan_data(filter: &SensorFilter) -> crate::utils::error::Result<Vec<FanHarvest>> {
+    let mut fan_vec: Vec<FanHarvest> = Vec::new();
+    if !cfg!(target_os = "linux") {
+        return Ok(fan_vec);
+    }
+
+    let hwmons = std::fs::read_dir("/sys/class/hwmon")?;
+
+    for hwmon in hwmons.flatten() {
+        let hwmon = hwmon.path();
+        let read_trimmed = |file_name: &str| {
+            std::fs::read_to_string(hwmon.join(file_name))
+                .ok()
+                .map(|contents| contents.trim().to_string())
+        };
+        let chip_name = read_trimmed("name").unwrap_or_else(|| "hwmon".to_string());
+
+        if let Ok(entries) = std::fs::read_dir(&hwmon) {
+            for entry in entries.flatten() {
+                let file_name = entry.file_name().to_string_lossy().to_string();
+                if !(file_name.starts_with("fan") && file_name.ends_with("_input")) {
+                    continue;
+                }
+
+                let fan = &file_name[..file_name.len() - "_input".len()];
+                let rpm = match read_trimmed(&file_name).and_then(|rpm| rpm.parse::<u64>().ok()) {
+                    Some(rpm) => rpm,
+                    None => continue,
+                };
+                let label = read_trimmed(&format!("{}_label", fan));
+
+                // Unlabelled fans at 0 RPM are usually unconnected headers
+                if rpm == 0 && label.is_none() {
+                    continue;
+                }
+
+                let component_name =
+                    format!("{} {}", chip_name, label.unwrap_or_else(|| fan.to_string()));
+                if filter.is_included(&component_name) {
+                    fan_vec.push(FanHarvest {
+                        component_name: filter.get_display_name(component_name),
+                        rpm,
+                    });
+                }
+            }
+        }
+    }
+
+    fan_vec.sort_by(|a, b| a.component_name.cmp(&b.component_name));
+    Ok(fan_vec)
+}
+
+pub fn convert_celsius(celsius: f32, temp_type: &TemperatureType) -> f32 {
+    match temp_type {
+        TemperatureType::Celsius => celsius,
+        TemperatureType::Kelvin => convert_celsius_to_kelvin(celsius),
+        TemperatureType::Fahrenheit => convert_celsius_to_fahrenheit(celsius),
+    }
+}
+
 fn convert_celsius_to_kelvin(celsius: f32) -> f32 {
     celsius + 273.15
 }
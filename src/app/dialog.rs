@@ -0,0 +1,117 @@
+//! A modal dialog for prompts, drawn centered over everything else.  It holds a message, an
+//! optional text input and a row of buttons, and keeps the keyboard focus cycling between them
+//! until a button is pressed or it's closed with Esc.
+
+use unicode_width::UnicodeWidthStr;
+
+const MAX_INPUT_LENGTH: usize = 200;
+
+/// What pressing a dialog's button does, after the dialog is closed.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DialogAction {
+    Close,
+    /// Kills the processes that were highlighted when the dialog was opened.
+    KillProcesses,
+}
+
+pub struct DialogButton {
+    pub label: String,
+    pub action: DialogAction,
+}
+
+pub struct Dialog {
+    pub title: String,
+    pub message: String,
+    /// What has been typed, if the dialog asks for text.
+    pub text_input: Option<String>,
+    pub buttons: Vec<DialogButton>,
+    /// The focused item, counting the text input first if there is one, and then the buttons.
+    pub focus: usize,
+}
+
+impl Dialog {
+    pub fn new(title: &str, message: &str) -> Self {
+        Dialog {
+            title: title.to_string(),
+            message: message.to_string(),
+            text_input: None,
+            buttons: Vec::new(),
+            focus: 0,
+        }
+    }
+
+    pub fn button(mut self, label: &str, action: DialogAction) -> Self {
+        self.buttons.push(DialogButton {
+            label: label.to_string(),
+            action,
+        });
+        self
+    }
+
+    /// Adds a text input above the buttons, which starts off focused.
+    pub fn text_input(mut self, initial_text: &str) -> Self {
+        self.text_input = Some(initial_text.to_string());
+        self.focus = 0;
+        self
+    }
+
+    /// Focuses a button by its position, rather than the first item.
+    pub fn focus_button(mut self, button_index: usize) -> Self {
+        self.focus = self.get_input_count() + button_index;
+        self
+    }
+
+    fn get_input_count(&self) -> usize {
+        if self.text_input.is_some() {
+            1
+        } else {
+            0
+        }
+    }
+
+    fn get_focusable_count(&self) -> usize {
+        self.get_input_count() + self.buttons.len()
+    }
+
+    pub fn focus_next(&mut self) {
+        if self.get_focusable_count() > 0 {
+            self.focus = (self.focus + 1) % self.get_focusable_count();
+        }
+    }
+
+    pub fn focus_previous(&mut self) {
+        if self.get_focusable_count() > 0 {
+            self.focus = (self.focus + self.get_focusable_count() - 1) % self.get_focusable_count();
+        }
+    }
+
+    pub fn is_input_focused(&self) -> bool {
+        self.text_input.is_some() && self.focus == 0
+    }
+
+    /// Returns the focused button's position and the button, if a button is focused.
+    pub fn get_focused_button(&self) -> Option<(usize, &DialogButton)> {
+        let button_index = self.focus.checked_sub(self.get_input_count())?;
+        self.buttons
+            .get(button_index)
+            .map(|button| (button_index, button))
+    }
+
+    pub fn on_char(&mut self, caught_char: char) {
+        if self.is_input_focused() {
+            if let Some(text_input) = &mut self.text_input {
+                if UnicodeWidthStr::width(text_input.as_str()) <= MAX_INPUT_LENGTH {
+                    text_input.push(caught_char);
+                }
+            }
+        }
+    }
+
+    pub fn on_backspace(&mut self) {
+        if self.is_input_focused() {
+            if let Some(text_input) = &mut self.text_input {
+                text_input.pop();
+            }
+        }
+    }
+}
@@ -0,0 +1,346 @@
+//! The arrangement of widgets on screen, as a tree of splits.  The top level stacks rows
+//! vertically, their children are placed side by side as columns, the children of those are
+//! stacked vertically again, and so on, with each sibling taking space by its ratio.
+
+use crate::app::{AppConfigFields, WidgetPosition};
+
+#[derive(Clone, Debug)]
+pub enum LayoutContent {
+    Split(Vec<LayoutNode>),
+    Widget(WidgetPosition),
+}
+
+#[derive(Clone, Debug)]
+pub struct LayoutNode {
+    pub ratio: u32,
+    pub content: LayoutContent,
+}
+
+impl LayoutNode {
+    pub fn widget(ratio: u32, widget: WidgetPosition) -> Self {
+        LayoutNode {
+            ratio,
+            content: LayoutContent::Widget(widget),
+        }
+    }
+
+    pub fn split(ratio: u32, children: Vec<LayoutNode>) -> Self {
+        LayoutNode {
+            ratio,
+            content: LayoutContent::Split(children),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug)]
+pub enum WidgetDirection {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Where a widget sits, as fractions of the whole layout.
+#[derive(Clone, Copy, Debug)]
+struct LayoutBox {
+    x: f64,
+    y: f64,
+    width: f64,
+    height: f64,
+}
+
+#[derive(Clone, Debug)]
+pub struct BottomLayout {
+    pub rows: Vec<LayoutNode>,
+}
+
+impl BottomLayout {
+    /// The layout used without a custom one, with optional widgets taking part of their
+    /// neighbour's space when enabled.
+    pub fn default_layout(config: &AppConfigFields) -> Self {
+        let mut top_row = vec![LayoutNode::widget(60, WidgetPosition::Cpu)];
+        if config.show_gpu {
+            top_row.push(LayoutNode::widget(40, WidgetPosition::Gpu));
+        }
+
+        let mut mem_row = vec![LayoutNode::widget(55, WidgetPosition::Mem)];
+        if config.show_psi {
+            mem_row.push(LayoutNode::widget(45, WidgetPosition::Psi));
+        }
+
+        let mut temp_row = vec![LayoutNode::widget(45, WidgetPosition::Temp)];
+        if config.show_temp_graph {
+            temp_row.push(LayoutNode::widget(55, WidgetPosition::TempGraph));
+        }
+        let mut disk_row = vec![LayoutNode::widget(55, WidgetPosition::Disk)];
+        if config.show_battery {
+            disk_row.push(LayoutNode::widget(45, WidgetPosition::Battery));
+        }
+        let mut side_column = vec![
+            LayoutNode::split(34, temp_row),
+            LayoutNode::split(33, disk_row),
+        ];
+        if config.show_disk_io_graph {
+            side_column.push(LayoutNode::widget(33, WidgetPosition::DiskIo));
+        }
+
+        let mut network_row = vec![LayoutNode::widget(60, WidgetPosition::Network)];
+        if !config.ping_hosts.is_empty() {
+            network_row.push(LayoutNode::widget(40, WidgetPosition::Ping));
+        }
+        let mut process_column = vec![LayoutNode::widget(55, WidgetPosition::Process)];
+        if config.show_sockets {
+            process_column.push(LayoutNode::widget(45, WidgetPosition::Sockets));
+        }
+
+        BottomLayout {
+            rows: vec![
+                LayoutNode::split(30, top_row),
+                LayoutNode::split(
+                    37,
+                    vec![
+                        LayoutNode::split(60, vec![LayoutNode::split(100, mem_row)]),
+                        LayoutNode::split(40, side_column),
+                    ],
+                ),
+                LayoutNode::split(
+                    33,
+                    vec![
+                        LayoutNode::split(50, vec![LayoutNode::split(100, network_row)]),
+                        LayoutNode::split(50, process_column),
+                    ],
+                ),
+            ],
+        }
+    }
+
+    /// Takes widgets out of the layout, leaving their siblings to share the space.  Splits left
+    /// empty are taken out as well.
+    pub fn without_widgets(&self, widgets: &[WidgetPosition]) -> Self {
+        BottomLayout {
+            rows: remove_widgets(&self.rows, widgets),
+        }
+    }
+
+    pub fn contains(&self, widget: WidgetPosition) -> bool {
+        let widget = get_layout_widget(widget);
+        self.get_boxes().iter().any(|(other, _)| *other == widget)
+    }
+
+    pub fn get_first_widget(&self) -> Option<WidgetPosition> {
+        self.get_boxes().first().map(|(widget, _)| *widget)
+    }
+
+    /// Lists the widgets in layout order, going through each row, and then each column within
+    /// it, from the top left.
+    pub fn get_widgets(&self) -> Vec<WidgetPosition> {
+        self.get_boxes()
+            .into_iter()
+            .map(|(widget, _)| widget)
+            .collect()
+    }
+
+    /// Finds the closest widget in a direction that lines up with the given one, preferring the
+    /// one sharing the most of its edge.
+    pub fn get_neighbour(
+        &self, widget: WidgetPosition, direction: WidgetDirection,
+    ) -> Option<WidgetPosition> {
+        const EPSILON: f64 = 0.000_001;
+
+        let widget = get_layout_widget(widget);
+        let boxes = self.get_boxes();
+        let current = boxes.iter().find(|(other, _)| *other == widget)?.1;
+
+        boxes
+            .iter()
+            .filter(|(other, _)| *other != widget)
+            .filter_map(|(other, other_box)| {
+                let (distance, overlap) = match direction {
+                    WidgetDirection::Left => (
+                        current.x - (other_box.x + other_box.width),
+                        get_overlap(current.y, current.height, other_box.y, other_box.height),
+                    ),
+                    WidgetDirection::Right => (
+                        other_box.x - (current.x + current.width),
+                        get_overlap(current.y, current.height, other_box.y, other_box.height),
+                    ),
+                    WidgetDirection::Up => (
+                        current.y - (other_box.y + other_box.height),
+                        get_overlap(current.x, current.width, other_box.x, other_box.width),
+                    ),
+                    WidgetDirection::Down => (
+                        other_box.y - (current.y + current.height),
+                        get_overlap(current.x, current.width, other_box.x, other_box.width),
+                    ),
+                };
+
+                if distance > -EPSILON && overlap > EPSILON {
+                    Some((*other, distance, overlap))
+                } else {
+                    None
+                }
+            })
+            .fold(
+                None,
+                |closest: Option<(WidgetPosition, f64, f64)>, candidate| match closest {
+                    Some(closest)
+                        if closest.1 < candidate.1 - EPSILON
+                            || (closest.1 < candidate.1 + EPSILON && closest.2 >= candidate.2) =>
+                    {
+                        Some(closest)
+                    }
+                    _ => Some(candidate),
+                },
+            )
+            .map(|(other, _, _)| other)
+    }
+
+    /// Grows or shrinks a widget's width or height, moving the edge it shares with a neighbour in
+    /// the closest split along that axis.  Returns whether the widget could be resized.
+    pub fn resize_widget(
+        &mut self, widget: WidgetPosition, is_horizontal: bool, is_growing: bool,
+    ) -> bool {
+        resize_in_nodes(
+            &mut self.rows,
+            get_layout_widget(widget),
+            true,
+            is_horizontal,
+            is_growing,
+        ) == Some(true)
+    }
+
+    fn get_boxes(&self) -> Vec<(WidgetPosition, LayoutBox)> {
+        let mut boxes = Vec::new();
+        add_boxes(
+            &self.rows,
+            LayoutBox {
+                x: 0.0,
+                y: 0.0,
+                width: 1.0,
+                height: 1.0,
+            },
+            true,
+            &mut boxes,
+        );
+        boxes
+    }
+}
+
+/// Widgets made of several positions, like the CPU graph and its legend, are laid out as one.
+pub fn get_layout_widget(widget: WidgetPosition) -> WidgetPosition {
+    match widget {
+        WidgetPosition::CpuLegend | WidgetPosition::BasicCpu => WidgetPosition::Cpu,
+        WidgetPosition::BasicMem => WidgetPosition::Mem,
+        WidgetPosition::NetworkLegend | WidgetPosition::BasicNet => WidgetPosition::Network,
+        WidgetPosition::ProcessSearch => WidgetPosition::Process,
+        _ => widget,
+    }
+}
+
+fn get_overlap(start: f64, length: f64, other_start: f64, other_length: f64) -> f64 {
+    f64::min(start + length, other_start + other_length) - f64::max(start, other_start)
+}
+
+fn add_boxes(
+    nodes: &[LayoutNode], bounds: LayoutBox, is_vertical: bool,
+    boxes: &mut Vec<(WidgetPosition, LayoutBox)>,
+) {
+    let total_ratio: u32 = nodes.iter().map(|node| node.ratio).sum();
+    if total_ratio == 0 {
+        return;
+    }
+
+    let mut offset = 0.0;
+    for node in nodes {
+        let fraction = node.ratio as f64 / total_ratio as f64;
+        let node_bounds = if is_vertical {
+            LayoutBox {
+                y: bounds.y + offset * bounds.height,
+                height: fraction * bounds.height,
+                ..bounds
+            }
+        } else {
+            LayoutBox {
+                x: bounds.x + offset * bounds.width,
+                width: fraction * bounds.width,
+                ..bounds
+            }
+        };
+        offset += fraction;
+
+        match &node.content {
+            LayoutContent::Split(children) => add_boxes(children, node_bounds, !is_vertical, boxes),
+            LayoutContent::Widget(widget) => boxes.push((*widget, node_bounds)),
+        }
+    }
+}
+
+fn node_contains(node: &LayoutNode, widget: WidgetPosition) -> bool {
+    match &node.content {
+        LayoutContent::Split(children) => children.iter().any(|child| node_contains(child, widget)),
+        LayoutContent::Widget(other) => *other == widget,
+    }
+}
+
+/// Returns `None` if the widget isn't within the nodes, and otherwise whether it was resized.
+/// The innermost split along the axis with room to give or take is the one changed, with each
+/// step being a twentieth of the split.
+fn resize_in_nodes(
+    nodes: &mut [LayoutNode], widget: WidgetPosition, is_vertical: bool, is_horizontal: bool,
+    is_growing: bool,
+) -> Option<bool> {
+    let index = nodes.iter().position(|node| node_contains(node, widget))?;
+    if let LayoutContent::Split(children) = &mut nodes[index].content {
+        if resize_in_nodes(children, widget, !is_vertical, is_horizontal, is_growing) == Some(true)
+        {
+            return Some(true);
+        }
+    }
+
+    if is_vertical == is_horizontal || nodes.len() < 2 {
+        return Some(false);
+    }
+
+    let neighbour = if index + 1 < nodes.len() {
+        index + 1
+    } else {
+        index - 1
+    };
+    let total_ratio: u32 = nodes.iter().map(|node| node.ratio).sum();
+    let step = std::cmp::max(1, total_ratio / 20);
+    let (growing, shrinking) = if is_growing {
+        (index, neighbour)
+    } else {
+        (neighbour, index)
+    };
+    if nodes[shrinking].ratio <= step {
+        return Some(false);
+    }
+
+    nodes[growing].ratio += step;
+    nodes[shrinking].ratio -= step;
+    Some(true)
+}
+
+fn remove_widgets(nodes: &[LayoutNode], widgets: &[WidgetPosition]) -> Vec<LayoutNode> {
+    nodes
+        .iter()
+        .filter_map(|node| match &node.content {
+            LayoutContent::Widget(widget) => {
+                if widgets.contains(widget) {
+                    None
+                } else {
+                    Some(node.clone())
+                }
+            }
+            LayoutContent::Split(children) => {
+                let children = remove_widgets(children, widgets);
+                if children.is_empty() {
+                    None
+                } else {
+                    Some(LayoutNode::split(node.ratio, children))
+                }
+            }
+        })
+        .collect()
+}
@@ -0,0 +1,46 @@
+//! Short messages about what an action did, like a process being killed, shown in the corner
+//! for a few seconds and then dismissed on their own.
+
+use std::time::Instant;
+
+use crate::constants::{MAX_TOASTS, TOAST_TIMEOUT_MILLISECONDS};
+
+pub struct Toast {
+    pub message: String,
+    pub is_error: bool,
+    pub created_instant: Instant,
+}
+
+impl Toast {
+    pub fn is_expired(&self) -> bool {
+        Instant::now()
+            .duration_since(self.created_instant)
+            .as_millis()
+            >= TOAST_TIMEOUT_MILLISECONDS as u128
+    }
+}
+
+/// The toasts being shown, oldest first.
+#[derive(Default)]
+pub struct ToastState {
+    pub toasts: Vec<Toast>,
+}
+
+impl ToastState {
+    pub fn push(&mut self, message: &str, is_error: bool) {
+        // An identical message just restarts its timer, so repeating an action doesn't stack them
+        self.toasts.retain(|toast| toast.message != message);
+        self.toasts.push(Toast {
+            message: message.to_string(),
+            is_error,
+            created_instant: Instant::now(),
+        });
+        if self.toasts.len() > MAX_TOASTS {
+            self.toasts.remove(0);
+        }
+    }
+
+    pub fn remove_expired(&mut self) {
+        self.toasts.retain(|toast| !toast.is_expired());
+    }
+}
@@ -1,25 +1,34 @@
 use std::cmp::max;
 use std::collections::HashMap;
+use std::rc::Rc;
 
 use tui::{
     backend::Backend,
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style},
     terminal::Frame,
-    widgets::Text,
+    widgets::{Paragraph, Text, Widget},
     Terminal,
 };
 
-use canvas_colours::*;
+pub use canvas_colours::{CanvasColours, ColourScheme};
+
 use dialogs::*;
 use widgets::*;
 
 use crate::{
-    app::{self, data_harvester::processes::ProcessHarvest, WidgetPosition},
+    app::{
+        self,
+        data_harvester::processes::ProcessHarvest,
+        layout::{BottomLayout, LayoutContent, LayoutNode},
+        WidgetPosition,
+    },
     constants::*,
-    data_conversion::{ConvertedCpuData, ConvertedProcessData},
-    utils::error,
+    data_conversion::{ConvertedCpuData, ConvertedInterfaceData, ConvertedProcessData, TempLevel},
+    utils::{error, gen_util::get_hostname},
 };
 
+mod ascii_fallback;
 mod canvas_colours;
 mod dialogs;
 mod drawing_utils;
@@ -31,21 +40,56 @@ pub struct DisplayableData {
     pub tx_display: String,
     pub total_rx_display: String,
     pub total_tx_display: String,
+    pub session_rx_display: String,
+    pub session_tx_display: String,
     pub network_data_rx: Vec<(f64, f64)>,
     pub network_data_tx: Vec<(f64, f64)>,
+    pub network_interface_data: Vec<ConvertedInterfaceData>,
     pub disk_data: Vec<Vec<String>>,
+    /// The used percentage of each disk, in the same order as `disk_data`.
+    pub disk_usage: Vec<f64>,
+    pub socket_data: Vec<Vec<String>>,
+    pub battery_data: Vec<Vec<String>>,
+    pub battery_health_data: Vec<String>,
+    pub power_label: String,
+    pub power_data: Vec<Vec<(f64, f64)>>,
     pub temp_sensor_data: Vec<Vec<String>>,
+    /// Whether each row of `temp_sensor_data` is past its thresholds.
+    pub temp_sensor_levels: Vec<TempLevel>,
     // Not the final value
     pub process_data: HashMap<u32, ProcessHarvest>,
-    // Not the final value
-    pub grouped_process_data: Vec<ConvertedProcessData>,
+    // Not the final value, and shared with the final value so rows aren't copied
+    pub process_rows: HashMap<u32, Rc<ConvertedProcessData>>,
+    // Not the final value, keyed by the name processes are grouped by
+    pub grouped_process_data: HashMap<String, Rc<ConvertedProcessData>>,
     // What's actually displayed
-    pub finalized_process_data: Vec<ConvertedProcessData>,
+    pub finalized_process_data: Vec<Rc<ConvertedProcessData>>,
     pub mem_label: String,
     pub swap_label: String,
+    pub mem_pool_label: String,
     pub mem_data: Vec<(f64, f64)>,
     pub swap_data: Vec<(f64, f64)>,
+    pub vram_labels: Vec<String>,
+    pub vram_data: Vec<Vec<(f64, f64)>>,
+    /// The utilization and VRAM labels of each GPU.
+    pub gpu_labels: Vec<(String, String)>,
+    pub gpu_data: Vec<Vec<(f64, f64)>>,
+    /// The VRAM usage of each GPU, over the GPU widget's displayed time.
+    pub gpu_vram_data: Vec<Vec<(f64, f64)>>,
+    pub commit_label: Option<String>,
+    pub commit_data: Vec<(f64, f64)>,
+    pub numa_labels: Vec<String>,
+    pub numa_data: Vec<Vec<(f64, f64)>>,
+    pub psi_labels: Vec<String>,
+    pub psi_data: Vec<Vec<(f64, f64)>>,
+    pub ping_labels: Vec<String>,
+    pub ping_data: Vec<Vec<(f64, f64)>>,
+    pub io_labels: Vec<String>,
+    pub io_data: Vec<Vec<(f64, f64)>>,
+    pub temp_labels: Vec<String>,
+    pub temp_data: Vec<Vec<(f64, f64)>>,
     pub cpu_data: Vec<ConvertedCpuData>,
+    pub cpu_temp_label: Option<String>,
 }
 
 #[allow(dead_code)]
@@ -63,55 +107,27 @@ pub struct Painter {
     cpu_chunk: Vec<Rect>,
     network_chunk: Vec<Rect>,
     pub colours: CanvasColours,
-    pub styled_general_help_text: Vec<Text<'static>>,
-    pub styled_process_help_text: Vec<Text<'static>>,
-    pub styled_search_help_text: Vec<Text<'static>>,
     is_mac_os: bool,
+    hostname: String,
 }
 
 impl Painter {
     /// Must be run once before drawing, but after setting colours.
-    /// This is to set some remaining styles and text.
     pub fn initialize(&mut self) {
         self.is_mac_os = cfg!(target_os = "macos");
+        self.hostname = get_hostname().unwrap_or_else(|| "unknown".to_string());
+    }
 
-        if GENERAL_HELP_TEXT.len() > 1 {
-            self.styled_general_help_text.push(Text::Styled(
-                GENERAL_HELP_TEXT[0].into(),
-                self.colours.table_header_style,
-            ));
-            self.styled_general_help_text.extend(
-                GENERAL_HELP_TEXT[1..]
-                    .iter()
-                    .map(|&text| Text::Styled(text.into(), self.colours.text_style))
-                    .collect::<Vec<_>>(),
-            );
-        }
-
-        if PROCESS_HELP_TEXT.len() > 1 {
-            self.styled_process_help_text.push(Text::Styled(
-                PROCESS_HELP_TEXT[0].into(),
-                self.colours.table_header_style,
-            ));
-            self.styled_process_help_text.extend(
-                PROCESS_HELP_TEXT[1..]
-                    .iter()
-                    .map(|&text| Text::Styled(text.into(), self.colours.text_style))
-                    .collect::<Vec<_>>(),
-            );
-        }
-
-        if SEARCH_HELP_TEXT.len() > 1 {
-            self.styled_search_help_text.push(Text::Styled(
-                SEARCH_HELP_TEXT[0].into(),
-                self.colours.table_header_style,
-            ));
-            self.styled_search_help_text.extend(
-                SEARCH_HELP_TEXT[1..]
-                    .iter()
-                    .map(|&text| Text::Styled(text.into(), self.colours.text_style))
-                    .collect::<Vec<_>>(),
-            );
+    /// Gives every other table row the stripe background if rows are striped.  Rows that already
+    /// have a background, like the selected one, keep theirs.
+    pub fn get_table_row_style(&self, app_state: &app::App, style: Style, position: u64) -> Style {
+        if app_state.app_config_fields.table_stripes
+            && position % 2 == 1
+            && style.bg == Color::Reset
+        {
+            style.bg(self.colours.table_stripe_colour)
+        } else {
+            style
         }
     }
 
@@ -146,12 +162,40 @@ impl Painter {
             self.width = current_width;
         }
 
+        // The legend needs more room to fit the frequency and min/max columns
+        let cpu_legend_percentage = match (
+            app_state.app_config_fields.show_cpu_frequency,
+            app_state.app_config_fields.show_cpu_min_max,
+        ) {
+            (true, true) => 35,
+            (true, false) | (false, true) => 25,
+            (false, false) => 15,
+        };
+
         terminal.autoresize()?;
         terminal.draw(|mut f| {
+            // The header takes the top row from whatever isn't a dialog
+            let show_header = app_state.app_config_fields.show_header
+                && !app_state.is_in_dialog()
+                && f.size().height > 1;
+            let body_loc = if show_header {
+                Rect::new(
+                    f.size().x,
+                    f.size().y + 1,
+                    f.size().width,
+                    f.size().height - 1,
+                )
+            } else {
+                f.size()
+            };
+
             if app_state.help_dialog_state.is_showing_help {
-                // TODO: [RESIZE] Scrolling dialog boxes is ideal.  This is currently VERY temporary!
-                // The width is currently not good and can wrap... causing this to not go so well!
-                let gen_help_len = GENERAL_HELP_TEXT.len() as u16 + 3;
+                // Sized to fit the general help and its search line, scrolling if it doesn't fit.
+                let gen_help_len = GENERAL_HELP_TEXT
+                    .iter()
+                    .map(|(_, lines)| lines.len() as u16 + 2)
+                    .sum::<u16>()
+                    + 2;
                 let border_len = (max(0, f.size().height as i64 - gen_help_len as i64)) as u16 / 2;
                 let vertical_dialog_chunk = Layout::default()
                     .direction(Direction::Vertical)
@@ -187,14 +231,84 @@ impl Painter {
                     .split(vertical_dialog_chunk[1]);
 
                 self.draw_help_dialog(&mut f, app_state, middle_dialog_chunk[1]);
-            } else if app_state.delete_dialog_state.is_showing_dd {
-                let bordering = (max(0, f.size().height as i64 - 7) as u16) / 2;
+            } else if app_state.env_dialog_state.is_showing_env {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(
+                        [
+                            Constraint::Percentage(10),
+                            Constraint::Percentage(80),
+                            Constraint::Percentage(10),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(f.size());
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(
+                        if f.size().width < 100 {
+                            // TODO: [REFACTOR] The point we start changing size at currently hard-coded in.
+                            [
+                                Constraint::Percentage(0),
+                                Constraint::Percentage(100),
+                                Constraint::Percentage(0),
+                            ]
+                        } else {
+                            [
+                                Constraint::Percentage(15),
+                                Constraint::Percentage(70),
+                                Constraint::Percentage(15),
+                            ]
+                        }
+                        .as_ref(),
+                    )
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_env_dialog(&mut f, app_state, middle_dialog_chunk[1]);
+            } else if app_state.disk_detail_dialog_state.is_showing_disk_detail {
+                let vertical_dialog_chunk = Layout::default()
+                    .direction(Direction::Vertical)
+                    .constraints(
+                        [
+                            Constraint::Percentage(20),
+                            Constraint::Percentage(60),
+                            Constraint::Percentage(20),
+                        ]
+                        .as_ref(),
+                    )
+                    .split(f.size());
+
+                let middle_dialog_chunk = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints(
+                        if f.size().width < 100 {
+                            // TODO: [REFACTOR] The point we start changing size at currently hard-coded in.
+                            [
+                                Constraint::Percentage(0),
+                                Constraint::Percentage(100),
+                                Constraint::Percentage(0),
+                            ]
+                        } else {
+                            [
+                                Constraint::Percentage(25),
+                                Constraint::Percentage(50),
+                                Constraint::Percentage(25),
+                            ]
+                        }
+                        .as_ref(),
+                    )
+                    .split(vertical_dialog_chunk[1]);
+
+                self.draw_disk_detail_dialog(&mut f, app_state, middle_dialog_chunk[1]);
+            } else if let Some(config_err) = &app_state.config_err {
+                let bordering = (max(0, f.size().height as i64 - 9) as u16) / 2;
                 let vertical_dialog_chunk = Layout::default()
                     .direction(Direction::Vertical)
                     .constraints(
                         [
                             Constraint::Length(bordering),
-                            Constraint::Length(7),
+                            Constraint::Length(9),
                             Constraint::Length(bordering),
                         ]
                         .as_ref(),
@@ -213,37 +327,47 @@ impl Painter {
                             ]
                         } else {
                             [
-                                Constraint::Percentage(30),
-                                Constraint::Percentage(40),
-                                Constraint::Percentage(30),
+                                Constraint::Percentage(25),
+                                Constraint::Percentage(50),
+                                Constraint::Percentage(25),
                             ]
                         }
                         .as_ref(),
                     )
                     .split(vertical_dialog_chunk[1]);
 
-                if let Some(dd_err) = &app_state.dd_err {
-                    self.draw_dd_error_dialog(&mut f, dd_err, middle_dialog_chunk[1]);
-                } else {
-                    // This is a bit nasty, but it works well... I guess.
-                    app_state.delete_dialog_state.is_showing_dd =
-                        self.draw_dd_dialog(&mut f, app_state, middle_dialog_chunk[1]);
-                }
+                self.draw_config_error_dialog(&mut f, config_err, middle_dialog_chunk[1]);
+            } else if let Some(dialog) = &app_state.dialog {
+                self.draw_modal_dialog(&mut f, dialog);
             } else if app_state.is_expanded {
                 let rect = Layout::default()
                     .margin(1)
                     .constraints([Constraint::Percentage(100)].as_ref())
-                    .split(f.size());
+                    .split(body_loc);
                 match &app_state.current_widget_selected {
+                    WidgetPosition::Cpu | WidgetPosition::BasicCpu | WidgetPosition::CpuLegend
+                        if app_state
+                            .app_config_fields
+                            .get_hidden_graph_parts(WidgetPosition::Cpu)
+                            .legend =>
+                    {
+                        self.draw_cpu_graph(&mut f, app_state, rect[0]);
+                    }
                     WidgetPosition::Cpu | WidgetPosition::BasicCpu | WidgetPosition::CpuLegend => {
                         let cpu_chunk = Layout::default()
                             .direction(Direction::Horizontal)
                             .margin(0)
                             .constraints(
                                 if app_state.app_config_fields.left_legend {
-                                    [Constraint::Percentage(15), Constraint::Percentage(85)]
+                                    [
+                                        Constraint::Percentage(cpu_legend_percentage),
+                                        Constraint::Percentage(100 - cpu_legend_percentage),
+                                    ]
                                 } else {
-                                    [Constraint::Percentage(85), Constraint::Percentage(15)]
+                                    [
+                                        Constraint::Percentage(100 - cpu_legend_percentage),
+                                        Constraint::Percentage(cpu_legend_percentage),
+                                    ]
                                 }
                                 .as_ref(),
                             )
@@ -266,9 +390,27 @@ impl Painter {
                     WidgetPosition::Mem | WidgetPosition::BasicMem => {
                         self.draw_memory_graph(&mut f, app_state, rect[0]);
                     }
+                    WidgetPosition::Gpu => {
+                        self.draw_gpu_graph(&mut f, app_state, rect[0]);
+                    }
+                    WidgetPosition::Psi => {
+                        self.draw_psi_graph(&mut f, app_state, rect[0]);
+                    }
+                    WidgetPosition::Ping => {
+                        self.draw_ping_graph(&mut f, app_state, rect[0]);
+                    }
+                    WidgetPosition::DiskIo => {
+                        self.draw_disk_io_graph(&mut f, app_state, rect[0]);
+                    }
+                    WidgetPosition::TempGraph => {
+                        self.draw_temp_graph(&mut f, app_state, rect[0]);
+                    }
                     WidgetPosition::Disk => {
                         self.draw_disk_table(&mut f, app_state, rect[0], true);
                     }
+                    WidgetPosition::Battery => {
+                        self.draw_battery_display(&mut f, app_state, rect[0]);
+                    }
                     WidgetPosition::Temp => {
                         self.draw_temp_table(&mut f, app_state, rect[0], true);
                     }
@@ -280,7 +422,22 @@ impl Painter {
                     WidgetPosition::Process | WidgetPosition::ProcessSearch => {
                         self.draw_process_and_search(&mut f, app_state, rect[0], true);
                     }
+                    WidgetPosition::Sockets => {
+                        self.draw_socket_table(&mut f, app_state, rect[0]);
+                    }
                 }
+                self.draw_harvest_error_marker(
+                    &mut f,
+                    app_state,
+                    app_state.current_widget_selected,
+                    rect[0],
+                );
+                self.draw_alert_marker(
+                    &mut f,
+                    app_state,
+                    app_state.current_widget_selected,
+                    rect[0],
+                );
             } else if app_state.app_config_fields.use_basic_mode {
                 // Basic mode.  This basically removes all graphs but otherwise
                 // the same info.
@@ -291,6 +448,13 @@ impl Painter {
                     } else {
                         1
                     });
+                // On short terminals, only the average CPU bar is shown so the table keeps at
+                // least ten rows
+                let cpu_height = if cpu_height > 1 && body_loc.height < cpu_height + 15 {
+                    1
+                } else {
+                    cpu_height
+                };
                 let vertical_chunks = Layout::default()
                     .direction(Direction::Vertical)
                     .constraints(
@@ -303,7 +467,7 @@ impl Painter {
                         ]
                         .as_ref(),
                     )
-                    .split(f.size());
+                    .split(body_loc);
 
                 let middle_chunks = Layout::default()
                     .direction(Direction::Horizontal)
@@ -331,95 +495,266 @@ impl Painter {
                     );
                 }
             } else {
-                let vertical_chunks = Layout::default()
-                    .direction(Direction::Vertical)
+                let layout = app_state
+                    .app_config_fields
+                    .custom_layout
+                    .clone()
+                    .unwrap_or_else(|| BottomLayout::default_layout(&app_state.app_config_fields));
+                let draw_loc = Layout::default()
                     .margin(1)
-                    .constraints(
-                        [
-                            Constraint::Percentage(30),
-                            Constraint::Percentage(37),
-                            Constraint::Percentage(33),
-                        ]
-                        .as_ref(),
-                    )
-                    .split(f.size());
+                    .constraints([Constraint::Percentage(100)].as_ref())
+                    .split(body_loc);
+                self.draw_layout_nodes(&mut f, app_state, &layout.rows, draw_loc[0], true);
+            }
 
-                let middle_chunks = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .margin(0)
-                    .constraints([Constraint::Percentage(60), Constraint::Percentage(40)].as_ref())
-                    .split(vertical_chunks[1]);
+            if show_header {
+                let header_loc = Rect::new(f.size().x, f.size().y, f.size().width, 1);
+                self.draw_header_bar(&mut f, app_state, header_loc);
+            }
 
-                let middle_divided_chunk_2 = Layout::default()
-                    .direction(Direction::Vertical)
-                    .margin(0)
-                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-                    .split(middle_chunks[1]);
+            app_state.toast_state.remove_expired();
+            self.draw_toasts(&mut f, app_state, if show_header { 1 } else { 0 });
 
-                let bottom_chunks = Layout::default()
-                    .direction(Direction::Horizontal)
-                    .margin(0)
-                    .constraints([Constraint::Percentage(50), Constraint::Percentage(50)].as_ref())
-                    .split(vertical_chunks[2]);
+            if !app_state.is_in_dialog() {
+                if let Some(time_travel_offset) = app_state.get_time_travel_offset() {
+                    self.draw_time_travel_label(&mut f, time_travel_offset);
+                }
+            }
 
-                // Component specific chunks
+            if app_state.app_config_fields.use_ascii {
+                let screen = f.size();
+                f.render(&mut ascii_fallback::AsciiFallback, screen);
+            }
+        })?;
+
+        app_state.is_resized = false;
+
+        Ok(())
+    }
+
+    /// Shows how far back the data is while time travelling, on the bottom row of the screen.
+    fn draw_time_travel_label<B: Backend>(&self, f: &mut Frame<'_, B>, time_travel_offset: u64) {
+        let label = format!(
+            " Time travel: {}s ago ── Left/Right to step, t to go back ",
+            time_travel_offset
+        );
+        let width = std::cmp::min(label.chars().count() as u16, f.size().width);
+        let draw_loc = Rect::new(
+            f.size().width - width,
+            max(1, f.size().height) - 1,
+            width,
+            1,
+        );
+
+        Paragraph::new([Text::styled(label, self.colours.highlighted_border_style)].iter())
+            .render(f, draw_loc);
+    }
+
+    /// Stacks the toasts in the top right corner, newest at the bottom, starting at the given row.
+    fn draw_toasts<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &app::App, top_row: u16) {
+        for (index, toast) in app_state.toast_state.toasts.iter().enumerate() {
+            let row = top_row + index as u16;
+            if row >= f.size().height {
+                break;
+            }
+
+            let label = format!(" {} ", toast.message);
+            let width = std::cmp::min(label.chars().count() as u16, f.size().width);
+            let draw_loc = Rect::new(f.size().width - width, row, width, 1);
+            let style = if toast.is_error {
+                self.colours.invalid_query_style
+            } else {
+                self.colours.highlighted_border_style
+            };
+
+            Paragraph::new([Text::styled(label, style)].iter()).render(f, draw_loc);
+        }
+    }
+
+    /// Splits the space between sibling rows or columns by their ratios, alternating direction
+    /// with each level of the layout.
+    fn draw_layout_nodes<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, nodes: &[LayoutNode],
+        draw_loc: Rect, is_vertical: bool,
+    ) {
+        let total_ratio: u32 = nodes.iter().map(|node| node.ratio).sum();
+        let constraints: Vec<Constraint> = nodes
+            .iter()
+            .map(|node| Constraint::Ratio(node.ratio, total_ratio))
+            .collect();
+        let chunks = Layout::default()
+            .direction(if is_vertical {
+                Direction::Vertical
+            } else {
+                Direction::Horizontal
+            })
+            .margin(0)
+            .constraints(constraints.as_ref())
+            .split(draw_loc);
+
+        for (node, chunk) in nodes.iter().zip(chunks) {
+            match &node.content {
+                LayoutContent::Split(children) => {
+                    self.draw_layout_nodes(f, app_state, children, chunk, !is_vertical)
+                }
+                LayoutContent::Widget(widget) => {
+                    self.draw_layout_widget(f, app_state, *widget, chunk)
+                }
+            }
+        }
+    }
+
+    fn draw_layout_widget<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, widget: WidgetPosition,
+        draw_loc: Rect,
+    ) {
+        let is_legend_hidden = app_state
+            .app_config_fields
+            .get_hidden_graph_parts(widget)
+            .legend;
+        match widget {
+            WidgetPosition::Cpu | WidgetPosition::CpuLegend | WidgetPosition::BasicCpu
+                if is_legend_hidden =>
+            {
+                self.draw_cpu_graph(f, app_state, draw_loc)
+            }
+            WidgetPosition::Network | WidgetPosition::NetworkLegend | WidgetPosition::BasicNet
+                if is_legend_hidden =>
+            {
+                self.draw_network_graph(f, app_state, draw_loc)
+            }
+            WidgetPosition::Cpu | WidgetPosition::CpuLegend | WidgetPosition::BasicCpu => {
+                // The legend needs more room to fit the frequency and min/max columns
+                let cpu_legend_percentage = match (
+                    app_state.app_config_fields.show_cpu_frequency,
+                    app_state.app_config_fields.show_cpu_min_max,
+                ) {
+                    (true, true) => 35,
+                    (true, false) | (false, true) => 25,
+                    (false, false) => 15,
+                };
                 let cpu_chunk = Layout::default()
                     .direction(Direction::Horizontal)
                     .margin(0)
                     .constraints(
                         if app_state.app_config_fields.left_legend {
-                            [Constraint::Percentage(15), Constraint::Percentage(85)]
+                            [
+                                Constraint::Percentage(cpu_legend_percentage),
+                                Constraint::Percentage(100 - cpu_legend_percentage),
+                            ]
                         } else {
-                            [Constraint::Percentage(85), Constraint::Percentage(15)]
+                            [
+                                Constraint::Percentage(100 - cpu_legend_percentage),
+                                Constraint::Percentage(cpu_legend_percentage),
+                            ]
                         }
                         .as_ref(),
                     )
-                    .split(vertical_chunks[0]);
+                    .split(draw_loc);
+
+                // Default chunk index based on left or right legend setting
+                let (legend_index, graph_index) = if app_state.app_config_fields.left_legend {
+                    (0, 1)
+                } else {
+                    (1, 0)
+                };
 
+                self.draw_cpu_graph(f, app_state, cpu_chunk[graph_index]);
+                self.draw_cpu_legend(f, app_state, cpu_chunk[legend_index]);
+            }
+            WidgetPosition::Network | WidgetPosition::NetworkLegend | WidgetPosition::BasicNet => {
                 let network_chunk = Layout::default()
                     .direction(Direction::Vertical)
                     .margin(0)
                     .constraints(
-                        if (bottom_chunks[0].height as f64 * 0.25) as u16 >= 4 {
+                        if (draw_loc.height as f64 * 0.25) as u16 >= 4 {
                             [Constraint::Percentage(75), Constraint::Percentage(25)]
                         } else {
-                            let required = if bottom_chunks[0].height < 10 {
-                                bottom_chunks[0].height / 2
+                            let required = if draw_loc.height < 10 {
+                                draw_loc.height / 2
                             } else {
                                 5
                             };
-                            let remaining = bottom_chunks[0].height - required;
+                            let remaining = draw_loc.height - required;
                             [Constraint::Length(remaining), Constraint::Length(required)]
                         }
                         .as_ref(),
                     )
-                    .split(bottom_chunks[0]);
-
-                // Default chunk index based on left or right legend setting
-                let legend_index = if app_state.app_config_fields.left_legend {
-                    0
-                } else {
-                    1
-                };
-                let graph_index = if app_state.app_config_fields.left_legend {
-                    1
-                } else {
-                    0
-                };
+                    .split(draw_loc);
 
-                self.draw_cpu_graph(&mut f, app_state, cpu_chunk[graph_index]);
-                self.draw_cpu_legend(&mut f, app_state, cpu_chunk[legend_index]);
-                self.draw_memory_graph(&mut f, app_state, middle_chunks[0]);
-                self.draw_network_graph(&mut f, app_state, network_chunk[0]);
-                self.draw_network_labels(&mut f, app_state, network_chunk[1]);
-                self.draw_temp_table(&mut f, app_state, middle_divided_chunk_2[0], true);
-                self.draw_disk_table(&mut f, app_state, middle_divided_chunk_2[1], true);
-                self.draw_process_and_search(&mut f, app_state, bottom_chunks[1], true);
+                self.draw_network_graph(f, app_state, network_chunk[0]);
+                self.draw_network_labels(f, app_state, network_chunk[1]);
             }
-        })?;
+            WidgetPosition::Mem | WidgetPosition::BasicMem => {
+                self.draw_memory_graph(f, app_state, draw_loc)
+            }
+            WidgetPosition::Gpu => self.draw_gpu_graph(f, app_state, draw_loc),
+            WidgetPosition::Psi => self.draw_psi_graph(f, app_state, draw_loc),
+            WidgetPosition::Ping => self.draw_ping_graph(f, app_state, draw_loc),
+            WidgetPosition::DiskIo => self.draw_disk_io_graph(f, app_state, draw_loc),
+            WidgetPosition::TempGraph => self.draw_temp_graph(f, app_state, draw_loc),
+            WidgetPosition::Temp => self.draw_temp_table(f, app_state, draw_loc, true),
+            WidgetPosition::Disk => self.draw_disk_table(f, app_state, draw_loc, true),
+            WidgetPosition::Battery => self.draw_battery_display(f, app_state, draw_loc),
+            WidgetPosition::Process | WidgetPosition::ProcessSearch => {
+                self.draw_process_and_search(f, app_state, draw_loc, true)
+            }
+            WidgetPosition::Sockets => self.draw_socket_table(f, app_state, draw_loc),
+        }
 
-        app_state.is_resized = false;
+        self.draw_harvest_error_marker(f, app_state, widget, draw_loc);
+        self.draw_alert_marker(f, app_state, widget, draw_loc);
+    }
 
-        Ok(())
+    /// Marks a widget whose data set off an alert, in the same place as a harvest error, which
+    /// takes precedence as the data may be wrong.
+    fn draw_alert_marker<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &app::App, widget: WidgetPosition, draw_loc: Rect,
+    ) {
+        if app_state
+            .harvest_error_state
+            .get_widget_error(widget)
+            .is_some()
+        {
+            return;
+        }
+        if let Some(rule) = app_state
+            .alert_state
+            .get_widget_alert(&app_state.app_config_fields.alert_rules, widget)
+        {
+            let label = format!(" ! {} ", rule.rule);
+            let width = label.chars().count() as u16;
+            if draw_loc.width >= width + 2 && draw_loc.height > 0 {
+                let marker_loc = Rect::new(
+                    draw_loc.x + draw_loc.width - width - 1,
+                    draw_loc.y,
+                    width,
+                    1,
+                );
+                Paragraph::new([Text::styled(label, self.colours.invalid_query_style)].iter())
+                    .render(f, marker_loc);
+            }
+        }
+    }
+
+    /// Marks a widget whose data failed to be collected in the latest harvest, on the right of
+    /// its top border.  The data shown is left over from before the failure.
+    fn draw_harvest_error_marker<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &app::App, widget: WidgetPosition, draw_loc: Rect,
+    ) {
+        if let Some(error) = app_state.harvest_error_state.get_widget_error(widget) {
+            let label = format!(" ! {} error ", error.source.get_name());
+            let width = label.chars().count() as u16;
+            if draw_loc.width >= width + 2 && draw_loc.height > 0 {
+                let marker_loc = Rect::new(
+                    draw_loc.x + draw_loc.width - width - 1,
+                    draw_loc.y,
+                    width,
+                    1,
+                );
+                Paragraph::new([Text::styled(label, self.colours.invalid_query_style)].iter())
+                    .render(f, marker_loc);
+            }
+        }
     }
 }
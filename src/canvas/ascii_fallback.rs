@@ -0,0 +1,119 @@
+//! For terminals that can't show unicode or more than the 8 standard colours, like serial
+//! consoles and the Linux console.  Rather than each widget knowing about it, everything drawn
+//! is rewritten at the end of a frame, with borders and other symbols swapped for ASCII and
+//! colours for the closest standard one.
+
+use tui::{buffer::Buffer, layout::Rect, style::Color, widgets::Widget};
+
+pub struct AsciiFallback;
+
+impl Widget for AsciiFallback {
+    fn draw(&mut self, area: Rect, buf: &mut Buffer) {
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                let cell = buf.get_mut(x, y);
+                if let Some(symbol) = get_ascii_symbol(&cell.symbol) {
+                    cell.symbol = symbol.to_string();
+                }
+                cell.style.fg = get_standard_colour(cell.style.fg);
+                cell.style.bg = get_standard_colour(cell.style.bg);
+            }
+        }
+    }
+}
+
+/// Gets what to show in place of a symbol that isn't ASCII.
+fn get_ascii_symbol(symbol: &str) -> Option<&'static str> {
+    if symbol.is_ascii() && !symbol.is_empty() {
+        return None;
+    }
+
+    // The cell after a wide character is left empty, and has to take up a column again now
+    let first_char = match symbol.chars().next() {
+        Some(first_char) => first_char,
+        None => return Some(" "),
+    };
+    Some(match first_char {
+        '─' | '━' | '═' | '╌' | '┄' => "-",
+        '│' | '┃' | '║' | '╎' | '┆' => "|",
+        '\u{2500}'..='\u{257f}' => "+",
+        '\u{2800}' => " ",
+        '\u{2801}'..='\u{28ff}' => ".",
+        '\u{2580}'..='\u{259f}' => "#",
+        '•' => "*",
+        '·' => ".",
+        '↑' | '▲' => "^",
+        '↓' | '▼' => "v",
+        '←' | '◀' => "<",
+        '→' | '▶' => ">",
+        '°' => " ",
+        _ => "?",
+    })
+}
+
+/// Gets the closest of the 8 standard colours, which every colour terminal has.
+fn get_standard_colour(colour: Color) -> Color {
+    match colour {
+        Color::DarkGray | Color::White => Color::Gray,
+        Color::LightRed => Color::Red,
+        Color::LightGreen => Color::Green,
+        Color::LightYellow => Color::Yellow,
+        Color::LightBlue => Color::Blue,
+        Color::LightMagenta => Color::Magenta,
+        Color::LightCyan => Color::Cyan,
+        Color::Rgb(r, g, b) => get_closest_standard_colour(r, g, b),
+        Color::Indexed(index) => match index {
+            0..=7 => get_standard_colour_by_bits(index),
+            8..=15 => get_standard_colour_by_bits(index - 8),
+            16..=231 => {
+                // The 6x6x6 colour cube
+                let level = |step: u8| if step == 0 { 0 } else { 55 + 40 * step };
+                let cube_index = index - 16;
+                get_closest_standard_colour(
+                    level(cube_index / 36),
+                    level(cube_index / 6 % 6),
+                    level(cube_index % 6),
+                )
+            }
+            _ => {
+                // The greyscale ramp
+                let grey = 8 + 10 * (index - 232);
+                get_closest_standard_colour(grey, grey, grey)
+            }
+        },
+        other => other,
+    }
+}
+
+/// Keeps the channels that are close to the brightest one, so that dim colours keep their hue
+/// rather than all becoming black.
+fn get_closest_standard_colour(r: u8, g: u8, b: u8) -> Color {
+    let brightest = r.max(g).max(b);
+    if brightest < 64 {
+        return Color::Black;
+    }
+
+    let threshold = u16::from(brightest) * 3 / 5;
+    let bit = |channel: u8, value: u8| {
+        if u16::from(channel) >= threshold {
+            value
+        } else {
+            0
+        }
+    };
+    get_standard_colour_by_bits(bit(r, 1) | bit(g, 2) | bit(b, 4))
+}
+
+/// Gets a standard colour by its ANSI number, where red, green, and blue are each a bit.
+fn get_standard_colour_by_bits(bits: u8) -> Color {
+    match bits {
+        1 => Color::Red,
+        2 => Color::Green,
+        3 => Color::Yellow,
+        4 => Color::Blue,
+        5 => Color::Magenta,
+        6 => Color::Cyan,
+        7 => Color::Gray,
+        _ => Color::Black,
+    }
+}
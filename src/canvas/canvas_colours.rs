@@ -1,29 +1,57 @@
 use tui::style::{Color, Modifier, Style};
 
+use colour_schemes::*;
 use colour_utils::*;
 
 use crate::{constants::*, utils::error};
 
+mod colour_schemes;
 mod colour_utils;
 
+/// The built-in colour schemes, which colours from the config file are applied on top of.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ColourScheme {
+    Default,
+    Gruvbox,
+    Nord,
+    /// For terminals with a light background.
+    Light,
+    /// Colours that can be told apart with colour blindness.
+    ColourBlind,
+    /// Colours that can be told apart with colour blindness, on a light background.
+    ColourBlindLight,
+}
+
 pub struct CanvasColours {
     pub currently_selected_text_colour: Color,
     pub currently_selected_bg_colour: Color,
     pub currently_selected_text_style: Style,
+    pub cursor_style: Style,
+    pub invalid_query_style: Style,
     pub table_header_style: Style,
+    /// The background of every other table row, if rows are striped.
+    pub table_stripe_colour: Color,
     pub ram_style: Style,
     pub swap_style: Style,
+    pub vram_style: Style,
+    pub commit_style: Style,
     pub rx_style: Style,
     pub tx_style: Style,
     pub total_rx_style: Style,
     pub total_tx_style: Style,
     pub avg_colour_style: Style,
+    pub disk_warning_style: Style,
+    pub disk_critical_style: Style,
+    pub temp_warning_style: Style,
+    pub temp_critical_style: Style,
     pub cpu_colour_styles: Vec<Style>,
     pub border_style: Style,
     pub highlighted_border_style: Style,
     pub text_style: Style,
     pub widget_title_style: Style,
     pub graph_style: Style,
+    /// Whether the CPU colours repeat, rather than being extended with generated ones.
+    pub cycle_cpu_colours: bool,
 }
 
 impl Default for CanvasColours {
@@ -34,25 +62,80 @@ impl Default for CanvasColours {
             currently_selected_text_colour: Color::Black,
             currently_selected_bg_colour: Color::Cyan,
             currently_selected_text_style: Style::default().fg(Color::Black).bg(Color::Cyan),
+            cursor_style: Style::default().fg(Color::Black).bg(Color::Cyan),
+            invalid_query_style: Style::default().fg(Color::Red),
             table_header_style: Style::default().fg(Color::LightBlue),
+            table_stripe_colour: Color::Rgb(0x26, 0x26, 0x26),
             ram_style: Style::default().fg(STANDARD_FIRST_COLOUR),
             swap_style: Style::default().fg(STANDARD_SECOND_COLOUR),
+            vram_style: Style::default().fg(STANDARD_THIRD_COLOUR),
+            commit_style: Style::default().fg(STANDARD_FOURTH_COLOUR),
             rx_style: Style::default().fg(STANDARD_FIRST_COLOUR),
             tx_style: Style::default().fg(STANDARD_SECOND_COLOUR),
             total_rx_style: Style::default().fg(STANDARD_THIRD_COLOUR),
             total_tx_style: Style::default().fg(STANDARD_FOURTH_COLOUR),
             avg_colour_style: Style::default().fg(AVG_COLOUR),
+            disk_warning_style: Style::default().fg(Color::Yellow),
+            disk_critical_style: Style::default().fg(Color::Red),
+            temp_warning_style: Style::default().fg(Color::Yellow),
+            temp_critical_style: Style::default().fg(Color::Red),
             cpu_colour_styles: Vec::new(),
             border_style: Style::default().fg(text_colour),
             highlighted_border_style: Style::default().fg(Color::LightBlue),
             text_style: Style::default().fg(text_colour),
             widget_title_style: Style::default().fg(text_colour),
             graph_style: Style::default().fg(text_colour),
+            cycle_cpu_colours: false,
         }
     }
 }
 
 impl CanvasColours {
+    pub fn from_scheme(scheme: ColourScheme) -> error::Result<Self> {
+        let palette = match scheme {
+            ColourScheme::Default => return Ok(CanvasColours::default()),
+            ColourScheme::Gruvbox => &GRUVBOX_PALETTE,
+            ColourScheme::Nord => &NORD_PALETTE,
+            ColourScheme::Light => &LIGHT_PALETTE,
+            ColourScheme::ColourBlind => &COLOURBLIND_PALETTE,
+            ColourScheme::ColourBlindLight => &COLOURBLIND_LIGHT_PALETTE,
+        };
+
+        let mut colours = CanvasColours::default();
+        colours.set_text_colour(palette.text)?;
+        colours.set_border_colour(palette.border)?;
+        colours.set_highlighted_border_colour(palette.highlighted_border)?;
+        colours.set_table_header_colour(palette.table_header)?;
+        colours.set_table_stripe_colour(palette.table_stripe)?;
+        colours.set_widget_title_colour(palette.widget_title)?;
+        colours.set_graph_colour(palette.graph)?;
+        colours.set_scroll_entry_text_color(palette.selected_text)?;
+        colours.set_scroll_entry_bg_color(palette.selected_bg)?;
+        colours.set_ram_colour(palette.ram)?;
+        colours.set_swap_colour(palette.swap)?;
+        colours.set_vram_colour(palette.vram)?;
+        colours.set_commit_colour(palette.commit)?;
+        colours.set_rx_colour(palette.rx)?;
+        colours.set_tx_colour(palette.tx)?;
+        colours.set_rx_total_colour(palette.rx_total)?;
+        colours.set_tx_total_colour(palette.tx_total)?;
+        colours.set_avg_cpu_colour(palette.avg_cpu)?;
+        colours.set_disk_warning_colour(palette.warning)?;
+        colours.set_disk_critical_colour(palette.critical)?;
+        colours.set_temp_warning_colour(palette.warning)?;
+        colours.set_temp_critical_colour(palette.critical)?;
+        colours.set_cpu_colours(
+            &palette
+                .cpu_cores
+                .iter()
+                .map(|colour| colour.to_string())
+                .collect::<Vec<_>>(),
+        )?;
+        colours.cycle_cpu_colours = palette.cycle_cpu_cores;
+
+        Ok(colours)
+    }
+
     pub fn set_text_colour(&mut self, colour: &str) -> error::Result<()> {
         self.text_style = get_style_from_config(colour)?;
         Ok(())
@@ -83,6 +166,16 @@ impl CanvasColours {
         Ok(())
     }
 
+    pub fn set_vram_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.vram_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_commit_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.commit_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
     pub fn set_rx_colour(&mut self, colour: &str) -> error::Result<()> {
         self.rx_style = get_style_from_config(colour)?;
         Ok(())
@@ -108,7 +201,29 @@ impl CanvasColours {
         Ok(())
     }
 
+    pub fn set_disk_warning_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.disk_warning_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_disk_critical_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.disk_critical_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_temp_warning_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.temp_warning_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    pub fn set_temp_critical_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.temp_critical_style = get_style_from_config(colour)?;
+        Ok(())
+    }
+
+    /// Replaces the core colours, including those from the colour scheme.
     pub fn set_cpu_colours(&mut self, colours: &[String]) -> error::Result<()> {
+        self.cpu_colour_styles.clear();
         let max_amount = std::cmp::min(colours.len(), NUM_COLOURS as usize);
         for (itx, colour) in colours.iter().enumerate() {
             if itx >= max_amount {
@@ -120,6 +235,10 @@ impl CanvasColours {
     }
 
     pub fn generate_remaining_cpu_colours(&mut self) {
+        if self.cycle_cpu_colours && !self.cpu_colour_styles.is_empty() {
+            // Lines are coloured by their index modulo the number of colours, so they repeat
+            return;
+        }
         let remaining_num_colours = NUM_COLOURS - self.cpu_colour_styles.len() as i32;
         self.cpu_colour_styles
             .extend(gen_n_styles(remaining_num_colours));
@@ -130,6 +249,7 @@ impl CanvasColours {
         self.currently_selected_text_style = Style::default()
             .fg(self.currently_selected_text_colour)
             .bg(self.currently_selected_bg_colour);
+        self.cursor_style = self.currently_selected_text_style;
         Ok(())
     }
 
@@ -138,6 +258,20 @@ impl CanvasColours {
         self.currently_selected_text_style = Style::default()
             .fg(self.currently_selected_text_colour)
             .bg(self.currently_selected_bg_colour);
+        self.cursor_style = self.currently_selected_text_style;
+        Ok(())
+    }
+
+    /// The cursor follows the selected text colours unless set, so this must be set after them.
+    pub fn set_cursor_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.cursor_style = Style::default()
+            .fg(self.currently_selected_text_colour)
+            .bg(get_colour_from_config(colour)?);
+        Ok(())
+    }
+
+    pub fn set_invalid_query_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.invalid_query_style = get_style_from_config(colour)?;
         Ok(())
     }
 
@@ -146,6 +280,11 @@ impl CanvasColours {
         Ok(())
     }
 
+    pub fn set_table_stripe_colour(&mut self, colour: &str) -> error::Result<()> {
+        self.table_stripe_colour = get_colour_from_config(colour)?;
+        Ok(())
+    }
+
     pub fn set_graph_colour(&mut self, colour: &str) -> error::Result<()> {
         self.graph_style = get_style_from_config(colour)?;
         Ok(())
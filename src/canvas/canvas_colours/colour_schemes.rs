@@ -0,0 +1,169 @@
+//! The built-in colour schemes, written the same way as colours in the config file.
+
+pub struct Palette {
+    pub text: &'static str,
+    pub border: &'static str,
+    pub highlighted_border: &'static str,
+    pub table_header: &'static str,
+    /// The background of every other table row, if rows are striped.
+    pub table_stripe: &'static str,
+    pub widget_title: &'static str,
+    pub graph: &'static str,
+    pub selected_text: &'static str,
+    pub selected_bg: &'static str,
+    pub ram: &'static str,
+    pub swap: &'static str,
+    pub vram: &'static str,
+    pub commit: &'static str,
+    pub rx: &'static str,
+    pub tx: &'static str,
+    pub rx_total: &'static str,
+    pub tx_total: &'static str,
+    pub avg_cpu: &'static str,
+    pub warning: &'static str,
+    pub critical: &'static str,
+    pub cpu_cores: &'static [&'static str],
+    /// Whether lines past the CPU core colours reuse them, rather than getting generated colours
+    /// that may be impossible to tell apart.
+    pub cycle_cpu_cores: bool,
+}
+
+pub const GRUVBOX_PALETTE: Palette = Palette {
+    text: "#ebdbb2",
+    border: "#ebdbb2",
+    highlighted_border: "#fe8019",
+    table_header: "#83a598",
+    table_stripe: "#3c3836",
+    widget_title: "#ebdbb2",
+    graph: "#ebdbb2",
+    selected_text: "#282828",
+    selected_bg: "#458588",
+    ram: "#8ec07c",
+    swap: "#fabd2f",
+    vram: "#83a598",
+    commit: "#d3869b",
+    rx: "#8ec07c",
+    tx: "#fabd2f",
+    rx_total: "#83a598",
+    tx_total: "#d3869b",
+    avg_cpu: "#fb4934",
+    warning: "#fabd2f",
+    critical: "#fb4934",
+    cpu_cores: &[
+        "#d3869b", "#fabd2f", "#8ec07c", "#b8bb26", "#83a598", "#fe8019", "#cc241d", "#458588",
+    ],
+    cycle_cpu_cores: false,
+};
+
+pub const NORD_PALETTE: Palette = Palette {
+    text: "#d8dee9",
+    border: "#4c566a",
+    highlighted_border: "#88c0d0",
+    table_header: "#81a1c1",
+    table_stripe: "#3b4252",
+    widget_title: "#d8dee9",
+    graph: "#d8dee9",
+    selected_text: "#2e3440",
+    selected_bg: "#88c0d0",
+    ram: "#88c0d0",
+    swap: "#d08770",
+    vram: "#b48ead",
+    commit: "#a3be8c",
+    rx: "#88c0d0",
+    tx: "#d08770",
+    rx_total: "#5e81ac",
+    tx_total: "#ebcb8b",
+    avg_cpu: "#bf616a",
+    warning: "#ebcb8b",
+    critical: "#bf616a",
+    cpu_cores: &[
+        "#8fbcbb", "#88c0d0", "#81a1c1", "#5e81ac", "#a3be8c", "#ebcb8b", "#d08770", "#b48ead",
+    ],
+    cycle_cpu_cores: false,
+};
+
+/// Dark colours that stay readable on a white or light background.
+pub const LIGHT_PALETTE: Palette = Palette {
+    text: "#383a42",
+    border: "#a0a1a7",
+    highlighted_border: "#4078f2",
+    table_header: "#4078f2",
+    table_stripe: "#ececec",
+    widget_title: "#383a42",
+    graph: "#383a42",
+    selected_text: "#fafafa",
+    selected_bg: "#4078f2",
+    ram: "#a626a4",
+    swap: "#c18401",
+    vram: "#0184bc",
+    commit: "#50a14f",
+    rx: "#0184bc",
+    tx: "#a626a4",
+    rx_total: "#50a14f",
+    tx_total: "#c18401",
+    avg_cpu: "#e45649",
+    warning: "#c18401",
+    critical: "#e45649",
+    cpu_cores: &[
+        "#e45649", "#50a14f", "#c18401", "#4078f2", "#a626a4", "#0184bc", "#986801", "#ca1243",
+    ],
+    cycle_cpu_cores: false,
+};
+
+/// The Okabe-Ito colours, which stay distinct with red-green and blue-yellow colour blindness.
+/// There are seven core colours, an odd number, so lines sharing a colour get different markers
+/// when markers alternate.
+pub const COLOURBLIND_PALETTE: Palette = Palette {
+    text: "#e0e0e0",
+    border: "#999999",
+    highlighted_border: "#56b4e9",
+    table_header: "#56b4e9",
+    table_stripe: "#262626",
+    widget_title: "#e0e0e0",
+    graph: "#e0e0e0",
+    selected_text: "#000000",
+    selected_bg: "#56b4e9",
+    ram: "#e69f00",
+    swap: "#56b4e9",
+    vram: "#cc79a7",
+    commit: "#009e73",
+    rx: "#e69f00",
+    tx: "#56b4e9",
+    rx_total: "#f0e442",
+    tx_total: "#cc79a7",
+    avg_cpu: "#ffffff",
+    warning: "#f0e442",
+    critical: "#d55e00",
+    cpu_cores: &[
+        "#e69f00", "#56b4e9", "#009e73", "#f0e442", "#0072b2", "#d55e00", "#cc79a7",
+    ],
+    cycle_cpu_cores: true,
+};
+
+/// The Okabe-Ito colours for a light background, with the yellow swapped for grey.
+pub const COLOURBLIND_LIGHT_PALETTE: Palette = Palette {
+    text: "#000000",
+    border: "#999999",
+    highlighted_border: "#0072b2",
+    table_header: "#0072b2",
+    table_stripe: "#ececec",
+    widget_title: "#000000",
+    graph: "#000000",
+    selected_text: "#ffffff",
+    selected_bg: "#0072b2",
+    ram: "#0072b2",
+    swap: "#d55e00",
+    vram: "#cc79a7",
+    commit: "#009e73",
+    rx: "#0072b2",
+    tx: "#d55e00",
+    rx_total: "#009e73",
+    tx_total: "#cc79a7",
+    avg_cpu: "#000000",
+    warning: "#e69f00",
+    critical: "#d55e00",
+    cpu_cores: &[
+        "#0072b2", "#e69f00", "#009e73", "#d55e00", "#cc79a7", "#56b4e9", "#666666",
+    ],
+    cycle_cpu_cores: true,
+};
@@ -1,5 +1,11 @@
-pub mod dd_dialog;
+pub mod config_error_dialog;
+pub mod disk_detail_dialog;
+pub mod env_dialog;
 pub mod help_dialog;
+pub mod modal_dialog;
 
-pub use dd_dialog::KillDialog;
+pub use config_error_dialog::ConfigErrorDialog;
+pub use disk_detail_dialog::DiskDetailDialog;
+pub use env_dialog::EnvDialog;
 pub use help_dialog::HelpDialog;
+pub use modal_dialog::ModalDialog;
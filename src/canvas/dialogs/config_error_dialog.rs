@@ -0,0 +1,52 @@
+use std::cmp::max;
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    widgets::{Block, Borders, Paragraph, Text, Widget},
+};
+
+use crate::canvas::Painter;
+
+const CONFIG_ERROR_BASE: &str = " Config Error ── Esc to close ";
+
+pub trait ConfigErrorDialog {
+    fn draw_config_error_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, config_err: &str, draw_loc: Rect,
+    );
+}
+
+impl ConfigErrorDialog for Painter {
+    fn draw_config_error_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, config_err: &str, draw_loc: Rect,
+    ) {
+        let error_text = [Text::raw(format!(
+            "\nUnable to reload the config file, so the previous settings are kept - {}",
+            config_err
+        ))];
+
+        let repeat_num = max(
+            0,
+            draw_loc.width as i32 - CONFIG_ERROR_BASE.chars().count() as i32 - 2,
+        );
+        let error_title = format!(
+            " Config Error ─{}─ Esc to close ",
+            "─".repeat(repeat_num as usize)
+        );
+
+        Paragraph::new(error_text.iter())
+            .block(
+                Block::default()
+                    .title(&error_title)
+                    .title_style(self.colours.border_style)
+                    .style(self.colours.border_style)
+                    .borders(Borders::ALL)
+                    .border_style(self.colours.border_style),
+            )
+            .style(self.colours.text_style)
+            .alignment(Alignment::Center)
+            .wrap(true)
+            .render(f, draw_loc);
+    }
+}
@@ -0,0 +1,102 @@
+use std::cmp::max;
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    widgets::{Block, Borders, Paragraph, Text, Widget},
+};
+
+use crate::{app::App, canvas::Painter};
+
+const DISK_DETAIL_BASE: &str = "── Esc to close ";
+
+pub trait DiskDetailDialog {
+    fn draw_disk_detail_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    );
+}
+
+impl DiskDetailDialog for Painter {
+    fn draw_disk_detail_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        let detail_state = &app_state.disk_detail_dialog_state;
+
+        let detail_title_base = format!(" {} ", detail_state.name);
+        let repeat_num = max(
+            0,
+            draw_loc.width as i32
+                - detail_title_base.chars().count() as i32
+                - DISK_DETAIL_BASE.chars().count() as i32
+                - 2,
+        );
+        let detail_title = format!(
+            "{}─{}─ Esc to close ",
+            detail_title_base,
+            "─".repeat(repeat_num as usize)
+        );
+
+        let mut detail_text = vec![
+            Text::styled("\nMount: ", self.colours.table_header_style),
+            Text::styled(
+                format!("{}\n", detail_state.mount_point),
+                self.colours.text_style,
+            ),
+        ];
+
+        if let Some(smart_err) = &detail_state.smart_err {
+            detail_text.push(Text::raw(format!(
+                "\nUnable to read S.M.A.R.T. data - {}",
+                smart_err
+            )));
+        } else if let Some(smart) = &detail_state.smart {
+            let not_reported = "Not reported".to_string();
+            for (label, value) in &[
+                ("Model: ", smart.model.as_ref().unwrap_or(&not_reported)),
+                ("Serial: ", smart.serial.as_ref().unwrap_or(&not_reported)),
+            ] {
+                detail_text.push(Text::styled(*label, self.colours.table_header_style));
+                detail_text.push(Text::styled(
+                    format!("{}\n", value),
+                    self.colours.text_style,
+                ));
+            }
+
+            detail_text.push(Text::styled("Health: ", self.colours.table_header_style));
+            detail_text.push(Text::styled(
+                format!("{}\n\n", smart.health.as_ref().unwrap_or(&not_reported)),
+                if smart.is_healthy() {
+                    self.colours.text_style
+                } else {
+                    self.colours.disk_critical_style
+                },
+            ));
+
+            for (attribute, value) in &smart.attributes {
+                detail_text.push(Text::styled(
+                    format!("{}: ", attribute),
+                    self.colours.table_header_style,
+                ));
+                detail_text.push(Text::styled(
+                    format!("{}\n", value),
+                    self.colours.text_style,
+                ));
+            }
+        }
+
+        Paragraph::new(detail_text.iter())
+            .block(
+                Block::default()
+                    .title(&detail_title)
+                    .title_style(self.colours.border_style)
+                    .style(self.colours.border_style)
+                    .borders(Borders::ALL)
+                    .border_style(self.colours.border_style),
+            )
+            .style(self.colours.text_style)
+            .alignment(Alignment::Left)
+            .wrap(true)
+            .render(f, draw_loc);
+    }
+}
@@ -0,0 +1,115 @@
+use std::cmp::max;
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    widgets::{Block, Borders, Paragraph, Text, Widget},
+};
+
+use crate::{app::App, canvas::Painter};
+
+const ENV_BASE: &str = "── Esc to close ";
+
+pub trait EnvDialog {
+    fn draw_env_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl EnvDialog for Painter {
+    fn draw_env_dialog<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let env_state = &mut app_state.env_dialog_state;
+
+        let env_title_base = format!(" Environment of {} ({}) ", env_state.name, env_state.pid);
+        let repeat_num = max(
+            0,
+            draw_loc.width as i32
+                - env_title_base.chars().count() as i32
+                - ENV_BASE.chars().count() as i32
+                - 2,
+        );
+        let env_title = format!(
+            "{}─{}─ Esc to close ",
+            env_title_base,
+            "─".repeat(repeat_num as usize)
+        );
+
+        let env_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+            .split(draw_loc);
+
+        // Only draw what fits, starting from an offset that keeps the selected entry visible.
+        let num_rows = u64::from(env_chunks[0].height);
+        let num_filtered = env_state.get_filtered_env_vars().len();
+        if env_state.current_scroll_position >= num_filtered as u64 {
+            env_state.current_scroll_position = max(0, num_filtered as i64 - 1) as u64;
+        }
+        let current_scroll_position = env_state.current_scroll_position;
+        let start_position = if num_rows == 0 || current_scroll_position < num_rows {
+            0
+        } else {
+            current_scroll_position - num_rows + 1
+        };
+
+        let filtered_env_vars = env_state.get_filtered_env_vars();
+
+        let env_text: Vec<Text<'_>> = if let Some(env_err) = &env_state.env_err {
+            vec![Text::raw(format!(
+                "\nUnable to read the environment of this process - {}",
+                env_err
+            ))]
+        } else if filtered_env_vars.is_empty() {
+            vec![Text::raw("\nNo environment variables to show.")]
+        } else {
+            filtered_env_vars
+                .iter()
+                .enumerate()
+                .skip(start_position as usize)
+                .take(num_rows as usize)
+                .flat_map(|(itx, (key, value))| {
+                    if itx as u64 == current_scroll_position {
+                        vec![Text::styled(
+                            format!("{}={}\n", key, value),
+                            self.colours.currently_selected_text_style,
+                        )]
+                    } else {
+                        vec![
+                            Text::styled(key.as_str(), self.colours.table_header_style),
+                            Text::styled(format!("={}\n", value), self.colours.text_style),
+                        ]
+                    }
+                })
+                .collect()
+        };
+
+        let filter_text = [
+            Text::styled("Filter: ", self.colours.table_header_style),
+            Text::styled(&env_state.filter_query, self.colours.text_style),
+        ];
+
+        Block::default()
+            .title(&env_title)
+            .title_style(self.colours.border_style)
+            .style(self.colours.border_style)
+            .borders(Borders::ALL)
+            .border_style(self.colours.border_style)
+            .render(f, draw_loc);
+
+        Paragraph::new(env_text.iter())
+            .style(self.colours.text_style)
+            .alignment(Alignment::Left)
+            .wrap(false)
+            .render(f, env_chunks[0]);
+
+        Paragraph::new(filter_text.iter())
+            .style(self.colours.text_style)
+            .alignment(Alignment::Left)
+            .wrap(false)
+            .render(f, env_chunks[1]);
+    }
+}
@@ -2,15 +2,12 @@ use std::cmp::max;
 
 use tui::{
     backend::Backend,
-    layout::{Alignment, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     terminal::Frame,
-    widgets::{Block, Borders, Paragraph, Widget},
+    widgets::{Block, Borders, Paragraph, Text, Widget},
 };
 
-use crate::{
-    app::{App, AppHelpCategory},
-    canvas::Painter,
-};
+use crate::{app::App, canvas::Painter};
 
 const HELP_BASE: &str = " Help ── 1: General ─── 2: Processes ─── 3: Search ─── Esc to close ";
 
@@ -33,25 +30,76 @@ impl HelpDialog for Painter {
             "─".repeat(repeat_num as usize)
         );
 
-        Paragraph::new(
-            match app_state.help_dialog_state.current_category {
-                AppHelpCategory::General => &self.styled_general_help_text,
-                AppHelpCategory::Process => &self.styled_process_help_text,
-                AppHelpCategory::Search => &self.styled_search_help_text,
-            }
-            .iter(),
-        )
-        .block(
-            Block::default()
-                .title(&help_title)
-                .title_style(self.colours.border_style)
-                .style(self.colours.border_style)
-                .borders(Borders::ALL)
-                .border_style(self.colours.border_style),
-        )
-        .style(self.colours.text_style)
-        .alignment(Alignment::Left)
-        .wrap(true)
-        .render(f, draw_loc);
+        let help_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(1)
+            .constraints([Constraint::Min(0), Constraint::Length(1)].as_ref())
+            .split(draw_loc);
+
+        let help_lines = app_state
+            .help_dialog_state
+            .get_help_lines(&app_state.app_config_fields.keybinds);
+
+        // Don't scroll past the point where the last line is at the bottom of the dialog.
+        let num_rows = u64::from(help_chunks[0].height);
+        let max_scroll_position = max(0, help_lines.len() as i64 - num_rows as i64) as u64;
+        if app_state.help_dialog_state.scroll_position > max_scroll_position {
+            app_state.help_dialog_state.scroll_position = max_scroll_position;
+        }
+
+        let help_text: Vec<Text<'_>> = if help_lines.is_empty() {
+            vec![Text::raw("No keybindings match the search.")]
+        } else {
+            help_lines
+                .iter()
+                .skip(app_state.help_dialog_state.scroll_position as usize)
+                .take(num_rows as usize)
+                .map(|(line, is_header)| {
+                    Text::styled(
+                        format!("{}\n", line),
+                        if *is_header {
+                            self.colours.table_header_style
+                        } else {
+                            self.colours.text_style
+                        },
+                    )
+                })
+                .collect()
+        };
+
+        let search_text = [
+            Text::styled(
+                if app_state.help_dialog_state.is_searching {
+                    "Search: "
+                } else {
+                    "/ to search: "
+                },
+                self.colours.table_header_style,
+            ),
+            Text::styled(
+                &app_state.help_dialog_state.search_query,
+                self.colours.text_style,
+            ),
+        ];
+
+        Block::default()
+            .title(&help_title)
+            .title_style(self.colours.border_style)
+            .style(self.colours.border_style)
+            .borders(Borders::ALL)
+            .border_style(self.colours.border_style)
+            .render(f, draw_loc);
+
+        Paragraph::new(help_text.iter())
+            .style(self.colours.text_style)
+            .alignment(Alignment::Left)
+            .wrap(false)
+            .render(f, help_chunks[0]);
+
+        Paragraph::new(search_text.iter())
+            .style(self.colours.text_style)
+            .alignment(Alignment::Left)
+            .wrap(false)
+            .render(f, help_chunks[1]);
     }
 }
@@ -0,0 +1,101 @@
+use std::cmp::{max, min};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    widgets::{Block, Borders, Paragraph, Text, Widget},
+};
+
+use crate::{app::dialog::Dialog, canvas::Painter};
+
+const MODAL_DIALOG_BASE: &str = "── Esc to close ";
+
+pub trait ModalDialog {
+    fn draw_modal_dialog<B: Backend>(&self, f: &mut Frame<'_, B>, dialog: &Dialog);
+}
+
+impl ModalDialog for Painter {
+    fn draw_modal_dialog<B: Backend>(&self, f: &mut Frame<'_, B>, dialog: &Dialog) {
+        // TODO: [REFACTOR] The point we start changing size at currently hard-coded in.
+        let screen = f.size();
+        let width = if screen.width < 100 {
+            screen.width * 9 / 10
+        } else {
+            screen.width * 2 / 5
+        };
+
+        // Room for the borders, the wrapped message and the buttons, with a blank line around the
+        // message, and the input if there is one
+        let inner_width = max(1, width as usize - min(width as usize, 2));
+        let message_height: usize = dialog
+            .message
+            .lines()
+            .map(|line| max(1, (line.chars().count() + inner_width - 1) / inner_width))
+            .sum();
+        let input_height = if dialog.text_input.is_some() { 2 } else { 0 };
+        let height = min(screen.height, (message_height + input_height + 5) as u16);
+        let draw_loc = Rect::new(
+            (screen.width - width) / 2,
+            (screen.height - height) / 2,
+            width,
+            height,
+        );
+
+        let title_base = format!(" {} ", dialog.title);
+        let repeat_num = max(
+            0,
+            draw_loc.width as i32
+                - title_base.chars().count() as i32
+                - MODAL_DIALOG_BASE.chars().count() as i32
+                - 2,
+        );
+        let dialog_title = format!(
+            "{}─{}─ Esc to close ",
+            title_base,
+            "─".repeat(repeat_num as usize)
+        );
+
+        let mut dialog_text = vec![Text::raw(format!("\n{}\n\n", dialog.message))];
+
+        if let Some(text_input) = &dialog.text_input {
+            dialog_text.push(Text::styled("> ", self.colours.table_header_style));
+            dialog_text.push(Text::styled(text_input.as_str(), self.colours.text_style));
+            if dialog.is_input_focused() {
+                dialog_text.push(Text::styled(" ", self.colours.cursor_style));
+            }
+            dialog_text.push(Text::raw("\n\n"));
+        }
+
+        let focused_button_index = dialog
+            .get_focused_button()
+            .map(|(button_index, _)| button_index);
+        for (button_index, button) in dialog.buttons.iter().enumerate() {
+            if button_index > 0 {
+                dialog_text.push(Text::raw("        "));
+            }
+            dialog_text.push(if Some(button_index) == focused_button_index {
+                Text::styled(
+                    button.label.as_str(),
+                    self.colours.currently_selected_text_style,
+                )
+            } else {
+                Text::raw(button.label.as_str())
+            });
+        }
+
+        Paragraph::new(dialog_text.iter())
+            .block(
+                Block::default()
+                    .title(&dialog_title)
+                    .title_style(self.colours.border_style)
+                    .style(self.colours.border_style)
+                    .borders(Borders::ALL)
+                    .border_style(self.colours.border_style),
+            )
+            .style(self.colours.text_style)
+            .alignment(Alignment::Center)
+            .wrap(true)
+            .render(f, draw_loc);
+    }
+}
@@ -1,3 +1,5 @@
+use tui::widgets::Marker;
+
 use crate::app;
 
 /// A somewhat jury-rigged solution to simulate a variable intrinsic layout for
@@ -5,16 +7,19 @@ use crate::app;
 /// allocate widths.  This will thus potentially cut off latter elements
 /// (return size of 0) if it is too small (threshold), but will try its best.
 ///
-/// `width thresholds` and `desired_widths_ratio` should be the same length.
+/// `width thresholds` and `desired_widths_ratio` should be the same length, and `column_gap`
+/// is the number of spaces between columns.
 /// Otherwise bad things happen.
 pub fn get_variable_intrinsic_widths(
-    total_width: u16, desired_widths_ratio: &[f64], width_thresholds: &[usize],
+    total_width: u16, column_gap: u16, desired_widths_ratio: &[f64], width_thresholds: &[usize],
 ) -> (Vec<u16>, usize) {
     let num_widths = desired_widths_ratio.len();
     let mut resulting_widths: Vec<u16> = vec![0; num_widths];
     let mut last_index = 0;
 
-    let mut remaining_width = (total_width - (num_widths as u16 - 1)) as i32; // Required for spaces...
+    // Required for spaces...
+    let mut remaining_width =
+        i32::from(total_width) - (num_widths as i32 - 1) * i32::from(column_gap);
     let desired_widths = desired_widths_ratio
         .iter()
         .map(|&desired_width_ratio| (desired_width_ratio * total_width as f64) as i32)
@@ -150,6 +155,40 @@ pub fn get_start_position(
     }
 }
 
+/// The start of a searchable table's title, with the search query while it's being typed or
+/// filtering the table.
+pub fn get_table_search_title(name: &str, search_state: &app::AppSearchState) -> String {
+    if search_state.is_enabled {
+        format!(
+            " {} ── Search: {}_ ",
+            name, search_state.current_search_query
+        )
+    } else if !search_state.is_blank_search {
+        format!(
+            " {} ── Search: {} ",
+            name, search_state.current_search_query
+        )
+    } else {
+        format!(" {} ", name)
+    }
+}
+
+/// Returns only the rows of a table that will actually be drawn, starting from `start_position`,
+/// along with the position of the first of them.  `num_rows` is the same value as passed into
+/// [`get_start_position`].
+///
+/// This clamps the start in case the data shrunk since the last draw, so rows that are scrolled
+/// out of view are never stringified or styled.  Positions and highlighting should be worked out
+/// from the returned start rather than `start_position`, as they may differ.
+pub fn get_visible_rows<T>(data: &[T], start_position: u64, num_rows: u64) -> (u64, &[T]) {
+    let start = std::cmp::min(start_position as usize, data.len().saturating_sub(1));
+    // `get_start_position` scrolls so the selected row is at most `num_rows` past the start, so
+    // that's `num_rows + 1` rows on screen, counting the start
+    let end = std::cmp::min(start + num_rows as usize + 1, data.len());
+
+    (start as u64, &data[start..end])
+}
+
 /// Calculate how many bars are to be
 /// drawn within basic mode's components.
 pub fn calculate_basic_use_bars(use_percentage: f64, num_bars_available: usize) -> usize {
@@ -158,3 +197,39 @@ pub fn calculate_basic_use_bars(use_percentage: f64, num_bars_available: usize)
         num_bars_available,
     )
 }
+
+/// Gets the marker for a line in a graph with several.  When varying, lines alternate between
+/// braille and dots, so neighbouring lines differ by more than colour.
+pub fn get_series_marker(use_dot: bool, vary_markers: bool, series_index: usize) -> Marker {
+    if use_dot != (vary_markers && series_index % 2 == 1) {
+        Marker::Dot
+    } else {
+        Marker::Braille
+    }
+}
+
+/// Gets the names of a graph's lines, each with its marker beside it when they vary.
+pub fn get_series_labels(labels: &[String], use_dot: bool, vary_markers: bool) -> Vec<String> {
+    labels
+        .iter()
+        .enumerate()
+        .map(|(itx, label)| {
+            if vary_markers {
+                format!(
+                    "{} {}",
+                    get_marker_symbol(get_series_marker(use_dot, vary_markers, itx)),
+                    label
+                )
+            } else {
+                label.clone()
+            }
+        })
+        .collect()
+}
+
+pub fn get_marker_symbol(marker: Marker) -> &'static str {
+    match marker {
+        Marker::Dot => "•",
+        Marker::Braille => "⣿",
+    }
+}
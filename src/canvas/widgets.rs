@@ -1,21 +1,37 @@
 pub mod basic_table_arrows;
+pub mod battery_display;
 pub mod cpu_basic;
 pub mod cpu_graph;
+pub mod disk_io_graph;
 pub mod disk_table;
+pub mod gpu_graph;
+pub mod header_bar;
 pub mod mem_basic;
 pub mod mem_graph;
 pub mod network_basic;
 pub mod network_graph;
+pub mod ping_graph;
 pub mod process_table;
+pub mod psi_graph;
+pub mod socket_table;
+pub mod temp_graph;
 pub mod temp_table;
 
 pub use basic_table_arrows::BasicTableArrows;
+pub use battery_display::BatteryDisplayWidget;
 pub use cpu_basic::CpuBasicWidget;
 pub use cpu_graph::CpuGraphWidget;
+pub use disk_io_graph::DiskIoGraphWidget;
 pub use disk_table::DiskTableWidget;
+pub use gpu_graph::GpuGraphWidget;
+pub use header_bar::HeaderBarWidget;
 pub use mem_basic::MemBasicWidget;
 pub use mem_graph::MemGraphWidget;
 pub use network_basic::NetworkBasicWidget;
 pub use network_graph::NetworkGraphWidget;
+pub use ping_graph::PingGraphWidget;
 pub use process_table::ProcessTableWidget;
+pub use psi_graph::PsiGraphWidget;
+pub use socket_table::SocketTableWidget;
+pub use temp_graph::TempGraphWidget;
 pub use temp_table::TempTableWidget;
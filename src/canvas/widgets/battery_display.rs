@@ -0,0 +1,277 @@
+use lazy_static::lazy_static;
+use std::cmp::max;
+
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Direction, Layout, Rect},
+    terminal::Frame,
+    widgets::{Axis, Block, Borders, Chart, Dataset, Marker, Paragraph, Row, Table, Text, Widget},
+};
+
+use crate::{
+    app::{App, WidgetPosition},
+    canvas::{drawing_utils::get_variable_intrinsic_widths, Painter},
+    constants::*,
+};
+
+const BATTERY_HEADERS: [&str; 4] = ["Battery", "Charge", "Power", "State"];
+
+lazy_static! {
+    static ref BATTERY_HEADERS_LENS: Vec<usize> = BATTERY_HEADERS
+        .iter()
+        .map(|entry| max(FORCE_MIN_THRESHOLD, entry.len()))
+        .collect::<Vec<_>>();
+}
+
+pub trait BatteryDisplayWidget {
+    fn draw_battery_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+
+    fn draw_battery_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect, title: &str,
+    );
+
+    fn draw_battery_health<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    );
+
+    fn draw_power_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl BatteryDisplayWidget for Painter {
+    fn draw_battery_display<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let title = if app_state.is_expanded {
+            const TITLE_BASE: &str = " Battery ── Esc to go back ";
+            let repeat_num = max(
+                0,
+                draw_loc.width as i32 - TITLE_BASE.chars().count() as i32 - 2,
+            );
+            let result_title = format!(
+                " Battery ─{}─ Esc to go back ",
+                "─".repeat(repeat_num as usize)
+            );
+
+            result_title
+        } else {
+            " Battery ".to_string()
+        };
+
+        // The table and health details only need a line per battery, along with their headers
+        // and borders, and the power graph gets whatever is left
+        let table_height = app_state.canvas_data.battery_data.len() as u16 + 4;
+        let health_height = if app_state.canvas_data.battery_health_data.is_empty() {
+            0
+        } else {
+            app_state.canvas_data.battery_health_data.len() as u16 + 2
+        };
+        let battery_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .margin(0)
+            .constraints(
+                [
+                    Constraint::Length(table_height),
+                    Constraint::Length(health_height),
+                    Constraint::Min(0),
+                ]
+                .as_ref(),
+            )
+            .split(draw_loc);
+
+        self.draw_battery_table(f, app_state, battery_chunks[0], &title);
+        if health_height > 0 {
+            self.draw_battery_health(f, app_state, battery_chunks[1]);
+        }
+        if battery_chunks[2].height >= 5 {
+            self.draw_power_graph(f, app_state, battery_chunks[2]);
+        }
+    }
+
+    fn draw_battery_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect, title: &str,
+    ) {
+        // There are rarely more than a couple of batteries, so this doesn't scroll
+        let battery_rows =
+            app_state
+                .canvas_data
+                .battery_data
+                .iter()
+                .enumerate()
+                .map(|(itx, battery_row)| {
+                    Row::StyledData(
+                        battery_row.iter(),
+                        self.get_table_row_style(app_state, self.colours.text_style, itx as u64),
+                    )
+                });
+
+        // Calculate widths
+        let width = f64::from(draw_loc.width);
+        let width_ratios = [0.25, 0.4, 0.15, 0.2];
+        let variable_intrinsic_results = get_variable_intrinsic_widths(
+            width as u16,
+            app_state.app_config_fields.table_gap,
+            &width_ratios,
+            &BATTERY_HEADERS_LENS,
+        );
+        let intrinsic_widths = &(variable_intrinsic_results.0)[0..variable_intrinsic_results.1];
+
+        // Draw
+        Table::new(BATTERY_HEADERS.iter(), battery_rows)
+            .block(
+                Block::default()
+                    .title(title)
+                    .title_style(if app_state.is_expanded {
+                        self.colours.highlighted_border_style
+                    } else {
+                        self.colours.widget_title_style
+                    })
+                    .borders(Borders::ALL)
+                    .border_style(match app_state.current_widget_selected {
+                        WidgetPosition::Battery => self.colours.highlighted_border_style,
+                        _ => self.colours.border_style,
+                    }),
+            )
+            .header_style(self.colours.table_header_style)
+            .column_spacing(app_state.app_config_fields.table_gap)
+            .widths(
+                &(intrinsic_widths
+                    .iter()
+                    .map(|calculated_width| Constraint::Length(*calculated_width as u16))
+                    .collect::<Vec<_>>()),
+            )
+            .render(f, draw_loc);
+    }
+
+    fn draw_battery_health<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect,
+    ) {
+        let health_text: Vec<Text<'_>> = app_state
+            .canvas_data
+            .battery_health_data
+            .iter()
+            .map(|health_row| Text::raw(format!("{}\n", health_row)))
+            .collect();
+
+        Paragraph::new(health_text.iter())
+            .block(
+                Block::default()
+                    .title(" Health ")
+                    .title_style(self.colours.widget_title_style)
+                    .borders(Borders::ALL)
+                    .border_style(match app_state.current_widget_selected {
+                        WidgetPosition::Battery => self.colours.highlighted_border_style,
+                        _ => self.colours.border_style,
+                    }),
+            )
+            .style(self.colours.text_style)
+            .render(f, draw_loc);
+    }
+
+    fn draw_power_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let display_time_labels = [
+            format!("{}s", app_state.battery_state.current_display_time / 1000),
+            "0s".to_string(),
+        ];
+        let x_axis = if app_state.app_config_fields.hide_time
+            || (app_state.app_config_fields.autohide_time
+                && app_state.battery_state.autohide_timer.is_none())
+        {
+            Axis::default().bounds([0.0, app_state.battery_state.current_display_time as f64])
+        } else if let Some(time) = app_state.battery_state.autohide_timer {
+            if std::time::Instant::now().duration_since(time).as_millis()
+                < AUTOHIDE_TIMEOUT_MILLISECONDS as u128
+            {
+                Axis::default()
+                    .bounds([0.0, app_state.battery_state.current_display_time as f64])
+                    .style(self.colours.graph_style)
+                    .labels_style(self.colours.graph_style)
+                    .labels(&display_time_labels)
+            } else {
+                app_state.battery_state.autohide_timer = None;
+                Axis::default().bounds([0.0, app_state.battery_state.current_display_time as f64])
+            }
+        } else {
+            Axis::default()
+                .bounds([0.0, app_state.battery_state.current_display_time as f64])
+                .style(self.colours.graph_style)
+                .labels_style(self.colours.graph_style)
+                .labels(&display_time_labels)
+        };
+
+        // Fit the highest draw in view, rounded up to the next 10W
+        let max_power = app_state
+            .canvas_data
+            .power_data
+            .iter()
+            .flat_map(|power_points| power_points.iter())
+            .filter(|(time, _)| *time >= 0.0)
+            .fold(0.0, |max_power, (_, power)| f64::max(max_power, *power));
+        let y_max = f64::max(10.0, (max_power / 10.0).ceil() * 10.0);
+        let y_labels = [
+            "0W".to_string(),
+            format!("{}W", y_max / 2.0),
+            format!("{}W", y_max),
+        ];
+        let hidden_parts = app_state
+            .app_config_fields
+            .get_hidden_graph_parts(WidgetPosition::Battery);
+
+        let y_axis: Axis<'_, String> = Axis::default()
+            .style(self.colours.graph_style)
+            .labels_style(self.colours.graph_style)
+            .bounds([-y_max * 0.005, y_max * 1.005]);
+        let y_axis = if hidden_parts.y_labels {
+            y_axis
+        } else {
+            y_axis.labels(&y_labels)
+        };
+
+        let power_canvas_vec: Vec<Dataset<'_>> = app_state
+            .canvas_data
+            .power_data
+            .iter()
+            .map(|power_points| {
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        &app_state.canvas_data.power_label
+                    })
+                    .marker(
+                        if app_state
+                            .app_config_fields
+                            .is_using_dot(WidgetPosition::Battery)
+                        {
+                            Marker::Dot
+                        } else {
+                            Marker::Braille
+                        },
+                    )
+                    .style(self.colours.cpu_colour_styles[0])
+                    .data(power_points)
+            })
+            .collect();
+
+        Chart::default()
+            .block(
+                Block::default()
+                    .title(if hidden_parts.title { "" } else { " Power " })
+                    .title_style(self.colours.widget_title_style)
+                    .borders(Borders::ALL)
+                    .border_style(match app_state.current_widget_selected {
+                        WidgetPosition::Battery => self.colours.highlighted_border_style,
+                        _ => self.colours.border_style,
+                    }),
+            )
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .datasets(&power_canvas_vec)
+            .render(f, draw_loc);
+    }
+}
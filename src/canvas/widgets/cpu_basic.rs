@@ -41,10 +41,67 @@ impl CpuBasicWidget for Painter {
                 .render(f, draw_loc);
         }
 
+        // The average is always the first entry; skip it if it is hidden.  Per core type averages
+        // are at the end, and are skipped as the bars are labelled by index.
+        let show_average_cpu = app_state.app_config_fields.cpu_graph_lines.shows_average();
+        let num_core_type_averages = app_state
+            .data_collection
+            .cpu_harvest
+            .iter()
+            .filter(|cpu| cpu.is_core_type_average)
+            .count();
+        let cpu_data = if cpu_data.len() <= num_core_type_averages {
+            cpu_data
+        } else {
+            &cpu_data[0..cpu_data.len() - num_core_type_averages]
+        };
+        let cpu_data = if show_average_cpu || cpu_data.is_empty() {
+            cpu_data
+        } else {
+            &cpu_data[1..]
+        };
+
         let num_cpus = cpu_data.len();
+        const REQUIRED_COLUMNS: usize = 4;
+
+        // Without room for every CPU, the average is shown across the whole width instead
+        if draw_loc.height > 0 && (draw_loc.height as usize) * REQUIRED_COLUMNS < num_cpus {
+            if let Some(average_cpu) = app_state.canvas_data.cpu_data.first() {
+                let margined_loc = Layout::default()
+                    .direction(Direction::Horizontal)
+                    .constraints([Constraint::Percentage(100)].as_ref())
+                    .horizontal_margin(1)
+                    .split(draw_loc);
+
+                // +11 due to 3 + 4 + 2 + 2 columns for the name + percentage + bar bounds + margin
+                let bar_length = max(0, draw_loc.width as i64 - 11) as usize;
+                let use_percentage = if let Some(cpu_usage) = average_cpu.cpu_data.last() {
+                    cpu_usage.1
+                } else {
+                    0.0
+                };
+                let num_bars = calculate_basic_use_bars(use_percentage, bar_length);
+                let average_bar = format!(
+                    "AVG[{}{}{:3.0}%]",
+                    "|".repeat(num_bars),
+                    " ".repeat(bar_length - num_bars),
+                    use_percentage.round(),
+                );
+
+                let average_text = [Text::Styled(
+                    average_bar.into(),
+                    self.colours.cpu_colour_styles[0],
+                )];
+
+                Paragraph::new(average_text.iter())
+                    .block(Block::default())
+                    .render(f, margined_loc[0]);
+                return;
+            }
+        }
+
         if draw_loc.height > 0 {
             let remaining_height = draw_loc.height as usize;
-            const REQUIRED_COLUMNS: usize = 4;
 
             let chunk_vec =
                 vec![Constraint::Percentage((100 / REQUIRED_COLUMNS) as u16); REQUIRED_COLUMNS];
@@ -76,7 +133,7 @@ impl CpuBasicWidget for Painter {
                     let num_bars = calculate_basic_use_bars(use_percentage, bar_length);
                     format!(
                         "{:3}[{}{}{:3.0}%]\n",
-                        if app_state.app_config_fields.show_average_cpu {
+                        if show_average_cpu {
                             if cpu_index == 0 {
                                 "AVG".to_string()
                             } else {
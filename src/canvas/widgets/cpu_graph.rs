@@ -5,7 +5,11 @@ use std::cmp::max;
 use crate::{
     app::{App, WidgetPosition},
     canvas::{
-        drawing_utils::{get_start_position, get_variable_intrinsic_widths},
+        drawing_utils::{
+            get_marker_symbol, get_series_marker, get_start_position,
+            get_variable_intrinsic_widths, get_visible_rows,
+        },
+        widgets::CpuBasicWidget,
         Painter,
     },
     constants::*,
@@ -16,16 +20,11 @@ use tui::{
     backend::Backend,
     layout::{Constraint, Rect},
     terminal::Frame,
-    widgets::{Axis, Block, Borders, Chart, Dataset, Marker, Row, Table, Widget},
+    widgets::{Axis, Block, Borders, Chart, Dataset, Row, Table, Widget},
 };
 
 const CPU_SELECT_LEGEND_HEADER: [&str; 2] = ["CPU", "Show"];
-const CPU_LEGEND_HEADER: [&str; 2] = ["CPU", "Use%"];
 lazy_static! {
-    static ref CPU_LEGEND_HEADER_LENS: Vec<usize> = CPU_LEGEND_HEADER
-        .iter()
-        .map(|entry| max(FORCE_MIN_THRESHOLD, entry.len()))
-        .collect::<Vec<_>>();
     static ref CPU_SELECT_LEGEND_HEADER_LENS: Vec<usize> = CPU_SELECT_LEGEND_HEADER
         .iter()
         .map(|entry| max(FORCE_MIN_THRESHOLD, entry.len()))
@@ -34,6 +33,7 @@ lazy_static! {
 
 pub trait CpuGraphWidget {
     fn draw_cpu_graph<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect);
+    fn draw_cpu_bars<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect);
     fn draw_cpu_legend<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
     );
@@ -43,6 +43,11 @@ impl CpuGraphWidget for Painter {
     fn draw_cpu_graph<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
     ) {
+        if app_state.cpu_state.is_showing_bars {
+            self.draw_cpu_bars(f, app_state, draw_loc);
+            return;
+        }
+
         let cpu_data: &[ConvertedCpuData] = &app_state.canvas_data.cpu_data;
 
         let display_time_labels = [
@@ -76,55 +81,64 @@ impl CpuGraphWidget for Painter {
                 .labels(&display_time_labels)
         };
 
+        let hidden_parts = app_state
+            .app_config_fields
+            .get_hidden_graph_parts(WidgetPosition::Cpu);
+
         // Note this is offset as otherwise the 0 value is not drawn!
         let y_axis = Axis::default()
             .style(self.colours.graph_style)
             .labels_style(self.colours.graph_style)
-            .bounds([-0.5, 100.5])
-            .labels(&["0%", "100%"]);
+            .bounds([-0.5, 100.5]);
+        let y_axis = if hidden_parts.y_labels {
+            y_axis
+        } else {
+            y_axis.labels(&["0%", "100%"])
+        };
 
-        let dataset_vector: Vec<Dataset<'_>> = cpu_data
-            .iter()
-            .enumerate()
-            .rev()
-            .filter_map(|(itx, cpu)| {
-                if app_state.cpu_state.core_show_vec[itx] {
-                    Some(
-                        Dataset::default()
-                            .marker(if app_state.app_config_fields.use_dot {
-                                Marker::Dot
-                            } else {
-                                Marker::Braille
-                            })
-                            .style(
-                                if app_state.app_config_fields.show_average_cpu && itx == 0 {
-                                    self.colours.avg_colour_style
-                                } else {
-                                    self.colours.cpu_colour_styles
-                                        [itx % self.colours.cpu_colour_styles.len()]
-                                },
-                            )
-                            .data(&cpu.cpu_data[..]),
-                    )
-                } else {
-                    None
-                }
-            })
-            .collect();
+        // While the legend is selected, the line of its highlighted entry stands out from the rest
+        let highlighted_index = match app_state.current_widget_selected {
+            WidgetPosition::CpuLegend => app_state.get_selected_cpu_index(),
+            _ => None,
+        };
 
-        let title = if app_state.is_expanded && !app_state.cpu_state.is_showing_tray {
-            const TITLE_BASE: &str = " CPU ── Esc to go back ";
-            let repeat_num = max(
-                0,
-                draw_loc.width as i32 - TITLE_BASE.chars().count() as i32 - 2,
-            );
-            let result_title =
-                format!(" CPU ─{}─ Esc to go back ", "─".repeat(repeat_num as usize));
+        let mut dataset_vector: Vec<Dataset<'_>> = Vec::new();
+        let mut highlighted_dataset = None;
+        for (itx, cpu) in cpu_data.iter().enumerate().rev() {
+            if !app_state.cpu_state.core_show_vec[itx] {
+                continue;
+            }
 
-            result_title
-        } else {
-            " CPU ".to_string()
-        };
+            let style = if highlighted_index.is_some() && highlighted_index != Some(itx) {
+                self.colours.graph_style
+            } else if itx == 0 {
+                self.colours.avg_colour_style
+            } else {
+                self.colours.cpu_colour_styles[itx % self.colours.cpu_colour_styles.len()]
+            };
+            let dataset = Dataset::default()
+                .marker(get_series_marker(
+                    app_state
+                        .app_config_fields
+                        .is_using_dot(WidgetPosition::Cpu),
+                    app_state.app_config_fields.vary_series_markers,
+                    itx,
+                ))
+                .style(style)
+                .data(&cpu.cpu_data[..]);
+
+            if highlighted_index == Some(itx) {
+                highlighted_dataset = Some(dataset);
+            } else {
+                dataset_vector.push(dataset);
+            }
+        }
+        // Drawn last so it sits on top of the others
+        if let Some(highlighted_dataset) = highlighted_dataset {
+            dataset_vector.push(highlighted_dataset);
+        }
+
+        let title = get_cpu_title(app_state, draw_loc);
 
         let border_style = match app_state.current_widget_selected {
             WidgetPosition::Cpu => self.colours.highlighted_border_style,
@@ -134,7 +148,7 @@ impl CpuGraphWidget for Painter {
         Chart::default()
             .block(
                 Block::default()
-                    .title(&title)
+                    .title(if hidden_parts.title { "" } else { &title })
                     .title_style(if app_state.is_expanded {
                         border_style
                     } else {
@@ -149,12 +163,39 @@ impl CpuGraphWidget for Painter {
             .render(f, draw_loc);
     }
 
+    /// Draws per-core usage bars in place of the graph; this reuses the basic mode's bars.
+    fn draw_cpu_bars<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect) {
+        let title = get_cpu_title(app_state, draw_loc);
+        let hidden_parts = app_state
+            .app_config_fields
+            .get_hidden_graph_parts(WidgetPosition::Cpu);
+        let border_style = match app_state.current_widget_selected {
+            WidgetPosition::Cpu => self.colours.highlighted_border_style,
+            _ => self.colours.border_style,
+        };
+
+        let mut cpu_block = Block::default()
+            .title(if hidden_parts.title { "" } else { &title })
+            .title_style(if app_state.is_expanded {
+                border_style
+            } else {
+                self.colours.widget_title_style
+            })
+            .borders(Borders::ALL)
+            .border_style(border_style);
+        let inner_loc = cpu_block.inner(draw_loc);
+        cpu_block.render(f, draw_loc);
+
+        self.draw_basic_cpu(f, app_state, inner_loc);
+    }
+
     fn draw_cpu_legend<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
     ) {
         let cpu_data: &[ConvertedCpuData] = &app_state.canvas_data.cpu_data;
 
         let num_rows = max(0, i64::from(draw_loc.height) - 5) as u64;
+        app_state.app_scroll_positions.cpu_scroll_state.num_rows = num_rows;
         let start_position = get_start_position(
             num_rows,
             &app_state.app_scroll_positions.scroll_direction,
@@ -169,7 +210,12 @@ impl CpuGraphWidget for Painter {
             app_state.is_resized,
         );
 
-        let sliced_cpu_data = &cpu_data[start_position as usize..];
+        let (start_position, sliced_cpu_data) =
+            get_visible_rows(cpu_data, start_position, num_rows);
+        let show_frequency =
+            app_state.app_config_fields.show_cpu_frequency && !app_state.cpu_state.is_showing_tray;
+        let show_min_max =
+            app_state.app_config_fields.show_cpu_min_max && !app_state.cpu_state.is_showing_tray;
 
         let mut offset_scroll_index = (app_state
             .app_scroll_positions
@@ -187,12 +233,30 @@ impl CpuGraphWidget for Painter {
                     },
                 ]
             } else if app_state.app_config_fields.show_disabled_data
-                || app_state.cpu_state.core_show_vec[itx]
+                || app_state.cpu_state.core_show_vec[itx + start_position as usize]
             {
-                vec![
-                    Cow::Borrowed(&cpu.cpu_name),
-                    Cow::Borrowed(&cpu.legend_value),
-                ]
+                let cpu_name = if app_state.app_config_fields.vary_series_markers {
+                    // Matches the line's marker in the graph
+                    let marker = get_series_marker(
+                        app_state
+                            .app_config_fields
+                            .is_using_dot(WidgetPosition::Cpu),
+                        true,
+                        itx + start_position as usize,
+                    );
+                    Cow::Owned(format!("{} {}", get_marker_symbol(marker), cpu.cpu_name))
+                } else {
+                    Cow::Borrowed(cpu.cpu_name.as_str())
+                };
+                let mut row = vec![cpu_name, Cow::Borrowed(cpu.legend_value.as_str())];
+                if show_frequency {
+                    row.push(Cow::Borrowed(cpu.frequency_value.as_str()));
+                }
+                if show_min_max {
+                    row.push(Cow::Borrowed(cpu.min_value.as_str()));
+                    row.push(Cow::Borrowed(cpu.max_value.as_str()));
+                }
+                row
             } else {
                 Vec::new()
             };
@@ -201,47 +265,73 @@ impl CpuGraphWidget for Painter {
                 offset_scroll_index += 1;
                 None
             } else {
-                Some(Row::StyledData(
-                    cpu_string_row.into_iter(),
-                    match app_state.current_widget_selected {
-                        WidgetPosition::CpuLegend => {
-                            if itx == offset_scroll_index {
-                                self.colours.currently_selected_text_style
-                            } else if app_state.app_config_fields.show_average_cpu && itx == 0 {
-                                self.colours.avg_colour_style
-                            } else {
-                                self.colours.cpu_colour_styles[itx
-                                    + start_position as usize
-                                        % self.colours.cpu_colour_styles.len()]
-                            }
+                let style = match app_state.current_widget_selected {
+                    WidgetPosition::CpuLegend => {
+                        if itx == offset_scroll_index {
+                            self.colours.currently_selected_text_style
+                        } else if itx + start_position as usize == 0 {
+                            self.colours.avg_colour_style
+                        } else {
+                            self.colours.cpu_colour_styles[(itx + start_position as usize)
+                                % self.colours.cpu_colour_styles.len()]
                         }
-                        _ => {
-                            if app_state.app_config_fields.show_average_cpu && itx == 0 {
-                                self.colours.avg_colour_style
-                            } else {
-                                self.colours.cpu_colour_styles[itx
-                                    + start_position as usize
-                                        % self.colours.cpu_colour_styles.len()]
-                            }
+                    }
+                    _ => {
+                        if itx + start_position as usize == 0 {
+                            self.colours.avg_colour_style
+                        } else {
+                            self.colours.cpu_colour_styles[(itx + start_position as usize)
+                                % self.colours.cpu_colour_styles.len()]
                         }
-                    },
+                    }
+                };
+                Some(Row::StyledData(
+                    cpu_string_row.into_iter(),
+                    self.get_table_row_style(app_state, style, itx as u64 + start_position),
                 ))
             }
         });
 
+        // The columns shown depend on the enabled options
+        let mut legend_header = vec!["CPU", "Use%"];
+        let mut width_ratios = vec![0.5, 0.5];
+        if show_frequency {
+            legend_header.push("Freq");
+            width_ratios.push(0.6);
+        }
+        if show_min_max {
+            legend_header.push("Min");
+            legend_header.push("Max");
+            width_ratios.push(0.4);
+            width_ratios.push(0.4);
+        }
+        let width_ratio_total: f64 = width_ratios.iter().sum();
+        let width_ratios: Vec<f64> = width_ratios
+            .iter()
+            .map(|ratio| ratio / width_ratio_total)
+            .collect();
+        let legend_header_lens: Vec<usize> = legend_header
+            .iter()
+            .map(|entry| max(FORCE_MIN_THRESHOLD, entry.len()))
+            .collect();
+
         // Calculate widths
         let width = f64::from(draw_loc.width);
-        let width_ratios = vec![0.5, 0.5];
-
-        let variable_intrinsic_results = get_variable_intrinsic_widths(
-            width as u16,
-            &width_ratios,
-            if app_state.cpu_state.is_showing_tray {
-                &CPU_SELECT_LEGEND_HEADER_LENS
-            } else {
-                &CPU_LEGEND_HEADER_LENS
-            },
-        );
+        let variable_intrinsic_results = if app_state.cpu_state.is_showing_tray {
+            get_variable_intrinsic_widths(
+                width as u16,
+                app_state.app_config_fields.table_gap,
+                &[0.5, 0.5],
+                &CPU_SELECT_LEGEND_HEADER_LENS,
+            )
+        } else {
+            get_variable_intrinsic_widths(
+                width as u16,
+                app_state.app_config_fields.table_gap,
+                &width_ratios,
+                &legend_header_lens,
+            )
+        };
         let intrinsic_widths = &(variable_intrinsic_results.0)[0..variable_intrinsic_results.1];
 
         let title = if app_state.cpu_state.is_showing_tray {
@@ -253,6 +343,8 @@ impl CpuGraphWidget for Painter {
             let result_title = format!("{} Esc to close ", "─".repeat(repeat_num as usize));
 
             result_title
+        } else if let Some(cpu_temp_label) = &app_state.canvas_data.cpu_temp_label {
+            format!(" {} ", cpu_temp_label)
         } else {
             "".to_string()
         };
@@ -265,9 +357,9 @@ impl CpuGraphWidget for Painter {
         // Draw
         Table::new(
             if app_state.cpu_state.is_showing_tray {
-                CPU_SELECT_LEGEND_HEADER
+                &CPU_SELECT_LEGEND_HEADER[..]
             } else {
-                CPU_LEGEND_HEADER
+                &legend_header[..]
             }
             .iter(),
             cpu_rows,
@@ -280,6 +372,7 @@ impl CpuGraphWidget for Painter {
                 .border_style(title_and_border_style),
         )
         .header_style(self.colours.table_header_style)
+        .column_spacing(app_state.app_config_fields.table_gap)
         .widths(
             &(intrinsic_widths
                 .iter()
@@ -289,3 +382,53 @@ impl CpuGraphWidget for Painter {
         .render(f, draw_loc);
     }
 }
+
+/// The CPU widget's title, which also holds the load averages on Unix.
+fn get_cpu_title(app_state: &App, draw_loc: Rect) -> String {
+    let cpu_title = if cfg!(unix) {
+        let load_avg = app_state.data_collection.load_avg_harvest;
+        format!(
+            " CPU ── Load {:.2} {:.2} {:.2} ",
+            load_avg[0], load_avg[1], load_avg[2]
+        )
+    } else {
+        " CPU ".to_string()
+    };
+
+    // Annotate the highlighted core with the process that's most using it
+    let cpu_title = match app_state.current_widget_selected {
+        WidgetPosition::CpuLegend => {
+            if let Some((core_name, process_name, cpu_usage)) =
+                app_state.get_top_process_of_selected_core()
+            {
+                format!(
+                    "{}── {} top: {} {:.1}% ",
+                    cpu_title, core_name, process_name, cpu_usage
+                )
+            } else {
+                cpu_title
+            }
+        }
+        _ => cpu_title,
+    };
+
+    if app_state.is_expanded && !app_state.cpu_state.is_showing_tray {
+        const TITLE_BASE: &str = "── Esc to go back ";
+        let repeat_num = max(
+            0,
+            draw_loc.width as i32
+                - cpu_title.chars().count() as i32
+                - TITLE_BASE.chars().count() as i32
+                - 2,
+        );
+        let result_title = format!(
+            "{}─{}─ Esc to go back ",
+            cpu_title,
+            "─".repeat(repeat_num as usize)
+        );
+
+        result_title
+    } else {
+        cpu_title
+    }
+}
@@ -0,0 +1,140 @@
+use std::cmp::max;
+
+use crate::{
+    app::{App, WidgetPosition},
+    canvas::{
+        drawing_utils::{get_series_labels, get_series_marker},
+        Painter,
+    },
+    constants::*,
+};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    widgets::{Axis, Block, Borders, Chart, Dataset, Widget},
+};
+
+pub trait DiskIoGraphWidget {
+    fn draw_disk_io_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl DiskIoGraphWidget for Painter {
+    fn draw_disk_io_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let display_time_labels = [
+            format!("{}s", app_state.disk_io_state.current_display_time / 1000),
+            "0s".to_string(),
+        ];
+        let x_axis = if app_state.app_config_fields.hide_time
+            || (app_state.app_config_fields.autohide_time
+                && app_state.disk_io_state.autohide_timer.is_none())
+        {
+            Axis::default().bounds([0.0, app_state.disk_io_state.current_display_time as f64])
+        } else if let Some(time) = app_state.disk_io_state.autohide_timer {
+            if std::time::Instant::now().duration_since(time).as_millis()
+                < AUTOHIDE_TIMEOUT_MILLISECONDS as u128
+            {
+                Axis::default()
+                    .bounds([0.0, app_state.disk_io_state.current_display_time as f64])
+                    .style(self.colours.graph_style)
+                    .labels_style(self.colours.graph_style)
+                    .labels(&display_time_labels)
+            } else {
+                app_state.disk_io_state.autohide_timer = None;
+                Axis::default().bounds([0.0, app_state.disk_io_state.current_display_time as f64])
+            }
+        } else {
+            Axis::default()
+                .bounds([0.0, app_state.disk_io_state.current_display_time as f64])
+                .style(self.colours.graph_style)
+                .labels_style(self.colours.graph_style)
+                .labels(&display_time_labels)
+        };
+
+        let hidden_parts = app_state
+            .app_config_fields
+            .get_hidden_graph_parts(WidgetPosition::DiskIo);
+
+        // Points are log2 of bytes per second, so labels are at powers of 1024 bytes.  0 is offset.
+        let y_axis: Axis<'_, &str> = Axis::default()
+            .style(self.colours.graph_style)
+            .labels_style(self.colours.graph_style)
+            .bounds([-0.5, 30_f64]);
+        let y_axis = if hidden_parts.y_labels {
+            y_axis
+        } else {
+            y_axis.labels(&["0B", "1KiB", "1MiB", "1GiB"])
+        };
+
+        let io_labels = get_series_labels(
+            &app_state.canvas_data.io_labels,
+            app_state
+                .app_config_fields
+                .is_using_dot(WidgetPosition::DiskIo),
+            app_state.app_config_fields.vary_series_markers,
+        );
+        let io_canvas_vec: Vec<Dataset<'_>> = app_state
+            .canvas_data
+            .io_data
+            .iter()
+            .zip(&io_labels)
+            .enumerate()
+            .map(|(itx, (io_points, io_label))| {
+                Dataset::default()
+                    .name(if hidden_parts.legend { "" } else { io_label })
+                    .marker(get_series_marker(
+                        app_state
+                            .app_config_fields
+                            .is_using_dot(WidgetPosition::DiskIo),
+                        app_state.app_config_fields.vary_series_markers,
+                        itx,
+                    ))
+                    .style(
+                        self.colours.cpu_colour_styles[itx % self.colours.cpu_colour_styles.len()],
+                    )
+                    .data(io_points)
+            })
+            .collect();
+
+        let title = if app_state.is_expanded {
+            const TITLE_BASE: &str = " Disk I/O ── Esc to go back ";
+            let repeat_num = max(
+                0,
+                draw_loc.width as i32 - TITLE_BASE.chars().count() as i32 - 2,
+            );
+            let result_title = format!(
+                " Disk I/O ─{}─ Esc to go back ",
+                "─".repeat(repeat_num as usize)
+            );
+
+            result_title
+        } else {
+            " Disk I/O ".to_string()
+        };
+
+        Chart::default()
+            .block(
+                Block::default()
+                    .title(if hidden_parts.title { "" } else { &title })
+                    .title_style(if app_state.is_expanded {
+                        self.colours.highlighted_border_style
+                    } else {
+                        self.colours.widget_title_style
+                    })
+                    .borders(Borders::ALL)
+                    .border_style(match app_state.current_widget_selected {
+                        WidgetPosition::DiskIo => self.colours.highlighted_border_style,
+                        _ => self.colours.border_style,
+                    }),
+            )
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .datasets(&io_canvas_vec)
+            .render(f, draw_loc);
+    }
+}
@@ -1,4 +1,3 @@
-use lazy_static::lazy_static;
 use std::cmp::max;
 use tui::{
     backend::Backend,
@@ -8,22 +7,20 @@ use tui::{
 };
 
 use crate::{
-    app::{self, WidgetPosition},
+    app::{self, data_harvester::disks::DiskSorting, WidgetPosition},
     canvas::{
-        drawing_utils::{get_start_position, get_variable_intrinsic_widths},
+        drawing_utils::{
+            get_start_position, get_table_search_title, get_variable_intrinsic_widths,
+            get_visible_rows,
+        },
         Painter,
     },
     constants::*,
 };
 
-const DISK_HEADERS: [&str; 7] = ["Disk", "Mount", "Used", "Free", "Total", "R/s", "W/s"];
-
-lazy_static! {
-    static ref DISK_HEADERS_LENS: Vec<usize> = DISK_HEADERS
-        .iter()
-        .map(|entry| max(FORCE_MIN_THRESHOLD, entry.len()))
-        .collect::<Vec<_>>();
-}
+const DISK_HEADERS: [&str; 8] = [
+    "Disk", "Mount", "Used", "Free", "Total", "R/s", "W/s", "Util",
+];
 
 pub trait DiskTableWidget {
     fn draw_disk_table<B: Backend>(
@@ -35,8 +32,14 @@ impl DiskTableWidget for Painter {
     fn draw_disk_table<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
     ) {
-        let disk_data: &[Vec<String>] = &app_state.canvas_data.disk_data;
+        let disk_rows = app_state.get_filtered_disk_rows();
+        let all_disk_data = &app_state.canvas_data.disk_data;
+        let disk_data: Vec<&Vec<String>> = disk_rows
+            .iter()
+            .map(|row_index| &all_disk_data[*row_index])
+            .collect();
         let num_rows = max(0, i64::from(draw_loc.height) - 5) as u64;
+        app_state.app_scroll_positions.disk_scroll_state.num_rows = num_rows;
         let start_position = get_start_position(
             num_rows,
             &app_state.app_scroll_positions.scroll_direction,
@@ -51,58 +54,121 @@ impl DiskTableWidget for Painter {
             app_state.is_resized,
         );
 
-        let sliced_vec = &disk_data[start_position as usize..];
-        let mut disk_counter: i64 = 0;
+        let selected_position = app_state
+            .app_scroll_positions
+            .disk_scroll_state
+            .current_scroll_position;
+        let is_selected_widget = match app_state.current_widget_selected {
+            WidgetPosition::Disk => true,
+            _ => false,
+        };
 
-        let disk_rows = sliced_vec.iter().map(|disk| {
+        let disk_usage = &app_state.canvas_data.disk_usage;
+        let (start_position, visible_rows) = get_visible_rows(&disk_data, start_position, num_rows);
+        let disk_rows = visible_rows.iter().enumerate().map(|(itx, disk)| {
+            let position = start_position + itx as u64;
+            let used_percentage = disk_rows
+                .get(position as usize)
+                .and_then(|row_index| disk_usage.get(*row_index))
+                .cloned()
+                .unwrap_or(0.0);
+            let style = if is_selected_widget && position == selected_position {
+                self.colours.currently_selected_text_style
+            } else if used_percentage >= DISK_CRITICAL_PERCENTAGE {
+                self.colours.disk_critical_style
+            } else if used_percentage >= DISK_WARNING_PERCENTAGE {
+                self.colours.disk_warning_style
+            } else {
+                self.colours.text_style
+            };
             Row::StyledData(
                 disk.iter(),
-                match app_state.current_widget_selected {
-                    WidgetPosition::Disk => {
-                        if disk_counter as u64
-                            == app_state
-                                .app_scroll_positions
-                                .disk_scroll_state
-                                .current_scroll_position
-                                - start_position
-                        {
-                            disk_counter = -1;
-                            self.colours.currently_selected_text_style
-                        } else {
-                            if disk_counter >= 0 {
-                                disk_counter += 1;
-                            }
-                            self.colours.text_style
-                        }
-                    }
-                    _ => self.colours.text_style,
-                },
+                self.get_table_row_style(app_state, style, position),
             )
         });
 
         // Calculate widths
         // TODO: [PRETTY] Ellipsis on strings?
         let width = f64::from(draw_loc.width);
-        let width_ratios = [0.2, 0.15, 0.13, 0.13, 0.13, 0.13, 0.13];
-        let variable_intrinsic_results =
-            get_variable_intrinsic_widths(width as u16, &width_ratios, &DISK_HEADERS_LENS);
+        let mut disk_headers: Vec<String> = DISK_HEADERS
+            .iter()
+            .map(|header| header.to_string())
+            .collect();
+        let mut width_weights = vec![0.2, 0.15, 0.2, 0.13, 0.13, 0.13, 0.13, 0.1];
+        // Optional columns, in the same order as they're added in `convert_disk_row`
+        for (header, is_shown) in &[
+            ("Inodes", app_state.app_config_fields.show_disk_inodes),
+            ("Temp", app_state.app_config_fields.show_disk_temp),
+        ] {
+            if *is_shown {
+                disk_headers.push(header.to_string());
+                width_weights.push(0.13);
+            }
+        }
+
+        let sorted_header = match app_state.disk_sorting_type {
+            DiskSorting::Name => "Disk",
+            DiskSorting::Mount => "Mount",
+            DiskSorting::Used => "Used",
+            DiskSorting::Free => "Free",
+            DiskSorting::Total => "Total",
+            DiskSorting::Read => "R/s",
+            DiskSorting::Write => "W/s",
+            DiskSorting::Util => "Util",
+            DiskSorting::Inodes => "Inodes",
+            DiskSorting::Temp => "Temp",
+        };
+        if let Some(header) = disk_headers
+            .iter_mut()
+            .find(|header| header.as_str() == sorted_header)
+        {
+            header.push_str(if app_state.disk_sorting_reverse {
+                "▼"
+            } else {
+                "▲"
+            });
+        }
+        let total_weight: f64 = width_weights.iter().sum();
+        let width_ratios: Vec<f64> = width_weights
+            .iter()
+            .map(|weight| weight / total_weight)
+            .collect();
+        let mut disk_headers_lens: Vec<usize> = disk_headers
+            .iter()
+            .map(|entry| max(FORCE_MIN_THRESHOLD, entry.chars().count()))
+            .collect();
+        // Fit the usage bar, like "[########] 100%"
+        disk_headers_lens[2] = max(disk_headers_lens[2], DISK_USAGE_BAR_WIDTH + 7);
+        let variable_intrinsic_results = get_variable_intrinsic_widths(
+            width as u16,
+            app_state.app_config_fields.table_gap,
+            &width_ratios,
+            &disk_headers_lens,
+        );
         let intrinsic_widths = &variable_intrinsic_results.0[0..variable_intrinsic_results.1];
 
+        let title_name = get_table_search_title("Disk", &app_state.disk_search_state);
         let title = if app_state.is_expanded {
-            const TITLE_BASE: &str = " Disk ── Esc to go back ";
             let repeat_num = max(
                 0,
-                draw_loc.width as i32 - TITLE_BASE.chars().count() as i32 - 2,
+                draw_loc.width as i32
+                    - title_name.chars().count() as i32
+                    - " Esc to go back ".chars().count() as i32
+                    - 4,
             );
             let result_title = format!(
-                " Disk ─{}─ Esc to go back ",
+                "{}─{}─ Esc to go back ",
+                title_name,
                 "─".repeat(repeat_num as usize)
             );
             result_title
-        } else if app_state.app_config_fields.use_basic_mode {
+        } else if app_state.app_config_fields.use_basic_mode
+            && !app_state.disk_search_state.is_enabled
+            && app_state.disk_search_state.is_blank_search
+        {
             String::new()
         } else {
-            " Disk ".to_string()
+            title_name
         };
 
         let disk_block = if draw_border {
@@ -141,9 +207,10 @@ impl DiskTableWidget for Painter {
             .split(draw_loc);
 
         // Draw!
-        Table::new(DISK_HEADERS.iter(), disk_rows)
+        Table::new(disk_headers.iter(), disk_rows)
             .block(disk_block)
             .header_style(self.colours.table_header_style)
+            .column_spacing(app_state.app_config_fields.table_gap)
             .widths(
                 &(intrinsic_widths
                     .iter()
@@ -0,0 +1,176 @@
+use std::cmp::max;
+
+use crate::{
+    app::{App, WidgetPosition},
+    canvas::Painter,
+    constants::*,
+};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    widgets::{Axis, Block, Borders, Chart, Dataset, Marker, Widget},
+};
+
+pub trait GpuGraphWidget {
+    fn draw_gpu_graph<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect);
+}
+
+impl GpuGraphWidget for Painter {
+    fn draw_gpu_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let display_time_labels = [
+            format!("{}s", app_state.gpu_state.current_display_time / 1000),
+            "0s".to_string(),
+        ];
+        let x_axis = if app_state.app_config_fields.hide_time
+            || (app_state.app_config_fields.autohide_time
+                && app_state.gpu_state.autohide_timer.is_none())
+        {
+            Axis::default().bounds([0.0, app_state.gpu_state.current_display_time as f64])
+        } else if let Some(time) = app_state.gpu_state.autohide_timer {
+            if std::time::Instant::now().duration_since(time).as_millis()
+                < AUTOHIDE_TIMEOUT_MILLISECONDS as u128
+            {
+                Axis::default()
+                    .bounds([0.0, app_state.gpu_state.current_display_time as f64])
+                    .style(self.colours.graph_style)
+                    .labels_style(self.colours.graph_style)
+                    .labels(&display_time_labels)
+            } else {
+                app_state.gpu_state.autohide_timer = None;
+                Axis::default().bounds([0.0, app_state.gpu_state.current_display_time as f64])
+            }
+        } else {
+            Axis::default()
+                .bounds([0.0, app_state.gpu_state.current_display_time as f64])
+                .style(self.colours.graph_style)
+                .labels_style(self.colours.graph_style)
+                .labels(&display_time_labels)
+        };
+
+        let hidden_parts = app_state
+            .app_config_fields
+            .get_hidden_graph_parts(WidgetPosition::Gpu);
+
+        let y_axis = Axis::default()
+            .style(self.colours.graph_style)
+            .labels_style(self.colours.graph_style)
+            .bounds([-0.5, 100.5]);
+        let y_axis = if hidden_parts.y_labels {
+            y_axis
+        } else {
+            y_axis.labels(&["0%", "100%"])
+        };
+
+        // Each GPU's utilization is drawn solid, with its VRAM usage in the next colour over
+        let marker = if app_state
+            .app_config_fields
+            .is_using_dot(WidgetPosition::Gpu)
+        {
+            Marker::Dot
+        } else {
+            Marker::Braille
+        };
+        let num_colours = self.colours.cpu_colour_styles.len();
+
+        // Fall back to every GPU if the selected one has since disappeared
+        let num_gpus = app_state.canvas_data.gpu_labels.len();
+        let selected_gpu = app_state
+            .selected_gpu
+            .filter(|selected_gpu| *selected_gpu < num_gpus);
+
+        let mut gpu_canvas_vec: Vec<Dataset<'_>> = Vec::new();
+        for (itx, ((utilization_label, vram_label), (gpu_points, vram_points))) in app_state
+            .canvas_data
+            .gpu_labels
+            .iter()
+            .zip(
+                app_state
+                    .canvas_data
+                    .gpu_data
+                    .iter()
+                    .zip(&app_state.canvas_data.gpu_vram_data),
+            )
+            .enumerate()
+        {
+            if selected_gpu.is_some() && selected_gpu != Some(itx) {
+                continue;
+            }
+
+            gpu_canvas_vec.push(
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        utilization_label
+                    })
+                    .marker(marker)
+                    .style(self.colours.cpu_colour_styles[(itx * 2) % num_colours])
+                    .data(gpu_points),
+            );
+            if !vram_label.is_empty() {
+                gpu_canvas_vec.push(
+                    Dataset::default()
+                        .name(if hidden_parts.legend { "" } else { vram_label })
+                        .marker(marker)
+                        .style(self.colours.cpu_colour_styles[(itx * 2 + 1) % num_colours])
+                        .data(vram_points),
+                );
+            }
+        }
+
+        let title_name = match selected_gpu {
+            Some(itx) => format!(
+                " GPU ── {} ({}/{}) ",
+                app_state
+                    .data_collection
+                    .gpu_harvest
+                    .get(itx)
+                    .map(|gpu| gpu.name.as_str())
+                    .unwrap_or_default(),
+                itx + 1,
+                num_gpus
+            ),
+            None => " GPU ".to_string(),
+        };
+        let title = if app_state.is_expanded {
+            let repeat_num = max(
+                0,
+                draw_loc.width as i32
+                    - title_name.chars().count() as i32
+                    - "── Esc to go back ".chars().count() as i32
+                    - 2,
+            );
+            format!(
+                "{}─{}─ Esc to go back ",
+                title_name,
+                "─".repeat(repeat_num as usize)
+            )
+        } else {
+            title_name
+        };
+
+        Chart::default()
+            .block(
+                Block::default()
+                    .title(if hidden_parts.title { "" } else { &title })
+                    .title_style(if app_state.is_expanded {
+                        self.colours.highlighted_border_style
+                    } else {
+                        self.colours.widget_title_style
+                    })
+                    .borders(Borders::ALL)
+                    .border_style(match app_state.current_widget_selected {
+                        WidgetPosition::Gpu => self.colours.highlighted_border_style,
+                        _ => self.colours.border_style,
+                    }),
+            )
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .datasets(&gpu_canvas_vec)
+            .render(f, draw_loc);
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{app::App, canvas::Painter};
+
+use tui::{
+    backend::Backend,
+    layout::{Alignment, Rect},
+    terminal::Frame,
+    widgets::{Paragraph, Text, Widget},
+};
+
+pub trait HeaderBarWidget {
+    fn draw_header_bar<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect);
+}
+
+impl HeaderBarWidget for Painter {
+    fn draw_header_bar<B: Backend>(&self, f: &mut Frame<'_, B>, app_state: &App, draw_loc: Rect) {
+        let data_collection = &app_state.data_collection;
+        let mut summary = format!(" ── up {}", get_uptime_text(data_collection.uptime_harvest));
+        if cfg!(unix) {
+            let load_avg = data_collection.load_avg_harvest;
+            summary.push_str(&format!(
+                " ── load {:.2} {:.2} {:.2}",
+                load_avg[0], load_avg[1], load_avg[2]
+            ));
+        }
+        // The average is always the first entry
+        if let Some(avg_cpu) = data_collection.cpu_harvest.first() {
+            summary.push_str(&format!(" ── CPU {:.1}%", avg_cpu.cpu_usage));
+        }
+        let memory = &data_collection.memory_harvest;
+        if memory.mem_total_in_mb > 0 {
+            summary.push_str(&format!(
+                " ── Mem {:.1}%",
+                memory.mem_used_in_mb as f64 / memory.mem_total_in_mb as f64 * 100.0
+            ));
+        }
+
+        let header_text = [
+            Text::styled(
+                format!(" {}", app_state.get_remote_host().unwrap_or(&self.hostname)),
+                self.colours.widget_title_style,
+            ),
+            Text::styled(summary, self.colours.text_style),
+        ];
+        Paragraph::new(header_text.iter())
+            .alignment(Alignment::Left)
+            .render(f, draw_loc);
+
+        let time_text = [Text::styled(
+            format!("{} ", chrono::Local::now().format("%H:%M:%S")),
+            self.colours.text_style,
+        )];
+        Paragraph::new(time_text.iter())
+            .alignment(Alignment::Right)
+            .render(f, draw_loc);
+    }
+}
+
+/// Formats seconds as days, hours, and minutes, like "3d 4h 12m".
+fn get_uptime_text(uptime: u64) -> String {
+    let days = uptime / 86400;
+    let hours = uptime % 86400 / 3600;
+    let minutes = uptime % 3600 / 60;
+    if days > 0 {
+        format!("{}d {}h {}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {}m", hours, minutes)
+    } else {
+        format!("{}m", minutes)
+    }
+}
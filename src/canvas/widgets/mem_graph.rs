@@ -4,6 +4,7 @@ use crate::{
     app::{App, WidgetPosition},
     canvas::Painter,
     constants::*,
+    utils::gen_util::get_exact_byte_values,
 };
 
 use tui::{
@@ -23,8 +24,63 @@ impl MemGraphWidget for Painter {
     fn draw_memory_graph<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
     ) {
-        let mem_data: &[(f64, f64)] = &app_state.canvas_data.mem_data;
-        let swap_data: &[(f64, f64)] = &app_state.canvas_data.swap_data;
+        let mem_as_value = app_state.app_config_fields.mem_as_value;
+        let mem_total_in_mb = app_state.data_collection.memory_harvest.mem_total_in_mb as f64;
+        let swap_total_in_mb = app_state.data_collection.swap_harvest.mem_total_in_mb as f64;
+
+        // The points are percentages of their own totals, so scale them to share an axis in MiB
+        let scaled_mem_data: Vec<(f64, f64)>;
+        let scaled_swap_data: Vec<(f64, f64)>;
+        let (mem_data, swap_data): (&[(f64, f64)], &[(f64, f64)]) = if mem_as_value {
+            scaled_mem_data = scale_mem_points(&app_state.canvas_data.mem_data, mem_total_in_mb);
+            scaled_swap_data = scale_mem_points(&app_state.canvas_data.swap_data, swap_total_in_mb);
+            (&scaled_mem_data, &scaled_swap_data)
+        } else {
+            (
+                &app_state.canvas_data.mem_data,
+                &app_state.canvas_data.swap_data,
+            )
+        };
+        let gpu_harvest = &app_state.data_collection.gpu_harvest;
+        let scaled_vram_data: Vec<Vec<(f64, f64)>>;
+        let vram_data: &[Vec<(f64, f64)>] = if mem_as_value {
+            scaled_vram_data = app_state
+                .canvas_data
+                .vram_data
+                .iter()
+                .zip(gpu_harvest)
+                .map(|(points, gpu)| scale_mem_points(points, gpu.memory.mem_total_in_mb as f64))
+                .collect();
+            &scaled_vram_data
+        } else {
+            &app_state.canvas_data.vram_data
+        };
+        let commit_total_in_mb = app_state
+            .data_collection
+            .commit_harvest
+            .as_ref()
+            .map_or(0.0, |commit| commit.mem_total_in_mb as f64);
+        let scaled_commit_data: Vec<(f64, f64)>;
+        let commit_data: &[(f64, f64)] = if mem_as_value {
+            scaled_commit_data =
+                scale_mem_points(&app_state.canvas_data.commit_data, commit_total_in_mb);
+            &scaled_commit_data
+        } else {
+            &app_state.canvas_data.commit_data
+        };
+        let scaled_numa_data: Vec<Vec<(f64, f64)>>;
+        let numa_data: &[Vec<(f64, f64)>] = if mem_as_value {
+            scaled_numa_data = app_state
+                .canvas_data
+                .numa_data
+                .iter()
+                .zip(&app_state.data_collection.numa_harvest)
+                .map(|(points, node)| scale_mem_points(points, node.mem_total_in_mb as f64))
+                .collect();
+            &scaled_numa_data
+        } else {
+            &app_state.canvas_data.numa_data
+        };
 
         let display_time_labels = [
             format!("{}s", app_state.mem_state.current_display_time / 1000),
@@ -56,54 +112,158 @@ impl MemGraphWidget for Painter {
                 .labels(&display_time_labels)
         };
 
-        // Offset as the zero value isn't drawn otherwise...
-        let y_axis: Axis<'_, &str> = Axis::default()
-            .style(self.colours.graph_style)
-            .labels_style(self.colours.graph_style)
-            .bounds([-0.5, 100.5])
-            .labels(&["0%", "100%"]);
-
-        let mem_canvas_vec: Vec<Dataset<'_>> = vec![
-            Dataset::default()
-                .name(&app_state.canvas_data.mem_label)
-                .marker(if app_state.app_config_fields.use_dot {
-                    Marker::Dot
-                } else {
-                    Marker::Braille
-                })
-                .style(self.colours.ram_style)
-                .data(&mem_data),
-            Dataset::default()
-                .name(&app_state.canvas_data.swap_label)
-                .marker(if app_state.app_config_fields.use_dot {
-                    Marker::Dot
-                } else {
-                    Marker::Braille
-                })
-                .style(self.colours.swap_style)
-                .data(&swap_data),
+        // Keep a non-empty range before anything has been harvested
+        let y_max_in_mb = gpu_harvest
+            .iter()
+            .fold(
+                mem_total_in_mb
+                    .max(swap_total_in_mb)
+                    .max(commit_total_in_mb),
+                |y_max, gpu| y_max.max(gpu.memory.mem_total_in_mb as f64),
+            )
+            .max(1.0);
+        let value_labels = [
+            "0B".to_string(),
+            get_mem_tick_label(y_max_in_mb / 2.0),
+            get_mem_tick_label(y_max_in_mb),
         ];
+        let percent_labels = ["0%".to_string(), "100%".to_string()];
+
+        let hidden_parts = app_state
+            .app_config_fields
+            .get_hidden_graph_parts(WidgetPosition::Mem);
+
+        // Offset as the zero value isn't drawn otherwise...
+        let y_axis: Axis<'_, String> = if mem_as_value {
+            Axis::default()
+                .style(self.colours.graph_style)
+                .labels_style(self.colours.graph_style)
+                .bounds([-y_max_in_mb * 0.005, y_max_in_mb * 1.005])
+        } else {
+            Axis::default()
+                .style(self.colours.graph_style)
+                .labels_style(self.colours.graph_style)
+                .bounds([-0.5, 100.5])
+        };
+        let y_axis = if hidden_parts.y_labels {
+            y_axis
+        } else if mem_as_value {
+            y_axis.labels(&value_labels)
+        } else {
+            y_axis.labels(&percent_labels)
+        };
+
+        let marker = if app_state
+            .app_config_fields
+            .is_using_dot(WidgetPosition::Mem)
+        {
+            Marker::Dot
+        } else {
+            Marker::Braille
+        };
+        let mut mem_canvas_vec: Vec<Dataset<'_>> = Vec::new();
+        if app_state.mem_state.is_showing_ram {
+            mem_canvas_vec.push(
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        &app_state.canvas_data.mem_label
+                    })
+                    .marker(marker)
+                    .style(self.colours.ram_style)
+                    .data(&mem_data),
+            );
+        }
+        if let Some(commit_label) = &app_state.canvas_data.commit_label {
+            mem_canvas_vec.push(
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        commit_label
+                    })
+                    .marker(marker)
+                    .style(self.colours.commit_style)
+                    .data(&commit_data),
+            );
+        }
+        if app_state.mem_state.is_showing_swap {
+            mem_canvas_vec.push(
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        &app_state.canvas_data.swap_label
+                    })
+                    .marker(marker)
+                    .style(self.colours.swap_style)
+                    .data(&swap_data),
+            );
+        }
+
+        // GPUs sharing system memory have no VRAM of their own to show
+        for (vram_points, vram_label) in vram_data
+            .iter()
+            .zip(&app_state.canvas_data.vram_labels)
+            .filter(|(_, vram_label)| !vram_label.is_empty())
+        {
+            mem_canvas_vec.push(
+                Dataset::default()
+                    .name(if hidden_parts.legend { "" } else { vram_label })
+                    .marker(marker)
+                    .style(self.colours.vram_style)
+                    .data(vram_points),
+            );
+        }
+
+        for (itx, (numa_points, numa_label)) in numa_data
+            .iter()
+            .zip(&app_state.canvas_data.numa_labels)
+            .enumerate()
+        {
+            mem_canvas_vec.push(
+                Dataset::default()
+                    .name(if hidden_parts.legend { "" } else { numa_label })
+                    .marker(marker)
+                    .style(
+                        self.colours.cpu_colour_styles[itx % self.colours.cpu_colour_styles.len()],
+                    )
+                    .data(numa_points),
+            );
+        }
+
+        // The ARC, compressed pools like zram and zswap, and huge pages are noted next to the name
+        let mem_title = if app_state.canvas_data.mem_pool_label.is_empty() {
+            " Memory ".to_string()
+        } else {
+            format!(" Memory ── {} ", app_state.canvas_data.mem_pool_label)
+        };
 
         let title = if app_state.is_expanded {
-            const TITLE_BASE: &str = " Memory ── Esc to go back ";
+            const TITLE_BASE: &str = "── Esc to go back ";
             let repeat_num = max(
                 0,
-                draw_loc.width as i32 - TITLE_BASE.chars().count() as i32 - 2,
+                draw_loc.width as i32
+                    - mem_title.chars().count() as i32
+                    - TITLE_BASE.chars().count() as i32
+                    - 2,
             );
             let result_title = format!(
-                " Memory ─{}─ Esc to go back ",
+                "{}─{}─ Esc to go back ",
+                mem_title,
                 "─".repeat(repeat_num as usize)
             );
 
             result_title
         } else {
-            " Memory ".to_string()
+            mem_title
         };
 
         Chart::default()
             .block(
                 Block::default()
-                    .title(&title)
+                    .title(if hidden_parts.title { "" } else { &title })
                     .title_style(if app_state.is_expanded {
                         self.colours.highlighted_border_style
                     } else {
@@ -121,3 +281,18 @@ impl MemGraphWidget for Painter {
             .render(f, draw_loc);
     }
 }
+
+fn scale_mem_points(data: &[(f64, f64)], total_in_mb: f64) -> Vec<(f64, f64)> {
+    data.iter()
+        .map(|(time, percent)| (*time, percent * total_in_mb / 100.0))
+        .collect()
+}
+
+fn get_mem_tick_label(value_in_mb: f64) -> String {
+    let (value, unit) = get_exact_byte_values((value_in_mb * 1_048_576.0) as u64, false);
+    if value.fract() == 0.0 {
+        format!("{:.0}{}", value, unit)
+    } else {
+        format!("{:.1}{}", value, unit)
+    }
+}
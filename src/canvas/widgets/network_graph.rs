@@ -2,9 +2,13 @@ use lazy_static::lazy_static;
 use std::cmp::max;
 
 use crate::{
-    app::{App, WidgetPosition},
-    canvas::{drawing_utils::get_variable_intrinsic_widths, Painter},
+    app::{App, NetInterfaceSelection, NetworkScale, WidgetPosition},
+    canvas::{
+        drawing_utils::{get_series_labels, get_series_marker, get_variable_intrinsic_widths},
+        Painter,
+    },
     constants::*,
+    utils::gen_util::{get_exact_byte_values, get_simple_bit_values},
 };
 
 use tui::{
@@ -14,7 +18,14 @@ use tui::{
     widgets::{Axis, Block, Borders, Chart, Dataset, Marker, Row, Table, Widget},
 };
 
-const NETWORK_HEADERS: [&str; 4] = ["RX", "TX", "Total RX", "Total TX"];
+const NETWORK_HEADERS: [&str; 6] = [
+    "RX",
+    "TX",
+    "Total RX",
+    "Total TX",
+    "Session RX",
+    "Session TX",
+];
 
 lazy_static! {
     static ref NETWORK_HEADERS_LENS: Vec<usize> = NETWORK_HEADERS
@@ -37,8 +48,8 @@ impl NetworkGraphWidget for Painter {
     fn draw_network_graph<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
     ) {
-        let network_data_rx: &[(f64, f64)] = &app_state.canvas_data.network_data_rx;
-        let network_data_tx: &[(f64, f64)] = &app_state.canvas_data.network_data_tx;
+        let network_data_rx: &Vec<(f64, f64)> = &app_state.canvas_data.network_data_rx;
+        let network_data_tx: &Vec<(f64, f64)> = &app_state.canvas_data.network_data_tx;
 
         let display_time_labels = [
             format!("{}s", app_state.net_state.current_display_time / 1000),
@@ -70,33 +81,254 @@ impl NetworkGraphWidget for Painter {
                 .labels(&display_time_labels)
         };
 
-        // 0 is offset.
-        let y_axis: Axis<'_, &str> = Axis::default()
+        let marker = if app_state
+            .app_config_fields
+            .is_using_dot(WidgetPosition::Network)
+        {
+            Marker::Dot
+        } else {
+            Marker::Braille
+        };
+
+        // Fall back to the total if the selected interface has since disappeared
+        let selected_interface = match &app_state.net_state.interface_selection {
+            NetInterfaceSelection::Single(name) => app_state
+                .canvas_data
+                .network_interface_data
+                .iter()
+                .find(|interface| &interface.name == name),
+            _ => None,
+        };
+        let is_showing_all_interfaces = selected_interface.is_none()
+            && app_state.net_state.interface_selection == NetInterfaceSelection::All;
+
+        let use_bits = app_state.app_config_fields.network_use_bits;
+        let network_y_max = app_state.app_config_fields.network_y_max;
+        let (y_bounds, y_labels) = match (app_state.app_config_fields.network_scale, network_y_max)
+        {
+            (NetworkScale::Log, Some(y_max)) => {
+                // Labels are spread evenly, so only the ends can be labelled on a log scale
+                (
+                    [-0.5, y_max.log2()],
+                    vec![
+                        get_network_tick_label(0.0, use_bits),
+                        get_network_tick_label(y_max, use_bits),
+                    ],
+                )
+            }
+            (NetworkScale::Log, None) => {
+                // 0 is offset.  Points are log2 of bytes, so labels are at powers of 1024 bytes.
+                let labels = if use_bits {
+                    ["0b", "8Kb", "8Mb", "8Gb"]
+                } else {
+                    ["0B", "1KiB", "1MiB", "1GiB"]
+                };
+                (
+                    [-0.5, 30_f64],
+                    labels.iter().map(|label| label.to_string()).collect(),
+                )
+            }
+            (NetworkScale::Linear, _) => {
+                let shown_points: Vec<&Vec<(f64, f64)>> = if is_showing_all_interfaces {
+                    app_state
+                        .canvas_data
+                        .network_interface_data
+                        .iter()
+                        .flat_map(|interface| vec![&interface.rx, &interface.tx])
+                        .collect()
+                } else if let Some(interface) = selected_interface {
+                    vec![&interface.rx, &interface.tx]
+                } else {
+                    vec![network_data_rx, network_data_tx]
+                };
+                let y_max = network_y_max.unwrap_or_else(|| {
+                    get_linear_y_max(
+                        shown_points
+                            .iter()
+                            .flat_map(|points| points.iter())
+                            .filter(|(time, _)| *time >= 0.0)
+                            .fold(0.0, |max_value, (_, value)| f64::max(max_value, *value)),
+                        use_bits,
+                    )
+                });
+                (
+                    [-y_max * 0.005, y_max * 1.005],
+                    vec![
+                        get_network_tick_label(0.0, use_bits),
+                        get_network_tick_label(y_max / 2.0, use_bits),
+                        get_network_tick_label(y_max, use_bits),
+                    ],
+                )
+            }
+        };
+        let hidden_parts = app_state
+            .app_config_fields
+            .get_hidden_graph_parts(WidgetPosition::Network);
+
+        let y_axis: Axis<'_, String> = Axis::default()
             .style(self.colours.graph_style)
             .labels_style(self.colours.graph_style)
-            .bounds([-0.5, 30_f64])
-            .labels(&["0B", "1KiB", "1MiB", "1GiB"]);
+            .bounds(y_bounds);
+        let y_axis = if hidden_parts.y_labels {
+            y_axis
+        } else {
+            y_axis.labels(&y_labels)
+        };
+
+        let title_name = if let Some(interface) = selected_interface {
+            if let Some(wireless_display) = &interface.wireless_display {
+                format!(" Network ── {} ── {} ", interface.name, wireless_display)
+            } else {
+                format!(" Network ── {} ", interface.name)
+            }
+        } else if is_showing_all_interfaces {
+            " Network ── All interfaces ".to_string()
+        } else {
+            " Network ".to_string()
+        };
 
         let title = if app_state.is_expanded {
-            const TITLE_BASE: &str = " Network ── Esc to go back ";
+            let title_base = format!("{}── Esc to go back ", title_name);
             let repeat_num = max(
                 0,
-                draw_loc.width as i32 - TITLE_BASE.chars().count() as i32 - 2,
+                draw_loc.width as i32 - title_base.chars().count() as i32 - 2,
             );
             let result_title = format!(
-                " Network ─{}─ Esc to go back ",
+                "{}─{}─ Esc to go back ",
+                title_name,
                 "─".repeat(repeat_num as usize)
             );
 
             result_title
         } else {
-            " Network ".to_string()
+            title_name
+        };
+
+        let dataset_names: Vec<String> = if is_showing_all_interfaces {
+            let interface_names: Vec<String> = app_state
+                .canvas_data
+                .network_interface_data
+                .iter()
+                .flat_map(|interface| {
+                    vec![
+                        format!("{} RX: {:7}", interface.name, interface.rx_display),
+                        format!("{} TX: {:7}", interface.name, interface.tx_display),
+                    ]
+                })
+                .collect();
+            get_series_labels(
+                &interface_names,
+                app_state
+                    .app_config_fields
+                    .is_using_dot(WidgetPosition::Network),
+                app_state.app_config_fields.vary_series_markers,
+            )
+        } else if let Some(interface) = selected_interface {
+            vec![
+                format!("RX: {:7}", interface.rx_display),
+                format!("TX: {:7}", interface.tx_display),
+                format!("Total RX: {:7}", interface.total_rx_display),
+                format!("Total TX: {:7}", interface.total_tx_display),
+                format!("Session RX: {:7}", interface.session_rx_display),
+                format!("Session TX: {:7}", interface.session_tx_display),
+            ]
+        } else {
+            vec![
+                format!("RX: {:7}", app_state.canvas_data.rx_display),
+                format!("TX: {:7}", app_state.canvas_data.tx_display),
+                format!("Total RX: {:7}", app_state.canvas_data.total_rx_display),
+                format!("Total TX: {:7}", app_state.canvas_data.total_tx_display),
+                format!("Session RX: {:7}", app_state.canvas_data.session_rx_display),
+                format!("Session TX: {:7}", app_state.canvas_data.session_tx_display),
+            ]
+        };
+
+        let datasets: Vec<Dataset<'_>> = if is_showing_all_interfaces {
+            app_state
+                .canvas_data
+                .network_interface_data
+                .iter()
+                .flat_map(|interface| vec![&interface.rx, &interface.tx])
+                .zip(&dataset_names)
+                .enumerate()
+                .map(|(itx, (points, name))| {
+                    Dataset::default()
+                        .name(if hidden_parts.legend { "" } else { name })
+                        .marker(get_series_marker(
+                            app_state
+                                .app_config_fields
+                                .is_using_dot(WidgetPosition::Network),
+                            app_state.app_config_fields.vary_series_markers,
+                            itx,
+                        ))
+                        .style(
+                            self.colours.cpu_colour_styles
+                                [itx % self.colours.cpu_colour_styles.len()],
+                        )
+                        .data(points)
+                })
+                .collect()
+        } else {
+            let (network_data_rx, network_data_tx) = if let Some(interface) = selected_interface {
+                (&interface.rx, &interface.tx)
+            } else {
+                (network_data_rx, network_data_tx)
+            };
+
+            vec![
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        &dataset_names[0]
+                    })
+                    .marker(marker)
+                    .style(self.colours.rx_style)
+                    .data(network_data_rx),
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        &dataset_names[1]
+                    })
+                    .marker(marker)
+                    .style(self.colours.tx_style)
+                    .data(network_data_tx),
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        &dataset_names[2]
+                    })
+                    .style(self.colours.total_rx_style),
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        &dataset_names[3]
+                    })
+                    .style(self.colours.total_tx_style),
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        &dataset_names[4]
+                    })
+                    .style(self.colours.total_rx_style),
+                Dataset::default()
+                    .name(if hidden_parts.legend {
+                        ""
+                    } else {
+                        &dataset_names[5]
+                    })
+                    .style(self.colours.total_tx_style),
+            ]
         };
 
         Chart::default()
             .block(
                 Block::default()
-                    .title(&title)
+                    .title(if hidden_parts.title { "" } else { &title })
                     .title_style(if app_state.is_expanded {
                         self.colours.highlighted_border_style
                     } else {
@@ -110,67 +342,59 @@ impl NetworkGraphWidget for Painter {
             )
             .x_axis(x_axis)
             .y_axis(y_axis)
-            .datasets(&[
-                Dataset::default()
-                    .name(&format!("RX: {:7}", app_state.canvas_data.rx_display))
-                    .marker(if app_state.app_config_fields.use_dot {
-                        Marker::Dot
-                    } else {
-                        Marker::Braille
-                    })
-                    .style(self.colours.rx_style)
-                    .data(&network_data_rx),
-                Dataset::default()
-                    .name(&format!("TX: {:7}", app_state.canvas_data.tx_display))
-                    .marker(if app_state.app_config_fields.use_dot {
-                        Marker::Dot
-                    } else {
-                        Marker::Braille
-                    })
-                    .style(self.colours.tx_style)
-                    .data(&network_data_tx),
-                Dataset::default()
-                    .name(&format!(
-                        "Total RX: {:7}",
-                        app_state.canvas_data.total_rx_display
-                    ))
-                    .style(self.colours.total_rx_style),
-                Dataset::default()
-                    .name(&format!(
-                        "Total TX: {:7}",
-                        app_state.canvas_data.total_tx_display
-                    ))
-                    .style(self.colours.total_tx_style),
-            ])
+            .datasets(&datasets)
             .render(f, draw_loc);
     }
 
     fn draw_network_labels<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
     ) {
-        let rx_display = &app_state.canvas_data.rx_display;
-        let tx_display = &app_state.canvas_data.tx_display;
-        let total_rx_display = &app_state.canvas_data.total_rx_display;
-        let total_tx_display = &app_state.canvas_data.total_tx_display;
-
+        let selected_interface = match &app_state.net_state.interface_selection {
+            NetInterfaceSelection::Single(name) => app_state
+                .canvas_data
+                .network_interface_data
+                .iter()
+                .find(|interface| &interface.name == name),
+            _ => None,
+        };
         // Gross but I need it to work...
-        let total_network = vec![vec![
-            rx_display,
-            tx_display,
-            total_rx_display,
-            total_tx_display,
-        ]];
-        let mapped_network = total_network
-            .iter()
-            .map(|val| Row::StyledData(val.iter(), self.colours.text_style));
+        let total_network = if let Some(interface) = selected_interface {
+            vec![vec![
+                &interface.rx_display,
+                &interface.tx_display,
+                &interface.total_rx_display,
+                &interface.total_tx_display,
+                &interface.session_rx_display,
+                &interface.session_tx_display,
+            ]]
+        } else {
+            vec![vec![
+                &app_state.canvas_data.rx_display,
+                &app_state.canvas_data.tx_display,
+                &app_state.canvas_data.total_rx_display,
+                &app_state.canvas_data.total_tx_display,
+                &app_state.canvas_data.session_rx_display,
+                &app_state.canvas_data.session_tx_display,
+            ]]
+        };
+        let mapped_network = total_network.iter().enumerate().map(|(itx, val)| {
+            Row::StyledData(
+                val.iter(),
+                self.get_table_row_style(app_state, self.colours.text_style, itx as u64),
+            )
+        });
 
         // Calculate widths
-        let width_ratios: Vec<f64> = vec![0.25, 0.25, 0.25, 0.25];
+        let width_ratios: Vec<f64> = vec![1.0 / 6.0; 6];
         let lens: &[usize] = &NETWORK_HEADERS_LENS;
         let width = f64::from(draw_loc.width);
 
-        let variable_intrinsic_results =
-            get_variable_intrinsic_widths(width as u16, &width_ratios, lens);
+        let variable_intrinsic_results = get_variable_intrinsic_widths(
+            width as u16,
+            app_state.app_config_fields.table_gap,
+            &width_ratios,
+            lens,
+        );
         let intrinsic_widths = &(variable_intrinsic_results.0)[0..variable_intrinsic_results.1];
 
         // Draw
@@ -182,6 +406,7 @@ impl NetworkGraphWidget for Painter {
                 },
             ))
             .header_style(self.colours.table_header_style)
+            .column_spacing(app_state.app_config_fields.table_gap)
             .style(self.colours.text_style)
             .widths(
                 &(intrinsic_widths
@@ -192,3 +417,38 @@ impl NetworkGraphWidget for Painter {
             .render(f, draw_loc);
     }
 }
+
+/// Rounds the highest rate in view up to a value that divides into clean labels, which is a
+/// power of two in bytes, or 1, 2, or 5 times a power of ten in bits.
+fn get_linear_y_max(max_bytes_per_sec: f64, use_bits: bool) -> f64 {
+    if use_bits {
+        let max_bits_per_sec = max_bytes_per_sec * 8.0;
+        let mut y_max = 1000.0;
+        for multiplier in [2.0, 2.5, 2.0].iter().cycle() {
+            if y_max >= max_bits_per_sec {
+                break;
+            }
+            y_max *= multiplier;
+        }
+        y_max / 8.0
+    } else {
+        let mut y_max = 1024.0;
+        while y_max < max_bytes_per_sec {
+            y_max *= 2.0;
+        }
+        y_max
+    }
+}
+
+fn get_network_tick_label(bytes_per_sec: f64, use_bits: bool) -> String {
+    let (value, unit) = if use_bits {
+        get_simple_bit_values((bytes_per_sec * 8.0) as u64)
+    } else {
+        get_exact_byte_values(bytes_per_sec as u64, false)
+    };
+    if value.fract() == 0.0 {
+        format!("{:.0}{}", value, unit)
+    } else {
+        format!("{:.1}{}", value, unit)
+    }
+}
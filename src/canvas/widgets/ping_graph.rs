@@ -0,0 +1,167 @@
+use std::cmp::max;
+
+use crate::{
+    app::{App, WidgetPosition},
+    canvas::{
+        drawing_utils::{get_series_labels, get_series_marker},
+        Painter,
+    },
+    constants::*,
+};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    widgets::{Axis, Block, Borders, Chart, Dataset, Widget},
+};
+
+pub trait PingGraphWidget {
+    fn draw_ping_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl PingGraphWidget for Painter {
+    fn draw_ping_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let display_time_labels = [
+            format!("{}s", app_state.ping_state.current_display_time / 1000),
+            "0s".to_string(),
+        ];
+        let x_axis = if app_state.app_config_fields.hide_time
+            || (app_state.app_config_fields.autohide_time
+                && app_state.ping_state.autohide_timer.is_none())
+        {
+            Axis::default().bounds([0.0, app_state.ping_state.current_display_time as f64])
+        } else if let Some(time) = app_state.ping_state.autohide_timer {
+            if std::time::Instant::now().duration_since(time).as_millis()
+                < AUTOHIDE_TIMEOUT_MILLISECONDS as u128
+            {
+                Axis::default()
+                    .bounds([0.0, app_state.ping_state.current_display_time as f64])
+                    .style(self.colours.graph_style)
+                    .labels_style(self.colours.graph_style)
+                    .labels(&display_time_labels)
+            } else {
+                app_state.ping_state.autohide_timer = None;
+                Axis::default().bounds([0.0, app_state.ping_state.current_display_time as f64])
+            }
+        } else {
+            Axis::default()
+                .bounds([0.0, app_state.ping_state.current_display_time as f64])
+                .style(self.colours.graph_style)
+                .labels_style(self.colours.graph_style)
+                .labels(&display_time_labels)
+        };
+
+        let y_max = get_latency_y_max(
+            app_state
+                .canvas_data
+                .ping_data
+                .iter()
+                .flat_map(|ping_points| ping_points.iter())
+                .filter(|(time, _)| *time >= 0.0)
+                .fold(0.0, |max_latency, (_, latency)| {
+                    f64::max(max_latency, *latency)
+                }),
+        );
+        let y_labels = [
+            "0ms".to_string(),
+            format!("{}ms", y_max / 2.0),
+            format!("{}ms", y_max),
+        ];
+        let hidden_parts = app_state
+            .app_config_fields
+            .get_hidden_graph_parts(WidgetPosition::Ping);
+
+        let y_axis: Axis<'_, String> = Axis::default()
+            .style(self.colours.graph_style)
+            .labels_style(self.colours.graph_style)
+            .bounds([-y_max * 0.005, y_max * 1.005]);
+        let y_axis = if hidden_parts.y_labels {
+            y_axis
+        } else {
+            y_axis.labels(&y_labels)
+        };
+
+        let ping_labels = get_series_labels(
+            &app_state.canvas_data.ping_labels,
+            app_state
+                .app_config_fields
+                .is_using_dot(WidgetPosition::Ping),
+            app_state.app_config_fields.vary_series_markers,
+        );
+        let ping_canvas_vec: Vec<Dataset<'_>> = app_state
+            .canvas_data
+            .ping_data
+            .iter()
+            .zip(&ping_labels)
+            .enumerate()
+            .map(|(itx, (ping_points, ping_label))| {
+                Dataset::default()
+                    .name(if hidden_parts.legend { "" } else { ping_label })
+                    .marker(get_series_marker(
+                        app_state
+                            .app_config_fields
+                            .is_using_dot(WidgetPosition::Ping),
+                        app_state.app_config_fields.vary_series_markers,
+                        itx,
+                    ))
+                    .style(
+                        self.colours.cpu_colour_styles[itx % self.colours.cpu_colour_styles.len()],
+                    )
+                    .data(ping_points)
+            })
+            .collect();
+
+        let title = if app_state.is_expanded {
+            const TITLE_BASE: &str = " Ping ── Esc to go back ";
+            let repeat_num = max(
+                0,
+                draw_loc.width as i32 - TITLE_BASE.chars().count() as i32 - 2,
+            );
+            let result_title = format!(
+                " Ping ─{}─ Esc to go back ",
+                "─".repeat(repeat_num as usize)
+            );
+
+            result_title
+        } else {
+            " Ping ".to_string()
+        };
+
+        Chart::default()
+            .block(
+                Block::default()
+                    .title(if hidden_parts.title { "" } else { &title })
+                    .title_style(if app_state.is_expanded {
+                        self.colours.highlighted_border_style
+                    } else {
+                        self.colours.widget_title_style
+                    })
+                    .borders(Borders::ALL)
+                    .border_style(match app_state.current_widget_selected {
+                        WidgetPosition::Ping => self.colours.highlighted_border_style,
+                        _ => self.colours.border_style,
+                    }),
+            )
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .datasets(&ping_canvas_vec)
+            .render(f, draw_loc);
+    }
+}
+
+/// Rounds the highest latency in view up to 1, 2, or 5 times a power of ten, starting at 10ms.
+fn get_latency_y_max(max_latency_ms: f64) -> f64 {
+    let mut y_max = 10.0;
+    for multiplier in [2.0, 2.5, 2.0].iter().cycle() {
+        if y_max >= max_latency_ms {
+            break;
+        }
+        y_max *= multiplier;
+    }
+    y_max
+}
@@ -1,15 +1,21 @@
-use std::cmp::{max, min};
+use std::{
+    borrow::Cow,
+    cmp::{max, min},
+    rc::Rc,
+};
 
 use crate::{
     app::{self, App, WidgetPosition},
     canvas::{
         drawing_utils::{
             get_search_start_position, get_start_position, get_variable_intrinsic_widths,
+            get_visible_rows,
         },
         Painter,
     },
     constants::*,
     data_conversion::ConvertedProcessData,
+    utils::gen_util::get_exact_byte_values,
 };
 
 use tui::{
@@ -58,19 +64,13 @@ impl ProcessTableWidget for Painter {
     fn draw_processes_table<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
     ) {
-        let process_data: &[ConvertedProcessData] = &app_state.canvas_data.finalized_process_data;
-
-        // Admittedly this is kinda a hack... but we need to:
-        // * Scroll
-        // * Show/hide elements based on scroll position
-        //
-        // As such, we use a process_counter to know when we've
-        // hit the process we've currently scrolled to.
-        // We also need to move the list - we can
-        // do so by hiding some elements!
+        let process_data: &[Rc<ConvertedProcessData>] =
+            &app_state.canvas_data.finalized_process_data;
+
         let num_rows = max(0, i64::from(draw_loc.height) - 5) as u64;
+        app_state.app_scroll_positions.process_scroll_state.num_rows = num_rows;
 
-        let position = get_start_position(
+        let start_position = get_start_position(
             num_rows,
             &app_state.app_scroll_positions.scroll_direction,
             &mut app_state
@@ -83,53 +83,18 @@ impl ProcessTableWidget for Painter {
                 .current_scroll_position,
             app_state.is_resized,
         );
-
-        // Sanity check
-        let start_position = if position >= process_data.len() as u64 {
-            std::cmp::max(0, process_data.len() as i64 - 1) as u64
-        } else {
-            position
+        let selected_position = app_state
+            .app_scroll_positions
+            .process_scroll_state
+            .current_scroll_position;
+        let is_selected_widget = match app_state.current_widget_selected {
+            WidgetPosition::Process => true,
+            _ => false,
         };
 
-        let sliced_vec = &process_data[start_position as usize..];
-        let mut process_counter: i64 = 0;
-
-        // Draw!
-        let process_rows = sliced_vec.iter().map(|process| {
-            let stringified_process_vec: Vec<String> = vec![
-                if app_state.is_grouped() {
-                    process.group_pids.len().to_string()
-                } else {
-                    process.pid.to_string()
-                },
-                process.name.clone(),
-                format!("{:.1}%", process.cpu_usage),
-                format!("{:.1}%", process.mem_usage),
-            ];
-            Row::StyledData(
-                stringified_process_vec.into_iter(),
-                match app_state.current_widget_selected {
-                    WidgetPosition::Process => {
-                        if process_counter as u64
-                            == app_state
-                                .app_scroll_positions
-                                .process_scroll_state
-                                .current_scroll_position
-                                - start_position
-                        {
-                            process_counter = -1;
-                            self.colours.currently_selected_text_style
-                        } else {
-                            if process_counter >= 0 {
-                                process_counter += 1;
-                            }
-                            self.colours.text_style
-                        }
-                    }
-                    _ => self.colours.text_style,
-                },
-            )
-        });
+        // Only stringify what we can actually see
+        let (start_position, visible_processes) =
+            get_visible_rows(process_data, start_position, num_rows);
 
         use app::data_harvester::processes::ProcessSorting;
         let mut pid_or_name = if app_state.is_grouped() {
@@ -138,9 +103,15 @@ impl ProcessTableWidget for Painter {
             "PID(p)"
         }
         .to_string();
-        let mut name = "Name(n)".to_string();
+        let mut name = if app_state.is_grouped_by_user() {
+            "User(n)"
+        } else {
+            "Name(n)"
+        }
+        .to_string();
         let mut cpu = "CPU%(c)".to_string();
         let mut mem = "Mem%(m)".to_string();
+        let mut swap = "Swap(s)".to_string();
 
         let direction_val = if app_state.process_sorting_reverse {
             "▼".to_string()
@@ -153,9 +124,19 @@ impl ProcessTableWidget for Painter {
             ProcessSorting::MEM => mem += &direction_val,
             ProcessSorting::PID => pid_or_name += &direction_val,
             ProcessSorting::NAME => name += &direction_val,
+            ProcessSorting::SWAP => swap += &direction_val,
         };
 
-        let process_headers = [pid_or_name, name, cpu, mem];
+        let show_process_swap = app_state.app_config_fields.show_process_swap;
+        let show_process_affinity = app_state.app_config_fields.show_process_affinity;
+
+        let mut process_headers = vec![pid_or_name, name, cpu, mem];
+        if show_process_swap {
+            process_headers.push(swap);
+        }
+        if show_process_affinity {
+            process_headers.push("Affinity".to_string());
+        }
         let process_headers_lens: Vec<usize> = process_headers
             .iter()
             .map(|entry| entry.len())
@@ -163,31 +144,119 @@ impl ProcessTableWidget for Painter {
 
         // Calculate widths
         let width = f64::from(draw_loc.width);
-        let width_ratios = [0.2, 0.4, 0.2, 0.2];
-        let variable_intrinsic_results =
-            get_variable_intrinsic_widths(width as u16, &width_ratios, &process_headers_lens);
-        let intrinsic_widths = &(variable_intrinsic_results.0)[0..variable_intrinsic_results.1];
+        let width_ratios: Vec<f64> = if show_process_swap || show_process_affinity {
+            let mut width_ratios = vec![0.15, 0.35, 0.15, 0.15];
+            if show_process_swap {
+                width_ratios.push(0.2);
+            }
+            if show_process_affinity {
+                width_ratios.push(0.2);
+            }
+
+            // Keep the ratios summing to 1
+            let total: f64 = width_ratios.iter().sum();
+            width_ratios.iter().map(|ratio| ratio / total).collect()
+        } else {
+            vec![0.2, 0.4, 0.2, 0.2]
+        };
+
+        // Columns that don't fit are scrolled to with Left and Right, never past the point where
+        // the last column is shown
+        let num_columns = process_headers.len();
+        let max_column_offset = (0..num_columns)
+            .find(|&column_offset| {
+                get_column_widths(
+                    width as u16,
+                    app_state.app_config_fields.table_gap,
+                    &width_ratios,
+                    &process_headers_lens,
+                    column_offset,
+                )
+                .1 == num_columns - column_offset
+            })
+            .unwrap_or(num_columns - 1);
+        if app_state.process_column_offset > max_column_offset {
+            app_state.process_column_offset = max_column_offset;
+        }
+        let column_offset = app_state.process_column_offset;
+        let (column_widths, num_shown_columns) = get_column_widths(
+            width as u16,
+            app_state.app_config_fields.table_gap,
+            &width_ratios,
+            &process_headers_lens,
+            column_offset,
+        );
+        let intrinsic_widths = &column_widths[0..num_shown_columns];
+
+        let column_indicator = if max_column_offset > 0 {
+            format!(
+                "── Columns {}-{} of {} ",
+                column_offset + 1,
+                column_offset + num_shown_columns,
+                num_columns
+            )
+        } else {
+            String::default()
+        };
 
         let title = if draw_border {
             if app_state.is_expanded && !app_state.process_search_state.search_state.is_enabled {
                 const TITLE_BASE: &str = " Processes ── Esc to go back ";
                 let repeat_num = max(
                     0,
-                    draw_loc.width as i32 - TITLE_BASE.chars().count() as i32 - 2,
+                    draw_loc.width as i32
+                        - TITLE_BASE.chars().count() as i32
+                        - column_indicator.chars().count() as i32
+                        - 2,
                 );
                 let result_title = format!(
-                    " Processes ─{}─ Esc to go back ",
+                    " Processes {}─{}─ Esc to go back ",
+                    column_indicator,
                     "─".repeat(repeat_num as usize)
                 );
 
                 result_title
             } else {
-                " Processes ".to_string()
+                format!(" Processes {}", column_indicator)
             }
         } else {
             String::default()
         };
 
+        // Draw!
+        let process_rows = visible_processes.iter().enumerate().map(|(itx, process)| {
+            let mut stringified_process_vec: Vec<Cow<'_, str>> = vec![
+                if app_state.is_grouped() {
+                    process.group_pids.len().to_string().into()
+                } else {
+                    process.pid.to_string().into()
+                },
+                Cow::Borrowed(&process.name),
+                format!("{:.1}%", process.cpu_usage).into(),
+                format!("{:.1}%", process.mem_usage).into(),
+            ];
+            if app_state.app_config_fields.show_process_swap {
+                let (swap_value, swap_unit) = get_exact_byte_values(process.swap_usage, false);
+                stringified_process_vec.push(format!("{:.1}{}", swap_value, swap_unit).into());
+            }
+            if app_state.app_config_fields.show_process_affinity {
+                stringified_process_vec.push(Cow::Borrowed(&process.cpu_affinity));
+            }
+            let position = start_position + itx as u64;
+            Row::StyledData(
+                stringified_process_vec.into_iter().skip(column_offset),
+                self.get_table_row_style(
+                    app_state,
+                    if is_selected_widget && position == selected_position {
+                        self.colours.currently_selected_text_style
+                    } else {
+                        self.colours.text_style
+                    },
+                    position,
+                ),
+            )
+        });
+
         let process_block = if draw_border {
             Block::default()
                 .title(&title)
@@ -223,9 +292,10 @@ impl ProcessTableWidget for Painter {
             .direction(Direction::Horizontal)
             .split(draw_loc);
 
-        Table::new(process_headers.iter(), process_rows)
+        Table::new(process_headers.iter().skip(column_offset), process_rows)
             .block(process_block)
             .header_style(self.colours.table_header_style)
+            .column_spacing(app_state.app_config_fields.table_gap)
             .widths(
                 &(intrinsic_widths
                     .iter()
@@ -238,9 +308,13 @@ impl ProcessTableWidget for Painter {
     fn draw_search_field<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect, draw_border: bool,
     ) {
-        let pid_search_text = "Search by PID (Tab for Name): ";
-        let name_search_text = "Search by Name (Tab for PID): ";
-        let grouped_search_text = "Search by Name: ";
+        let pid_search_text = "Search by PID (Alt-p for Name): ";
+        let name_search_text = "Search by Name (Alt-p for PID): ";
+        let grouped_search_text = if app_state.is_grouped_by_user() {
+            "Search by User: "
+        } else {
+            "Search by Name: "
+        };
         let num_columns = draw_loc.width as usize;
 
         let chosen_text = if app_state.is_grouped() {
@@ -287,7 +361,7 @@ impl ProcessTableWidget for Painter {
                             None
                         } else {
                             let styled = if grapheme.0 == cursor_position {
-                                Text::styled(grapheme.1, self.colours.currently_selected_text_style)
+                                Text::styled(grapheme.1, self.colours.cursor_style)
                             } else {
                                 Text::styled(grapheme.1, self.colours.text_style)
                             };
@@ -297,10 +371,7 @@ impl ProcessTableWidget for Painter {
                     .collect::<Vec<_>>();
 
                 if cursor_position >= query.len() {
-                    res.push(Text::styled(
-                        " ",
-                        self.colours.currently_selected_text_style,
-                    ))
+                    res.push(Text::styled(" ", self.colours.cursor_style))
                 }
 
                 res
@@ -388,7 +459,7 @@ impl ProcessTableWidget for Painter {
             .search_state
             .is_invalid_search
         {
-            *INVALID_REGEX_STYLE
+            self.colours.invalid_query_style
         } else {
             match app_state.current_widget_selected {
                 WidgetPosition::ProcessSearch => self.colours.highlighted_border_style,
@@ -441,3 +512,22 @@ impl ProcessTableWidget for Painter {
             .render(f, margined_draw_loc[0]);
     }
 }
+
+/// Calculates the widths of the columns from `column_offset` onwards, sharing out the width by
+/// their ratios.  Also returns how many of them fit.
+fn get_column_widths(
+    width: u16, column_gap: u16, width_ratios: &[f64], header_lens: &[usize], column_offset: usize,
+) -> (Vec<u16>, usize) {
+    let total: f64 = width_ratios[column_offset..].iter().sum();
+    let shown_ratios: Vec<f64> = width_ratios[column_offset..]
+        .iter()
+        .map(|ratio| ratio / total)
+        .collect();
+
+    get_variable_intrinsic_widths(
+        width,
+        column_gap,
+        &shown_ratios,
+        &header_lens[column_offset..],
+    )
+}
@@ -0,0 +1,145 @@
+use lazy_static::lazy_static;
+use std::cmp::max;
+use tui::{
+    backend::Backend,
+    layout::{Constraint, Rect},
+    terminal::Frame,
+    widgets::{Block, Borders, Row, Table, Widget},
+};
+
+use crate::{
+    app::{self, WidgetPosition},
+    canvas::{
+        drawing_utils::{
+            get_start_position, get_table_search_title, get_variable_intrinsic_widths,
+            get_visible_rows,
+        },
+        Painter,
+    },
+    constants::*,
+};
+
+const SOCKET_HEADERS: [&str; 6] = ["Proto", "Local", "Remote", "State", "PID", "Name"];
+
+lazy_static! {
+    static ref SOCKET_HEADERS_LENS: Vec<usize> = SOCKET_HEADERS
+        .iter()
+        .map(|entry| max(FORCE_MIN_THRESHOLD, entry.len()))
+        .collect::<Vec<_>>();
+}
+
+pub trait SocketTableWidget {
+    fn draw_socket_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect,
+    );
+}
+
+impl SocketTableWidget for Painter {
+    fn draw_socket_table<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect,
+    ) {
+        let num_rows = max(0, i64::from(draw_loc.height) - 5) as u64;
+        app_state.app_scroll_positions.socket_scroll_state.num_rows = num_rows;
+        let start_position = get_start_position(
+            num_rows,
+            &app_state.app_scroll_positions.scroll_direction,
+            &mut app_state
+                .app_scroll_positions
+                .socket_scroll_state
+                .previous_scroll_position,
+            app_state
+                .app_scroll_positions
+                .socket_scroll_state
+                .current_scroll_position,
+            app_state.is_resized,
+        );
+
+        let selected_position = app_state
+            .app_scroll_positions
+            .socket_scroll_state
+            .current_scroll_position;
+        let is_selected_widget = match app_state.current_widget_selected {
+            WidgetPosition::Sockets => true,
+            _ => false,
+        };
+
+        let socket_rows = app_state.get_filtered_socket_rows();
+        let all_socket_data = &app_state.canvas_data.socket_data;
+        let socket_data: Vec<&Vec<String>> = socket_rows
+            .iter()
+            .map(|row_index| &all_socket_data[*row_index])
+            .collect();
+        let (start_position, visible_rows) =
+            get_visible_rows(&socket_data, start_position, num_rows);
+        let socket_rows = visible_rows.iter().enumerate().map(|(itx, socket)| {
+            let position = start_position + itx as u64;
+            Row::StyledData(
+                socket.iter(),
+                self.get_table_row_style(
+                    app_state,
+                    if is_selected_widget && position == selected_position {
+                        self.colours.currently_selected_text_style
+                    } else {
+                        self.colours.text_style
+                    },
+                    position,
+                ),
+            )
+        });
+
+        // Calculate widths
+        let width = f64::from(draw_loc.width);
+        let width_ratios = [0.08, 0.24, 0.24, 0.14, 0.1, 0.2];
+        let variable_intrinsic_results = get_variable_intrinsic_widths(
+            width as u16,
+            app_state.app_config_fields.table_gap,
+            &width_ratios,
+            &SOCKET_HEADERS_LENS,
+        );
+        let intrinsic_widths = &variable_intrinsic_results.0[0..variable_intrinsic_results.1];
+
+        let title_name = get_table_search_title("Sockets", &app_state.socket_search_state);
+        let title = if app_state.is_expanded {
+            let title_base = format!("{}── Esc to go back ", title_name);
+            let repeat_num = max(
+                0,
+                draw_loc.width as i32 - title_base.chars().count() as i32 - 2,
+            );
+            let result_title = format!(
+                "{}─{}─ Esc to go back ",
+                title_name,
+                "─".repeat(repeat_num as usize)
+            );
+            result_title
+        } else {
+            title_name
+        };
+
+        // Draw!
+        Table::new(SOCKET_HEADERS.iter(), socket_rows)
+            .block(
+                Block::default()
+                    .title(&title)
+                    .title_style(if app_state.is_expanded {
+                        self.colours.highlighted_border_style
+                    } else {
+                        self.colours.widget_title_style
+                    })
+                    .borders(Borders::ALL)
+                    .border_style(if is_selected_widget {
+                        self.colours.highlighted_border_style
+                    } else {
+                        self.colours.border_style
+                    }),
+            )
+            .header_style(self.colours.table_header_style)
+            .column_spacing(app_state.app_config_fields.table_gap)
+            .widths(
+                &(intrinsic_widths
+                    .iter()
+                    .map(|calculated_width| Constraint::Length(*calculated_width as u16))
+                    .collect::<Vec<_>>()),
+            )
+            .render(f, draw_loc);
+    }
+}
@@ -0,0 +1,161 @@
+use std::cmp::max;
+
+use crate::{
+    app::{App, WidgetPosition},
+    canvas::{
+        drawing_utils::{get_series_labels, get_series_marker},
+        Painter,
+    },
+    constants::*,
+    data_conversion::get_temperature_unit,
+};
+
+use tui::{
+    backend::Backend,
+    layout::Rect,
+    terminal::Frame,
+    widgets::{Axis, Block, Borders, Chart, Dataset, Widget},
+};
+
+pub trait TempGraphWidget {
+    fn draw_temp_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    );
+}
+
+impl TempGraphWidget for Painter {
+    fn draw_temp_graph<B: Backend>(
+        &self, f: &mut Frame<'_, B>, app_state: &mut App, draw_loc: Rect,
+    ) {
+        let display_time_labels = [
+            format!(
+                "{}s",
+                app_state.temp_graph_state.current_display_time / 1000
+            ),
+            "0s".to_string(),
+        ];
+        let x_axis = if app_state.app_config_fields.hide_time
+            || (app_state.app_config_fields.autohide_time
+                && app_state.temp_graph_state.autohide_timer.is_none())
+        {
+            Axis::default().bounds([0.0, app_state.temp_graph_state.current_display_time as f64])
+        } else if let Some(time) = app_state.temp_graph_state.autohide_timer {
+            if std::time::Instant::now().duration_since(time).as_millis()
+                < AUTOHIDE_TIMEOUT_MILLISECONDS as u128
+            {
+                Axis::default()
+                    .bounds([0.0, app_state.temp_graph_state.current_display_time as f64])
+                    .style(self.colours.graph_style)
+                    .labels_style(self.colours.graph_style)
+                    .labels(&display_time_labels)
+            } else {
+                app_state.temp_graph_state.autohide_timer = None;
+                Axis::default()
+                    .bounds([0.0, app_state.temp_graph_state.current_display_time as f64])
+            }
+        } else {
+            Axis::default()
+                .bounds([0.0, app_state.temp_graph_state.current_display_time as f64])
+                .style(self.colours.graph_style)
+                .labels_style(self.colours.graph_style)
+                .labels(&display_time_labels)
+        };
+
+        // Fit the hottest sensor in view, rounded up to the next 20 degrees
+        let max_temperature = app_state
+            .canvas_data
+            .temp_data
+            .iter()
+            .flat_map(|temp_points| temp_points.iter())
+            .filter(|(time, _)| *time >= 0.0)
+            .fold(0.0, |max_temperature, (_, temperature)| {
+                f64::max(max_temperature, *temperature)
+            });
+        let y_max = f64::max(20.0, (max_temperature / 20.0).ceil() * 20.0);
+        let unit = get_temperature_unit(&app_state.app_config_fields.temperature_type);
+        let y_labels = [
+            format!("0{}", unit),
+            format!("{}{}", y_max / 2.0, unit),
+            format!("{}{}", y_max, unit),
+        ];
+        let hidden_parts = app_state
+            .app_config_fields
+            .get_hidden_graph_parts(WidgetPosition::TempGraph);
+
+        let y_axis: Axis<'_, String> = Axis::default()
+            .style(self.colours.graph_style)
+            .labels_style(self.colours.graph_style)
+            .bounds([-y_max * 0.005, y_max * 1.005]);
+        let y_axis = if hidden_parts.y_labels {
+            y_axis
+        } else {
+            y_axis.labels(&y_labels)
+        };
+
+        let temp_labels = get_series_labels(
+            &app_state.canvas_data.temp_labels,
+            app_state
+                .app_config_fields
+                .is_using_dot(WidgetPosition::TempGraph),
+            app_state.app_config_fields.vary_series_markers,
+        );
+        let temp_canvas_vec: Vec<Dataset<'_>> = app_state
+            .canvas_data
+            .temp_data
+            .iter()
+            .zip(&temp_labels)
+            .enumerate()
+            .map(|(itx, (temp_points, temp_label))| {
+                Dataset::default()
+                    .name(if hidden_parts.legend { "" } else { temp_label })
+                    .marker(get_series_marker(
+                        app_state
+                            .app_config_fields
+                            .is_using_dot(WidgetPosition::TempGraph),
+                        app_state.app_config_fields.vary_series_markers,
+                        itx,
+                    ))
+                    .style(
+                        self.colours.cpu_colour_styles[itx % self.colours.cpu_colour_styles.len()],
+                    )
+                    .data(temp_points)
+            })
+            .collect();
+
+        let title = if app_state.is_expanded {
+            const TITLE_BASE: &str = " Temperature History ── Esc to go back ";
+            let repeat_num = max(
+                0,
+                draw_loc.width as i32 - TITLE_BASE.chars().count() as i32 - 2,
+            );
+            let result_title = format!(
+                " Temperature History ─{}─ Esc to go back ",
+                "─".repeat(repeat_num as usize)
+            );
+
+            result_title
+        } else {
+            " Temperature History ".to_string()
+        };
+
+        Chart::default()
+            .block(
+                Block::default()
+                    .title(if hidden_parts.title { "" } else { &title })
+                    .title_style(if app_state.is_expanded {
+                        self.colours.highlighted_border_style
+                    } else {
+                        self.colours.widget_title_style
+                    })
+                    .borders(Borders::ALL)
+                    .border_style(match app_state.current_widget_selected {
+                        WidgetPosition::TempGraph => self.colours.highlighted_border_style,
+                        _ => self.colours.border_style,
+                    }),
+            )
+            .x_axis(x_axis)
+            .y_axis(y_axis)
+            .datasets(&temp_canvas_vec)
+            .render(f, draw_loc);
+    }
+}
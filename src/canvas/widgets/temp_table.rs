@@ -11,10 +11,14 @@ use tui::{
 use crate::{
     app::{self, WidgetPosition},
     canvas::{
-        drawing_utils::{get_start_position, get_variable_intrinsic_widths},
+        drawing_utils::{
+            get_start_position, get_table_search_title, get_variable_intrinsic_widths,
+            get_visible_rows,
+        },
         Painter,
     },
     constants::*,
+    data_conversion::TempLevel,
 };
 
 const TEMP_HEADERS: [&str; 2] = ["Sensor", "Temp"];
@@ -35,9 +39,15 @@ impl TempTableWidget for Painter {
     fn draw_temp_table<B: Backend>(
         &self, f: &mut Frame<'_, B>, app_state: &mut app::App, draw_loc: Rect, draw_border: bool,
     ) {
-        let temp_sensor_data: &[Vec<String>] = &app_state.canvas_data.temp_sensor_data;
+        let temp_rows = app_state.get_filtered_temp_rows();
+        let all_temp_sensor_data = &app_state.canvas_data.temp_sensor_data;
+        let temp_sensor_data: Vec<&Vec<String>> = temp_rows
+            .iter()
+            .map(|row_index| &all_temp_sensor_data[*row_index])
+            .collect();
 
         let num_rows = max(0, i64::from(draw_loc.height) - 5) as u64;
+        app_state.app_scroll_positions.temp_scroll_state.num_rows = num_rows;
         let start_position = get_start_position(
             num_rows,
             &app_state.app_scroll_positions.scroll_direction,
@@ -52,58 +62,72 @@ impl TempTableWidget for Painter {
             app_state.is_resized,
         );
 
-        let sliced_vec = &temp_sensor_data[start_position as usize..];
-        let mut temp_row_counter: i64 = 0;
+        let selected_position = app_state
+            .app_scroll_positions
+            .temp_scroll_state
+            .current_scroll_position;
+        let is_selected_widget = match app_state.current_widget_selected {
+            WidgetPosition::Temp => true,
+            _ => false,
+        };
 
-        let temperature_rows = sliced_vec.iter().map(|temp_row| {
+        let temp_sensor_levels = &app_state.canvas_data.temp_sensor_levels;
+        let (start_position, visible_rows) =
+            get_visible_rows(&temp_sensor_data, start_position, num_rows);
+        let temperature_rows = visible_rows.iter().enumerate().map(|(itx, temp_row)| {
+            let position = start_position + itx as u64;
+            let style = if is_selected_widget && position == selected_position {
+                self.colours.currently_selected_text_style
+            } else {
+                match temp_rows
+                    .get(position as usize)
+                    .and_then(|row_index| temp_sensor_levels.get(*row_index))
+                {
+                    Some(TempLevel::Critical) => self.colours.temp_critical_style,
+                    Some(TempLevel::Warning) => self.colours.temp_warning_style,
+                    _ => self.colours.text_style,
+                }
+            };
             Row::StyledData(
                 temp_row.iter(),
-                match app_state.current_widget_selected {
-                    WidgetPosition::Temp => {
-                        if temp_row_counter as u64
-                            == app_state
-                                .app_scroll_positions
-                                .temp_scroll_state
-                                .current_scroll_position
-                                - start_position
-                        {
-                            temp_row_counter = -1;
-                            self.colours.currently_selected_text_style
-                        } else {
-                            if temp_row_counter >= 0 {
-                                temp_row_counter += 1;
-                            }
-                            self.colours.text_style
-                        }
-                    }
-                    _ => self.colours.text_style,
-                },
+                self.get_table_row_style(app_state, style, position),
             )
         });
 
         // Calculate widths
         let width = f64::from(draw_loc.width);
         let width_ratios = [0.5, 0.5];
-        let variable_intrinsic_results =
-            get_variable_intrinsic_widths(width as u16, &width_ratios, &TEMP_HEADERS_LENS);
+        let variable_intrinsic_results = get_variable_intrinsic_widths(
+            width as u16,
+            app_state.app_config_fields.table_gap,
+            &width_ratios,
+            &TEMP_HEADERS_LENS,
+        );
         let intrinsic_widths = &(variable_intrinsic_results.0)[0..variable_intrinsic_results.1];
 
+        let title_name = get_table_search_title("Temperatures", &app_state.temp_search_state);
         let title = if app_state.is_expanded {
-            const TITLE_BASE: &str = " Temperatures ── Esc to go back ";
             let repeat_num = max(
                 0,
-                draw_loc.width as i32 - TITLE_BASE.chars().count() as i32 - 2,
+                draw_loc.width as i32
+                    - title_name.chars().count() as i32
+                    - " Esc to go back ".chars().count() as i32
+                    - 4,
             );
             let result_title = format!(
-                " Temperatures ─{}─ Esc to go back ",
+                "{}─{}─ Esc to go back ",
+                title_name,
                 "─".repeat(repeat_num as usize)
             );
 
             result_title
-        } else if app_state.app_config_fields.use_basic_mode {
+        } else if app_state.app_config_fields.use_basic_mode
+            && !app_state.temp_search_state.is_enabled
+            && app_state.temp_search_state.is_blank_search
+        {
             String::new()
         } else {
-            " Temperatures ".to_string()
+            title_name
         };
 
         let temp_block = if draw_border {
@@ -145,6 +169,7 @@ impl TempTableWidget for Painter {
         Table::new(TEMP_HEADERS.iter(), temperature_rows)
             .block(temp_block)
             .header_style(self.colours.table_header_style)
+            .column_spacing(app_state.app_config_fields.table_gap)
             .widths(
                 &(intrinsic_widths
                     .iter()
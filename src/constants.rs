@@ -1,18 +1,35 @@
 use lazy_static::lazy_static;
 
 // How long to store data.
-pub const STALE_MAX_MILLISECONDS: u64 = 600 * 1000; // Keep 10 minutes of data.
+pub const STALE_MAX_MILLISECONDS: u64 = 600 * 1000; // Keep 10 minutes of data by default.
+pub const MAX_RETENTION_MILLISECONDS: u64 = 60 * 60 * 1000;
+// About how many snapshots of the tables are kept for time travel, spread through the retained
+// history.  Every update keeps one if there are fewer updates than this in that time.
+pub const MAX_TIME_TRAVEL_SNAPSHOTS: usize = 120;
 
 // How much data is SHOWN
 pub const DEFAULT_TIME_MILLISECONDS: u64 = 60 * 1000; // Defaults to 1 min.
 pub const STALE_MIN_MILLISECONDS: u64 = 30 * 1000; // Lowest is 30 seconds
 pub const TIME_CHANGE_MILLISECONDS: u64 = 15 * 1000; // How much to increment each time
 pub const AUTOHIDE_TIMEOUT_MILLISECONDS: u64 = 5000; // 5 seconds to autohide
+pub const TOAST_TIMEOUT_MILLISECONDS: u64 = 4000; // 4 seconds before a message is dismissed
+pub const MAX_TOASTS: usize = 3;
+pub const MAX_LOGGED_HARVEST_ERRORS: usize = 20;
 
 pub const TICK_RATE_IN_MILLISECONDS: u64 = 200;
+// How often the config file is checked for saved changes
+pub const CONFIG_CHECK_INTERVAL_IN_MILLISECONDS: u128 = 1000;
 // How fast the screen refreshes
 pub const DEFAULT_REFRESH_RATE_IN_MILLISECONDS: u64 = 1000;
+pub const MAX_SEARCH_HISTORY_LENGTH: usize = 50;
 pub const MAX_KEY_TIMEOUT_IN_MILLISECONDS: u64 = 1000;
+// How long a network mount's stat may take before it's considered unresponsive
+pub const DEFAULT_NETWORK_DISK_TIMEOUT_MILLISECONDS: u64 = 1000;
+pub const DEFAULT_PROMETHEUS_ADDRESS: &str = "0.0.0.0";
+pub const DEFAULT_TABLE_GAP: u16 = 1;
+pub const DEFAULT_METRICS_LOG_MAX_SIZE_MB: u64 = 50;
+pub const DEFAULT_REMOTE_COMMAND: &str = "btm";
+pub const DEFAULT_LINE_PROTOCOL_INTERVAL_SECONDS: u64 = 10;
 // Number of colours to generate for the CPU chart/table
 pub const NUM_COLOURS: i32 = 256;
 
@@ -20,6 +37,11 @@ pub const NUM_COLOURS: i32 = 256;
 // The minimum threshold when resizing tables
 pub const FORCE_MIN_THRESHOLD: usize = 5;
 
+// Disk usage bars
+pub const DISK_USAGE_BAR_WIDTH: usize = 8;
+pub const DISK_WARNING_PERCENTAGE: f64 = 75.0;
+pub const DISK_CRITICAL_PERCENTAGE: f64 = 90.0;
+
 // Side borders
 lazy_static! {
     pub static ref SIDE_BORDERS: tui::widgets::Borders =
@@ -28,62 +50,258 @@ lazy_static! {
         tui::style::Style::default().fg(tui::style::Color::Gray);
     pub static ref DEFAULT_HEADER_STYLE: tui::style::Style =
         tui::style::Style::default().fg(tui::style::Color::LightBlue);
-    pub static ref INVALID_REGEX_STYLE: tui::style::Style =
-        tui::style::Style::default().fg(tui::style::Color::Red);
 }
 
-// Help text
-pub const GENERAL_HELP_TEXT: [&str; 18] = [
-    "General Keybindings\n\n",
-    "q, Ctrl-c      Quit bottom\n",
-    "Esc            Close filters, dialog boxes, etc.\n",
-    "Ctrl-r         Reset all data\n",
-    "f              Freeze display\n",
-    "Ctrl-Arrow     Change your selected widget\n",
-    "Shift-Arrow    Change your selected widget\n",
-    "H/J/K/L        Change your selected widget up/down/left/right\n",
-    "Up, k          Move cursor up\n",
-    "Down, j        Move cursor down\n",
-    "?              Open the help screen\n",
-    "gg             Skip to the first entry of a list\n",
-    "G              Skip to the last entry of a list\n",
-    "Enter          Maximize the currently selected widget\n",
-    "/              Filter out graph lines (only CPU at the moment)\n",
-    "+              Zoom in (decrease time range)\n",
-    "-              Zoom out (increase time range)\n",
-    "=              Reset zoom\n",
-];
-
-pub const PROCESS_HELP_TEXT: [&str; 8] = [
-    "Process Keybindings\n\n",
-    "dd             Kill the highlighted process\n",
-    "c              Sort by CPU usage\n",
-    "m              Sort by memory usage\n",
-    "p              Sort by PID\n",
-    "n              Sort by process name\n",
-    "Tab            Group together processes with the same name\n",
-    "Ctrl-f, /      Open up the search widget\n",
+// Help text, grouped by widget.  Each line is its keys and what they do, with the names of
+// remappable actions in braces, which are shown as the keys they're bound to.
+pub type HelpSection = (&'static str, &'static [(&'static str, &'static str)]);
+
+pub const GENERAL_HELP_TEXT: [HelpSection; 8] = [
+    (
+        "General",
+        &[
+            ("{quit}, Ctrl-c", "Quit bottom"),
+            ("Esc", "Close filters, dialog boxes, etc."),
+            ("Ctrl-r", "Reset all data"),
+            (
+                "Ctrl-n",
+                "Switch to the next remote host, or back to this one",
+            ),
+            ("{freeze}", "Freeze display"),
+            (
+                "{time_travel}",
+                "Pause and step through the history, or go back to the live data",
+            ),
+            (
+                "Left/Right",
+                "Step back or forward through the history while paused",
+            ),
+            ("Ctrl-Arrow", "Change your selected widget"),
+            ("Shift-Arrow", "Change your selected widget"),
+            (
+                "{left_widget}/{down_widget}/{up_widget}/{right_widget}",
+                "Change your selected widget left/down/up/right",
+            ),
+            ("{left}/{right}", "Change your selected widget left/right"),
+            (
+                "Tab/Shift-Tab",
+                "Select the next/previous widget in layout order",
+            ),
+            ("1-9", "Select a widget by its place in layout order"),
+            ("Up, {up}", "Move cursor up"),
+            ("Down, {down}", "Move cursor down"),
+            ("Ctrl-u/Ctrl-d", "Move cursor up/down by half a page"),
+            ("PgUp/PgDn", "Move cursor up/down by a page"),
+            ("{help}", "Open the help screen"),
+            ("{errors}", "List recent data collection errors"),
+            ("{first}{first}, Home", "Skip to the first entry of a list"),
+            ("{last}, End", "Skip to the last entry of a list"),
+            ("Enter", "Maximize the currently selected widget"),
+            (
+                "{maximize}",
+                "Maximize or restore the selected widget, including disks",
+            ),
+            (
+                "{graph_parts}",
+                "Hide the selected graph's legend, then y-axis labels, then title",
+            ),
+            ("{zoom_in}", "Zoom in (decrease time range)"),
+            ("{zoom_out}", "Zoom out (increase time range)"),
+            ("{reset_zoom}", "Reset zoom"),
+            (
+                "{shrink_width}/{grow_width}",
+                "Shrink/grow the selected widget's width",
+            ),
+            (
+                "{shrink_height}/{grow_height}",
+                "Shrink/grow the selected widget's height",
+            ),
+            (
+                "{next_layout}",
+                "Switch to the next layout set in the config file",
+            ),
+            (
+                "{previous_layout}",
+                "Switch to the previous layout set in the config file",
+            ),
+        ],
+    ),
+    (
+        "CPU",
+        &[
+            ("{search}", "Filter out graph lines"),
+            (
+                "{toggle_line}",
+                "Toggle the highlighted graph line while filtering",
+            ),
+            ("{toggle_all}", "Toggle all graph lines while filtering"),
+            (
+                "{cycle_cpu_lines}",
+                "Cycle graph lines between all, average, cores, and core types",
+            ),
+            ("{toggle_bars}", "Switch to per-core usage bars and back"),
+        ],
+    ),
+    ("Memory", &[("{sort_swap}", "Show or hide the swap line")]),
+    (
+        "Network",
+        &[
+            (
+                "{cycle_interfaces}",
+                "Cycle the graph between the total and each interface",
+            ),
+            (
+                "{toggle_bars}",
+                "Switch throughput between bits and bytes per second",
+            ),
+        ],
+    ),
+    (
+        "GPU",
+        &[
+            (
+                "{cycle_interfaces}",
+                "Cycle the graph between every GPU and each GPU alone",
+            ),
+            (
+                "Up/Down, {up}/{down}",
+                "Graph the previous/next GPU while graphing one alone",
+            ),
+        ],
+    ),
+    (
+        "Temperatures",
+        &[(
+            "{search}",
+            "Search the sensors, with Enter to stop editing and Esc to clear",
+        )],
+    ),
+    (
+        "Disks",
+        &[
+            (
+                "{search}",
+                "Search the disks, with Enter to stop editing and Esc to clear",
+            ),
+            (
+                "Enter",
+                "Show the highlighted disk's details and S.M.A.R.T. data",
+            ),
+            ("{sort_swap}", "Cycle which column the disks are sorted by"),
+            ("{reverse_disk_sort}", "Reverse the sort order"),
+        ],
+    ),
+    (
+        "Sockets",
+        &[(
+            "{search}",
+            "Search the sockets, with Enter to stop editing and Esc to clear",
+        )],
+    ),
 ];
 
-pub const SEARCH_HELP_TEXT: [&str; 13] = [
-    "Search Keybindings\n\n",
-    "Tab            Toggle between searching for PID and name.\n",
-    "Esc            Close search widget\n",
-    "Ctrl-a         Skip to the start of search widget\n",
-    "Ctrl-e         Skip to the end of search widget\n",
-    "Ctrl-u         Clear the current search query\n",
-    "Backspace      Delete the character behind the cursor\n",
-    "Delete         Delete the character at the cursor\n",
-    "Left           Move cursor left\n",
-    "Right          Move cursor right\n",
-    "Alt-c/F1       Toggle whether to ignore case\n",
-    "Alt-w/F2       Toggle whether to match the whole word\n",
-    "Alt-r/F3       Toggle whether to use regex\n",
+pub const PROCESS_HELP_TEXT: [HelpSection; 1] = [(
+    "Processes",
+    &[
+        ("{kill}{kill}", "Kill the highlighted process"),
+        (
+            "{environment}",
+            "Show the highlighted process' environment variables",
+        ),
+        (
+            "{copy}",
+            "Copy the highlighted row, or just its PID, to the clipboard",
+        ),
+        ("{sort_cpu}", "Sort by CPU usage"),
+        ("{sort_mem}", "Sort by memory usage"),
+        ("{sort_pid}", "Sort by PID"),
+        ("{sort_name}", "Sort by process name"),
+        ("{sort_swap}", "Sort by swap usage, if shown"),
+        ("{grouping}", "Group together processes with the same name"),
+        ("{user_grouping}", "Toggle showing one row per user"),
+        ("{kernel_threads}", "Toggle hiding kernel threads"),
+        (
+            "Left/Right",
+            "Scroll through the columns if they don't all fit",
+        ),
+        ("Ctrl-f, {search}", "Open up the search widget"),
+    ],
+)];
+
+pub const SEARCH_HELP_TEXT: [HelpSection; 1] = [(
+    "Process Search",
+    &[
+        ("Alt-p/F4", "Toggle between searching for PID and name"),
+        ("Esc", "Close search widget"),
+        ("Ctrl-a", "Skip to the start of search widget"),
+        ("Ctrl-e", "Skip to the end of search widget"),
+        ("Ctrl-u", "Clear the current search query"),
+        ("Backspace", "Delete the character behind the cursor"),
+        ("Delete", "Delete the character at the cursor"),
+        ("Left", "Move cursor left"),
+        ("Right", "Move cursor right"),
+        ("Up/Down", "Go through previous search queries"),
+        ("Alt-c/F1", "Toggle whether to ignore case"),
+        ("Alt-w/F2", "Toggle whether to match the whole word"),
+        ("Alt-r/F3", "Toggle whether to use regex"),
+    ],
+)];
+
+// Remappable actions and their default keys, by their names in the keybinds config section
+pub const DEFAULT_KEYBINDS: [(&str, char); 44] = [
+    ("quit", 'q'),
+    ("search", '/'),
+    ("kill", 'd'),
+    ("first", 'g'),
+    ("last", 'G'),
+    ("up", 'k'),
+    ("down", 'j'),
+    ("left_widget", 'H'),
+    ("right_widget", 'L'),
+    ("up_widget", 'K'),
+    ("down_widget", 'J'),
+    ("left", 'h'),
+    ("right", 'l'),
+    ("environment", 'e'),
+    ("copy", 'y'),
+    ("freeze", 'f'),
+    ("time_travel", 't'),
+    ("errors", '!'),
+    ("graph_parts", 'v'),
+    ("sort_cpu", 'c'),
+    ("sort_mem", 'm'),
+    ("sort_pid", 'p'),
+    ("sort_name", 'n'),
+    ("sort_swap", 's'),
+    ("reverse_disk_sort", 'S'),
+    ("cycle_interfaces", 'i'),
+    ("user_grouping", 'u'),
+    ("grouping", 'N'),
+    ("toggle_all", 'a'),
+    ("cycle_cpu_lines", 'A'),
+    ("toggle_bars", 'b'),
+    ("kernel_threads", 'T'),
+    ("help", '?'),
+    ("toggle_line", ' '),
+    ("zoom_in", '+'),
+    ("zoom_out", '-'),
+    ("reset_zoom", '='),
+    ("shrink_width", '<'),
+    ("grow_width", '>'),
+    ("shrink_height", '['),
+    ("grow_height", ']'),
+    ("maximize", 'z'),
+    ("next_layout", 'w'),
+    ("previous_layout", 'W'),
 ];
 
 // Config and flags
 pub const DEFAULT_UNIX_CONFIG_FILE_PATH: &str = ".config/bottom/bottom.toml";
 pub const DEFAULT_WINDOWS_CONFIG_FILE_PATH: &str = "bottom/bottom.toml";
+pub const DEFAULT_UNIX_SEARCH_HISTORY_FILE_PATH: &str = ".config/bottom/search_history";
+pub const DEFAULT_WINDOWS_SEARCH_HISTORY_FILE_PATH: &str = "bottom/search_history";
+pub const DEFAULT_UNIX_STATE_FILE_PATH: &str = ".config/bottom/state.toml";
+pub const DEFAULT_WINDOWS_STATE_FILE_PATH: &str = "bottom/state.toml";
 
 // Default config file
 pub const DEFAULT_CONFIG_CONTENT: &str = r##"
@@ -99,9 +317,17 @@ pub const DEFAULT_CONFIG_CONTENT: &str = r##"
 # Whether to display an average cpu entry.
 #avg_cpu = true
 
+# Which lines to draw in the CPU graph; one of "all", "average", "cores", or "types".  The
+# latter shows the average of each core type on hybrid CPUs.  Overrides avg_cpu.
+#cpu_lines = "all"
+
 # Whether to use dot markers rather than braille.
 #dot_marker = false
 
+# Whether lines in the same graph alternate between braille and dot markers, so they can be told
+# apart by more than colour.
+#series_markers = false
+
 # The update rate of the application.
 #rate = 1000
 
@@ -111,6 +337,136 @@ pub const DEFAULT_CONFIG_CONTENT: &str = r##"
 # Whether to set CPU% on a process to be based on the total CPU or just current usage.
 #current_usage = false
 
+# Whether to show each core's frequency in the CPU legend.
+#cpu_frequency = false
+
+# Whether to show the CPU package temperature above the CPU legend.
+#cpu_temp = false
+
+# Whether to show each core's min and max usage over the displayed time window in the CPU legend.
+#cpu_min_max = false
+
+# Whether to show a widget graphing pressure stall information next to the memory widget.  Only works within Linux.
+#psi = false
+
+# Whether to show a widget listing open TCP and UDP sockets below the process widget.  Only works within Linux.
+#sockets = false
+
+# Whether to show a widget graphing the total read and write rate of every disk below the disk widget.
+#disk_io_graph = false
+
+# Whether to show a widget graphing each sensor's temperature next to the temperature widget.
+#temp_graph = false
+
+# Whether to show a widget graphing the utilization and VRAM usage of each GPU next to the CPU widget.  Requires building with the gpu feature.
+#gpu = false
+
+# Whether to show a widget listing the charge, power draw, and state of each battery next to the disk widget.
+#battery = false
+
+# Whether to show the temperature of NVMe drives, and SATA drives using the drivetemp module, in the disk widget.  Only works within Linux.
+#disk_temp = false
+
+# Whether to show the percentage of each disk's inodes that are used in the disk widget.
+#disk_inodes = false
+
+# Whether to hide NFS, CIFS, and SSHFS mounts from the disk widget.
+#hide_network_disks = false
+
+# Whether to take widgets out of the layout, giving their space to the others.  Not used in basic mode.
+#hide_cpu = false
+#hide_mem = false
+#hide_net = false
+#hide_temps = false
+#hide_disks = false
+#hide_procs = false
+
+# How long in milliseconds a network mount may take to respond before it's hidden from the disk widget until it does.
+#network_disk_timeout = 1000
+
+# Hosts to graph the latency and packet loss of in a widget next to the network widget.
+#ping_hosts = ["1.1.1.1", "example.com"]
+
+# Whether to show memory and swap usage as absolute values rather than percentages.
+#mem_as_value = false
+
+# Whether to count the ZFS ARC as free rather than used memory.  Only works within Linux.
+#subtract_arc = false
+
+# Whether to show network throughput in bits rather than bytes per second.
+#network_use_bits = false
+
+# The scale of the network graph, either "log" or "linear".  A linear scale fits the highest rate in view.
+#network_scale = "log"
+
+# A fixed maximum for the network graph's y-axis, like "1Gb" or "100MiB", rather than fitting the rates in view.
+#network_y_max = "1Gb"
+
+# Whether to hide kernel threads in the process widget.  Only works within Linux.
+#hide_kernel_threads = false
+
+# Whether to show a swap usage column in the process widget.  Only works within Linux.
+#process_swap = false
+
+# Whether to show a column with the CPUs each process may run on.  Only works within Linux.
+#process_affinity = false
+
+# Whether y copies only the PID of the highlighted process, rather than the whole row.
+#copy_pid = false
+
+# Whether to show a one-line summary above the widgets, with the hostname, uptime, load average,
+# CPU and memory usage, and the time.
+#header = false
+
+# Whether every other row of a table has a background, to make rows easier to follow.  The colour
+# is set by table_stripe_color.
+#table_stripes = false
+
+# How many spaces are between the columns of a table.
+#table_gap = 1
+
+# A file to append a record of the metrics to every update, as CSV if it ends in .csv and as lines
+# of JSON otherwise.  Once it reaches log_metrics_max_size megabytes, it's moved to <file>.1 and
+# a new one is started.
+#log_metrics = "/tmp/bottom_metrics.csv"
+#log_metrics_max_size = 50
+
+# Serves the metrics for Prometheus to scrape at /metrics on this port.  It's served on every
+# interface, so other machines can reach it, unless prometheus_address is set.
+#prometheus_port = 9100
+#prometheus_address = "127.0.0.1"
+
+# Serves the metrics as JSON on this port of localhost, at / for everything or at /cpu, /memory,
+# /network, /disks, /temperatures, /processes, or /errors for a part.
+#api_port = 3030
+
+# Sends the metrics in InfluxDB's line protocol every line_protocol_interval seconds, to an HTTP
+# write endpoint or a UDP or TCP listener.  line_protocol_token is sent as an InfluxDB 2 API token.
+#line_protocol = "http://localhost:8086/api/v2/write?org=home&bucket=bottom"
+#line_protocol_interval = 10
+#line_protocol_token = "<token>"
+
+# Hosts whose metrics can be shown rather than this machine's, switched between with Ctrl-n.
+# bottom is run on them over SSH, which has to be able to log in without a password, and
+# remote_command is what runs it.
+#remote_hosts = ["web1", "admin@db.example.com"]
+#remote_command = "btm"
+
+# What processes are sorted by on launch, one of "cpu", "mem", "swap", "pid", or "name".
+# Defaults to CPU usage.
+#default_sort = "cpu"
+
+# The keys used (in order) to break ties when sorting processes.  Each is one of
+# "cpu", "mem", "swap", "pid", or "name".  Defaults to name, then PID.
+#secondary_sort = ["name", "pid"]
+
+# Whether grouping processes by name ignores case.  Defaults to true on Windows.
+#group_ignore_case = false
+
+# What grouped processes are bucketed by; either the executable's "basename" or the
+# full "command" it was launched with.  Defaults to basename.
+#group_by = "basename"
+
 # Whether to group processes with the same name together by default.
 #group_processes = false
 
@@ -123,9 +479,28 @@ pub const DEFAULT_CONFIG_CONTENT: &str = r##"
 # Whether to make process searching use regex by default.
 #regex = true
 
+# Whether to save process search history between sessions.
+#persist_search_history = false
+
+# Whether to save the process sort, search, and grouping, and the selected widget, between
+# sessions.
+#persist_state = false
+
+# The colour scheme, one of "default", "gruvbox", "nord", "light", "colorblind", or
+# "colorblind_light".  Colours set under [colors] are applied on top of it.
+#theme = "default"
+
+# Whether to only draw ASCII characters and the 8 standard colours, for terminals that can't show
+# more, one of "auto", "always", or "never".  Auto does so on terminals like the Linux console,
+# or without a UTF-8 locale.
+#ascii = "auto"
+
 # Whether to show CPU entries in the legend when they are hidden.
 #show_disabled_data = true
 
+# Whether to hide graphs and use a more basic look.
+#basic = false
+
 # Defaults to Celsius.  Temperature is one of:
 #temperature_type = "k"
 #temperature_type = "f"
@@ -142,12 +517,71 @@ pub const DEFAULT_CONFIG_CONTENT: &str = r##"
 #default_widget = "network_default"
 #default_widget = "process_default"
 
+# Whether to start with the default widget maximized.
+#expanded = false
+
 # The default time interval (in milliseconds).
 #default_time_value = 60000
 
+# The default time interval of specific graphs (in milliseconds).  Defaults to default_time_value.
+#cpu_default_time_value = 600000
+#mem_default_time_value = 60000
+#net_default_time_value = 30000
+
+# How much data to keep (in milliseconds).  This is also the furthest graphs can be zoomed out to.
+#retention = 600000
+
 # The time delta on each zoom in/out action (in milliseconds).
 #time_delta = 15000
 
+# Whether to completely hide the time scale of graphs.
+#hide_time = false
+
+# Whether to hide the time scale of graphs shortly after zooming in or out.
+#autohide_time = false
+
+# Regexes matched against network interface names, to choose which are included in the
+# network widget's totals and per-interface lines.  Useful to avoid double-counting the
+# traffic of virtual interfaces.
+[network_filter]
+
+# If any are set, only interfaces matching one of these are included.
+#allow = ["^eth", "^wlan"]
+
+# Interfaces matching any of these are excluded.
+#deny = ["^lo$", "^docker", "^veth"]
+
+# Regexes matched against each disk's name and mount point, to pick which disks are shown
+# in the disk widget.
+[disk_filter]
+
+# If any are set, only disks with a name or mount point matching one of these are included.
+#allow = ["^/dev/sd", "^/dev/nvme"]
+
+# Disks with a name or mount point matching any of these are excluded.
+#deny = ["^/snap/", "^tmpfs$", "^overlay$"]
+
+# Regexes matched against each sensor's name, to pick which temperatures and fans are shown
+# in the temperature widget, along with names to show in place of the original ones.
+[temp_filter]
+
+# If any are set, only sensors matching one of these are included.
+#allow = ["^k10temp", "^nvme"]
+
+# Sensors matching any of these are excluded.
+#deny = ["^acpitz", "^iwlwifi"]
+
+# Maps original sensor names to new ones.
+#[temp_filter.rename]
+#"k10temp Tctl" = "CPU"
+#"nvme Composite" = "SSD"
+
+# Temperatures in celsius past which a sensor is coloured, keyed by its name as shown in the
+# temperature widget.  Otherwise, the sensor's own trip points are used if it has any.
+[temp_thresholds]
+#"CPU" = { warning = 80, critical = 95 }
+#"SSD" = { warning = 60, critical = 70 }
+
 # These are all the components that support custom theming.  Currently, it only
 # supports taking in a string representing a hex colour.  Note that colour support
 # will, at the end of the day, depend on terminal support - for example, the
@@ -159,6 +593,9 @@ pub const DEFAULT_CONFIG_CONTENT: &str = r##"
 # Represents the colour of table headers (processes, CPU, disks, temperature).
 #table_header_color="#458588"
 
+# Represents the background of every other table row, if table_stripes is set.
+#table_stripe_color="#3c3836"
+
 # Represents the colour of the label each widget has.
 #widget_title_color="#cc241d"
 
@@ -174,12 +611,34 @@ pub const DEFAULT_CONFIG_CONTENT: &str = r##"
 # Represents the colour SWAP will use in the memory legend and graph.
 #swap_color="#fabd2f"
 
+# Represents the colour VRAM will use in the memory legend and graph.
+#vram_color="#8ec07c"
+
+# Represents the colour the commit charge will use in the memory legend and graph.  Only used within Windows.
+#commit_color="#d65d0e"
+
 # Represents the colour rx will use in the network legend and graph.
 #rx_color="#458588"
 
 # Represents the colour tx will use in the network legend and graph.
 #tx_color="#689d6a"
 
+# Represents the colour of the total rx and tx in the network legend.
+#rx_total_color="#83a598"
+#tx_total_color="#8ec07c"
+
+# Represents the colour of disks that are at least 75% full.
+#disk_warning_color="#fabd2f"
+
+# Represents the colour of disks that are at least 90% full.
+#disk_critical_color="#fb4934"
+
+# Represents the colour of sensors past their warning threshold.
+#temp_warning_color="#fabd2f"
+
+# Represents the colour of sensors past their critical threshold.
+#temp_critical_color="#fb4934"
+
 # Represents the colour of the border of unselected widgets.
 #border_color="#ebdbb2"
 
@@ -200,4 +659,73 @@ pub const DEFAULT_CONFIG_CONTENT: &str = r##"
 
 # Represents the cursor's colour.
 #cursor_color="#458588"
+
+# Represents the colour of the search widget's border when the query is invalid.
+#invalid_search_color="#fb4934"
+
+# Actions can be bound to other keys under [keybinds], by action name.  Each key is a single
+# character, and may only be bound to one action.  For example:
+#[keybinds]
+#kill = "x"
+#sort_mem = "M"
+
+# Graph widgets can each use their own marker, "braille" or "dot", under [markers], by widget
+# type.  Those not listed use dot_marker.  For example:
+#[markers]
+#cpu = "dot"
+#net = "braille"
+
+# Graph widgets can hide their "legend", "y_labels", and "title" under [hidden_graph_parts], by
+# widget type, to leave more room for the plot.  v cycles them while running.  For example:
+#[hidden_graph_parts]
+#cpu = ["legend"]
+#mem = ["y_labels", "title"]
+
+# Rules checked every update, which mark their widget and pop up a message once they've held for
+# their duration.  Metrics are cpu_total, mem used, swap used, disk "<mount point>" used,
+# temp "<sensor>", and load.  Each can also run a command, with the rule in $BOTTOM_ALERT and the
+# value in $BOTTOM_ALERT_VALUE, or send a desktop notification.  The command is run as written
+# through "sh -c" (or "cmd /C" on Windows) as your user, so anyone who can edit this file can run
+# anything with it.  For example:
+#[[alerts]]
+#rule = "cpu_total > 90 for 30s"
+#command = "logger \"$BOTTOM_ALERT\""
+#desktop_notification = true
+
+# A custom layout replaces the default one.  Rows are stacked from the top, each row's children
+# are placed side by side, their children are stacked again, and so on.  Each entry holds either
+# a widget type or more children, and takes space among its siblings by its ratio (default 1).
+# Widget types are cpu, mem, net, proc, temp, disk, temp_graph, disk_io, battery, gpu, psi, ping,
+# and sockets.  For example, the CPU widget taking the whole top half:
+#[[row]]
+#  ratio = 1
+#  [[row.child]]
+#    type = "cpu"
+#[[row]]
+#  ratio = 1
+#  [[row.child]]
+#    ratio = 2
+#    type = "proc"
+#  [[row.child]]
+#    [[row.child.child]]
+#      type = "mem"
+#    [[row.child.child]]
+#      type = "net"
+
+# Profiles are merged over the rest of this file, for machines needing different settings.  One
+# is picked with --profile, or by being named after this machine's hostname or listing it under
+# hosts.  For example:
+#[profiles.server]
+#  hosts = ["web-1", "web-2"]
+#[profiles.server.flags]
+#  basic = true
+
+# Named layouts can be switched to while running with w and W, after the one above (or the
+# default one).  Each holds rows like the above, for example:
+#[[layout]]
+#  name = "network"
+#  [[layout.row]]
+#    type = "net"
+#  [[layout.row]]
+#    type = "proc"
 "##;
@@ -1,15 +1,18 @@
 //! This mainly concerns converting collected data into things that the canvas
 //! can actually handle.
 
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{hash_map::Entry, HashMap, HashSet};
+use std::rc::Rc;
 
 use crate::{
     app::{
         data_farmer,
         data_harvester::{self, processes::ProcessHarvest},
-        App,
+        App, NetworkScale,
     },
-    utils::gen_util::{get_exact_byte_values, get_simple_byte_values},
+    constants::DISK_USAGE_BAR_WIDTH,
+    utils::gen_util::{get_exact_byte_values, get_simple_bit_values, get_simple_byte_values},
 };
 
 type Point = (f64, f64);
@@ -22,6 +25,24 @@ pub struct ConvertedNetworkData {
     pub tx_display: String,
     pub total_rx_display: String,
     pub total_tx_display: String,
+    pub session_rx_display: String,
+    pub session_tx_display: String,
+    pub interfaces: Vec<ConvertedInterfaceData>,
+}
+
+#[derive(Default, Debug)]
+pub struct ConvertedInterfaceData {
+    pub name: String,
+    pub rx: Vec<Point>,
+    pub tx: Vec<Point>,
+    pub rx_display: String,
+    pub tx_display: String,
+    pub total_rx_display: String,
+    pub total_tx_display: String,
+    pub session_rx_display: String,
+    pub session_tx_display: String,
+    /// The signal and link rate, if this is a wireless interface.
+    pub wireless_display: Option<String>,
 }
 
 #[derive(Clone, Default, Debug)]
@@ -30,7 +51,12 @@ pub struct ConvertedProcessData {
     pub name: String,
     pub cpu_usage: f64,
     pub mem_usage: f64,
+    pub swap_usage: u64,
     pub group_pids: Vec<u32>,
+    /// For a group, this is only true if every process in it is a kernel thread.
+    pub is_kernel_thread: bool,
+    /// Empty for groups.
+    pub cpu_affinity: String,
 }
 
 #[derive(Clone, Default, Debug)]
@@ -39,39 +65,188 @@ pub struct ConvertedCpuData {
     /// Tuple is time, value
     pub cpu_data: Vec<Point>,
     pub legend_value: String,
+    pub frequency_value: String,
+    pub min_value: String,
+    pub max_value: String,
+}
+
+/// How close a temperature sensor is to overheating.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TempLevel {
+    Normal,
+    Warning,
+    Critical,
 }
 
-pub fn convert_temp_row(app: &App) -> Vec<Vec<String>> {
+/// The rows of the temperature table, and whether each is past its thresholds.  Thresholds from
+/// the config take precedence over the sensor's own trip points.
+pub fn convert_temp_row(app: &App) -> (Vec<Vec<String>>, Vec<TempLevel>) {
     let mut sensor_vector: Vec<Vec<String>> = Vec::new();
+    let mut sensor_levels: Vec<TempLevel> = Vec::new();
 
     let current_data = &app.data_collection;
     let temp_type = &app.app_config_fields.temperature_type;
 
-    if current_data.temp_harvest.is_empty() {
-        sensor_vector.push(vec!["No Sensors Found".to_string(), "".to_string()])
+    if current_data.temp_harvest.is_empty() && current_data.fan_harvest.is_empty() {
+        sensor_vector.push(vec!["No Sensors Found".to_string(), "".to_string()]);
+        sensor_levels.push(TempLevel::Normal);
     } else {
         for sensor in &current_data.temp_harvest {
+            let thresholds = app
+                .app_config_fields
+                .temp_thresholds
+                .get(&sensor.component_name);
+            let get_threshold = |configured: Option<f32>, reported: Option<f32>| {
+                configured
+                    .map(|celsius| data_harvester::temperature::convert_celsius(celsius, temp_type))
+                    .or(reported)
+            };
+            let warning = get_threshold(thresholds.and_then(|t| t.warning), sensor.high);
+            let critical = get_threshold(thresholds.and_then(|t| t.critical), sensor.critical);
+
             sensor_vector.push(vec![
                 sensor.component_name.to_string(),
-                (sensor.temperature.ceil() as u64).to_string()
-                    + match temp_type {
-                        data_harvester::temperature::TemperatureType::Celsius => "C",
-                        data_harvester::temperature::TemperatureType::Kelvin => "K",
-                        data_harvester::temperature::TemperatureType::Fahrenheit => "F",
-                    },
+                (sensor.temperature.ceil() as u64).to_string() + get_temperature_unit(temp_type),
+            ]);
+            sensor_levels.push(
+                if critical.map_or(false, |critical| sensor.temperature >= critical) {
+                    TempLevel::Critical
+                } else if warning.map_or(false, |warning| sensor.temperature >= warning) {
+                    TempLevel::Warning
+                } else {
+                    TempLevel::Normal
+                },
+            );
+        }
+
+        // Fans are listed after every temperature
+        for fan in &current_data.fan_harvest {
+            sensor_vector.push(vec![
+                fan.component_name.to_string(),
+                format!("{} RPM", fan.rpm),
             ]);
+            sensor_levels.push(TempLevel::Normal);
         }
     }
 
-    sensor_vector
+    (sensor_vector, sensor_levels)
 }
 
-pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> Vec<Vec<String>> {
+/// Returns the temperature of the hottest sensor that looks like it belongs to the CPU, if any.
+/// Within Linux this is generally the package sensor of coretemp/k10temp.
+pub fn convert_cpu_temp_label(app: &App) -> Option<String> {
+    let temp_type = &app.app_config_fields.temperature_type;
+
+    app.data_collection
+        .temp_harvest
+        .iter()
+        .filter(|sensor| {
+            let component_name = sensor.component_name.to_lowercase();
+            component_name.contains("cpu")
+                || component_name.contains("package")
+                || component_name.starts_with("coretemp")
+                || component_name.starts_with("k10temp")
+                || component_name.starts_with("zenpower")
+        })
+        .max_by(|a, b| {
+            a.temperature
+                .partial_cmp(&b.temperature)
+                .unwrap_or(std::cmp::Ordering::Equal)
+        })
+        .map(|sensor| {
+            (sensor.temperature.ceil() as u64).to_string()
+                + match temp_type {
+                    data_harvester::temperature::TemperatureType::Celsius => "C",
+                    data_harvester::temperature::TemperatureType::Kelvin => "K",
+                    data_harvester::temperature::TemperatureType::Fahrenheit => "F",
+                }
+        })
+}
+
+pub fn get_temperature_unit(
+    temp_type: &data_harvester::temperature::TemperatureType,
+) -> &'static str {
+    match temp_type {
+        data_harvester::temperature::TemperatureType::Celsius => "C",
+        data_harvester::temperature::TemperatureType::Kelvin => "K",
+        data_harvester::temperature::TemperatureType::Fahrenheit => "F",
+    }
+}
+
+/// The rows of the disk table, and the used percentage of each disk.  Sorted by the disk sorting
+/// type.  The inode and temperature columns are only added if they're shown.
+pub fn convert_disk_row(app: &App) -> (Vec<Vec<String>>, Vec<f64>) {
+    use data_harvester::disks::DiskSorting;
+
+    let current_data = &app.data_collection;
+    let show_disk_inodes = app.app_config_fields.show_disk_inodes;
+    let show_disk_temp = app.app_config_fields.show_disk_temp;
+    let temp_type = &app.app_config_fields.temperature_type;
+
+    let mut disks: Vec<(&data_harvester::disks::DiskHarvest, Option<&(u64, u64)>)> = current_data
+        .disk_harvest
+        .iter()
+        .map(|disk| {
+            let io_rates = disk
+                .name
+                .split('/')
+                .last()
+                .and_then(|trim| current_data.io_labels.get(trim));
+            (disk, io_rates)
+        })
+        .collect();
+    let get_utilization = |disk: &data_harvester::disks::DiskHarvest| -> Option<f64> {
+        disk.name
+            .split('/')
+            .last()
+            .and_then(|trim| current_data.io_utilization.get(trim))
+            .cloned()
+    };
+
+    // Disks missing a value are treated as the lowest
+    let get_sort_value =
+        |disk: &data_harvester::disks::DiskHarvest, io_rates: Option<&(u64, u64)>| -> f64 {
+            match app.disk_sorting_type {
+                DiskSorting::Used => disk.used_space as f64 / disk.total_space as f64,
+                DiskSorting::Free => disk.free_space as f64,
+                DiskSorting::Total => disk.total_space as f64,
+                DiskSorting::Read => io_rates
+                    .map(|(read_rate, _)| *read_rate as f64)
+                    .unwrap_or(-1.0),
+                DiskSorting::Write => io_rates
+                    .map(|(_, write_rate)| *write_rate as f64)
+                    .unwrap_or(-1.0),
+                DiskSorting::Util => get_utilization(disk).unwrap_or(-1.0),
+                DiskSorting::Inodes => disk
+                    .inodes
+                    .map(|(used_inodes, total_inodes)| used_inodes as f64 / total_inodes as f64)
+                    .unwrap_or(-1.0),
+                DiskSorting::Temp => disk.temperature.map(f64::from).unwrap_or(std::f64::MIN),
+                DiskSorting::Name | DiskSorting::Mount => 0.0,
+            }
+        };
+    disks.sort_by(|a, b| match app.disk_sorting_type {
+        DiskSorting::Name => a.0.name.cmp(&b.0.name),
+        DiskSorting::Mount => a.0.mount_point.cmp(&b.0.mount_point),
+        _ => get_sort_value(a.0, a.1)
+            .partial_cmp(&get_sort_value(b.0, b.1))
+            .unwrap_or(std::cmp::Ordering::Equal),
+    });
+    if app.disk_sorting_reverse {
+        disks.reverse();
+    }
+
     let mut disk_vector: Vec<Vec<String>> = Vec::new();
-    for (itx, disk) in current_data.disk_harvest.iter().enumerate() {
-        let io_activity = if current_data.io_labels.len() > itx {
-            let converted_read = get_simple_byte_values(current_data.io_labels[itx].0, false);
-            let converted_write = get_simple_byte_values(current_data.io_labels[itx].1, false);
+    let mut disk_usage: Vec<f64> = Vec::new();
+    for (disk, io_rates) in disks {
+        let used_percentage = if disk.total_space > 0 {
+            disk.used_space as f64 / disk.total_space as f64 * 100_f64
+        } else {
+            0.0
+        };
+        let io_activity = if let Some((read_rate, write_rate)) = io_rates {
+            let converted_read = get_simple_byte_values(*read_rate, false);
+            let converted_write = get_simple_byte_values(*write_rate, false);
             (
                 format!("{:.*}{}/s", 0, converted_read.0, converted_read.1),
                 format!("{:.*}{}/s", 0, converted_write.0, converted_write.1),
@@ -82,13 +257,10 @@ pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> Vec<Vec<S
 
         let converted_free_space = get_simple_byte_values(disk.free_space, false);
         let converted_total_space = get_simple_byte_values(disk.total_space, false);
-        disk_vector.push(vec![
+        let mut disk_row = vec![
             disk.name.to_string(),
             disk.mount_point.to_string(),
-            format!(
-                "{:.0}%",
-                disk.used_space as f64 / disk.total_space as f64 * 100_f64
-            ),
+            get_usage_bar(used_percentage),
             format!("{:.*}{}", 0, converted_free_space.0, converted_free_space.1),
             format!(
                 "{:.*}{}",
@@ -96,10 +268,175 @@ pub fn convert_disk_row(current_data: &data_farmer::DataCollection) -> Vec<Vec<S
             ),
             io_activity.0,
             io_activity.1,
+            match get_utilization(disk) {
+                Some(utilization) => format!("{:.0}%", utilization),
+                None => "N/A".to_string(),
+            },
+        ];
+        if show_disk_inodes {
+            disk_row.push(match disk.inodes {
+                Some((used_inodes, total_inodes)) => {
+                    format!("{:.0}%", used_inodes as f64 / total_inodes as f64 * 100_f64)
+                }
+                None => "N/A".to_string(),
+            });
+        }
+        if show_disk_temp {
+            disk_row.push(match disk.temperature {
+                Some(temperature) => {
+                    (temperature.ceil() as u64).to_string() + get_temperature_unit(temp_type)
+                }
+                None => "N/A".to_string(),
+            });
+        }
+        disk_vector.push(disk_row);
+        disk_usage.push(used_percentage);
+    }
+
+    (disk_vector, disk_usage)
+}
+
+/// A bar like "[####....] 52%".
+fn get_usage_bar(percentage: f64) -> String {
+    let filled = ((percentage / 100.0 * DISK_USAGE_BAR_WIDTH as f64).round() as usize)
+        .min(DISK_USAGE_BAR_WIDTH);
+    format!(
+        "[{}{}] {:.0}%",
+        "#".repeat(filled),
+        ".".repeat(DISK_USAGE_BAR_WIDTH - filled),
+        percentage
+    )
+}
+
+/// Each socket's protocol, local and remote addresses, state, PID, and process name.
+pub fn convert_socket_rows(current_data: &data_farmer::DataCollection) -> Vec<Vec<String>> {
+    current_data
+        .socket_harvest
+        .iter()
+        .map(|socket| {
+            vec![
+                socket.protocol.clone(),
+                socket.local_address.clone(),
+                socket.remote_address.clone(),
+                socket.state.clone(),
+                socket
+                    .pid
+                    .map(|pid| pid.to_string())
+                    .unwrap_or_else(|| "N/A".to_string()),
+                socket.process_name.clone(),
+            ]
+        })
+        .collect()
+}
+
+/// Each battery's name, charge, power, and state, followed by their combined total if there's more than
+/// one.  The total charge is weighted by each battery's capacity.
+pub fn convert_battery_rows(current_data: &data_farmer::DataCollection) -> Vec<Vec<String>> {
+    let batteries = &current_data.battery_harvest;
+    if batteries.is_empty() {
+        return vec![vec![
+            "No Batteries Found".to_string(),
+            "".to_string(),
+            "".to_string(),
+            "".to_string(),
+        ]];
+    }
+
+    let mut battery_rows: Vec<Vec<String>> = batteries
+        .iter()
+        .map(|battery| {
+            vec![
+                battery.name.clone(),
+                get_usage_bar(battery.charge_percent),
+                format!("{:.1}W", battery.power_watts),
+                battery.state.clone(),
+            ]
+        })
+        .collect();
+
+    if batteries.len() > 1 {
+        let energy_wh: f64 = batteries.iter().map(|battery| battery.energy_wh).sum();
+        let energy_full_wh: f64 = batteries.iter().map(|battery| battery.energy_full_wh).sum();
+        let charge_percent = if energy_full_wh > 0.0 {
+            energy_wh / energy_full_wh * 100.0
+        } else {
+            batteries
+                .iter()
+                .map(|battery| battery.charge_percent)
+                .sum::<f64>()
+                / batteries.len() as f64
+        };
+
+        // Batteries usually take turns, so one charging or discharging describes them all
+        let state = ["Charging", "Discharging"]
+            .iter()
+            .find(|state| batteries.iter().any(|battery| battery.state == **state))
+            .map(|state| state.to_string())
+            .unwrap_or_else(|| {
+                if batteries.iter().all(|battery| battery.state == "Full") {
+                    "Full".to_string()
+                } else {
+                    "Idle".to_string()
+                }
+            });
+
+        let power_watts: f64 = batteries.iter().map(|battery| battery.power_watts).sum();
+
+        battery_rows.push(vec![
+            "Total".to_string(),
+            get_usage_bar(charge_percent),
+            format!("{:.1}W", power_watts),
+            state,
         ]);
     }
 
-    disk_vector
+    battery_rows
+}
+
+/// A line for each battery with its health, which is its current full charge capacity over its
+/// design capacity, and its cycle count where reported.
+pub fn convert_battery_health_rows(current_data: &data_farmer::DataCollection) -> Vec<String> {
+    current_data
+        .battery_harvest
+        .iter()
+        .map(|battery| {
+            let health = if battery.energy_full_design_wh > 0.0 {
+                format!(
+                    "{:.0}% health ({:.1} of {:.1}Wh)",
+                    battery.energy_full_wh / battery.energy_full_design_wh * 100.0,
+                    battery.energy_full_wh,
+                    battery.energy_full_design_wh
+                )
+            } else {
+                "Health unknown".to_string()
+            };
+            match battery.cycle_count {
+                Some(cycle_count) => {
+                    format!("{}: {}, {} cycles", battery.name, health, cycle_count)
+                }
+                None => format!("{}: {}", battery.name, health),
+            }
+        })
+        .collect()
+}
+
+pub fn convert_power_data_points(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+) -> Vec<Vec<Point>> {
+    convert_data_points_list(current_data, display_time, is_frozen, |data| {
+        &data.power_data
+    })
+}
+
+/// Labels the power graph by whether the batteries are being drained or charged.
+pub fn convert_power_label(current_data: &data_farmer::DataCollection) -> String {
+    let batteries = &current_data.battery_harvest;
+    let power_watts: f64 = batteries.iter().map(|battery| battery.power_watts).sum();
+    if batteries.iter().any(|battery| battery.state == "Charging") {
+        format!("Charging: {:.1}W", power_watts)
+    } else {
+        format!("Draw: {:.1}W", power_watts)
+    }
 }
 
 pub fn convert_cpu_data_points(
@@ -116,7 +453,11 @@ pub fn convert_cpu_data_points(
         current_data.current_instant
     };
 
-    for (time, data) in &current_data.timed_data_vec {
+    for (time, data) in current_data
+        .timed_data_vec
+        .iter()
+        .take_while(|(time, _)| *time <= current_time)
+    {
         let time_from_start: f64 =
             (display_time as f64 - current_time.duration_since(*time).as_millis() as f64).floor();
 
@@ -127,6 +468,10 @@ pub fn convert_cpu_data_points(
                 cpu_data_vector.push(ConvertedCpuData::default());
                 cpu_data_vector[itx_offset].cpu_name =
                     current_data.cpu_harvest[itx].cpu_name.clone();
+                cpu_data_vector[itx_offset].frequency_value = get_frequency_string(
+                    current_data.cpu_harvest[itx].frequency_mhz,
+                    current_data.cpu_harvest[itx].max_frequency_mhz,
+                );
             }
 
             cpu_data_vector[itx_offset].legend_value = format!("{:.0}%", cpu.0.round());
@@ -149,9 +494,42 @@ pub fn convert_cpu_data_points(
         }
     }
 
+    // Only points within the displayed window count towards the min and max
+    for cpu in &mut cpu_data_vector {
+        let (min, max) = cpu
+            .cpu_data
+            .iter()
+            .filter(|(time, _val)| *time >= 0.0)
+            .fold(
+                (None, None),
+                |(min, max): (Option<f64>, Option<f64>), (_time, val)| {
+                    (
+                        Some(min.map_or(*val, |min| min.min(*val))),
+                        Some(max.map_or(*val, |max| max.max(*val))),
+                    )
+                },
+            );
+        cpu.min_value = min.map_or_else(|| "N/A".to_string(), |min| format!("{:.0}%", min.round()));
+        cpu.max_value = max.map_or_else(|| "N/A".to_string(), |max| format!("{:.0}%", max.round()));
+    }
+
     cpu_data_vector
 }
 
+fn get_frequency_string(frequency_mhz: u64, max_frequency_mhz: Option<u64>) -> String {
+    if frequency_mhz == 0 {
+        "N/A".to_string()
+    } else if let Some(max_frequency_mhz) = max_frequency_mhz {
+        format!(
+            "{:.1}/{:.1}GHz",
+            frequency_mhz as f64 / 1000.0,
+            max_frequency_mhz as f64 / 1000.0
+        )
+    } else {
+        format!("{:.1}GHz", frequency_mhz as f64 / 1000.0)
+    }
+}
+
 pub fn convert_mem_data_points(
     current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
 ) -> Vec<Point> {
@@ -166,7 +544,11 @@ pub fn convert_mem_data_points(
         current_data.current_instant
     };
 
-    for (time, data) in &current_data.timed_data_vec {
+    for (time, data) in current_data
+        .timed_data_vec
+        .iter()
+        .take_while(|(time, _)| *time <= current_time)
+    {
         let time_from_start: f64 =
             (display_time as f64 - current_time.duration_since(*time).as_millis() as f64).floor();
 
@@ -200,7 +582,11 @@ pub fn convert_swap_data_points(
         current_data.current_instant
     };
 
-    for (time, data) in &current_data.timed_data_vec {
+    for (time, data) in current_data
+        .timed_data_vec
+        .iter()
+        .take_while(|(time, _)| *time <= current_time)
+    {
         let time_from_start: f64 =
             (display_time as f64 - current_time.duration_since(*time).as_millis() as f64).floor();
 
@@ -220,40 +606,422 @@ pub fn convert_swap_data_points(
     result
 }
 
-pub fn convert_mem_labels(current_data: &data_farmer::DataCollection) -> (String, String) {
-    let mem_label = if current_data.memory_harvest.mem_total_in_mb == 0 {
-        "".to_string()
-    } else {
-        "RAM:".to_string()
-            + &format!(
-                "{:3.0}%",
-                (current_data.memory_harvest.mem_used_in_mb as f64 * 100.0
-                    / current_data.memory_harvest.mem_total_in_mb as f64)
-            )
-            + &format!(
-                "   {:.1}GB/{:.1}GB",
-                current_data.memory_harvest.mem_used_in_mb as f64 / 1024.0,
-                (current_data.memory_harvest.mem_total_in_mb as f64 / 1024.0)
+/// Returns the VRAM usage points of each GPU.
+pub fn convert_vram_data_points(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+) -> Vec<Vec<Point>> {
+    convert_named_data_points_list(
+        current_data,
+        display_time,
+        is_frozen,
+        &get_gpu_keys(current_data),
+        |data| &data.vram_data,
+    )
+}
+
+/// The keys of each GPU's points, in the same order as the GPU harvest.
+fn get_gpu_keys(current_data: &data_farmer::DataCollection) -> Vec<String> {
+    data_farmer::get_series_keys(current_data.gpu_harvest.iter().map(|gpu| gpu.name.as_str()))
+}
+
+/// Returns the utilization points of each GPU.
+pub fn convert_gpu_data_points(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+) -> Vec<Vec<Point>> {
+    convert_named_data_points_list(
+        current_data,
+        display_time,
+        is_frozen,
+        &get_gpu_keys(current_data),
+        |data| &data.gpu_data,
+    )
+}
+
+/// Labels each GPU's utilization with its temperature and power draw where reported, and its
+/// VRAM usage with the amount used.
+pub fn convert_gpu_labels(app: &App) -> Vec<(String, String)> {
+    let temp_type = &app.app_config_fields.temperature_type;
+    app.data_collection
+        .gpu_harvest
+        .iter()
+        .map(|gpu| {
+            let mut utilization_label = match gpu.utilization_percent {
+                Some(utilization) => format!("{}: {:3.0}%", gpu.name, utilization),
+                None => format!("{}: N/A", gpu.name),
+            };
+            if let Some(celsius) = gpu.temperature_celsius {
+                utilization_label.push_str(&format!(
+                    " {:.0}{}",
+                    data_harvester::temperature::convert_celsius(celsius, temp_type),
+                    get_temperature_unit(temp_type)
+                ));
+            }
+            if let Some(power_watts) = gpu.power_watts {
+                utilization_label.push_str(&format!(" {:.0}W", power_watts));
+            }
+
+            (
+                utilization_label,
+                get_mem_label(&format!("{} VRAM:", gpu.name), &gpu.memory, true),
             )
+        })
+        .collect()
+}
+
+/// Returns the memory usage points of each NUMA node.
+pub fn convert_numa_data_points(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+) -> Vec<Vec<Point>> {
+    convert_data_points_list(current_data, display_time, is_frozen, |data| {
+        &data.numa_data
+    })
+}
+
+/// Returns the commit charge points, if the commit charge is reported.
+pub fn convert_commit_data_points(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+) -> Vec<Point> {
+    convert_data_points_list(current_data, display_time, is_frozen, |data| {
+        &data.commit_data
+    })
+    .pop()
+    .unwrap_or_default()
+}
+
+/// Returns the points of each pressure series, in the order of `PSI_SERIES_NAMES`.
+pub fn convert_psi_data_points(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+) -> Vec<Vec<Point>> {
+    let series_keys: Vec<String> = data_harvester::psi::PSI_SERIES_NAMES
+        .iter()
+        .map(|series_name| series_name.to_string())
+        .collect();
+    convert_named_data_points_list(
+        current_data,
+        display_time,
+        is_frozen,
+        &series_keys,
+        |data| &data.psi_data,
+    )
+}
+
+/// Converts a list of series stored in each timed entry, such as one per GPU, into points.
+fn convert_data_points_list(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+    get_series: fn(&data_farmer::TimedData) -> &Vec<data_farmer::JoinedDataPoints>,
+) -> Vec<Vec<Point>> {
+    let mut data_vector: Vec<Vec<Point>> = Vec::new();
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
     };
 
-    let swap_label = if current_data.swap_harvest.mem_total_in_mb == 0 {
+    for (time, data) in current_data
+        .timed_data_vec
+        .iter()
+        .take_while(|(time, _)| *time <= current_time)
+    {
+        let time_from_start: f64 =
+            (display_time as f64 - current_time.duration_since(*time).as_millis() as f64).floor();
+
+        for (itx, series) in get_series(data).iter().enumerate() {
+            if data_vector.len() <= itx {
+                data_vector.push(Vec::new());
+            }
+
+            //Insert joiner points
+            for &(joiner_offset, joiner_val) in &series.1 {
+                let offset_time = time_from_start - joiner_offset as f64;
+                data_vector[itx].push((offset_time, joiner_val));
+            }
+
+            data_vector[itx].push((time_from_start, series.0));
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    data_vector
+}
+
+/// Like `convert_data_points_list`, but for points keyed by name, with the lines in the order of
+/// `keys`.
+fn convert_named_data_points_list(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+    keys: &[String], get_series: fn(&data_farmer::TimedData) -> &data_farmer::NamedDataPoints,
+) -> Vec<Vec<Point>> {
+    let mut data_vector: Vec<Vec<Point>> = vec![Vec::new(); keys.len()];
+    let current_time = if is_frozen {
+        if let Some(frozen_instant) = current_data.frozen_instant {
+            frozen_instant
+        } else {
+            current_data.current_instant
+        }
+    } else {
+        current_data.current_instant
+    };
+
+    for (time, data) in current_data
+        .timed_data_vec
+        .iter()
+        .take_while(|(time, _)| *time <= current_time)
+    {
+        let time_from_start: f64 =
+            (display_time as f64 - current_time.duration_since(*time).as_millis() as f64).floor();
+
+        let named_series = get_series(data);
+        for (key, points) in keys.iter().zip(data_vector.iter_mut()) {
+            if let Some(series) = named_series.get(key) {
+                //Insert joiner points
+                for &(joiner_offset, joiner_val) in &series.1 {
+                    let offset_time = time_from_start - joiner_offset as f64;
+                    points.push((offset_time, joiner_val));
+                }
+
+                points.push((time_from_start, series.0));
+            }
+        }
+
+        if *time == current_time {
+            break;
+        }
+    }
+
+    data_vector
+}
+
+/// Labels each pressure series with its 10 and 60 second averages.
+pub fn convert_psi_labels(current_data: &data_farmer::DataCollection) -> Vec<String> {
+    if let Some(psi) = &current_data.psi_harvest {
+        data_harvester::psi::PSI_SERIES_NAMES
+            .iter()
+            .zip(psi.averages().iter())
+            .map(|(name, (avg10, avg60))| format!("{}: {:5.1}% {:5.1}%", name, avg10, avg60))
+            .collect()
+    } else {
+        Vec::new()
+    }
+}
+
+/// Returns the points of the total read and write rates, in that order, on a log scale.
+pub fn convert_io_data_points(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+) -> Vec<Vec<Point>> {
+    convert_data_points_list(current_data, display_time, is_frozen, |data| &data.io_data)
+        .into_iter()
+        .map(|points| scale_network_points(points, NetworkScale::Log))
+        .collect()
+}
+
+pub fn convert_io_labels(current_data: &data_farmer::DataCollection) -> Vec<String> {
+    let total_read: u64 = current_data
+        .io_labels
+        .values()
+        .map(|(read_rate, _)| read_rate)
+        .sum();
+    let total_write: u64 = current_data
+        .io_labels
+        .values()
+        .map(|(_, write_rate)| write_rate)
+        .sum();
+
+    vec![
+        format!("Read: {}/s", get_network_byte_display(total_read)),
+        format!("Write: {}/s", get_network_byte_display(total_write)),
+    ]
+}
+
+pub fn convert_temp_data_points(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+) -> Vec<Vec<Point>> {
+    let sensor_keys = data_farmer::get_series_keys(
+        current_data
+            .temp_harvest
+            .iter()
+            .map(|sensor| sensor.component_name.as_str()),
+    );
+    convert_named_data_points_list(
+        current_data,
+        display_time,
+        is_frozen,
+        &sensor_keys,
+        |data| &data.temp_data,
+    )
+}
+
+pub fn convert_temp_labels(app: &App) -> Vec<String> {
+    let temp_type = &app.app_config_fields.temperature_type;
+    app.data_collection
+        .temp_harvest
+        .iter()
+        .map(|sensor| {
+            format!(
+                "{}: {}{}",
+                sensor.component_name,
+                sensor.temperature.ceil() as u64,
+                get_temperature_unit(temp_type)
+            )
+        })
+        .collect()
+}
+
+pub fn convert_ping_data_points(
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+) -> Vec<Vec<Point>> {
+    let ping_keys = data_farmer::get_series_keys(
+        current_data
+            .ping_harvest
+            .iter()
+            .map(|ping| ping.host.as_str()),
+    );
+    convert_named_data_points_list(current_data, display_time, is_frozen, &ping_keys, |data| {
+        &data.ping_data
+    })
+}
+
+pub fn convert_ping_labels(current_data: &data_farmer::DataCollection) -> Vec<String> {
+    current_data
+        .ping_harvest
+        .iter()
+        .map(|ping| {
+            if ping.is_reachable {
+                format!(
+                    "{}: {:.1}ms {:.0}% loss",
+                    ping.host, ping.latency_ms, ping.loss_percentage
+                )
+            } else {
+                format!("{}: timeout {:.0}% loss", ping.host, ping.loss_percentage)
+            }
+        })
+        .collect()
+}
+
+pub fn convert_mem_labels(
+    current_data: &data_farmer::DataCollection, mem_as_value: bool,
+) -> (String, String) {
+    (
+        get_mem_label("RAM:", &current_data.memory_harvest, mem_as_value),
+        // Swap is the pagefile within Windows
+        get_mem_label(
+            if cfg!(target_os = "windows") {
+                "PGF:"
+            } else {
+                "SWP:"
+            },
+            &current_data.swap_harvest,
+            mem_as_value,
+        ),
+    )
+}
+
+/// The labels of each GPU's VRAM usage, which are only numbered if there's more than one GPU.
+pub fn convert_vram_labels(
+    current_data: &data_farmer::DataCollection, mem_as_value: bool,
+) -> Vec<String> {
+    let num_gpus = current_data.gpu_harvest.len();
+    current_data
+        .gpu_harvest
+        .iter()
+        .enumerate()
+        .map(|(itx, gpu)| {
+            let prefix = if num_gpus > 1 {
+                format!("VRAM{}:", itx)
+            } else {
+                "VRAM:".to_string()
+            };
+            get_mem_label(&prefix, &gpu.memory, mem_as_value)
+        })
+        .collect()
+}
+
+/// The label of the commit charge, if it's reported.
+pub fn convert_commit_label(
+    current_data: &data_farmer::DataCollection, mem_as_value: bool,
+) -> Option<String> {
+    current_data
+        .commit_harvest
+        .as_ref()
+        .map(|commit| get_mem_label("CMT:", commit, mem_as_value))
+}
+
+/// The labels of each NUMA node's memory usage.
+pub fn convert_numa_labels(
+    current_data: &data_farmer::DataCollection, mem_as_value: bool,
+) -> Vec<String> {
+    current_data
+        .numa_harvest
+        .iter()
+        .enumerate()
+        .map(|(itx, node)| get_mem_label(&format!("N{}:", itx), node, mem_as_value))
+        .collect()
+}
+
+fn get_mem_label(
+    prefix: &str, harvest: &data_harvester::mem::MemHarvest, mem_as_value: bool,
+) -> String {
+    if harvest.mem_total_in_mb == 0 {
         "".to_string()
+    } else if mem_as_value {
+        // Lead with the absolute values instead, as that's what the axis is in
+        let (used, used_unit) = get_exact_byte_values(harvest.mem_used_in_mb * 1_048_576, false);
+        let (total, total_unit) = get_exact_byte_values(harvest.mem_total_in_mb * 1_048_576, false);
+        prefix.to_string()
+            + &format!("{:.1}{}/{:.1}{}", used, used_unit, total, total_unit)
+            + &format!(
+                "   {:.0}%",
+                (harvest.mem_used_in_mb as f64 * 100.0 / harvest.mem_total_in_mb as f64)
+            )
     } else {
-        "SWP:".to_string()
+        prefix.to_string()
             + &format!(
                 "{:3.0}%",
-                (current_data.swap_harvest.mem_used_in_mb as f64 * 100.0
-                    / current_data.swap_harvest.mem_total_in_mb as f64)
+                (harvest.mem_used_in_mb as f64 * 100.0 / harvest.mem_total_in_mb as f64)
             )
             + &format!(
                 "   {:.1}GB/{:.1}GB",
-                current_data.swap_harvest.mem_used_in_mb as f64 / 1024.0,
-                (current_data.swap_harvest.mem_total_in_mb as f64 / 1024.0)
+                harvest.mem_used_in_mb as f64 / 1024.0,
+                (harvest.mem_total_in_mb as f64 / 1024.0)
             )
-    };
+    }
+}
 
-    (mem_label, swap_label)
+/// Describes the ZFS ARC's size, each compressed memory pool as its compressed/original size
+/// and compression ratio, and how many huge pages are free and reserved.
+pub fn convert_mem_pool_label(current_data: &data_farmer::DataCollection) -> String {
+    current_data
+        .arc_harvest
+        .map(|arc_size_in_mb| format!("ARC {:.1}GB", arc_size_in_mb as f64 / 1024.0))
+        .into_iter()
+        .chain(current_data.compressed_mem_harvest.iter().map(|pool| {
+            format!(
+                "{} {:.1}GB/{:.1}GB ({:.1}x)",
+                pool.name,
+                pool.compressed_bytes as f64 / 1_073_741_824.0,
+                pool.original_bytes as f64 / 1_073_741_824.0,
+                if pool.compressed_bytes == 0 {
+                    0.0
+                } else {
+                    pool.original_bytes as f64 / pool.compressed_bytes as f64
+                }
+            )
+        }))
+        .chain(current_data.huge_pages_harvest.iter().map(|huge_pages| {
+            let (page_size, page_size_unit) =
+                get_exact_byte_values(huge_pages.page_size_in_kb * 1024, false);
+            format!(
+                "Huge {:.0}{} {}/{} free, {} rsvd",
+                page_size, page_size_unit, huge_pages.free, huge_pages.total, huge_pages.reserved
+            )
+        }))
+        .collect::<Vec<_>>()
+        .join(" ── ")
 }
 
 pub fn get_rx_tx_data_points(
@@ -273,7 +1041,11 @@ pub fn get_rx_tx_data_points(
     };
 
     // TODO: [REFACTOR] Can we use collect on this, CPU, and MEM?
-    for (time, data) in &current_data.timed_data_vec {
+    for (time, data) in current_data
+        .timed_data_vec
+        .iter()
+        .take_while(|(time, _)| *time <= current_time)
+    {
         let time_from_start: f64 =
             (display_time as f64 - current_time.duration_since(*time).as_millis() as f64).floor();
 
@@ -300,79 +1072,204 @@ pub fn get_rx_tx_data_points(
 }
 
 pub fn convert_network_data_points(
-    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool,
+    current_data: &data_farmer::DataCollection, display_time: u64, is_frozen: bool, use_bits: bool,
+    network_scale: NetworkScale,
 ) -> ConvertedNetworkData {
     let (rx, tx) = get_rx_tx_data_points(current_data, display_time, is_frozen);
 
-    let total_rx_converted_result: (f64, String);
-    let rx_converted_result: (f64, String);
-    let total_tx_converted_result: (f64, String);
-    let tx_converted_result: (f64, String);
-
-    rx_converted_result = get_exact_byte_values(current_data.network_harvest.rx, false);
-    total_rx_converted_result = get_exact_byte_values(current_data.network_harvest.total_rx, false);
-    let rx_display = format!("{:.*}{}", 1, rx_converted_result.0, rx_converted_result.1);
-    let total_rx_display = format!(
-        "{:.*}{}",
-        1, total_rx_converted_result.0, total_rx_converted_result.1
-    );
+    let mut interface_rx =
+        convert_data_points_list(current_data, display_time, is_frozen, |data| {
+            &data.interface_rx_data
+        })
+        .into_iter();
+    let mut interface_tx =
+        convert_data_points_list(current_data, display_time, is_frozen, |data| {
+            &data.interface_tx_data
+        })
+        .into_iter();
 
-    tx_converted_result = get_exact_byte_values(current_data.network_harvest.tx, false);
-    total_tx_converted_result = get_exact_byte_values(current_data.network_harvest.total_tx, false);
-    let tx_display = format!("{:.*}{}", 1, tx_converted_result.0, tx_converted_result.1);
-    let total_tx_display = format!(
-        "{:.*}{}",
-        1, total_tx_converted_result.0, total_tx_converted_result.1
-    );
+    let interfaces = current_data
+        .network_harvest
+        .interfaces
+        .iter()
+        .map(|interface| ConvertedInterfaceData {
+            name: interface.name.clone(),
+            rx: scale_network_points(interface_rx.next().unwrap_or_default(), network_scale),
+            tx: scale_network_points(interface_tx.next().unwrap_or_default(), network_scale),
+            rx_display: get_network_rate_display(interface.rx, use_bits),
+            tx_display: get_network_rate_display(interface.tx, use_bits),
+            total_rx_display: get_network_byte_display(interface.total_rx),
+            total_tx_display: get_network_byte_display(interface.total_tx),
+            session_rx_display: get_network_byte_display(interface.session_rx),
+            session_tx_display: get_network_byte_display(interface.session_tx),
+            wireless_display: interface.wireless.as_ref().map(|wireless| {
+                let signal = format!("{} dBm ({:.0}%)", wireless.signal_dbm, wireless.quality);
+                match wireless.link_rate_mbps {
+                    Some(link_rate) => format!("{} ── {:.1} Mb/s", signal, link_rate),
+                    None => signal,
+                }
+            }),
+        })
+        .collect();
 
     ConvertedNetworkData {
-        rx,
-        tx,
-        rx_display,
-        tx_display,
-        total_rx_display,
-        total_tx_display,
+        rx: scale_network_points(rx, network_scale),
+        tx: scale_network_points(tx, network_scale),
+        rx_display: get_network_rate_display(current_data.network_harvest.rx, use_bits),
+        tx_display: get_network_rate_display(current_data.network_harvest.tx, use_bits),
+        total_rx_display: get_network_byte_display(current_data.network_harvest.total_rx),
+        total_tx_display: get_network_byte_display(current_data.network_harvest.total_tx),
+        session_rx_display: get_network_byte_display(current_data.network_harvest.session_rx),
+        session_tx_display: get_network_byte_display(current_data.network_harvest.session_tx),
+        interfaces,
     }
 }
 
+/// Rates are harvested in bytes per second, and are only converted to the log2 scale here.
+fn scale_network_points(points: Vec<Point>, network_scale: NetworkScale) -> Vec<Point> {
+    match network_scale {
+        NetworkScale::Log => points
+            .into_iter()
+            .map(|(time, value)| (time, if value > 1.0 { value.log2() } else { 0.0 }))
+            .collect(),
+        NetworkScale::Linear => points,
+    }
+}
+
+/// Rates are optionally shown in bits per second, while amounts transferred are always in bytes.
+pub fn get_network_rate_display(bytes_per_sec: u64, use_bits: bool) -> String {
+    if use_bits {
+        let converted_result = get_simple_bit_values(bytes_per_sec * 8);
+        format!("{:.*}{}", 1, converted_result.0, converted_result.1)
+    } else {
+        get_network_byte_display(bytes_per_sec)
+    }
+}
+
+fn get_network_byte_display(bytes: u64) -> String {
+    let converted_result = get_exact_byte_values(bytes, false);
+    format!("{:.*}{}", 1, converted_result.0, converted_result.1)
+}
+
+/// Updates the process map and the converted rows, all kept between updates, rather than
+/// rebuilding them every tick.  Only what changed is copied, and PIDs and groups that are gone
+/// are dropped.  Grouped rows are keyed by the name they're grouped by.
 pub fn convert_process_data(
     current_data: &data_farmer::DataCollection,
-) -> (HashMap<u32, ProcessHarvest>, Vec<ConvertedProcessData>) {
-    let mut single_list: HashMap<u32, ProcessHarvest> = HashMap::new();
-
-    // cpu, mem, pids
-    let mut grouped_hashmap: HashMap<String, (u32, f64, f64, Vec<u32>)> =
-        std::collections::HashMap::new();
-
-    // Go through every single process in the list... and build a hashmap + single list
-    for process in &(current_data).process_harvest {
-        let entry = grouped_hashmap.entry(process.name.clone()).or_insert((
-            process.pid,
-            0.0,
-            0.0,
-            Vec::new(),
-        ));
-
-        (*entry).1 += process.cpu_usage_percent;
-        (*entry).2 += process.mem_usage_percent;
-        (*entry).3.push(process.pid);
-
-        single_list.insert(process.pid, process.clone());
+    existing_process_data: &mut HashMap<u32, ProcessHarvest>,
+    process_rows: &mut HashMap<u32, Rc<ConvertedProcessData>>,
+    grouped_rows: &mut HashMap<String, Rc<ConvertedProcessData>>, group_ignore_case: bool,
+    group_by_command: bool,
+) {
+    let mut seen_pids: HashSet<u32> = HashSet::with_capacity(current_data.process_harvest.len());
+
+    // Groups are summed up again, but keep their rows
+    for grouped_row in grouped_rows.values_mut() {
+        let grouped_row = Rc::make_mut(grouped_row);
+        grouped_row.cpu_usage = 0.0;
+        grouped_row.mem_usage = 0.0;
+        grouped_row.swap_usage = 0;
+        grouped_row.group_pids.clear();
+        grouped_row.is_kernel_thread = true;
     }
 
-    let grouped_list: Vec<ConvertedProcessData> = grouped_hashmap
-        .iter()
-        .map(|(name, process_details)| {
-            let p = process_details.clone();
-            ConvertedProcessData {
-                pid: p.0,
-                name: name.to_string(),
-                cpu_usage: p.1,
-                mem_usage: p.2,
-                group_pids: p.3,
+    for process in current_data.process_harvest.iter() {
+        // Note the name is already just the executable's basename.
+        let display_name: &str = if group_by_command && !process.command.is_empty() {
+            &process.command
+        } else {
+            &process.name
+        };
+        let group_key = if group_ignore_case {
+            Cow::Owned(display_name.to_lowercase())
+        } else {
+            Cow::Borrowed(display_name)
+        };
+
+        if !grouped_rows.contains_key(group_key.as_ref()) {
+            grouped_rows.insert(
+                group_key.to_string(),
+                Rc::new(ConvertedProcessData {
+                    is_kernel_thread: true,
+                    ..ConvertedProcessData::default()
+                }),
+            );
+        }
+        if let Some(grouped_row) = grouped_rows.get_mut(group_key.as_ref()) {
+            let grouped_row = Rc::make_mut(grouped_row);
+            // A group is shown as its first process
+            if grouped_row.group_pids.is_empty() {
+                grouped_row.pid = process.pid;
+                if grouped_row.name != display_name {
+                    grouped_row.name = display_name.to_string();
+                }
             }
-        })
-        .collect::<Vec<_>>();
+            grouped_row.cpu_usage += process.cpu_usage_percent;
+            grouped_row.mem_usage += process.mem_usage_percent;
+            grouped_row.swap_usage += process.swap_usage_bytes;
+            grouped_row.group_pids.push(process.pid);
+            grouped_row.is_kernel_thread &= process.is_kernel_thread;
+        }
+
+        seen_pids.insert(process.pid);
+        match existing_process_data.entry(process.pid) {
+            Entry::Occupied(mut occupied) => {
+                let existing = occupied.get_mut();
+                *existing = ProcessHarvest {
+                    name: reuse_string(&mut existing.name, &process.name),
+                    command: reuse_string(&mut existing.command, &process.command),
+                    user: reuse_string(&mut existing.user, &process.user),
+                    cpu_affinity: reuse_string(&mut existing.cpu_affinity, &process.cpu_affinity),
+                    ..*process
+                };
+            }
+            Entry::Vacant(vacant) => {
+                vacant.insert(process.clone());
+            }
+        }
+
+        match process_rows.entry(process.pid) {
+            Entry::Occupied(mut occupied) => {
+                let row = Rc::make_mut(occupied.get_mut());
+                *row = ConvertedProcessData {
+                    pid: process.pid,
+                    name: reuse_string(&mut row.name, &process.name),
+                    cpu_usage: process.cpu_usage_percent,
+                    mem_usage: process.mem_usage_percent,
+                    swap_usage: process.swap_usage_bytes,
+                    group_pids: std::mem::take(&mut row.group_pids),
+                    is_kernel_thread: process.is_kernel_thread,
+                    cpu_affinity: reuse_string(&mut row.cpu_affinity, &process.cpu_affinity),
+                };
+            }
+            Entry::Vacant(vacant) => {
+                vacant.insert(Rc::new(ConvertedProcessData {
+                    pid: process.pid,
+                    name: process.name.clone(),
+                    cpu_usage: process.cpu_usage_percent,
+                    mem_usage: process.mem_usage_percent,
+                    swap_usage: process.swap_usage_bytes,
+                    group_pids: vec![process.pid],
+                    is_kernel_thread: process.is_kernel_thread,
+                    cpu_affinity: process.cpu_affinity.clone(),
+                }));
+            }
+        }
+    }
+
+    // Remove any processes that have since died
+    if existing_process_data.len() != seen_pids.len() {
+        existing_process_data.retain(|pid, _| seen_pids.contains(pid));
+        process_rows.retain(|pid, _| seen_pids.contains(pid));
+    }
+    grouped_rows.retain(|_, grouped_row| !grouped_row.group_pids.is_empty());
+}
 
-    (single_list, grouped_list)
+/// Takes the old string if it's unchanged, so it isn't copied again.
+fn reuse_string(old: &mut String, new: &str) -> String {
+    if old == new {
+        std::mem::take(old)
+    } else {
+        new.to_string()
+    }
 }
@@ -5,8 +5,10 @@ extern crate log;
 
 use std::{
     boxed::Box,
+    collections::HashMap,
     io::{stdout, Write},
     panic::{self, PanicInfo},
+    rc::Rc,
     sync::mpsc,
     thread,
     time::{Duration, Instant},
@@ -37,6 +39,7 @@ use utils::error;
 pub mod app;
 
 mod utils {
+    pub mod clipboard;
     pub mod error;
     pub mod gen_util;
     pub mod logging;
@@ -45,6 +48,7 @@ mod utils {
 mod canvas;
 mod constants;
 mod data_conversion;
+mod metrics;
 
 pub mod options;
 
@@ -53,10 +57,26 @@ enum BottomEvent<I, J> {
     MouseInput(J),
     Update(Box<data_harvester::Data>),
     Clean,
+    /// The remote host being shown stopped sending harvests.
+    RemoteDisconnected {
+        host: String,
+        reason: String,
+    },
 }
 
 enum ResetEvent {
     Reset,
+    /// Collection settings re-read from a changed config file.
+    Reload {
+        network_filter: data_harvester::network::InterfaceFilter,
+        disk_filter: data_harvester::disks::DiskFilter,
+        sensor_filter: data_harvester::temperature::SensorFilter,
+        get_psi: bool,
+        get_sockets: bool,
+        get_battery: bool,
+    },
+    /// The host to collect from, or `None` for this machine.
+    SwitchHost(Option<String>),
 }
 
 fn get_matches() -> clap::ArgMatches<'static> {
@@ -67,6 +87,7 @@ fn get_matches() -> clap::ArgMatches<'static> {
 		(about: crate_description!())
 		(@arg AVG_CPU: -a --avg_cpu "Enables showing the average CPU usage.")
 		(@arg DOT_MARKER: -m --dot_marker "Use a dot marker instead of the default braille marker.")
+		(@arg SERIES_MARKERS: --series_markers "Alternates lines in the same graph between braille and dot markers, shown beside their names, so they can be told apart by more than colour.")
 		(@group TEMPERATURE_TYPE =>
 			(@arg KELVIN : -k --kelvin "Sets the temperature type to Kelvin.")
 			(@arg FAHRENHEIT : -f --fahrenheit "Sets the temperature type to Fahrenheit.")
@@ -75,17 +96,68 @@ fn get_matches() -> clap::ArgMatches<'static> {
 		(@arg RATE_MILLIS: -r --rate +takes_value "Sets a refresh rate in milliseconds; the minimum is 250ms, defaults to 1000ms.  Smaller values may take more resources.")
 		(@arg LEFT_LEGEND: -l --left_legend "Puts external chart legends on the left side rather than the default right side.")
 		(@arg USE_CURR_USAGE: -u --current_usage "Within Linux, sets a process' CPU usage to be based on the total current CPU usage, rather than assuming 100% usage.")
+		(@arg HIDE_KERNEL_THREADS: --hide_kernel_threads "Within Linux, hides kernel threads from the process widget by default.")
+		(@arg CPU_FREQUENCY: --cpu_frequency "Shows each core's current frequency in the CPU legend, along with its max frequency within Linux.")
+		(@arg CPU_TEMP: --cpu_temp "Shows the CPU package temperature above the CPU legend.")
+		(@arg PSI: --psi "Within Linux, shows a widget graphing CPU, memory, and IO pressure stall information next to the memory widget.")
+		(@arg MEM_AS_VALUE: --mem_as_value "Shows memory and swap usage as absolute values rather than percentages in the memory widget.")
+		(@arg SOCKETS: --sockets "Within Linux, shows a widget listing open TCP and UDP sockets below the process widget.")
+		(@arg DISK_INODES: --disk_inodes "Shows the percentage of each disk's inodes that are used in the disk widget.")
+		(@arg HIDE_CPU: --hide_cpu "Hides the CPU widget, giving its space to the other widgets.")
+		(@arg HIDE_MEM: --hide_mem "Hides the memory widget, giving its space to the other widgets.")
+		(@arg HIDE_NET: --hide_net "Hides the network widget, giving its space to the other widgets.")
+		(@arg HIDE_TEMPS: --hide_temps "Hides the temperature widget, giving its space to the other widgets.")
+		(@arg HIDE_DISKS: --hide_disks "Hides the disk widget, giving its space to the other widgets.")
+		(@arg HIDE_PROCS: --hide_procs "Hides the process widget, giving its space to the other widgets.")
+		(@arg HIDE_NETWORK_DISKS: --hide_network_disks "Hides NFS, CIFS, and SSHFS mounts from the disk widget.")
+		(@arg NETWORK_DISK_TIMEOUT: --network_disk_timeout +takes_value "Sets how long in milliseconds a network mount may take to respond before it's left out of the disk widget.  Defaults to 1000ms.")
+		(@arg DISK_TEMP: --disk_temp "Within Linux, shows the temperature of NVMe drives, and SATA drives using the drivetemp module, in the disk widget.")
+		(@arg DISK_IO_GRAPH: --disk_io_graph "Shows a widget below the disk widget graphing the total read and write rate of every disk.")
+		(@arg GPU: --gpu "Shows a widget next to the CPU widget graphing the utilization and VRAM usage of each GPU.  Requires building with the gpu feature.")
+		(@arg BATTERY: --battery "Shows a widget next to the disk widget listing the charge, power draw, and state of each battery.")
+		(@arg TEMP_GRAPH: --temp_graph "Shows a widget next to the temperature widget graphing each sensor's temperature.")
+		(@arg PING: --ping +takes_value +multiple "Shows a widget next to the network widget graphing the latency and packet loss of each given host.")
+		(@arg NETWORK_SCALE: --network_scale +takes_value "Sets the network graph's scale to either <log|linear>.  A linear scale fits the highest rate in view.  Defaults to log.")
+		(@arg NETWORK_Y_MAX: --network_y_max +takes_value "Pins the network graph's y-axis maximum to a rate, like 1Gb or 100MiB.")
+		(@arg NETWORK_USE_BITS: --network_use_bits "Shows network throughput in bits rather than bytes per second.")
+		(@arg SUBTRACT_ARC: --subtract_arc "Within Linux, counts the ZFS ARC as free rather than used memory.")
+		(@arg CPU_MIN_MAX: --cpu_min_max "Shows each core's minimum and maximum usage over the displayed time window in the CPU legend.")
+		(@arg PROCESS_SWAP: --process_swap "Within Linux, shows a swap usage column in the process widget.  Hidden by default as it requires extra reads.")
+		(@arg PROCESS_AFFINITY: --process_affinity "Within Linux, shows a column with the CPUs each process may run on in the process widget.  Hidden by default as it requires extra reads.")
+		(@arg HEADER: --header "Shows a one-line summary above the widgets, with the hostname, uptime, load average, CPU and memory usage, and the time.")
+		(@arg TABLE_STRIPES: --table_stripes "Gives every other row of a table a background, to make rows easier to follow.")
+		(@arg TABLE_GAP: --table_gap +takes_value "Sets how many spaces are between the columns of a table.  Defaults to 1.")
+		(@arg COPY_PID: --copy_pid "Copies only the PID of the highlighted process with y, rather than the whole row.")
+		(@arg GENERATE_CONFIG: --generate_config "Writes a commented config file with every option to the config location, or the one set with -C, and exits.  An existing file is never replaced.")
+		(@arg PRINT_CONFIG: --print_config "Prints a commented config file with every option and exits.")
+		(@arg LOG_METRICS: --log_metrics +takes_value "Appends a record of the metrics to the given file every update, as CSV if it ends in .csv and as lines of JSON otherwise.")
+		(@arg LOG_METRICS_MAX_SIZE: --log_metrics_max_size +takes_value "Sets the size in megabytes the metrics log may reach before it's moved to <file>.1 and a new one is started.  Defaults to 50.")
+		(@arg PROMETHEUS_PORT: --prometheus_port +takes_value "Serves the metrics for Prometheus to scrape at /metrics on the given port.")
+		(@arg PROMETHEUS_ADDRESS: --prometheus_address +takes_value "Sets the IP address to serve the metrics for Prometheus on.  Defaults to 0.0.0.0, which other machines can reach.")
+		(@arg API_PORT: --api_port +takes_value "Serves the metrics as JSON on the given local port, at / for everything or at paths like /cpu and /processes for a part.")
+		(@arg LINE_PROTOCOL: --line_protocol +takes_value "Sends the metrics in InfluxDB's line protocol to the given http://, udp://, or tcp:// URL, like http://localhost:8086/write?db=bottom.")
+		(@arg LINE_PROTOCOL_INTERVAL: --line_protocol_interval +takes_value "Sets how often in seconds the metrics are sent with --line_protocol.  Defaults to 10.")
+		(@arg HEADLESS: --headless "Collects metrics without starting the interface, only sending them to outputs like --log_metrics, --prometheus_port, --api_port, or --line_protocol, until stopped.")
+		(@arg REMOTE: --remote +takes_value +multiple "Shows the metrics of the given hosts rather than this one, starting with the first, by running bottom on them over SSH.  Ctrl-n switches between them and this machine.")
+		(@arg AGENT: --agent "Prints every harvest as a line of JSON rather than starting the interface, for --remote to read over SSH.")
+		(@arg DUMP: --dump "Collects every metric once, prints them as JSON, and exits, rather than starting the interface.")
+		(@arg PROFILE: --profile +takes_value "Uses the named [profiles.<name>] table of the config file over the rest of it.  Otherwise, a profile listing this machine's hostname is used, if there is one.")
 		(@arg CONFIG_LOCATION: -C --config +takes_value "Sets the location of the config file.  Expects a config file in the TOML format. If it doesn't exist, one is created.")
 		(@arg BASIC_MODE: -b --basic "Hides graphs and uses a more basic look")
+		(@arg ASCII: --ascii +takes_value "Whether to only draw ASCII characters and the 8 standard colours, one of auto, always, or never.  Defaults to auto, which does so on terminals like the Linux console, or without a UTF-8 locale.")
+		(@arg THEME: --theme +takes_value "Sets the colour scheme, one of default, gruvbox, nord, light, colorblind, or colorblind_light.  Colours set in the config file are applied on top.")
 		(@arg GROUP_PROCESSES: -g --group "Groups processes with the same name together on launch.")
+		(@arg DEFAULT_SORT: --default_sort +takes_value "Sets what processes are sorted by on launch, one of cpu, mem, swap, pid, or name.  Defaults to cpu.")
 		(@arg CASE_SENSITIVE: -S --case_sensitive "Match case when searching by default.")
 		(@arg WHOLE_WORD: -W --whole_word "Match whole word when searching by default.")
 		(@arg REGEX_DEFAULT: -R --regex "Use regex in searching by default.")
         (@arg SHOW_DISABLED_DATA: -s --show_disabled_data "Show disabled data entries.")
         (@arg DEFAULT_TIME_VALUE: -t --default_time_value +takes_value "Default time value for graphs in milliseconds; minimum is 30s, defaults to 60s.")
+        (@arg RETENTION: --retention +takes_value "How much data to keep in milliseconds, which is also the furthest graphs can be zoomed out to; minimum is 30s, maximum is 1 hour, defaults to 10 minutes.")
         (@arg TIME_DELTA: -d --time_delta +takes_value "The amount changed upon zooming in/out in milliseconds; minimum is 1s, defaults to 15s.")
         (@arg HIDE_TIME: --hide_time "Completely hide the time scaling")
         (@arg AUTOHIDE_TIME: --autohide_time "Automatically hide the time scaling in graphs after being shown for a brief moment when zoomed in/out.  If time is disabled via --hide_time then this will have no effect.")
+        (@arg EXPANDED: --expanded "Starts with the default widget maximized, as if Enter was pressed on it.  Esc returns to the usual layout.")
         (@group DEFAULT_WIDGET =>
 			(@arg CPU_WIDGET: --cpu_default "Selects the CPU widget to be selected by default.")
 			(@arg MEM_WIDGET: --memory_default "Selects the memory widget to be selected by default.")
@@ -103,16 +175,46 @@ fn main() -> error::Result<()> {
     create_logger()?;
     let matches = get_matches();
 
-    let config: Config = create_config(matches.value_of("CONFIG_LOCATION"))?;
+    if matches.is_present("PRINT_CONFIG") {
+        print!("{}", DEFAULT_CONFIG_CONTENT);
+        return Ok(());
+    }
+
+    let config_path = get_config_path(matches.value_of("CONFIG_LOCATION"));
+    if matches.is_present("GENERATE_CONFIG") {
+        return generate_config(&config_path);
+    }
+    let config: Config = create_config(&config_path, matches.value_of("PROFILE"))?;
+    let mut config_modified_time = get_modified_time(&config_path);
+    let mut last_config_check = Instant::now();
 
     // Create "app" struct, which will control most of the program and store settings/state
     let mut app = build_app(&matches, &config)?;
+    if matches.is_present("DUMP") {
+        return dump_metrics(&app);
+    }
+    if matches.is_present("AGENT") {
+        return run_agent(&app);
+    }
+    let mut metrics_outputs = metrics::MetricsOutputs::new(&app.app_config_fields)?;
+    if matches.is_present("HEADLESS") {
+        return run_headless(&app, metrics_outputs);
+    }
+    let mut painter = create_painter(&matches, &config)?;
 
     // TODO: [REFACTOR] Change this
     enable_app_grouping(&matches, &config, &mut app);
     enable_app_case_sensitive(&matches, &config, &mut app);
     enable_app_match_whole_word(&matches, &config, &mut app);
     enable_app_use_regex(&matches, &config, &mut app);
+    if app.app_config_fields.persist_search_history {
+        app.process_search_state.search_history = read_search_history();
+    }
+    if app.app_config_fields.persist_state {
+        if let Some(saved_state) = read_saved_state() {
+            app.restore_saved_state(saved_state);
+        }
+    }
 
     // Set up up tui and crossterm
     let mut stdout_val = stdout();
@@ -144,19 +246,21 @@ fn main() -> error::Result<()> {
     create_event_thread(
         tx,
         rrx,
-        app.app_config_fields.use_current_cpu_total,
+        get_harvester_options(&app.app_config_fields),
         app.app_config_fields.update_rate_in_milliseconds,
-        app.app_config_fields.temperature_type.clone(),
-        app.app_config_fields.show_average_cpu,
+        get_remote_agent_command(&app.app_config_fields),
     );
-
-    let mut painter = canvas::Painter::default();
-    if let Err(config_check) = generate_config_colours(&config, &mut painter) {
-        cleanup_terminal(&mut terminal)?;
-        return Err(config_check);
+    if let Some(host) = app.get_remote_host().map(|host| host.to_string()) {
+        if rtx
+            .send(ResetEvent::SwitchHost(Some(host.clone())))
+            .is_err()
+        {
+            app.notify_error(&format!(
+                "Unable to connect to {} - data collection has stopped",
+                host
+            ));
+        }
     }
-    painter.colours.generate_remaining_cpu_colours();
-    painter.initialize();
 
     let mut first_run = true;
     loop {
@@ -173,69 +277,60 @@ fn main() -> error::Result<()> {
                     handle_force_redraws(&mut app);
                 }
                 BottomEvent::Update(data) => {
-                    app.data_collection.eat_data(&data);
+                    // Pick up any saved changes to the config file, without hitting the disk
+                    // on every update when the rate is fast
+                    if last_config_check.elapsed().as_millis()
+                        >= CONFIG_CHECK_INTERVAL_IN_MILLISECONDS
+                    {
+                        last_config_check = Instant::now();
+                        let modified_time = get_modified_time(&config_path);
+                        if modified_time.is_some() && modified_time != config_modified_time {
+                            config_modified_time = modified_time;
+                            if let Err(err) =
+                                reload_config(&matches, &config_path, &mut app, &mut painter, &rtx)
+                            {
+                                app.config_err = Some(err.to_string());
+                            } else {
+                                app.config_err = None;
+                                app.notify("Config reloaded");
+                            }
+                        }
+                    }
+
+                    app.data_collection
+                        .eat_data(&data, app.app_config_fields.retention_time);
+                    app.harvest_error_state.update(&data.errors);
+                    for event in app
+                        .alert_state
+                        .update(&app.app_config_fields.alert_rules, &data)
+                    {
+                        app.toast_state.push(&event.message, event.is_error);
+                    }
+                    if let Err(err) =
+                        metrics_outputs.update(&data, &app.app_config_fields.temperature_type)
+                    {
+                        app.notify_error(&err.to_string());
+                    }
 
                     if !app.is_frozen {
-                        // Convert all data into tui-compliant components
-
-                        // Network
-                        let network_data = convert_network_data_points(
-                            &app.data_collection,
-                            app.net_state.current_display_time,
-                            false,
-                        );
-                        app.canvas_data.network_data_rx = network_data.rx;
-                        app.canvas_data.network_data_tx = network_data.tx;
-                        app.canvas_data.rx_display = network_data.rx_display;
-                        app.canvas_data.tx_display = network_data.tx_display;
-                        app.canvas_data.total_rx_display = network_data.total_rx_display;
-                        app.canvas_data.total_tx_display = network_data.total_tx_display;
-
-                        // Disk
-                        app.canvas_data.disk_data = convert_disk_row(&app.data_collection);
-
-                        // Temperatures
-                        app.canvas_data.temp_sensor_data = convert_temp_row(&app);
-                        // Memory
-                        app.canvas_data.mem_data = convert_mem_data_points(
-                            &app.data_collection,
-                            app.mem_state.current_display_time,
-                            false,
-                        );
-                        app.canvas_data.swap_data = convert_swap_data_points(
-                            &app.data_collection,
-                            app.mem_state.current_display_time,
-                            false,
-                        );
-                        let memory_and_swap_labels = convert_mem_labels(&app.data_collection);
-                        app.canvas_data.mem_label = memory_and_swap_labels.0;
-                        app.canvas_data.swap_label = memory_and_swap_labels.1;
-
-                        // Pre-fill CPU if needed
-                        if first_run {
-                            let cpu_len = app.data_collection.cpu_harvest.len();
+                        convert_all_data(&mut app);
+
+                        // Pre-fill CPU if needed, or again if the cores changed, like after
+                        // switching hosts
+                        let cpu_len = app.data_collection.cpu_harvest.len();
+                        if first_run || app.cpu_state.core_show_vec.len() != cpu_len {
                             app.cpu_state.core_show_vec = vec![true; cpu_len];
-                            app.cpu_state.num_cpus_shown = cpu_len as u64;
+                            app.reset_cpu_core_visibility();
                             first_run = false;
                         }
-
-                        // CPU
-                        app.canvas_data.cpu_data = convert_cpu_data_points(
-                            &app.data_collection,
-                            app.cpu_state.current_display_time,
-                            false,
-                        );
-
-                        // Processes
-                        let (single, grouped) = convert_process_data(&app.data_collection);
-                        app.canvas_data.process_data = single;
-                        app.canvas_data.grouped_process_data = grouped;
-                        update_final_process_list(&mut app);
                     }
                 }
                 BottomEvent::Clean => {
                     app.data_collection
-                        .clean_data(constants::STALE_MAX_MILLISECONDS);
+                        .clean_data(app.app_config_fields.retention_time);
+                }
+                BottomEvent::RemoteDisconnected { host, reason } => {
+                    app.notify_error(&format!("Lost connection to {} - {}", host, reason));
                 }
             }
         }
@@ -251,6 +346,17 @@ fn main() -> error::Result<()> {
         try_drawing(&mut terminal, &mut app, &mut painter)?;
     }
 
+    if app.app_config_fields.persist_search_history {
+        if let Err(err) = write_search_history(&app.process_search_state.search_history) {
+            error!("Failed to save the search history: {}", err);
+        }
+    }
+    if app.app_config_fields.persist_state {
+        if let Err(err) = write_saved_state(&app.get_saved_state()) {
+            error!("Failed to save the state: {}", err);
+        }
+    }
+
     cleanup_terminal(&mut terminal)?;
     Ok(())
 }
@@ -275,12 +381,20 @@ fn handle_key_event_or_break(
 
     if event.modifiers.is_empty() {
         // Required catch for searching - otherwise you couldn't search with q.
-        if event.code == KeyCode::Char('q') && !app.is_in_search_widget() {
-            return true;
+        if let KeyCode::Char(caught_char) = event.code {
+            if app.get_default_key(caught_char) == Some('q')
+                && !app.is_in_search_widget()
+                && !app.is_typing_in_dialog()
+                && !app.is_typing_in_table_search()
+            {
+                return true;
+            }
         }
         match event.code {
             KeyCode::End => app.skip_to_last(),
             KeyCode::Home => app.skip_to_first(),
+            KeyCode::PageUp => app.scroll_page(false, false),
+            KeyCode::PageDown => app.scroll_page(true, false),
             KeyCode::Up => app.on_up_key(),
             KeyCode::Down => app.on_down_key(),
             KeyCode::Left => app.on_left_key(),
@@ -289,6 +403,7 @@ fn handle_key_event_or_break(
             KeyCode::Esc => app.on_esc(),
             KeyCode::Enter => app.on_enter(),
             KeyCode::Tab => app.on_tab(),
+            KeyCode::BackTab => app.on_back_tab(),
             KeyCode::Backspace => app.on_backspace(),
             KeyCode::Delete => app.on_delete(),
             KeyCode::F(1) => {
@@ -306,6 +421,11 @@ fn handle_key_event_or_break(
                     app.toggle_search_regex();
                 }
             }
+            KeyCode::F(4) => {
+                if app.is_in_search_widget() {
+                    app.toggle_search_pid();
+                }
+            }
             _ => {}
         }
     } else {
@@ -327,6 +447,11 @@ fn handle_key_event_or_break(
                         app.toggle_search_regex();
                     }
                 }
+                KeyCode::Char('p') | KeyCode::Char('P') => {
+                    if app.is_in_search_widget() {
+                        app.toggle_search_pid();
+                    }
+                }
                 _ => {}
             }
         } else if let KeyModifiers::CONTROL = event.modifiers {
@@ -345,13 +470,27 @@ fn handle_key_event_or_break(
                         app.reset();
                     }
                 }
+                KeyCode::Char('n') => {
+                    if let Some(host) = app.next_remote_host() {
+                        if rtx.send(ResetEvent::SwitchHost(host.clone())).is_ok() {
+                            app.reset();
+                            app.notify(&format!(
+                                "Showing {}",
+                                host.as_deref().unwrap_or("this machine")
+                            ));
+                        }
+                    }
+                }
                 KeyCode::Char('a') => app.skip_cursor_beginning(),
                 KeyCode::Char('e') => app.skip_cursor_end(),
-                KeyCode::Char('u') => app.clear_search(),
-                // KeyCode::Char('j') => {}, // Move down
-                // KeyCode::Char('k') => {}, // Move up
-                // KeyCode::Char('h') => {}, // Move right
-                // KeyCode::Char('l') => {}, // Move left
+                KeyCode::Char('u') => {
+                    if app.is_in_search_widget() {
+                        app.clear_search();
+                    } else {
+                        app.scroll_page(false, true);
+                    }
+                }
+                KeyCode::Char('d') => app.scroll_page(true, true),
                 // Can't do now, CTRL+BACKSPACE doesn't work and graphemes
                 // are hard to iter while truncating last (eloquently).
                 // KeyCode::Backspace => app.skip_word_backspace(),
@@ -363,6 +502,7 @@ fn handle_key_event_or_break(
                 KeyCode::Right => app.move_widget_selection_right(),
                 KeyCode::Up => app.move_widget_selection_up(),
                 KeyCode::Down => app.move_widget_selection_down(),
+                KeyCode::BackTab => app.on_back_tab(),
                 KeyCode::Char(caught_char) => app.on_char_key(caught_char),
                 _ => {}
             }
@@ -379,39 +519,186 @@ fn create_logger() -> error::Result<()> {
     Ok(())
 }
 
-fn create_config(flag_config_location: Option<&str>) -> error::Result<Config> {
-    use std::{ffi::OsString, fs};
-    let config_path = if let Some(conf_loc) = flag_config_location {
-        OsString::from(conf_loc)
+fn get_config_path(flag_config_location: Option<&str>) -> std::path::PathBuf {
+    if let Some(conf_loc) = flag_config_location {
+        std::path::PathBuf::from(conf_loc)
     } else if cfg!(target_os = "windows") {
         if let Some(home_path) = dirs::config_dir() {
             let mut path = home_path;
             path.push(DEFAULT_WINDOWS_CONFIG_FILE_PATH);
-            path.into_os_string()
+            path
         } else {
-            OsString::new()
+            std::path::PathBuf::new()
         }
     } else if let Some(home_path) = dirs::home_dir() {
         let mut path = home_path;
         path.push(DEFAULT_UNIX_CONFIG_FILE_PATH);
-        path.into_os_string()
+        path
     } else {
-        OsString::new()
-    };
+        std::path::PathBuf::new()
+    }
+}
 
-    let path = std::path::Path::new(&config_path);
+fn get_modified_time(path: &std::path::Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+/// Writes the commented default config, refusing to replace a file that's already there.
+fn generate_config(path: &std::path::Path) -> error::Result<()> {
+    if path.exists() {
+        return Err(error::BottomError::ConfigError(format!(
+            "{} already exists.  Please move it first, or pass another location with -C.",
+            path.display()
+        )));
+    }
+
+    if let Some(parent_path) = path.parent() {
+        std::fs::create_dir_all(parent_path)?;
+    }
+    std::fs::write(path, DEFAULT_CONFIG_CONTENT)?;
+    println!("Wrote a default config file to {}", path.display());
 
-    if let Ok(config_string) = fs::read_to_string(path) {
-        Ok(toml::from_str(config_string.as_str())?)
+    Ok(())
+}
+
+fn create_config(path: &std::path::Path, profile_name: Option<&str>) -> error::Result<Config> {
+    use std::fs;
+
+    // Name the file, as the default location may not be the one the user expects
+    let to_config_error = |err: toml::de::Error| {
+        error::BottomError::ConfigError(format!("{} - {}", path.display(), err))
+    };
+
+    let mut config_value: toml::Value = if let Ok(config_string) = fs::read_to_string(path) {
+        toml::from_str(config_string.as_str()).map_err(to_config_error)?
     } else {
         if let Some(parent_path) = path.parent() {
             fs::create_dir_all(parent_path)?;
         }
         fs::File::create(path)?.write_all(DEFAULT_CONFIG_CONTENT.as_bytes())?;
-        Ok(toml::from_str(DEFAULT_CONFIG_CONTENT)?)
+        toml::from_str(DEFAULT_CONFIG_CONTENT)?
+    };
+
+    apply_profile(&mut config_value, profile_name)?;
+    config_value.try_into().map_err(to_config_error)
+}
+
+fn create_painter(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<canvas::Painter> {
+    let mut painter = canvas::Painter::default();
+    painter.colours = canvas::CanvasColours::from_scheme(get_colour_scheme(matches, config)?)?;
+    generate_config_colours(config, &mut painter)?;
+    painter.colours.generate_remaining_cpu_colours();
+    painter.initialize();
+
+    Ok(painter)
+}
+
+/// Re-reads the config file and applies its colours, layout, and filters.  The whole file is
+/// checked first, so a broken edit leaves everything as it was.  Only what's listed under
+/// "Reloading" in docs/config.md is applied; the rest needs a restart.
+fn reload_config(
+    matches: &clap::ArgMatches<'static>, config_path: &std::path::Path, app: &mut App,
+    painter: &mut canvas::Painter, rtx: &std::sync::mpsc::Sender<ResetEvent>,
+) -> error::Result<()> {
+    let config = create_config(config_path, matches.value_of("PROFILE"))?;
+    let new_fields = build_app(matches, &config)?.app_config_fields;
+    let new_painter = create_painter(matches, &config)?;
+
+    let reload_event = ResetEvent::Reload {
+        network_filter: new_fields.network_filter.clone(),
+        disk_filter: new_fields.disk_filter.clone(),
+        sensor_filter: new_fields.sensor_filter.clone(),
+        get_psi: new_fields.show_psi,
+        get_sockets: new_fields.show_sockets,
+        get_battery: new_fields.show_battery,
+    };
+    if rtx.send(reload_event).is_ok() {
+        app.update_config_fields(new_fields);
+        *painter = new_painter;
+    }
+
+    Ok(())
+}
+
+/// Gets where a file bottom keeps between sessions goes, which is next to the default config.
+fn get_data_file_path(unix_path: &str, windows_path: &str) -> Option<std::path::PathBuf> {
+    if cfg!(target_os = "windows") {
+        dirs::config_dir().map(|mut path| {
+            path.push(windows_path);
+            path
+        })
+    } else {
+        dirs::home_dir().map(|mut path| {
+            path.push(unix_path);
+            path
+        })
     }
 }
 
+fn get_search_history_path() -> Option<std::path::PathBuf> {
+    get_data_file_path(
+        DEFAULT_UNIX_SEARCH_HISTORY_FILE_PATH,
+        DEFAULT_WINDOWS_SEARCH_HISTORY_FILE_PATH,
+    )
+}
+
+fn get_state_path() -> Option<std::path::PathBuf> {
+    get_data_file_path(
+        DEFAULT_UNIX_STATE_FILE_PATH,
+        DEFAULT_WINDOWS_STATE_FILE_PATH,
+    )
+}
+
+/// Reads previously saved search queries, one per line.  A missing file just means no history.
+fn read_search_history() -> Vec<String> {
+    if let Some(path) = get_search_history_path() {
+        if let Ok(history_string) = std::fs::read_to_string(path) {
+            return history_string
+                .lines()
+                .filter(|query| !query.is_empty())
+                .map(|query| query.to_string())
+                .collect();
+        }
+    }
+
+    Vec::new()
+}
+
+fn write_search_history(search_history: &[String]) -> error::Result<()> {
+    if let Some(path) = get_search_history_path() {
+        if let Some(parent_path) = path.parent() {
+            std::fs::create_dir_all(parent_path)?;
+        }
+        std::fs::write(path, search_history.join("\n"))?;
+    }
+
+    Ok(())
+}
+
+/// Reads the view saved on the last exit.  A missing or unreadable file is ignored, as it'll
+/// be replaced on the next exit anyway.
+fn read_saved_state() -> Option<app::SavedState> {
+    let state_string = std::fs::read_to_string(get_state_path()?).ok()?;
+    toml::from_str(&state_string).ok()
+}
+
+fn write_saved_state(saved_state: &app::SavedState) -> error::Result<()> {
+    if let Some(path) = get_state_path() {
+        if let Some(parent_path) = path.parent() {
+            std::fs::create_dir_all(parent_path)?;
+        }
+        let state_string = toml::to_string(saved_state)
+            .map_err(|err| error::BottomError::GenericError(err.to_string()))?;
+        std::fs::write(path, state_string)?;
+    }
+
+    Ok(())
+}
+
 fn try_drawing(
     terminal: &mut tui::terminal::Terminal<tui::backend::CrosstermBackend<std::io::Stdout>>,
     app: &mut App, painter: &mut canvas::Painter,
@@ -471,6 +758,14 @@ fn generate_config_colours(config: &Config, painter: &mut canvas::Painter) -> er
             painter.colours.set_swap_colour(swap_color)?;
         }
 
+        if let Some(vram_color) = &colours.vram_color {
+            painter.colours.set_vram_colour(vram_color)?;
+        }
+
+        if let Some(commit_color) = &colours.commit_color {
+            painter.colours.set_commit_colour(commit_color)?;
+        }
+
         if let Some(rx_color) = &colours.rx_color {
             painter.colours.set_rx_colour(rx_color)?;
         }
@@ -487,12 +782,42 @@ fn generate_config_colours(config: &Config, painter: &mut canvas::Painter) -> er
             painter.colours.set_tx_total_colour(tx_total_color)?;
         }
 
+        if let Some(disk_warning_color) = &colours.disk_warning_color {
+            painter
+                .colours
+                .set_disk_warning_colour(disk_warning_color)?;
+        }
+
+        if let Some(disk_critical_color) = &colours.disk_critical_color {
+            painter
+                .colours
+                .set_disk_critical_colour(disk_critical_color)?;
+        }
+
+        if let Some(temp_warning_color) = &colours.temp_warning_color {
+            painter
+                .colours
+                .set_temp_warning_colour(temp_warning_color)?;
+        }
+
+        if let Some(temp_critical_color) = &colours.temp_critical_color {
+            painter
+                .colours
+                .set_temp_critical_colour(temp_critical_color)?;
+        }
+
         if let Some(table_header_color) = &colours.table_header_color {
             painter
                 .colours
                 .set_table_header_colour(table_header_color)?;
         }
 
+        if let Some(table_stripe_color) = &colours.table_stripe_color {
+            painter
+                .colours
+                .set_table_stripe_colour(table_stripe_color)?;
+        }
+
         if let Some(scroll_entry_text_color) = &colours.selected_text_color {
             painter
                 .colours
@@ -514,6 +839,16 @@ fn generate_config_colours(config: &Config, painter: &mut canvas::Painter) -> er
         if let Some(graph_color) = &colours.graph_color {
             painter.colours.set_graph_colour(graph_color)?;
         }
+
+        if let Some(cursor_color) = &colours.cursor_color {
+            painter.colours.set_cursor_colour(cursor_color)?;
+        }
+
+        if let Some(invalid_search_color) = &colours.invalid_search_color {
+            painter
+                .colours
+                .set_invalid_query_colour(invalid_search_color)?;
+        }
     }
 
     Ok(())
@@ -549,12 +884,188 @@ fn panic_hook(panic_info: &PanicInfo<'_>) {
     .unwrap();
 }
 
+/// Converts all of the collected data into what's drawn.
+fn convert_all_data(app: &mut App) {
+    // Network
+    let network_data = convert_network_data_points(
+        &app.data_collection,
+        app.net_state.current_display_time,
+        app.is_frozen,
+        app.app_config_fields.network_use_bits,
+        app.app_config_fields.network_scale,
+    );
+    app.canvas_data.network_data_rx = network_data.rx;
+    app.canvas_data.network_data_tx = network_data.tx;
+    app.canvas_data.rx_display = network_data.rx_display;
+    app.canvas_data.tx_display = network_data.tx_display;
+    app.canvas_data.total_rx_display = network_data.total_rx_display;
+    app.canvas_data.total_tx_display = network_data.total_tx_display;
+    app.canvas_data.session_rx_display = network_data.session_rx_display;
+    app.canvas_data.session_tx_display = network_data.session_tx_display;
+    app.canvas_data.network_interface_data = network_data.interfaces;
+
+    // Disk
+    let (disk_data, disk_usage) = convert_disk_row(app);
+    app.canvas_data.disk_data = disk_data;
+    app.canvas_data.disk_usage = disk_usage;
+
+    // Sockets
+    if app.app_config_fields.show_sockets {
+        app.canvas_data.socket_data = convert_socket_rows(&app.data_collection);
+    }
+
+    // Batteries
+    if app.app_config_fields.show_battery {
+        app.canvas_data.battery_data = convert_battery_rows(&app.data_collection);
+        app.canvas_data.battery_health_data = convert_battery_health_rows(&app.data_collection);
+        app.canvas_data.power_data = convert_power_data_points(
+            &app.data_collection,
+            app.battery_state.current_display_time,
+            app.is_frozen,
+        );
+        app.canvas_data.power_label = convert_power_label(&app.data_collection);
+    }
+
+    // Temperatures
+    let (temp_sensor_data, temp_sensor_levels) = convert_temp_row(app);
+    app.canvas_data.temp_sensor_data = temp_sensor_data;
+    app.canvas_data.temp_sensor_levels = temp_sensor_levels;
+    if app.app_config_fields.show_cpu_temp {
+        app.canvas_data.cpu_temp_label = convert_cpu_temp_label(app);
+    }
+    // Memory
+    app.canvas_data.mem_data = convert_mem_data_points(
+        &app.data_collection,
+        app.mem_state.current_display_time,
+        app.is_frozen,
+    );
+    app.canvas_data.swap_data = convert_swap_data_points(
+        &app.data_collection,
+        app.mem_state.current_display_time,
+        app.is_frozen,
+    );
+    let memory_and_swap_labels =
+        convert_mem_labels(&app.data_collection, app.app_config_fields.mem_as_value);
+    app.canvas_data.mem_label = memory_and_swap_labels.0;
+    app.canvas_data.swap_label = memory_and_swap_labels.1;
+    app.canvas_data.mem_pool_label = convert_mem_pool_label(&app.data_collection);
+    app.canvas_data.vram_data = convert_vram_data_points(
+        &app.data_collection,
+        app.mem_state.current_display_time,
+        app.is_frozen,
+    );
+    app.canvas_data.vram_labels =
+        convert_vram_labels(&app.data_collection, app.app_config_fields.mem_as_value);
+    app.canvas_data.commit_data = convert_commit_data_points(
+        &app.data_collection,
+        app.mem_state.current_display_time,
+        app.is_frozen,
+    );
+    app.canvas_data.commit_label =
+        convert_commit_label(&app.data_collection, app.app_config_fields.mem_as_value);
+    app.canvas_data.numa_data = convert_numa_data_points(
+        &app.data_collection,
+        app.mem_state.current_display_time,
+        app.is_frozen,
+    );
+    app.canvas_data.numa_labels =
+        convert_numa_labels(&app.data_collection, app.app_config_fields.mem_as_value);
+
+    // Pressure
+    if app.app_config_fields.show_psi {
+        app.canvas_data.psi_data = convert_psi_data_points(
+            &app.data_collection,
+            app.psi_state.current_display_time,
+            app.is_frozen,
+        );
+        app.canvas_data.psi_labels = convert_psi_labels(&app.data_collection);
+    }
+
+    // Disk I/O
+    if app.app_config_fields.show_disk_io_graph {
+        app.canvas_data.io_data = convert_io_data_points(
+            &app.data_collection,
+            app.disk_io_state.current_display_time,
+            app.is_frozen,
+        );
+        app.canvas_data.io_labels = convert_io_labels(&app.data_collection);
+    }
+
+    // GPUs
+    if app.app_config_fields.show_gpu {
+        app.canvas_data.gpu_data = convert_gpu_data_points(
+            &app.data_collection,
+            app.gpu_state.current_display_time,
+            app.is_frozen,
+        );
+        app.canvas_data.gpu_vram_data = convert_vram_data_points(
+            &app.data_collection,
+            app.gpu_state.current_display_time,
+            app.is_frozen,
+        );
+        app.canvas_data.gpu_labels = convert_gpu_labels(app);
+    }
+
+    // Temperature history
+    if app.app_config_fields.show_temp_graph {
+        app.canvas_data.temp_data = convert_temp_data_points(
+            &app.data_collection,
+            app.temp_graph_state.current_display_time,
+            app.is_frozen,
+        );
+        app.canvas_data.temp_labels = convert_temp_labels(app);
+    }
+
+    // Ping
+    if !app.app_config_fields.ping_hosts.is_empty() {
+        app.canvas_data.ping_data = convert_ping_data_points(
+            &app.data_collection,
+            app.ping_state.current_display_time,
+            app.is_frozen,
+        );
+        app.canvas_data.ping_labels = convert_ping_labels(&app.data_collection);
+    }
+
+    // CPU
+    app.canvas_data.cpu_data = convert_cpu_data_points(
+        &app.data_collection,
+        app.cpu_state.current_display_time,
+        app.is_frozen,
+    );
+
+    // Processes.  The shown rows are let go of first, so the rows they share are updated in
+    // place rather than copied.
+    app.canvas_data.finalized_process_data.clear();
+    convert_process_data(
+        &app.data_collection,
+        &mut app.canvas_data.process_data,
+        &mut app.canvas_data.process_rows,
+        &mut app.canvas_data.grouped_process_data,
+        app.app_config_fields.group_ignore_case,
+        app.app_config_fields.group_by_command,
+    );
+    update_final_process_list(app);
+}
+
 fn handle_force_redraws(app: &mut App) {
+    // Everything is redone to show another point in the history
+    if app.force_update_time_travel {
+        convert_all_data(app);
+        app.force_update_time_travel = false;
+    }
+
     if app.force_update_processes {
         update_final_process_list(app);
         app.force_update_processes = false;
     }
 
+    if app.force_update_disks {
+        let (disk_data, disk_usage) = convert_disk_row(app);
+        app.canvas_data.disk_data = disk_data;
+        app.canvas_data.disk_usage = disk_usage;
+        app.force_update_disks = false;
+    }
+
     if app.cpu_state.force_update {
         app.canvas_data.cpu_data = convert_cpu_data_points(
             &app.data_collection,
@@ -575,28 +1086,165 @@ fn handle_force_redraws(app: &mut App) {
             app.mem_state.current_display_time,
             app.is_frozen,
         );
+        app.canvas_data.vram_data = convert_vram_data_points(
+            &app.data_collection,
+            app.mem_state.current_display_time,
+            app.is_frozen,
+        );
+        app.canvas_data.commit_data = convert_commit_data_points(
+            &app.data_collection,
+            app.mem_state.current_display_time,
+            app.is_frozen,
+        );
+        app.canvas_data.numa_data = convert_numa_data_points(
+            &app.data_collection,
+            app.mem_state.current_display_time,
+            app.is_frozen,
+        );
         app.mem_state.force_update = false;
     }
 
     if app.net_state.force_update {
-        let (rx, tx) = get_rx_tx_data_points(
+        let network_data = convert_network_data_points(
             &app.data_collection,
             app.net_state.current_display_time,
             app.is_frozen,
+            app.app_config_fields.network_use_bits,
+            app.app_config_fields.network_scale,
         );
-        app.canvas_data.network_data_rx = rx;
-        app.canvas_data.network_data_tx = tx;
+        app.canvas_data.network_data_rx = network_data.rx;
+        app.canvas_data.network_data_tx = network_data.tx;
+        // Only the points change when zooming, so keep the (possibly frozen) labels
+        for (interface, converted_interface) in app
+            .canvas_data
+            .network_interface_data
+            .iter_mut()
+            .zip(network_data.interfaces)
+        {
+            interface.rx = converted_interface.rx;
+            interface.tx = converted_interface.tx;
+        }
         app.net_state.force_update = false;
     }
+
+    if app.psi_state.force_update {
+        app.canvas_data.psi_data = convert_psi_data_points(
+            &app.data_collection,
+            app.psi_state.current_display_time,
+            app.is_frozen,
+        );
+        app.psi_state.force_update = false;
+    }
+
+    if app.ping_state.force_update {
+        app.canvas_data.ping_data = convert_ping_data_points(
+            &app.data_collection,
+            app.ping_state.current_display_time,
+            app.is_frozen,
+        );
+        app.ping_state.force_update = false;
+    }
+
+    if app.disk_io_state.force_update {
+        app.canvas_data.io_data = convert_io_data_points(
+            &app.data_collection,
+            app.disk_io_state.current_display_time,
+            app.is_frozen,
+        );
+        app.disk_io_state.force_update = false;
+    }
+
+    if app.temp_graph_state.force_update {
+        app.canvas_data.temp_data = convert_temp_data_points(
+            &app.data_collection,
+            app.temp_graph_state.current_display_time,
+            app.is_frozen,
+        );
+        app.temp_graph_state.force_update = false;
+    }
+
+    if app.gpu_state.force_update {
+        app.canvas_data.gpu_data = convert_gpu_data_points(
+            &app.data_collection,
+            app.gpu_state.current_display_time,
+            app.is_frozen,
+        );
+        app.canvas_data.gpu_vram_data = convert_vram_data_points(
+            &app.data_collection,
+            app.gpu_state.current_display_time,
+            app.is_frozen,
+        );
+        app.gpu_state.force_update = false;
+    }
+
+    if app.battery_state.force_update {
+        app.canvas_data.power_data = convert_power_data_points(
+            &app.data_collection,
+            app.battery_state.current_display_time,
+            app.is_frozen,
+        );
+        app.battery_state.force_update = false;
+    }
 }
 
 fn update_final_process_list(app: &mut App) {
-    let mut filtered_process_data: Vec<ConvertedProcessData> = if app.is_grouped() {
+    let mut filtered_process_data: Vec<Rc<ConvertedProcessData>> = if app.is_grouped_by_user() {
+        // Aggregate from the ungrouped data so hidden kernel threads don't count towards a user
+        let mut user_hashmap: HashMap<&str, ConvertedProcessData> = HashMap::new();
+        for process in app.canvas_data.process_data.values() {
+            if app.app_config_fields.hide_kernel_threads && process.is_kernel_thread {
+                continue;
+            }
+
+            let user: &str = if process.user.is_empty() {
+                "N/A"
+            } else {
+                &process.user
+            };
+            let entry = user_hashmap
+                .entry(user)
+                .or_insert_with(|| ConvertedProcessData {
+                    pid: process.pid,
+                    name: user.to_string(),
+                    cpu_usage: 0.0,
+                    mem_usage: 0.0,
+                    swap_usage: 0,
+                    group_pids: Vec::new(),
+                    is_kernel_thread: false,
+                    cpu_affinity: String::new(),
+                });
+            entry.cpu_usage += process.cpu_usage_percent;
+            entry.mem_usage += process.mem_usage_percent;
+            entry.swap_usage += process.swap_usage_bytes;
+            entry.group_pids.push(process.pid);
+        }
+
+        user_hashmap
+            .into_iter()
+            .filter_map(|(_user, user_process)| {
+                if !app
+                    .process_search_state
+                    .search_state
+                    .is_invalid_or_blank_search()
+                {
+                    if let Some(Ok(matcher)) = app.get_current_regex_matcher() {
+                        if !matcher.is_match(&user_process.name) {
+                            return None;
+                        }
+                    }
+                }
+
+                Some(Rc::new(user_process))
+            })
+            .collect::<Vec<_>>()
+    } else if app.is_grouped() {
         app.canvas_data
             .grouped_process_data
-            .iter()
+            .values()
             .filter(|process| {
-                if app
+                if app.app_config_fields.hide_kernel_threads && process.is_kernel_thread {
+                    return false;
+                } else if app
                     .process_search_state
                     .search_state
                     .is_invalid_or_blank_search()
@@ -614,9 +1262,13 @@ fn update_final_process_list(app: &mut App) {
             .collect::<Vec<_>>()
     } else {
         app.canvas_data
-            .process_data
-            .iter()
-            .filter_map(|(_pid, process)| {
+            .process_rows
+            .values()
+            .filter(|process| {
+                if app.app_config_fields.hide_kernel_threads && process.is_kernel_thread {
+                    return false;
+                }
+
                 let mut result = true;
 
                 if !app
@@ -635,18 +1287,9 @@ fn update_final_process_list(app: &mut App) {
                     }
                 }
 
-                if result {
-                    return Some(ConvertedProcessData {
-                        pid: process.pid,
-                        name: process.name.clone(),
-                        cpu_usage: process.cpu_usage_percent,
-                        mem_usage: process.mem_usage_percent,
-                        group_pids: vec![process.pid],
-                    });
-                }
-
-                None
+                result
             })
+            .cloned()
             .collect::<Vec<_>>()
     };
 
@@ -654,33 +1297,59 @@ fn update_final_process_list(app: &mut App) {
     app.canvas_data.finalized_process_data = filtered_process_data;
 }
 
-fn sort_process_data(to_sort_vec: &mut Vec<ConvertedProcessData>, app: &App) {
-    to_sort_vec.sort_by(|a, b| utils::gen_util::get_ordering(&a.name, &b.name, false));
-
-    match app.process_sorting_type {
+fn compare_process_data(
+    a: &ConvertedProcessData, b: &ConvertedProcessData, sorting_type: &ProcessSorting,
+    reverse_order: bool, is_grouped: bool,
+) -> std::cmp::Ordering {
+    match sorting_type {
         ProcessSorting::CPU => {
-            to_sort_vec.sort_by(|a, b| {
-                utils::gen_util::get_ordering(a.cpu_usage, b.cpu_usage, app.process_sorting_reverse)
-            });
+            utils::gen_util::get_ordering(a.cpu_usage, b.cpu_usage, reverse_order)
         }
         ProcessSorting::MEM => {
-            to_sort_vec.sort_by(|a, b| {
-                utils::gen_util::get_ordering(a.mem_usage, b.mem_usage, app.process_sorting_reverse)
-            });
+            utils::gen_util::get_ordering(a.mem_usage, b.mem_usage, reverse_order)
+        }
+        ProcessSorting::SWAP => {
+            utils::gen_util::get_ordering(a.swap_usage, b.swap_usage, reverse_order)
         }
-        ProcessSorting::NAME => to_sort_vec.sort_by(|a, b| {
-            utils::gen_util::get_ordering(&a.name, &b.name, app.process_sorting_reverse)
-        }),
+        ProcessSorting::NAME => utils::gen_util::get_ordering(&a.name, &b.name, reverse_order),
         ProcessSorting::PID => {
-            if !app.is_grouped() {
-                to_sort_vec.sort_by(|a, b| {
-                    utils::gen_util::get_ordering(a.pid, b.pid, app.process_sorting_reverse)
-                });
+            // PIDs are meaningless for a group
+            if is_grouped {
+                std::cmp::Ordering::Equal
+            } else {
+                utils::gen_util::get_ordering(a.pid, b.pid, reverse_order)
             }
         }
     }
 }
 
+fn sort_process_data(to_sort_vec: &mut Vec<Rc<ConvertedProcessData>>, app: &App) {
+    // Ties in the primary key are broken by the secondary keys in order, so that rows
+    // with identical values don't jitter around between refreshes.
+    to_sort_vec.sort_by(|a, b| {
+        app.app_config_fields.secondary_sort_keys.iter().fold(
+            compare_process_data(
+                a,
+                b,
+                &app.process_sorting_type,
+                app.process_sorting_reverse,
+                app.is_grouped(),
+            ),
+            |ordering, sort_key| {
+                ordering.then_with(|| {
+                    compare_process_data(
+                        a,
+                        b,
+                        sort_key,
+                        sort_key.is_reverse_by_default(),
+                        app.is_grouped(),
+                    )
+                })
+            },
+        )
+    });
+}
+
 fn create_input_thread(
     tx: std::sync::mpsc::Sender<
         BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
@@ -716,34 +1385,220 @@ fn create_input_thread(
     });
 }
 
+/// Sets up collection the same way as the interface would, for modes that don't start it.
+fn get_harvester_options(fields: &app::AppConfigFields) -> data_harvester::HarvesterOptions {
+    data_harvester::HarvesterOptions {
+        temperature_type: fields.temperature_type.clone(),
+        process_options: data_harvester::processes::ProcessOptions {
+            use_current_cpu_total: fields.use_current_cpu_total,
+            get_process_swap: fields.show_process_swap,
+            get_process_affinity: fields.show_process_affinity,
+        },
+        get_cpu_frequency: fields.show_cpu_frequency,
+        subtract_arc: fields.subtract_arc,
+        get_psi: fields.show_psi,
+        get_sockets: fields.show_sockets,
+        get_disk_temp: fields.show_disk_temp,
+        get_disk_inodes: fields.show_disk_inodes,
+        get_network_disks: !fields.hide_network_disks,
+        network_disk_timeout: fields.network_disk_timeout,
+        ping_hosts: fields.ping_hosts.clone(),
+        network_filter: fields.network_filter.clone(),
+        disk_filter: fields.disk_filter.clone(),
+        sensor_filter: fields.sensor_filter.clone(),
+        get_battery: fields.show_battery,
+    }
+}
+
+/// A harvester for collecting outside of the interface, which leaves out what's only shown in it.
+fn create_data_state(fields: &app::AppConfigFields) -> data_harvester::DataState {
+    let mut data_state = data_harvester::DataState::default();
+    data_state.set_options(data_harvester::HarvesterOptions {
+        get_psi: false,
+        get_sockets: false,
+        get_disk_inodes: false,
+        ping_hosts: Vec::new(),
+        get_battery: false,
+        ..get_harvester_options(fields)
+    });
+
+    data_state
+}
+
+/// Collects everything once and prints it as JSON, for scripts rather than people.
+fn dump_metrics(app: &App) -> error::Result<()> {
+    let fields = &app.app_config_fields;
+    let mut data_state = create_data_state(fields);
+
+    // Rates like CPU usage are measured between two harvests, so the first only sets where they
+    // start from
+    data_state.init();
+    futures::executor::block_on(data_state.update_data());
+
+    let snapshot = metrics::MetricsSnapshot::new(&data_state.data, &fields.temperature_type);
+    println!("{}", serde_json::to_string_pretty(&snapshot)?);
+
+    Ok(())
+}
+
+/// Prints every harvest as a line of JSON for a client reading over SSH, until it goes away.
+fn run_agent(app: &App) -> error::Result<()> {
+    let fields = &app.app_config_fields;
+    let mut data_state = create_data_state(fields);
+    data_state.init();
+    let stdout = stdout();
+    loop {
+        thread::sleep(Duration::from_millis(fields.update_rate_in_milliseconds));
+        futures::executor::block_on(data_state.update_data());
+
+        let mut stdout_lock = stdout.lock();
+        let written = serde_json::to_writer(&mut stdout_lock, &data_state.data)
+            .map_err(error::BottomError::from)
+            .and_then(|_| {
+                writeln!(stdout_lock)?;
+                stdout_lock.flush()?;
+                Ok(())
+            });
+        if written.is_err() {
+            // The client disconnected
+            return Ok(());
+        }
+    }
+}
+
+/// The command run on remote hosts, with the collection settings they should share with this one.
+fn get_remote_agent_command(fields: &app::AppConfigFields) -> String {
+    let temperature_flag = match fields.temperature_type {
+        data_harvester::temperature::TemperatureType::Celsius => "--celsius",
+        data_harvester::temperature::TemperatureType::Kelvin => "--kelvin",
+        data_harvester::temperature::TemperatureType::Fahrenheit => "--fahrenheit",
+    };
+
+    format!(
+        "{} --agent --rate {} {}",
+        fields.remote_command, fields.update_rate_in_milliseconds, temperature_flag
+    )
+}
+
+/// Collects metrics at the update rate and sends them to the outputs, without drawing anything,
+/// until killed.
+fn run_headless(app: &App, mut metrics_outputs: metrics::MetricsOutputs) -> error::Result<()> {
+    let fields = &app.app_config_fields;
+    if metrics_outputs.is_empty() && fields.alert_rules.is_empty() {
+        return Err(error::BottomError::InvalidArg(
+            "Headless mode needs somewhere to send metrics, like --log_metrics, --prometheus_port, \
+             --api_port, or --line_protocol, or alerts to check."
+                .to_string(),
+        ));
+    }
+
+    let mut alert_state = app::alerts::AlertState::default();
+    let mut data_state = create_data_state(fields);
+    data_state.init();
+    loop {
+        thread::sleep(Duration::from_millis(fields.update_rate_in_milliseconds));
+        futures::executor::block_on(data_state.update_data());
+
+        if let Err(err) = metrics_outputs.update(&data_state.data, &fields.temperature_type) {
+            eprintln!("{}", err);
+        }
+        for event in alert_state.update(&fields.alert_rules, &data_state.data) {
+            eprintln!("{}", event.message);
+        }
+    }
+}
+
 fn create_event_thread(
     tx: std::sync::mpsc::Sender<
         BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
     >,
-    rrx: std::sync::mpsc::Receiver<ResetEvent>, use_current_cpu_total: bool,
-    update_rate_in_milliseconds: u64, temp_type: data_harvester::temperature::TemperatureType,
-    show_average_cpu: bool,
+    rrx: std::sync::mpsc::Receiver<ResetEvent>,
+    harvester_options: data_harvester::HarvesterOptions, update_rate_in_milliseconds: u64,
+    remote_agent_command: String,
 ) {
     thread::spawn(move || {
         let tx = tx.clone();
         let mut data_state = data_harvester::DataState::default();
+        // The options decide what the first harvest in `init` collects, so they come first
+        data_state.set_options(harvester_options);
         data_state.init();
-        data_state.set_temperature_type(temp_type);
-        data_state.set_use_current_cpu_total(use_current_cpu_total);
-        data_state.set_show_average_cpu(show_average_cpu);
+        let mut remote_harvester = None;
         loop {
             if let Ok(message) = rrx.try_recv() {
                 match message {
                     ResetEvent::Reset => {
                         data_state.data.first_run_cleanup();
                     }
+                    ResetEvent::Reload {
+                        network_filter,
+                        disk_filter,
+                        sensor_filter,
+                        get_psi,
+                        get_sockets,
+                        get_battery,
+                    } => {
+                        data_state.set_network_filter(network_filter);
+                        data_state.set_disk_filter(disk_filter);
+                        data_state.set_sensor_filter(sensor_filter);
+                        data_state.set_get_psi(get_psi);
+                        data_state.set_get_sockets(get_sockets);
+                        data_state.set_get_battery(get_battery);
+                    }
+                    ResetEvent::SwitchHost(host) => {
+                        // Dropping the old connection closes it
+                        remote_harvester = None;
+                        data_state.data.first_run_cleanup();
+                        if let Some(host) = host {
+                            remote_harvester =
+                                connect_remote_host(host, &remote_agent_command, tx.clone());
+                        }
+                    }
                 }
             }
-            futures::executor::block_on(data_state.update_data());
-            let event = BottomEvent::Update(Box::from(data_state.data));
-            data_state.data = data_harvester::Data::default();
-            tx.send(event).unwrap();
+            // A remote host sends its own harvests, so there's nothing to collect here
+            if remote_harvester.is_none() {
+                futures::executor::block_on(data_state.update_data());
+                // The harvest is kept, so anything that fails next time keeps its last good value
+                let event = BottomEvent::Update(Box::from(data_state.data.clone()));
+                tx.send(event).unwrap();
+            }
             thread::sleep(Duration::from_millis(update_rate_in_milliseconds));
         }
     });
 }
+
+/// Starts sending the host's harvests as updates, or says why it couldn't.
+fn connect_remote_host(
+    host: String, agent_command: &str,
+    tx: std::sync::mpsc::Sender<
+        BottomEvent<crossterm::event::KeyEvent, crossterm::event::MouseEvent>,
+    >,
+) -> Option<data_harvester::remote::RemoteHarvester> {
+    let event_tx = tx.clone();
+    let event_host = host.clone();
+    let connection =
+        data_harvester::remote::RemoteHarvester::connect(&host, agent_command, move |event| {
+            let event = match event {
+                data_harvester::remote::RemoteEvent::Harvest(data) => BottomEvent::Update(data),
+                data_harvester::remote::RemoteEvent::Disconnected(reason) => {
+                    BottomEvent::RemoteDisconnected {
+                        host: event_host.clone(),
+                        reason,
+                    }
+                }
+            };
+            // The interface may have already quit
+            let _ = event_tx.send(event);
+        });
+
+    match connection {
+        Ok(remote_harvester) => Some(remote_harvester),
+        Err(err) => {
+            let _ = tx.send(BottomEvent::RemoteDisconnected {
+                host,
+                reason: format!("couldn't run ssh - {}", err),
+            });
+            None
+        }
+    }
+}
@@ -0,0 +1,305 @@
+//! Everything collected in a harvest, laid out for scripts and other tools rather than for
+//! drawing.  Field names carry their units so the output can be read without the source.
+
+use serde::Serialize;
+
+use crate::{
+    app::{
+        data_harvester::{temperature::TemperatureType, Data},
+        AppConfigFields,
+    },
+    utils::{
+        error::{self, BottomError},
+        gen_util::get_hostname,
+    },
+};
+
+pub mod api;
+mod http;
+pub mod line_protocol;
+pub mod logger;
+pub mod prometheus;
+
+use api::JsonApi;
+use line_protocol::LineProtocolSink;
+use logger::MetricsLogger;
+use prometheus::PrometheusExporter;
+
+#[derive(Serialize)]
+pub struct MetricsSnapshot<'a> {
+    /// When the snapshot was made, in RFC 3339.
+    pub timestamp: String,
+    pub hostname: String,
+    pub uptime_seconds: u64,
+    /// The 1, 5, and 15 minute load averages.  Only set on Unix.
+    pub load_average: Option<[f64; 3]>,
+    pub cpu: Vec<CpuMetrics<'a>>,
+    pub memory: MemoryMetrics,
+    pub swap: MemoryMetrics,
+    pub network: NetworkMetrics<'a>,
+    pub disks: Vec<DiskMetrics<'a>>,
+    /// The unit of every temperature, one of "celsius", "kelvin", or "fahrenheit".
+    pub temperature_unit: &'static str,
+    pub temperatures: Vec<TemperatureMetrics<'a>>,
+    pub processes: Vec<ProcessMetrics<'a>>,
+    /// What failed to be collected, in which case its values are left empty or at zero.
+    pub errors: Vec<ErrorMetrics<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct CpuMetrics<'a> {
+    /// "AVG" for the average of every core, then each core.
+    pub name: &'a str,
+    pub usage_percent: f64,
+    /// Only gathered if the frequency is shown, and 0 if unavailable.
+    pub frequency_mhz: u64,
+}
+
+#[derive(Serialize)]
+pub struct MemoryMetrics {
+    pub used_bytes: u64,
+    pub total_bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct NetworkMetrics<'a> {
+    pub rx_bytes_per_second: u64,
+    pub tx_bytes_per_second: u64,
+    /// Received since boot.
+    pub total_rx_bytes: u64,
+    /// Sent since boot.
+    pub total_tx_bytes: u64,
+    pub interfaces: Vec<InterfaceMetrics<'a>>,
+}
+
+#[derive(Serialize)]
+pub struct InterfaceMetrics<'a> {
+    pub name: &'a str,
+    pub rx_bytes_per_second: u64,
+    pub tx_bytes_per_second: u64,
+    pub total_rx_bytes: u64,
+    pub total_tx_bytes: u64,
+}
+
+#[derive(Serialize)]
+pub struct DiskMetrics<'a> {
+    pub name: &'a str,
+    pub mount_point: &'a str,
+    pub used_bytes: u64,
+    pub free_bytes: u64,
+    pub total_bytes: u64,
+    /// Read since boot, if the device's IO is known.
+    pub read_bytes: Option<u64>,
+    /// Written since boot, if the device's IO is known.
+    pub write_bytes: Option<u64>,
+}
+
+#[derive(Serialize)]
+pub struct TemperatureMetrics<'a> {
+    pub name: &'a str,
+    pub temperature: f32,
+}
+
+#[derive(Serialize)]
+pub struct ProcessMetrics<'a> {
+    pub pid: u32,
+    pub name: &'a str,
+    pub command: &'a str,
+    /// Only gathered on Linux.
+    pub user: &'a str,
+    pub cpu_percent: f64,
+    pub mem_percent: f64,
+}
+
+#[derive(Serialize)]
+pub struct ErrorMetrics<'a> {
+    pub source: &'static str,
+    pub message: &'a str,
+}
+
+/// Everywhere harvests are sent other than the interface.
+pub struct MetricsOutputs {
+    pub logger: Option<MetricsLogger>,
+    pub prometheus: Option<PrometheusExporter>,
+    pub api: Option<JsonApi>,
+    pub line_protocol: Option<LineProtocolSink>,
+}
+
+impl MetricsOutputs {
+    /// Sets up the outputs that were asked for, starting any servers.
+    pub fn new(fields: &AppConfigFields) -> error::Result<Self> {
+        let logger = fields
+            .metrics_log_path
+            .clone()
+            .map(|path| MetricsLogger::new(path, fields.metrics_log_max_size_bytes));
+        let prometheus = match fields.prometheus_port {
+            Some(port) => Some(
+                PrometheusExporter::new(fields.prometheus_address, port).map_err(|err| {
+                    BottomError::InvalidIO(format!(
+                        "Unable to serve Prometheus metrics on {} port {} - {}",
+                        fields.prometheus_address, port, err
+                    ))
+                })?,
+            ),
+            None => None,
+        };
+        let api = match fields.api_port {
+            Some(port) => Some(JsonApi::new(port).map_err(|err| {
+                BottomError::InvalidIO(format!(
+                    "Unable to serve the API on port {} - {}",
+                    port, err
+                ))
+            })?),
+            None => None,
+        };
+
+        let line_protocol = fields
+            .line_protocol_target
+            .clone()
+            .map(|target| LineProtocolSink::new(target, fields.line_protocol_interval));
+
+        Ok(MetricsOutputs {
+            logger,
+            prometheus,
+            api,
+            line_protocol,
+        })
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.logger.is_none()
+            && self.prometheus.is_none()
+            && self.api.is_none()
+            && self.line_protocol.is_none()
+    }
+
+    pub fn update(&mut self, data: &Data, temperature_type: &TemperatureType) -> error::Result<()> {
+        if self.is_empty() {
+            return Ok(());
+        }
+
+        // Every output is updated even if one fails, with the first failure given back
+        let snapshot = MetricsSnapshot::new(data, temperature_type);
+        let mut result = Ok(());
+        if let Some(prometheus) = &self.prometheus {
+            prometheus.update(&snapshot);
+        }
+        if let Some(api) = &self.api {
+            result = result.and(api.update(&snapshot));
+        }
+        if let Some(line_protocol) = &mut self.line_protocol {
+            result = result.and(line_protocol.update(&snapshot));
+        }
+        if let Some(logger) = &mut self.logger {
+            result = result.and(logger.log(&snapshot).map_err(|err| {
+                BottomError::GenericError(format!("Unable to log metrics - {}", err))
+            }));
+        }
+
+        result
+    }
+}
+
+impl<'a> MetricsSnapshot<'a> {
+    pub fn new(data: &'a Data, temperature_type: &TemperatureType) -> Self {
+        MetricsSnapshot {
+            timestamp: chrono::Local::now().to_rfc3339(),
+            hostname: get_hostname().unwrap_or_default(),
+            uptime_seconds: data.uptime,
+            load_average: if cfg!(unix) {
+                Some(data.load_avg)
+            } else {
+                None
+            },
+            cpu: data
+                .cpu
+                .iter()
+                .map(|cpu| CpuMetrics {
+                    name: &cpu.cpu_name,
+                    usage_percent: cpu.cpu_usage,
+                    frequency_mhz: cpu.frequency_mhz,
+                })
+                .collect(),
+            memory: MemoryMetrics {
+                used_bytes: data.memory.mem_used_in_mb * 1_000_000,
+                total_bytes: data.memory.mem_total_in_mb * 1_000_000,
+            },
+            swap: MemoryMetrics {
+                used_bytes: data.swap.mem_used_in_mb * 1_000_000,
+                total_bytes: data.swap.mem_total_in_mb * 1_000_000,
+            },
+            network: NetworkMetrics {
+                rx_bytes_per_second: data.network.rx,
+                tx_bytes_per_second: data.network.tx,
+                total_rx_bytes: data.network.total_rx,
+                total_tx_bytes: data.network.total_tx,
+                interfaces: data
+                    .network
+                    .interfaces
+                    .iter()
+                    .map(|interface| InterfaceMetrics {
+                        name: &interface.name,
+                        rx_bytes_per_second: interface.rx,
+                        tx_bytes_per_second: interface.tx,
+                        total_rx_bytes: interface.total_rx,
+                        total_tx_bytes: interface.total_tx,
+                    })
+                    .collect(),
+            },
+            disks: data
+                .disks
+                .iter()
+                .map(|disk| {
+                    // IO is keyed by the device's name without its path, like in the disk widget
+                    let io = disk
+                        .name
+                        .split('/')
+                        .last()
+                        .and_then(|device_name| data.io.get(device_name));
+                    DiskMetrics {
+                        name: &disk.name,
+                        mount_point: &disk.mount_point,
+                        used_bytes: disk.used_space,
+                        free_bytes: disk.free_space,
+                        total_bytes: disk.total_space,
+                        read_bytes: io.map(|io| io.read_bytes),
+                        write_bytes: io.map(|io| io.write_bytes),
+                    }
+                })
+                .collect(),
+            temperature_unit: match temperature_type {
+                TemperatureType::Celsius => "celsius",
+                TemperatureType::Kelvin => "kelvin",
+                TemperatureType::Fahrenheit => "fahrenheit",
+            },
+            temperatures: data
+                .temperature_sensors
+                .iter()
+                .map(|sensor| TemperatureMetrics {
+                    name: &sensor.component_name,
+                    temperature: sensor.temperature,
+                })
+                .collect(),
+            processes: data
+                .list_of_processes
+                .iter()
+                .map(|process| ProcessMetrics {
+                    pid: process.pid,
+                    name: &process.name,
+                    command: &process.command,
+                    user: &process.user,
+                    cpu_percent: process.cpu_usage_percent,
+                    mem_percent: process.mem_usage_percent,
+                })
+                .collect(),
+            errors: data
+                .errors
+                .iter()
+                .map(|error| ErrorMetrics {
+                    source: error.source.get_name(),
+                    message: &error.message,
+                })
+                .collect(),
+        }
+    }
+}
@@ -0,0 +1,88 @@
+//! Serves the latest harvest as JSON, so scripts and dashboards can ask a running bottom what it
+//! sees.  `/` has everything, and each section of it has its own path, like `/cpu` or
+//! `/processes`.  It's only served locally, as commands and users in the process list aren't
+//! meant for other machines, and only to requests for a local host name, so web pages can't read
+//! it either.
+
+use std::{
+    collections::HashMap,
+    net::TcpListener,
+    sync::{Arc, Mutex},
+};
+
+use super::{
+    http::{self, Response},
+    MetricsSnapshot,
+};
+use crate::utils::error;
+
+pub struct JsonApi {
+    /// The body served at each path, redrawn each harvest rather than each request.
+    bodies: Arc<Mutex<HashMap<&'static str, String>>>,
+}
+
+impl JsonApi {
+    pub fn new(port: u16) -> std::io::Result<Self> {
+        let listener = TcpListener::bind(("127.0.0.1", port))?;
+        let bodies: Arc<Mutex<HashMap<&'static str, String>>> =
+            Arc::new(Mutex::new(HashMap::new()));
+
+        let served_bodies = bodies.clone();
+        http::spawn_server(listener, Some(http::LOCAL_HOSTS), move |path| {
+            let path = if path.len() > 1 {
+                path.trim_end_matches('/')
+            } else {
+                path
+            };
+            let body = served_bodies.lock().ok()?.get(path)?.clone();
+            Some(Response {
+                content_type: "application/json",
+                body,
+            })
+        });
+
+        Ok(JsonApi { bodies })
+    }
+
+    pub fn update(&self, snapshot: &MetricsSnapshot<'_>) -> error::Result<()> {
+        let mut new_bodies = HashMap::new();
+        new_bodies.insert("/", serde_json::to_string(snapshot)?);
+        new_bodies.insert("/cpu", serde_json::to_string(&snapshot.cpu)?);
+        new_bodies.insert(
+            "/memory",
+            serde_json::to_string(&MemorySection {
+                memory: &snapshot.memory,
+                swap: &snapshot.swap,
+            })?,
+        );
+        new_bodies.insert("/network", serde_json::to_string(&snapshot.network)?);
+        new_bodies.insert("/disks", serde_json::to_string(&snapshot.disks)?);
+        new_bodies.insert(
+            "/temperatures",
+            serde_json::to_string(&TemperatureSection {
+                temperature_unit: snapshot.temperature_unit,
+                temperatures: &snapshot.temperatures,
+            })?,
+        );
+        new_bodies.insert("/processes", serde_json::to_string(&snapshot.processes)?);
+        new_bodies.insert("/errors", serde_json::to_string(&snapshot.errors)?);
+
+        if let Ok(mut bodies) = self.bodies.lock() {
+            *bodies = new_bodies;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(serde::Serialize)]
+struct MemorySection<'a> {
+    memory: &'a super::MemoryMetrics,
+    swap: &'a super::MemoryMetrics,
+}
+
+#[derive(serde::Serialize)]
+struct TemperatureSection<'a> {
+    temperature_unit: &'static str,
+    temperatures: &'a [super::TemperatureMetrics<'a>],
+}
@@ -0,0 +1,210 @@
+//! Just enough HTTP to answer `GET` requests for metrics, so exporting them doesn't need a web
+//! framework.  Each connection is handled on its own thread, and closed after one response.
+
+use std::{
+    io::{self, BufRead, BufReader, Read, Write},
+    net::{TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+
+/// How long a client has to send its whole request, and then to take the response, before it's
+/// dropped.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// How many connections are answered at once.  Any more are closed straight away, so a flood of
+/// them can't start a flood of threads.
+const MAX_CONNECTIONS: usize = 16;
+
+/// The longest request line or header read, in bytes, so a client can't grow memory without
+/// limit.  Requests for metrics never come close.
+const MAX_LINE_LENGTH: u64 = 8 * 1024;
+
+/// How many headers are read before the request is refused.
+const MAX_HEADER_COUNT: usize = 64;
+
+/// The hosts a server that's only meant for this machine answers to.  Checking the `Host` header
+/// stops web pages from reading it through a domain they've pointed at 127.0.0.1, which browsers
+/// would otherwise treat as their own.
+pub const LOCAL_HOSTS: &[&str] = &["localhost", "127.0.0.1", "[::1]"];
+
+pub struct Response {
+    pub content_type: &'static str,
+    pub body: String,
+}
+
+/// Answers requests on the listener from a new thread, with `respond` taking the path requested
+/// and giving `None` for paths it doesn't serve.  If `allowed_hosts` is given, requests with any
+/// other `Host` are refused.
+pub fn spawn_server<F>(
+    listener: TcpListener, allowed_hosts: Option<&'static [&'static str]>, respond: F,
+) where
+    F: Fn(&str) -> Option<Response> + Send + Sync + 'static,
+{
+    let respond = Arc::new(respond);
+    let connection_count = Arc::new(AtomicUsize::new(0));
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    debug!("Failed to accept a metrics request: {}", err);
+                    continue;
+                }
+            };
+            if connection_count.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                connection_count.fetch_sub(1, Ordering::SeqCst);
+                continue;
+            }
+
+            let respond = respond.clone();
+            let connection_count = connection_count.clone();
+            thread::spawn(move || {
+                if let Err(err) = handle_connection(stream, allowed_hosts, &*respond) {
+                    debug!("Failed to answer a metrics request: {}", err);
+                }
+                connection_count.fetch_sub(1, Ordering::SeqCst);
+            });
+        }
+    });
+}
+
+/// Reads from a stream until a deadline, however the reads are split up, so a client sending a
+/// byte at a time can't keep its connection open.
+struct DeadlineReader<'a> {
+    stream: &'a TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining == Duration::from_secs(0) {
+            return Err(io::Error::new(
+                io::ErrorKind::TimedOut,
+                "the request took too long to arrive",
+            ));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+fn handle_connection<F>(
+    mut stream: TcpStream, allowed_hosts: Option<&[&str]>, respond: &F,
+) -> io::Result<()>
+where
+    F: Fn(&str) -> Option<Response>,
+{
+    stream.set_write_timeout(Some(REQUEST_TIMEOUT))?;
+    let mut reader = BufReader::new(DeadlineReader {
+        stream: &stream,
+        deadline: Instant::now() + REQUEST_TIMEOUT,
+    });
+    let request_line = match read_limited_line(&mut reader)? {
+        Some(request_line) => request_line,
+        None => return write_response(&mut stream, "400 Bad Request", None),
+    };
+    // Only the host is used, but closing with the headers unread can reset the connection before
+    // the client gets the response
+    let mut host = None;
+    let mut header_count = 0;
+    loop {
+        let header = match read_limited_line(&mut reader)? {
+            Some(header) => header,
+            None => {
+                return write_response(&mut stream, "431 Request Header Fields Too Large", None)
+            }
+        };
+        if header.trim_end().is_empty() {
+            break;
+        }
+        header_count += 1;
+        if header_count > MAX_HEADER_COUNT {
+            return write_response(&mut stream, "431 Request Header Fields Too Large", None);
+        }
+
+        let mut header_parts = header.splitn(2, ':');
+        let name = header_parts.next().unwrap_or_default();
+        if name.trim().eq_ignore_ascii_case("host") {
+            host = header_parts.next().map(|value| value.trim().to_lowercase());
+        }
+    }
+
+    if let Some(allowed_hosts) = allowed_hosts {
+        let is_allowed = host
+            .as_ref()
+            .map(|host| allowed_hosts.contains(&get_host_name(host)))
+            .unwrap_or(false);
+        if !is_allowed {
+            return write_response(&mut stream, "403 Forbidden", None);
+        }
+    }
+
+    let mut request_parts = request_line.split_whitespace();
+    let method = request_parts.next().unwrap_or_default();
+    // Query strings aren't used, so they're ignored rather than making the path unknown
+    let path = request_parts
+        .next()
+        .unwrap_or_default()
+        .split('?')
+        .next()
+        .unwrap_or_default();
+
+    let (status, response) = if method != "GET" {
+        ("405 Method Not Allowed", None)
+    } else {
+        match respond(path) {
+            Some(response) => ("200 OK", Some(response)),
+            None => ("404 Not Found", None),
+        }
+    };
+    write_response(&mut stream, status, response)
+}
+
+/// Reads a line of at most [`MAX_LINE_LENGTH`] bytes, giving `None` if it's any longer.
+fn read_limited_line(reader: &mut BufReader<DeadlineReader<'_>>) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    let read_length = reader.take(MAX_LINE_LENGTH).read_line(&mut line)?;
+    if read_length as u64 == MAX_LINE_LENGTH && !line.ends_with('\n') {
+        Ok(None)
+    } else {
+        Ok(Some(line))
+    }
+}
+
+/// The host of a `Host` header's value, without the port.
+fn get_host_name(host: &str) -> &str {
+    if host.starts_with('[') {
+        // An IPv6 address, which has colons of its own
+        match host.find(']') {
+            Some(end) => &host[..=end],
+            None => host,
+        }
+    } else {
+        host.split(':').next().unwrap_or_default()
+    }
+}
+
+fn write_response(
+    stream: &mut TcpStream, status: &str, response: Option<Response>,
+) -> io::Result<()> {
+    let response = response.unwrap_or(Response {
+        content_type: "text/plain; charset=utf-8",
+        body: format!("{}\n", status),
+    });
+
+    write!(
+        stream,
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        response.content_type,
+        response.body.len(),
+        response.body
+    )?;
+    stream.flush()
+}
@@ -0,0 +1,460 @@
+//! Pushes the metrics in InfluxDB's line protocol, so there's a history of them without running
+//! a collection agent.  They can go to an HTTP write endpoint, like InfluxDB's own, or as plain
+//! lines over UDP or TCP to anything that takes them, like Telegraf's socket listener.
+//!
+//! Sending is done on its own thread, so a slow or missing server never holds up the interface.
+//! Anything that fails to send is dropped rather than retried, and so is a batch made while the
+//! thread is still busy with earlier ones, so they can't pile up behind a slow server.
+
+use std::{
+    fmt::Write as FmtWrite,
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpStream, ToSocketAddrs, UdpSocket},
+    sync::{mpsc, Arc, Mutex},
+    thread,
+    time::{Duration, Instant},
+};
+
+use super::MetricsSnapshot;
+use crate::utils::error::{self, BottomError};
+
+/// How long connecting and sending can take before giving up on a batch.
+const SEND_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Kept under the usual MTU, so datagrams aren't fragmented.
+const MAX_UDP_PAYLOAD_BYTES: usize = 1400;
+
+#[derive(Clone, Debug)]
+pub enum LineProtocolTarget {
+    Http {
+        /// The host and port to connect to.
+        address: String,
+        /// The `Host` header, which is the address without a default port.
+        host: String,
+        /// The path and query, like `/write?db=bottom`.
+        path: String,
+        /// Sent as `Authorization: Token <token>`, as InfluxDB 2 wants.
+        token: Option<String>,
+    },
+    Udp(String),
+    Tcp(String),
+}
+
+impl LineProtocolTarget {
+    /// Reads a URL like `http://localhost:8086/write?db=bottom`, `udp://localhost:8089`, or
+    /// `tcp://localhost:8094`.
+    pub fn parse(url: &str, token: Option<String>) -> error::Result<Self> {
+        let invalid_url = |reason: &str| {
+            BottomError::InvalidArg(format!("Invalid line protocol URL \"{}\": {}", url, reason))
+        };
+
+        if url.starts_with("https://") {
+            return Err(invalid_url(
+                "HTTPS isn't supported, so please send to a local HTTP endpoint or a UDP or TCP listener instead",
+            ));
+        } else if let Some(rest) = url.strip_prefix("http://") {
+            let (host, path) = match rest.find('/') {
+                Some(path_start) => (&rest[..path_start], &rest[path_start..]),
+                None => {
+                    return Err(invalid_url(
+                        "expected a path to write to, like /write?db=bottom",
+                    ))
+                }
+            };
+            if host.is_empty() {
+                return Err(invalid_url("expected a host"));
+            }
+            let address = if has_port(host) {
+                host.to_string()
+            } else {
+                format!("{}:80", host)
+            };
+
+            return Ok(LineProtocolTarget::Http {
+                address,
+                host: host.to_string(),
+                path: path.to_string(),
+                token,
+            });
+        }
+
+        let (is_udp, address) = if let Some(address) = url.strip_prefix("udp://") {
+            (true, address)
+        } else if let Some(address) = url.strip_prefix("tcp://") {
+            (false, address)
+        } else {
+            return Err(invalid_url(
+                "expected it to start with http://, udp://, or tcp://",
+            ));
+        };
+        if !has_port(address) {
+            return Err(invalid_url("expected a port, like localhost:8089"));
+        }
+
+        Ok(if is_udp {
+            LineProtocolTarget::Udp(address.to_string())
+        } else {
+            LineProtocolTarget::Tcp(address.to_string())
+        })
+    }
+}
+
+pub struct LineProtocolSink {
+    interval: Duration,
+    last_sent_instant: Option<Instant>,
+    sender: mpsc::SyncSender<String>,
+    /// Why the last batch failed to send, if it did and hasn't been reported yet.
+    send_error: Arc<Mutex<Option<String>>>,
+}
+
+impl LineProtocolSink {
+    pub fn new(target: LineProtocolTarget, interval: Duration) -> Self {
+        // Only one batch can wait behind the one being sent
+        let (sender, receiver) = mpsc::sync_channel::<String>(1);
+        let send_error = Arc::new(Mutex::new(None));
+
+        let thread_send_error = send_error.clone();
+        thread::spawn(move || {
+            let mut connection = Connection::default();
+            for lines in receiver {
+                if let Err(err) = connection.send(&target, &lines) {
+                    if let Ok(mut send_error) = thread_send_error.lock() {
+                        *send_error = Some(err);
+                    }
+                }
+            }
+        });
+
+        LineProtocolSink {
+            interval,
+            last_sent_instant: None,
+            sender,
+            send_error,
+        }
+    }
+
+    /// Queues the metrics to be sent, if it's been long enough since the last time.  Gives an
+    /// error if an earlier batch failed to send.
+    pub fn update(&mut self, snapshot: &MetricsSnapshot<'_>) -> error::Result<()> {
+        let is_due = match self.last_sent_instant {
+            Some(last_sent_instant) => last_sent_instant.elapsed() >= self.interval,
+            None => true,
+        };
+        if is_due {
+            self.last_sent_instant = Some(Instant::now());
+            // Fails if the thread is still behind, in which case this batch is dropped
+            let _ = self.sender.try_send(get_lines(snapshot));
+        }
+
+        let send_error = self
+            .send_error
+            .lock()
+            .ok()
+            .and_then(|mut send_error| send_error.take());
+        match send_error {
+            Some(err) => Err(BottomError::GenericError(format!(
+                "Unable to send line protocol metrics - {}",
+                err
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// What's kept open between batches.  HTTP connections aren't, as the server closes them.
+#[derive(Default)]
+struct Connection {
+    udp_socket: Option<UdpSocket>,
+    tcp_stream: Option<TcpStream>,
+}
+
+impl Connection {
+    fn send(&mut self, target: &LineProtocolTarget, lines: &str) -> Result<(), String> {
+        match target {
+            LineProtocolTarget::Http {
+                address,
+                host,
+                path,
+                token,
+            } => send_http(address, host, path, token.as_deref(), lines),
+            LineProtocolTarget::Udp(address) => {
+                let socket_address = resolve(address)?;
+                // The socket has to be of the same family as the target, which may change if the
+                // name resolves differently later
+                let is_same_family = self
+                    .udp_socket
+                    .as_ref()
+                    .and_then(|udp_socket| udp_socket.local_addr().ok())
+                    .map_or(false, |local_address| {
+                        local_address.is_ipv4() == socket_address.is_ipv4()
+                    });
+                if !is_same_family {
+                    let bind_address = if socket_address.is_ipv4() {
+                        "0.0.0.0:0"
+                    } else {
+                        "[::]:0"
+                    };
+                    self.udp_socket =
+                        Some(UdpSocket::bind(bind_address).map_err(|err| err.to_string())?);
+                }
+                let udp_socket = self.udp_socket.as_ref().unwrap();
+                for datagram in split_datagrams(lines) {
+                    udp_socket
+                        .send_to(datagram.as_bytes(), socket_address)
+                        .map_err(|err| err.to_string())?;
+                }
+                Ok(())
+            }
+            LineProtocolTarget::Tcp(address) => {
+                if self.tcp_stream.is_none() {
+                    let tcp_stream = TcpStream::connect_timeout(&resolve(address)?, SEND_TIMEOUT)
+                        .map_err(|err| err.to_string())?;
+                    tcp_stream
+                        .set_write_timeout(Some(SEND_TIMEOUT))
+                        .map_err(|err| err.to_string())?;
+                    self.tcp_stream = Some(tcp_stream);
+                }
+                let result = self
+                    .tcp_stream
+                    .as_mut()
+                    .unwrap()
+                    .write_all(lines.as_bytes());
+                if let Err(err) = result {
+                    // Reconnect next time, in case the server restarted
+                    self.tcp_stream = None;
+                    return Err(err.to_string());
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Whether a host has a port after it, which for an IPv6 address like `[::1]:8089` comes after
+/// the brackets.
+fn has_port(host: &str) -> bool {
+    match host.rfind(':') {
+        Some(colon_index) => !host[colon_index..].contains(']'),
+        None => false,
+    }
+}
+
+fn resolve(address: &str) -> Result<SocketAddr, String> {
+    address
+        .to_socket_addrs()
+        .map_err(|err| err.to_string())?
+        .next()
+        .ok_or_else(|| format!("{} couldn't be found", address))
+}
+
+fn send_http(
+    address: &str, host: &str, path: &str, token: Option<&str>, lines: &str,
+) -> Result<(), String> {
+    let mut stream = TcpStream::connect_timeout(&resolve(address)?, SEND_TIMEOUT)
+        .map_err(|err| err.to_string())?;
+    stream
+        .set_write_timeout(Some(SEND_TIMEOUT))
+        .and_then(|_| stream.set_read_timeout(Some(SEND_TIMEOUT)))
+        .map_err(|err| err.to_string())?;
+
+    let mut request = format!(
+        "POST {} HTTP/1.1\r\nHost: {}\r\nContent-Type: text/plain; charset=utf-8\r\nContent-Length: {}\r\nConnection: close\r\n",
+        path,
+        host,
+        lines.len()
+    );
+    if let Some(token) = token {
+        let _ = write!(request, "Authorization: Token {}\r\n", token);
+    }
+    request.push_str("\r\n");
+    request.push_str(lines);
+    stream
+        .write_all(request.as_bytes())
+        .map_err(|err| err.to_string())?;
+
+    let mut status_line = String::new();
+    BufReader::new(&stream)
+        .read_line(&mut status_line)
+        .map_err(|err| err.to_string())?;
+    let status_line = status_line.trim();
+    match status_line.split_whitespace().nth(1) {
+        Some(status) if status.starts_with('2') => Ok(()),
+        _ => Err(format!("the server responded with \"{}\"", status_line)),
+    }
+}
+
+/// Groups whole lines into datagrams, as a line split across two would be lost.
+fn split_datagrams(lines: &str) -> Vec<String> {
+    let mut datagrams = vec![String::new()];
+    for line in lines.lines() {
+        let datagram = datagrams.last_mut().unwrap();
+        if !datagram.is_empty() && datagram.len() + line.len() + 1 > MAX_UDP_PAYLOAD_BYTES {
+            datagrams.push(String::new());
+        }
+        let datagram = datagrams.last_mut().unwrap();
+        datagram.push_str(line);
+        datagram.push('\n');
+    }
+
+    datagrams
+}
+
+fn get_lines(snapshot: &MetricsSnapshot<'_>) -> String {
+    let now = chrono::Utc::now();
+    let timestamp = now.timestamp() * 1_000_000_000 + i64::from(now.timestamp_subsec_nanos());
+    let host = [("host", snapshot.hostname.as_str())];
+    let mut lines = String::new();
+
+    push_line(
+        &mut lines,
+        "bottom_system",
+        &host,
+        &[
+            ("uptime_seconds", Field::Integer(snapshot.uptime_seconds)),
+            ("processes", Field::Integer(snapshot.processes.len() as u64)),
+            (
+                "collection_errors",
+                Field::Integer(snapshot.errors.len() as u64),
+            ),
+        ],
+        timestamp,
+    );
+    if let Some(load_average) = snapshot.load_average {
+        push_line(
+            &mut lines,
+            "bottom_load",
+            &host,
+            &[
+                ("load1", Field::Float(load_average[0])),
+                ("load5", Field::Float(load_average[1])),
+                ("load15", Field::Float(load_average[2])),
+            ],
+            timestamp,
+        );
+    }
+    for cpu in &snapshot.cpu {
+        push_line(
+            &mut lines,
+            "bottom_cpu",
+            &[host[0], ("cpu", cpu.name)],
+            &[("usage_percent", Field::Float(cpu.usage_percent))],
+            timestamp,
+        );
+    }
+    for (measurement, memory) in &[
+        ("bottom_memory", &snapshot.memory),
+        ("bottom_swap", &snapshot.swap),
+    ] {
+        push_line(
+            &mut lines,
+            measurement,
+            &host,
+            &[
+                ("used_bytes", Field::Integer(memory.used_bytes)),
+                ("total_bytes", Field::Integer(memory.total_bytes)),
+            ],
+            timestamp,
+        );
+    }
+    for interface in &snapshot.network.interfaces {
+        push_line(
+            &mut lines,
+            "bottom_network",
+            &[host[0], ("interface", interface.name)],
+            &[
+                (
+                    "rx_bytes_per_second",
+                    Field::Integer(interface.rx_bytes_per_second),
+                ),
+                (
+                    "tx_bytes_per_second",
+                    Field::Integer(interface.tx_bytes_per_second),
+                ),
+                ("rx_bytes_total", Field::Integer(interface.total_rx_bytes)),
+                ("tx_bytes_total", Field::Integer(interface.total_tx_bytes)),
+            ],
+            timestamp,
+        );
+    }
+    for disk in &snapshot.disks {
+        let mut fields = vec![
+            ("used_bytes", Field::Integer(disk.used_bytes)),
+            ("free_bytes", Field::Integer(disk.free_bytes)),
+            ("total_bytes", Field::Integer(disk.total_bytes)),
+        ];
+        if let Some(read_bytes) = disk.read_bytes {
+            fields.push(("read_bytes_total", Field::Integer(read_bytes)));
+        }
+        if let Some(write_bytes) = disk.write_bytes {
+            fields.push(("written_bytes_total", Field::Integer(write_bytes)));
+        }
+        push_line(
+            &mut lines,
+            "bottom_disk",
+            &[
+                host[0],
+                ("disk", disk.name),
+                ("mount_point", disk.mount_point),
+            ],
+            &fields,
+            timestamp,
+        );
+    }
+    for sensor in &snapshot.temperatures {
+        push_line(
+            &mut lines,
+            "bottom_temperature",
+            &[
+                host[0],
+                ("sensor", sensor.name),
+                ("unit", snapshot.temperature_unit),
+            ],
+            &[("value", Field::Float(f64::from(sensor.temperature)))],
+            timestamp,
+        );
+    }
+
+    lines
+}
+
+enum Field {
+    Integer(u64),
+    Float(f64),
+}
+
+/// Adds a line like `bottom_cpu,host=a,cpu=AVG usage_percent=12.5 1600000000000000000`.  Tags
+/// with no value and fields that aren't finite are left out, as neither can be written, and so
+/// is the line if that leaves no fields.
+fn push_line(
+    lines: &mut String, measurement: &str, tags: &[(&str, &str)], fields: &[(&str, Field)],
+    timestamp: i64,
+) {
+    let fields = fields
+        .iter()
+        .filter_map(|(key, value)| match value {
+            Field::Integer(value) => Some(format!("{}={}i", key, value)),
+            Field::Float(value) if value.is_finite() => Some(format!("{}={}", key, value)),
+            Field::Float(_) => None,
+        })
+        .collect::<Vec<_>>();
+    if fields.is_empty() {
+        return;
+    }
+
+    lines.push_str(measurement);
+    for (key, value) in tags {
+        if !value.is_empty() {
+            let _ = write!(lines, ",{}={}", key, escape_tag_value(value));
+        }
+    }
+    let _ = writeln!(lines, " {} {}", fields.join(","), timestamp);
+}
+
+fn escape_tag_value(value: &str) -> String {
+    value
+        .replace('\n', " ")
+        .replace('\\', "\\\\")
+        .replace(',', "\\,")
+        .replace('=', "\\=")
+        .replace(' ', "\\ ")
+}
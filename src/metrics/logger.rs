@@ -0,0 +1,127 @@
+//! Appends a record of every harvest to a file, so metrics can be looked back on after a long
+//! run.  Files ending in `.csv` get a row of the main totals, and anything else gets the whole
+//! snapshot as a line of JSON.  Once the file would grow past its size limit, it's moved to
+//! `<file>.1`, replacing the one before, and a new one is started.
+
+use std::{
+    fs::{self, File, OpenOptions},
+    io::Write,
+    path::PathBuf,
+};
+
+use super::MetricsSnapshot;
+use crate::utils::error;
+
+const CSV_HEADER: &str = "timestamp,uptime_seconds,load_average_1m,load_average_5m,\
+                          load_average_15m,cpu_percent,memory_used_bytes,memory_total_bytes,\
+                          swap_used_bytes,swap_total_bytes,rx_bytes_per_second,\
+                          tx_bytes_per_second,process_count,error_count";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MetricsLogFormat {
+    /// One JSON object per line.
+    Json,
+    Csv,
+}
+
+pub struct MetricsLogger {
+    path: PathBuf,
+    format: MetricsLogFormat,
+    max_size_bytes: u64,
+    file: Option<File>,
+}
+
+impl MetricsLogger {
+    pub fn new(path: PathBuf, max_size_bytes: u64) -> Self {
+        let format = match path.extension() {
+            Some(extension) if extension.eq_ignore_ascii_case("csv") => MetricsLogFormat::Csv,
+            _ => MetricsLogFormat::Json,
+        };
+
+        MetricsLogger {
+            path,
+            format,
+            max_size_bytes,
+            file: None,
+        }
+    }
+
+    pub fn log(&mut self, snapshot: &MetricsSnapshot<'_>) -> error::Result<()> {
+        let record = match self.format {
+            MetricsLogFormat::Json => serde_json::to_string(snapshot)?,
+            MetricsLogFormat::Csv => get_csv_record(snapshot),
+        };
+
+        self.rotate_if_full(record.len() as u64 + 1)?;
+        let file = match &mut self.file {
+            Some(file) => file,
+            None => {
+                let mut file = OpenOptions::new()
+                    .create(true)
+                    .append(true)
+                    .open(&self.path)?;
+                if self.format == MetricsLogFormat::Csv && file.metadata()?.len() == 0 {
+                    writeln!(file, "{}", CSV_HEADER)?;
+                }
+                self.file.get_or_insert(file)
+            }
+        };
+        writeln!(file, "{}", record)?;
+
+        Ok(())
+    }
+
+    /// Moves the file aside if the record would take it past the size limit.  A file with
+    /// nothing in it is kept, so a record bigger than the limit is still written.
+    fn rotate_if_full(&mut self, record_size_bytes: u64) -> error::Result<()> {
+        let current_size_bytes = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata.len(),
+            Err(_) => return Ok(()),
+        };
+        if current_size_bytes == 0 || current_size_bytes + record_size_bytes <= self.max_size_bytes
+        {
+            return Ok(());
+        }
+
+        self.file = None;
+        let mut rotated_path = self.path.clone().into_os_string();
+        rotated_path.push(".1");
+        fs::rename(&self.path, rotated_path)?;
+
+        Ok(())
+    }
+}
+
+fn get_csv_record(snapshot: &MetricsSnapshot<'_>) -> String {
+    let load_average = match snapshot.load_average {
+        Some(load_average) => load_average
+            .iter()
+            .map(|load| format!("{:.2}", load))
+            .collect::<Vec<_>>(),
+        None => vec![String::new(); 3],
+    };
+    // The average is always the first entry
+    let cpu_percent = snapshot
+        .cpu
+        .first()
+        .map(|cpu| format!("{:.1}", cpu.usage_percent))
+        .unwrap_or_default();
+
+    format!(
+        "{},{},{},{},{},{},{},{},{},{},{},{},{},{}",
+        snapshot.timestamp,
+        snapshot.uptime_seconds,
+        load_average[0],
+        load_average[1],
+        load_average[2],
+        cpu_percent,
+        snapshot.memory.used_bytes,
+        snapshot.memory.total_bytes,
+        snapshot.swap.used_bytes,
+        snapshot.swap.total_bytes,
+        snapshot.network.rx_bytes_per_second,
+        snapshot.network.tx_bytes_per_second,
+        snapshot.processes.len(),
+        snapshot.errors.len()
+    )
+}
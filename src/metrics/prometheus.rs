@@ -0,0 +1,313 @@
+//! Serves the latest harvest in Prometheus' text exposition format at `/metrics`, so bottom can
+//! be scraped directly.  Only totals and per-device values are exported, as a series per
+//! process would be far too many for Prometheus to keep.
+
+use std::{
+    fmt::{Display, Write},
+    net::{IpAddr, TcpListener},
+    sync::{Arc, Mutex},
+};
+
+use super::{
+    http::{self, Response},
+    MetricsSnapshot,
+};
+
+pub struct PrometheusExporter {
+    /// The text served, redrawn each harvest rather than each scrape.
+    exposition: Arc<Mutex<String>>,
+}
+
+impl PrometheusExporter {
+    /// Starts serving on the address, which is every interface by default, as scrapers are
+    /// usually on another machine.
+    pub fn new(address: IpAddr, port: u16) -> std::io::Result<Self> {
+        let listener = TcpListener::bind((address, port))?;
+        let exposition = Arc::new(Mutex::new(String::new()));
+
+        let served_exposition = exposition.clone();
+        http::spawn_server(listener, None, move |path| match path {
+            "/metrics" => Some(Response {
+                content_type: "text/plain; version=0.0.4; charset=utf-8",
+                body: served_exposition
+                    .lock()
+                    .map(|exposition| exposition.clone())
+                    .unwrap_or_default(),
+            }),
+            _ => None,
+        });
+
+        Ok(PrometheusExporter { exposition })
+    }
+
+    pub fn update(&self, snapshot: &MetricsSnapshot<'_>) {
+        let new_exposition = get_exposition(snapshot);
+        if let Ok(mut exposition) = self.exposition.lock() {
+            *exposition = new_exposition;
+        }
+    }
+}
+
+fn get_exposition(snapshot: &MetricsSnapshot<'_>) -> String {
+    let mut text = String::new();
+
+    push_family(
+        &mut text,
+        "bottom_uptime_seconds",
+        "gauge",
+        "Time since boot.",
+    );
+    push_sample(
+        &mut text,
+        "bottom_uptime_seconds",
+        &[],
+        snapshot.uptime_seconds,
+    );
+
+    if let Some(load_average) = snapshot.load_average {
+        push_family(
+            &mut text,
+            "bottom_load_average",
+            "gauge",
+            "System load average.",
+        );
+        for (period, load) in ["1m", "5m", "15m"].iter().zip(load_average.iter()) {
+            push_sample(
+                &mut text,
+                "bottom_load_average",
+                &[("period", period)],
+                load,
+            );
+        }
+    }
+
+    push_family(
+        &mut text,
+        "bottom_cpu_usage_percent",
+        "gauge",
+        "CPU usage, with \"AVG\" for the average of every core.",
+    );
+    for cpu in &snapshot.cpu {
+        push_sample(
+            &mut text,
+            "bottom_cpu_usage_percent",
+            &[("cpu", cpu.name)],
+            cpu.usage_percent,
+        );
+    }
+
+    push_family(
+        &mut text,
+        "bottom_memory_used_bytes",
+        "gauge",
+        "Memory in use.",
+    );
+    push_sample(
+        &mut text,
+        "bottom_memory_used_bytes",
+        &[],
+        snapshot.memory.used_bytes,
+    );
+    push_family(
+        &mut text,
+        "bottom_memory_total_bytes",
+        "gauge",
+        "Total memory.",
+    );
+    push_sample(
+        &mut text,
+        "bottom_memory_total_bytes",
+        &[],
+        snapshot.memory.total_bytes,
+    );
+    push_family(&mut text, "bottom_swap_used_bytes", "gauge", "Swap in use.");
+    push_sample(
+        &mut text,
+        "bottom_swap_used_bytes",
+        &[],
+        snapshot.swap.used_bytes,
+    );
+    push_family(&mut text, "bottom_swap_total_bytes", "gauge", "Total swap.");
+    push_sample(
+        &mut text,
+        "bottom_swap_total_bytes",
+        &[],
+        snapshot.swap.total_bytes,
+    );
+
+    push_family(
+        &mut text,
+        "bottom_network_receive_bytes_per_second",
+        "gauge",
+        "Network receive rate.",
+    );
+    for interface in &snapshot.network.interfaces {
+        push_sample(
+            &mut text,
+            "bottom_network_receive_bytes_per_second",
+            &[("interface", interface.name)],
+            interface.rx_bytes_per_second,
+        );
+    }
+    push_family(
+        &mut text,
+        "bottom_network_transmit_bytes_per_second",
+        "gauge",
+        "Network transmit rate.",
+    );
+    for interface in &snapshot.network.interfaces {
+        push_sample(
+            &mut text,
+            "bottom_network_transmit_bytes_per_second",
+            &[("interface", interface.name)],
+            interface.tx_bytes_per_second,
+        );
+    }
+    push_family(
+        &mut text,
+        "bottom_network_receive_bytes_total",
+        "counter",
+        "Bytes received since boot.",
+    );
+    for interface in &snapshot.network.interfaces {
+        push_sample(
+            &mut text,
+            "bottom_network_receive_bytes_total",
+            &[("interface", interface.name)],
+            interface.total_rx_bytes,
+        );
+    }
+    push_family(
+        &mut text,
+        "bottom_network_transmit_bytes_total",
+        "counter",
+        "Bytes sent since boot.",
+    );
+    for interface in &snapshot.network.interfaces {
+        push_sample(
+            &mut text,
+            "bottom_network_transmit_bytes_total",
+            &[("interface", interface.name)],
+            interface.total_tx_bytes,
+        );
+    }
+
+    push_family(
+        &mut text,
+        "bottom_disk_used_bytes",
+        "gauge",
+        "Disk space in use.",
+    );
+    for disk in &snapshot.disks {
+        push_sample(
+            &mut text,
+            "bottom_disk_used_bytes",
+            &[("disk", disk.name), ("mount_point", disk.mount_point)],
+            disk.used_bytes,
+        );
+    }
+    push_family(
+        &mut text,
+        "bottom_disk_total_bytes",
+        "gauge",
+        "Total disk space.",
+    );
+    for disk in &snapshot.disks {
+        push_sample(
+            &mut text,
+            "bottom_disk_total_bytes",
+            &[("disk", disk.name), ("mount_point", disk.mount_point)],
+            disk.total_bytes,
+        );
+    }
+    push_family(
+        &mut text,
+        "bottom_disk_read_bytes_total",
+        "counter",
+        "Bytes read from the disk since boot.",
+    );
+    for disk in &snapshot.disks {
+        if let Some(read_bytes) = disk.read_bytes {
+            push_sample(
+                &mut text,
+                "bottom_disk_read_bytes_total",
+                &[("disk", disk.name), ("mount_point", disk.mount_point)],
+                read_bytes,
+            );
+        }
+    }
+    push_family(
+        &mut text,
+        "bottom_disk_written_bytes_total",
+        "counter",
+        "Bytes written to the disk since boot.",
+    );
+    for disk in &snapshot.disks {
+        if let Some(write_bytes) = disk.write_bytes {
+            push_sample(
+                &mut text,
+                "bottom_disk_written_bytes_total",
+                &[("disk", disk.name), ("mount_point", disk.mount_point)],
+                write_bytes,
+            );
+        }
+    }
+
+    // Named after the unit, so changing the temperature type doesn't mix units in one series
+    let temperature_name = format!("bottom_temperature_{}", snapshot.temperature_unit);
+    push_family(&mut text, &temperature_name, "gauge", "Sensor temperature.");
+    for sensor in &snapshot.temperatures {
+        push_sample(
+            &mut text,
+            &temperature_name,
+            &[("sensor", sensor.name)],
+            sensor.temperature,
+        );
+    }
+
+    push_family(
+        &mut text,
+        "bottom_processes",
+        "gauge",
+        "Number of processes.",
+    );
+    push_sample(&mut text, "bottom_processes", &[], snapshot.processes.len());
+    push_family(
+        &mut text,
+        "bottom_collection_errors",
+        "gauge",
+        "Number of sources that failed to be collected in the last harvest.",
+    );
+    push_sample(
+        &mut text,
+        "bottom_collection_errors",
+        &[],
+        snapshot.errors.len(),
+    );
+
+    text
+}
+
+fn push_family(text: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(text, "# HELP {} {}", name, help);
+    let _ = writeln!(text, "# TYPE {} {}", name, kind);
+}
+
+fn push_sample(text: &mut String, name: &str, labels: &[(&str, &str)], value: impl Display) {
+    text.push_str(name);
+    if !labels.is_empty() {
+        let labels = labels
+            .iter()
+            .map(|(label, value)| format!("{}=\"{}\"", label, escape_label_value(value)))
+            .collect::<Vec<_>>();
+        let _ = write!(text, "{{{}}}", labels.join(","));
+    }
+    let _ = writeln!(text, " {}", value);
+}
+
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -1,215 +1,1561 @@
 use serde::Deserialize;
 
-use std::time::Instant;
+use std::{collections::HashMap, time::Instant};
 
 use crate::{
-    app::{data_harvester, App, AppConfigFields, CpuState, MemState, NetState, WidgetPosition},
+    app::{
+        alerts::AlertRule, data_harvester, layout::BottomLayout, App, AppConfigFields,
+        CpuGraphLines, CpuState, GraphState, HiddenGraphParts, MemState, NetState, NetworkScale,
+        WidgetPosition,
+    },
+    canvas::ColourScheme,
     constants::*,
-    utils::error::{self, BottomError},
+    metrics::line_protocol::LineProtocolTarget,
+    utils::{
+        error::{self, BottomError},
+        gen_util::get_hostname,
+    },
 };
 
-// use layout_manager::*;
+use layout_manager::*;
 
-// mod layout_manager;
+mod layout_manager;
 
 #[derive(Default, Deserialize)]
 pub struct Config {
     pub flags: Option<ConfigFlags>,
     pub colors: Option<ConfigColours>,
+    pub network_filter: Option<ConfigNetworkFilter>,
+    pub disk_filter: Option<ConfigDiskFilter>,
+    pub temp_filter: Option<ConfigTempFilter>,
+    pub temp_thresholds: Option<HashMap<String, ConfigTempThresholds>>,
+    pub row: Option<Vec<ConfigLayoutNode>>,
+    /// New keys for actions, keyed by action name.
+    pub keybinds: Option<HashMap<String, String>>,
+    /// The marker of each graph widget, keyed by widget type.
+    pub markers: Option<HashMap<String, String>>,
+    /// The parts hidden in each graph widget, keyed by widget type.
+    pub hidden_graph_parts: Option<HashMap<String, Vec<String>>>,
+    /// Layouts that can be switched to while running.
+    pub layout: Option<Vec<ConfigLayout>>,
+    pub alerts: Option<Vec<ConfigAlert>>,
 }
 
 #[derive(Default, Deserialize)]
 pub struct ConfigFlags {
     pub avg_cpu: Option<bool>,
+    pub cpu_lines: Option<String>,
     pub dot_marker: Option<bool>,
     pub temperature_type: Option<String>,
     pub rate: Option<u64>,
     pub left_legend: Option<bool>,
     pub current_usage: Option<bool>,
+    pub cpu_frequency: Option<bool>,
+    pub cpu_temp: Option<bool>,
+    pub cpu_min_max: Option<bool>,
+    pub subtract_arc: Option<bool>,
+    pub mem_as_value: Option<bool>,
+    pub psi: Option<bool>,
+    pub sockets: Option<bool>,
+    pub ping_hosts: Option<Vec<String>>,
+    pub disk_io_graph: Option<bool>,
+    pub temp_graph: Option<bool>,
+    pub battery: Option<bool>,
+    pub gpu: Option<bool>,
+    pub disk_temp: Option<bool>,
+    pub disk_inodes: Option<bool>,
+    pub hide_network_disks: Option<bool>,
+    pub network_disk_timeout: Option<u64>,
+    pub network_use_bits: Option<bool>,
+    pub network_scale: Option<String>,
+    pub network_y_max: Option<String>,
+    pub process_swap: Option<bool>,
+    pub process_affinity: Option<bool>,
+    pub copy_pid: Option<bool>,
+    pub header: Option<bool>,
+    pub table_stripes: Option<bool>,
+    pub table_gap: Option<u16>,
+    pub log_metrics: Option<String>,
+    pub log_metrics_max_size: Option<u64>,
+    pub prometheus_port: Option<u16>,
+    pub prometheus_address: Option<String>,
+    pub api_port: Option<u16>,
+    pub line_protocol: Option<String>,
+    pub line_protocol_interval: Option<u64>,
+    /// Only in the config file, to keep it out of the process list.
+    pub line_protocol_token: Option<String>,
+    pub remote_hosts: Option<Vec<String>>,
+    pub remote_command: Option<String>,
+    pub series_markers: Option<bool>,
+    pub hide_kernel_threads: Option<bool>,
+    pub hide_cpu: Option<bool>,
+    pub hide_mem: Option<bool>,
+    pub hide_net: Option<bool>,
+    pub hide_temps: Option<bool>,
+    pub hide_disks: Option<bool>,
+    pub hide_procs: Option<bool>,
+    pub default_sort: Option<String>,
+    pub secondary_sort: Option<Vec<String>>,
+    pub group_ignore_case: Option<bool>,
+    pub group_by: Option<String>,
     pub group_processes: Option<bool>,
     pub case_sensitive: Option<bool>,
     pub whole_word: Option<bool>,
     pub regex: Option<bool>,
+    pub persist_search_history: Option<bool>,
+    pub persist_state: Option<bool>,
     pub default_widget: Option<String>,
+    pub expanded: Option<bool>,
+    pub theme: Option<String>,
+    pub ascii: Option<String>,
     pub show_disabled_data: Option<bool>,
     pub basic: Option<bool>,
     pub default_time_value: Option<u64>,
+    pub cpu_default_time_value: Option<u64>,
+    pub mem_default_time_value: Option<u64>,
+    pub net_default_time_value: Option<u64>,
+    pub retention: Option<u64>,
     pub time_delta: Option<u64>,
     pub autohide_time: Option<bool>,
     pub hide_time: Option<bool>,
     //disabled_cpu_cores: Option<Vec<u64>>, // TODO: [FEATURE] Enable disabling cores in config/flags
 }
 
+/// Lists of regexes matched against network interface names.
+#[derive(Default, Deserialize)]
+pub struct ConfigNetworkFilter {
+    pub allow: Option<Vec<String>>,
+    pub deny: Option<Vec<String>>,
+}
+
+/// Lists of regexes matched against disk names and mount points.
+#[derive(Default, Deserialize)]
+pub struct ConfigDiskFilter {
+    pub allow: Option<Vec<String>>,
+    pub deny: Option<Vec<String>>,
+}
+
+/// Lists of regexes matched against sensor names, and new names for sensors.
+#[derive(Default, Deserialize)]
+pub struct ConfigTempFilter {
+    pub allow: Option<Vec<String>>,
+    pub deny: Option<Vec<String>>,
+    pub rename: Option<HashMap<String, String>>,
+}
+
+/// Temperatures in celsius past which a sensor is highlighted.
+#[derive(Default, Deserialize)]
+pub struct ConfigTempThresholds {
+    pub warning: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+/// A rule checked against every harvest, and what to do when it fires.
+#[derive(Default, Deserialize)]
+pub struct ConfigAlert {
+    pub rule: String,
+    pub command: Option<String>,
+    pub desktop_notification: Option<bool>,
+}
+
 #[derive(Default, Deserialize)]
 pub struct ConfigColours {
     pub table_header_color: Option<String>,
+    pub table_stripe_color: Option<String>,
     pub avg_cpu_color: Option<String>,
     pub cpu_core_colors: Option<Vec<String>>,
     pub ram_color: Option<String>,
     pub swap_color: Option<String>,
+    pub vram_color: Option<String>,
+    pub commit_color: Option<String>,
     pub rx_color: Option<String>,
     pub tx_color: Option<String>,
     pub rx_total_color: Option<String>,
     pub tx_total_color: Option<String>,
+    pub disk_warning_color: Option<String>,
+    pub disk_critical_color: Option<String>,
+    pub temp_warning_color: Option<String>,
+    pub temp_critical_color: Option<String>,
     pub border_color: Option<String>,
     pub highlighted_border_color: Option<String>,
     pub text_color: Option<String>,
     pub selected_text_color: Option<String>,
     pub selected_bg_color: Option<String>,
+    pub cursor_color: Option<String>,
+    pub invalid_search_color: Option<String>,
     pub widget_title_color: Option<String>,
     pub graph_color: Option<String>,
 }
 
-pub fn build_app(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<App> {
-    let autohide_time = get_autohide_time(&matches, &config);
-    let default_time_value = get_default_time_value(&matches, &config)?;
-    let default_widget = get_default_widget(&matches, &config);
-    let use_basic_mode = get_use_basic_mode(&matches, &config);
+pub fn build_app(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<App> {
+    let autohide_time = get_autohide_time(&matches, &config);
+    let retention_time = get_retention_time(&matches, &config)?;
+    let default_time_value = get_default_time_value(&matches, &config, retention_time)?;
+    let (cpu_default_time_value, mem_default_time_value, net_default_time_value) =
+        if let Some(flags) = &config.flags {
+            (
+                get_widget_default_time_value(
+                    flags.cpu_default_time_value,
+                    default_time_value,
+                    retention_time,
+                )?,
+                get_widget_default_time_value(
+                    flags.mem_default_time_value,
+                    default_time_value,
+                    retention_time,
+                )?,
+                get_widget_default_time_value(
+                    flags.net_default_time_value,
+                    default_time_value,
+                    retention_time,
+                )?,
+            )
+        } else {
+            (default_time_value, default_time_value, default_time_value)
+        };
+    let use_basic_mode = get_use_basic_mode(&matches, &config);
+    let custom_layout = get_custom_layout(&config.row)?;
+    let default_widget = get_default_widget(&matches, &config)?;
+    let is_expanded = get_expanded(&matches, &config);
+    let default_sort = get_default_sort(&matches, &config)?;
+
+    let mut app_config_fields = AppConfigFields {
+        update_rate_in_milliseconds: get_update_rate_in_milliseconds(matches, config)?,
+        temperature_type: get_temperature(matches, config)?,
+        cpu_graph_lines: get_cpu_graph_lines(matches, config)?,
+        network_scale: get_network_scale(matches, config)?,
+        network_y_max: get_network_y_max(matches, config)?,
+        network_filter: get_network_filter(config)?,
+        disk_filter: get_disk_filter(config)?,
+        sensor_filter: get_sensor_filter(config)?,
+        temp_thresholds: get_temp_thresholds(config),
+        use_dot: get_use_dot(matches, config),
+        widget_use_dot: get_widget_use_dot(config)?,
+        hidden_graph_parts: get_hidden_graph_parts(config)?,
+        use_ascii: get_use_ascii(matches, config)?,
+        left_legend: get_use_left_legend(matches, config),
+        use_current_cpu_total: get_use_current_cpu_total(matches, config),
+        show_disabled_data: get_show_disabled_data(matches, config),
+        use_basic_mode,
+        default_time_value,
+        cpu_default_time_value,
+        mem_default_time_value,
+        net_default_time_value,
+        retention_time,
+        time_interval: get_time_interval(matches, config)?,
+        hide_time: get_hide_time(matches, config),
+        autohide_time,
+        show_cpu_frequency: get_show_cpu_frequency(matches, config),
+        show_cpu_temp: get_show_cpu_temp(matches, config),
+        show_cpu_min_max: get_show_cpu_min_max(matches, config),
+        subtract_arc: get_subtract_arc(matches, config),
+        mem_as_value: get_mem_as_value(matches, config),
+        show_psi: get_show_psi(matches, config),
+        show_sockets: get_show_sockets(matches, config),
+        ping_hosts: get_ping_hosts(matches, config),
+        show_disk_io_graph: get_show_disk_io_graph(matches, config),
+        show_temp_graph: get_show_temp_graph(matches, config),
+        show_battery: get_show_battery(matches, config),
+        show_gpu: get_show_gpu(matches, config),
+        show_disk_temp: get_show_disk_temp(matches, config),
+        show_disk_inodes: get_show_disk_inodes(matches, config),
+        hide_network_disks: get_hide_network_disks(matches, config),
+        network_disk_timeout: get_network_disk_timeout(matches, config)?,
+        network_use_bits: get_network_use_bits(matches, config),
+        show_process_swap: get_show_process_swap(matches, config),
+        show_process_affinity: get_show_process_affinity(matches, config),
+        copy_pid_only: get_copy_pid_only(matches, config),
+        table_stripes: get_table_stripes(matches, config),
+        table_gap: get_table_gap(matches, config)?,
+        metrics_log_path: get_metrics_log_path(matches, config),
+        metrics_log_max_size_bytes: get_metrics_log_max_size_bytes(matches, config)?,
+        prometheus_port: get_prometheus_port(matches, config)?,
+        prometheus_address: get_prometheus_address(matches, config)?,
+        api_port: get_api_port(matches, config)?,
+        line_protocol_target: get_line_protocol_target(matches, config)?,
+        line_protocol_interval: get_line_protocol_interval(matches, config)?,
+        remote_hosts: get_remote_hosts(matches, config),
+        remote_command: get_remote_command(config),
+        alert_rules: get_alert_rules(config)?,
+        show_header: get_show_header(matches, config),
+        vary_series_markers: get_vary_series_markers(matches, config),
+        secondary_sort_keys: get_secondary_sort_keys(config)?,
+        persist_search_history: get_persist_search_history(config),
+        persist_state: get_persist_state(config),
+        hide_kernel_threads: get_hide_kernel_threads(matches, config),
+        group_ignore_case: get_group_ignore_case(config),
+        group_by_command: get_group_by_command(config)?,
+        custom_layout: None,
+        layouts: Vec::new(),
+        hidden_widgets: get_hidden_widgets(matches, config),
+        keybinds: get_keybinds(config)?,
+    };
+
+    // Optional widgets are shown if a custom layout has them, regardless of their flags
+    if let Some(layout) = &custom_layout {
+        app_config_fields.show_gpu = layout.contains(WidgetPosition::Gpu);
+        app_config_fields.show_psi = layout.contains(WidgetPosition::Psi);
+        app_config_fields.show_sockets = layout.contains(WidgetPosition::Sockets);
+        app_config_fields.show_disk_io_graph = layout.contains(WidgetPosition::DiskIo);
+        app_config_fields.show_temp_graph = layout.contains(WidgetPosition::TempGraph);
+        app_config_fields.show_battery = layout.contains(WidgetPosition::Battery);
+    }
+
+    // Hidden widgets are taken out of the layout in use, with the rest taking their space
+    let hidden_widgets = app_config_fields.hidden_widgets.clone();
+    let (custom_layout, named_layouts) = if hidden_widgets.is_empty() || use_basic_mode {
+        (custom_layout, get_named_layouts(&config.layout)?)
+    } else {
+        let main_layout = custom_layout
+            .unwrap_or_else(|| BottomLayout::default_layout(&app_config_fields))
+            .without_widgets(&hidden_widgets);
+        if main_layout.rows.is_empty() {
+            return Err(BottomError::ConfigError(
+                "Invalid hidden widgets.  Please leave at least one widget shown.".to_string(),
+            ));
+        }
+
+        let named_layouts = get_named_layouts(&config.layout)?
+            .into_iter()
+            .map(|(name, layout)| (name, layout.without_widgets(&hidden_widgets)))
+            .filter(|(_, layout)| !layout.rows.is_empty())
+            .collect();
+        (Some(main_layout), named_layouts)
+    };
+
+    // Named layouts are switched between along with the main one, so optional widgets are shown
+    // if any of them have them
+    if !named_layouts.is_empty() {
+        let main_layout = match &custom_layout {
+            Some(layout) => layout.clone(),
+            None => BottomLayout::default_layout(&app_config_fields),
+        };
+        let mut layouts = vec![("default".to_string(), main_layout)];
+        layouts.extend(named_layouts);
+
+        let any_layout_contains =
+            |widget| layouts.iter().any(|(_, layout)| layout.contains(widget));
+        app_config_fields.show_gpu = any_layout_contains(WidgetPosition::Gpu);
+        app_config_fields.show_psi = any_layout_contains(WidgetPosition::Psi);
+        app_config_fields.show_sockets = any_layout_contains(WidgetPosition::Sockets);
+        app_config_fields.show_disk_io_graph = any_layout_contains(WidgetPosition::DiskIo);
+        app_config_fields.show_temp_graph = any_layout_contains(WidgetPosition::TempGraph);
+        app_config_fields.show_battery = any_layout_contains(WidgetPosition::Battery);
+
+        app_config_fields.custom_layout = Some(layouts[0].1.clone());
+        app_config_fields.layouts = layouts;
+    } else {
+        app_config_fields.custom_layout = custom_layout;
+    }
+
+    // Fall back to the first widget of a custom layout if the default one isn't in it
+    let default_widget = match &app_config_fields.custom_layout {
+        Some(layout) if !use_basic_mode && !layout.contains(default_widget) => {
+            layout.get_first_widget().unwrap_or(default_widget)
+        }
+        _ => default_widget,
+    };
+
+    // Basic mode's widgets are swapped for the full ones when expanded, as with Enter
+    let current_widget_selected = if use_basic_mode && !is_expanded {
+        match default_widget {
+            WidgetPosition::Cpu => WidgetPosition::BasicCpu,
+            WidgetPosition::Network => WidgetPosition::BasicNet,
+            WidgetPosition::Mem => WidgetPosition::BasicMem,
+            _ => default_widget,
+        }
+    } else {
+        default_widget
+    };
+
+    let previous_basic_table_selected = if default_widget.is_widget_table() {
+        default_widget
+    } else {
+        WidgetPosition::Process
+    };
+
+    let time_now = if autohide_time {
+        Some(Instant::now())
+    } else {
+        None
+    };
+
+    Ok(App::builder()
+        .app_config_fields(app_config_fields)
+        .process_sorting_reverse(default_sort.is_reverse_by_default())
+        .process_sorting_type(default_sort)
+        .current_widget_selected(current_widget_selected)
+        .is_expanded(is_expanded)
+        .previous_basic_table_selected(previous_basic_table_selected)
+        .cpu_state(CpuState::init(cpu_default_time_value, time_now))
+        .mem_state(MemState::init(mem_default_time_value, time_now))
+        .net_state(NetState::init(net_default_time_value, time_now))
+        .psi_state(GraphState::init(default_time_value, time_now))
+        .ping_state(GraphState::init(default_time_value, time_now))
+        .disk_io_state(GraphState::init(default_time_value, time_now))
+        .temp_graph_state(GraphState::init(default_time_value, time_now))
+        .battery_state(GraphState::init(default_time_value, time_now))
+        .gpu_state(GraphState::init(default_time_value, time_now))
+        // Hosts given as flags are what's wanted now, unlike those in the config file
+        .remote_host_index(if matches.is_present("REMOTE") {
+            Some(0)
+        } else {
+            None
+        })
+        .build())
+}
+
+/// Merges a `[profiles.<name>]` table over the rest of the config, with its tables merged key by
+/// key and anything else replaced.  Without a named profile, the one listing this machine's
+/// hostname under `hosts`, or named after it, is used if there is one.
+pub fn apply_profile(
+    config_value: &mut toml::Value, profile_name: Option<&str>,
+) -> error::Result<()> {
+    let profiles = match config_value
+        .as_table_mut()
+        .and_then(|config_table| config_table.remove("profiles"))
+    {
+        Some(toml::Value::Table(profiles)) => profiles,
+        Some(_) => {
+            return Err(BottomError::ConfigError(
+                "Invalid profiles.  Please have each profile be a [profiles.<name>] table."
+                    .to_string(),
+            ));
+        }
+        None => toml::value::Table::new(),
+    };
+
+    let profile = if let Some(profile_name) = profile_name {
+        match profiles.get(profile_name) {
+            Some(profile) => Some(profile),
+            None => {
+                return Err(BottomError::ConfigError(format!(
+                    "Invalid profile \"{}\".  Please have it be the name of a [profiles.<name>] table.",
+                    profile_name
+                )));
+            }
+        }
+    } else if let Some(hostname) = get_hostname() {
+        profiles
+            .iter()
+            .find(|(name, profile)| {
+                **name == hostname
+                    || profile
+                        .get("hosts")
+                        .and_then(|hosts| hosts.as_array())
+                        .map_or(false, |hosts| {
+                            hosts
+                                .iter()
+                                .any(|host| host.as_str() == Some(hostname.as_str()))
+                        })
+            })
+            .map(|(_, profile)| profile)
+    } else {
+        None
+    };
+
+    if let Some(profile) = profile {
+        let mut profile = match profile {
+            toml::Value::Table(profile) => profile.clone(),
+            _ => {
+                return Err(BottomError::ConfigError(
+                    "Invalid profiles.  Please have each profile be a [profiles.<name>] table."
+                        .to_string(),
+                ));
+            }
+        };
+        profile.remove("hosts");
+        merge_config_values(config_value, toml::Value::Table(profile));
+    }
+
+    Ok(())
+}
+
+fn merge_config_values(base: &mut toml::Value, overlay: toml::Value) {
+    match (base, overlay) {
+        (toml::Value::Table(base_table), toml::Value::Table(overlay_table)) => {
+            for (key, value) in overlay_table {
+                match base_table.get_mut(&key) {
+                    Some(base_value) => merge_config_values(base_value, value),
+                    None => {
+                        base_table.insert(key, value);
+                    }
+                }
+            }
+        }
+        (base, overlay) => *base = overlay,
+    }
+}
+
+fn get_update_rate_in_milliseconds(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<u64> {
+    let update_rate_in_milliseconds = if let Some(update_rate) = matches.value_of("RATE_MILLIS") {
+        update_rate.parse::<u128>()?
+    } else if let Some(flags) = &config.flags {
+        if let Some(rate) = flags.rate {
+            rate as u128
+        } else {
+            DEFAULT_REFRESH_RATE_IN_MILLISECONDS as u128
+        }
+    } else {
+        DEFAULT_REFRESH_RATE_IN_MILLISECONDS as u128
+    };
+
+    if update_rate_in_milliseconds < 250 {
+        return Err(BottomError::InvalidArg(
+            "Please set your update rate to be at least 250 milliseconds.".to_string(),
+        ));
+    } else if update_rate_in_milliseconds as u128 > std::u64::MAX as u128 {
+        return Err(BottomError::InvalidArg(
+            "Please set your update rate to be at most unsigned INT_MAX.".to_string(),
+        ));
+    }
+
+    Ok(update_rate_in_milliseconds as u64)
+}
+
+fn get_temperature(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<data_harvester::temperature::TemperatureType> {
+    if matches.is_present("FAHRENHEIT") {
+        return Ok(data_harvester::temperature::TemperatureType::Fahrenheit);
+    } else if matches.is_present("KELVIN") {
+        return Ok(data_harvester::temperature::TemperatureType::Kelvin);
+    } else if matches.is_present("CELSIUS") {
+        return Ok(data_harvester::temperature::TemperatureType::Celsius);
+    } else if let Some(flags) = &config.flags {
+        if let Some(temp_type) = &flags.temperature_type {
+            // Give lowest priority to config.
+            return match temp_type.as_str() {
+                "fahrenheit" | "f" => Ok(data_harvester::temperature::TemperatureType::Fahrenheit),
+                "kelvin" | "k" => Ok(data_harvester::temperature::TemperatureType::Kelvin),
+                "celsius" | "c" => Ok(data_harvester::temperature::TemperatureType::Celsius),
+                _ => Err(BottomError::ConfigError(
+                    "Invalid temperature type.  Please have the value be of the form \
+						 <kelvin|k|celsius|c|fahrenheit|f>"
+                        .to_string(),
+                )),
+            };
+        }
+    }
+    Ok(data_harvester::temperature::TemperatureType::Celsius)
+}
+
+fn get_cpu_graph_lines(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<CpuGraphLines> {
+    if matches.is_present("AVG_CPU") {
+        return Ok(CpuGraphLines::All);
+    } else if let Some(flags) = &config.flags {
+        if let Some(cpu_lines) = &flags.cpu_lines {
+            return match cpu_lines.as_str() {
+                "all" => Ok(CpuGraphLines::All),
+                "average" | "avg" => Ok(CpuGraphLines::AverageOnly),
+                "cores" => Ok(CpuGraphLines::CoresOnly),
+                "types" => Ok(CpuGraphLines::CoreTypes),
+                _ => Err(BottomError::ConfigError(
+                    "Invalid CPU line type.  Please have the value be of the form \
+						 <all|average|avg|cores|types>"
+                        .to_string(),
+                )),
+            };
+        } else if let Some(true) = flags.avg_cpu {
+            return Ok(CpuGraphLines::All);
+        }
+    }
+
+    Ok(CpuGraphLines::CoresOnly)
+}
+
+fn get_network_scale(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<NetworkScale> {
+    let network_scale = if let Some(network_scale) = matches.value_of("NETWORK_SCALE") {
+        network_scale
+    } else if let Some(network_scale) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.network_scale.as_ref())
+    {
+        network_scale.as_str()
+    } else {
+        return Ok(NetworkScale::Log);
+    };
+
+    match network_scale {
+        "log" => Ok(NetworkScale::Log),
+        "linear" => Ok(NetworkScale::Linear),
+        _ => Err(BottomError::ConfigError(
+            "Invalid network scale.  Please have the value be of the form <log|linear>".to_string(),
+        )),
+    }
+}
+
+/// Parses a rate like "1Gb" or "100MiB" into bytes per second.  A lowercase "b" is bits, and an
+/// "i" after the prefix uses powers of 1024 rather than 1000.
+fn get_network_y_max(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<Option<f64>> {
+    let network_y_max = if let Some(network_y_max) = matches.value_of("NETWORK_Y_MAX") {
+        network_y_max
+    } else if let Some(network_y_max) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.network_y_max.as_ref())
+    {
+        network_y_max.as_str()
+    } else {
+        return Ok(None);
+    };
+
+    let invalid_error = || {
+        BottomError::ConfigError(
+            "Invalid network y-axis maximum.  Please have the value be of the form <number><K|M|G|T><i><B|b>, like 1Gb or 100MiB".to_string(),
+        )
+    };
+
+    let unit_start = network_y_max
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or_else(|| network_y_max.len());
+    let value = network_y_max[..unit_start]
+        .parse::<f64>()
+        .map_err(|_| invalid_error())?;
+    let mut unit = network_y_max[unit_start..].trim().chars().peekable();
+
+    let exponent = match unit.peek() {
+        Some('K') | Some('k') => 1,
+        Some('M') => 2,
+        Some('G') => 3,
+        Some('T') => 4,
+        _ => 0,
+    };
+    if exponent > 0 {
+        unit.next();
+    }
+    let base: f64 = if unit.peek() == Some(&'i') {
+        unit.next();
+        1024.0
+    } else {
+        1000.0
+    };
+    let bits_per_unit = match (unit.next(), unit.next()) {
+        (Some('B'), None) | (None, None) => 8.0,
+        (Some('b'), None) => 1.0,
+        _ => return Err(invalid_error()),
+    };
+
+    let bytes_per_sec = value * base.powi(exponent) * bits_per_unit / 8.0;
+    if bytes_per_sec > 0.0 {
+        Ok(Some(bytes_per_sec))
+    } else {
+        Err(invalid_error())
+    }
+}
+
+fn compile_filter_regexes(
+    patterns: &Option<Vec<String>>, filter_name: &str,
+) -> error::Result<Vec<regex::Regex>> {
+    patterns
+        .iter()
+        .flatten()
+        .map(|pattern| {
+            regex::Regex::new(pattern).map_err(|err| {
+                BottomError::ConfigError(format!(
+                    "Invalid {} filter regex \"{}\": {}",
+                    filter_name, pattern, err
+                ))
+            })
+        })
+        .collect()
+}
+
+fn get_network_filter(config: &Config) -> error::Result<data_harvester::network::InterfaceFilter> {
+    if let Some(network_filter) = &config.network_filter {
+        Ok(data_harvester::network::InterfaceFilter {
+            allow: compile_filter_regexes(&network_filter.allow, "network")?,
+            deny: compile_filter_regexes(&network_filter.deny, "network")?,
+        })
+    } else {
+        Ok(data_harvester::network::InterfaceFilter::default())
+    }
+}
+
+fn get_disk_filter(config: &Config) -> error::Result<data_harvester::disks::DiskFilter> {
+    if let Some(disk_filter) = &config.disk_filter {
+        Ok(data_harvester::disks::DiskFilter {
+            allow: compile_filter_regexes(&disk_filter.allow, "disk")?,
+            deny: compile_filter_regexes(&disk_filter.deny, "disk")?,
+        })
+    } else {
+        Ok(data_harvester::disks::DiskFilter::default())
+    }
+}
+
+fn get_sensor_filter(config: &Config) -> error::Result<data_harvester::temperature::SensorFilter> {
+    if let Some(temp_filter) = &config.temp_filter {
+        Ok(data_harvester::temperature::SensorFilter {
+            allow: compile_filter_regexes(&temp_filter.allow, "temperature")?,
+            deny: compile_filter_regexes(&temp_filter.deny, "temperature")?,
+            rename: temp_filter.rename.clone().unwrap_or_default(),
+        })
+    } else {
+        Ok(data_harvester::temperature::SensorFilter::default())
+    }
+}
+
+fn get_alert_rules(config: &Config) -> error::Result<Vec<AlertRule>> {
+    config
+        .alerts
+        .iter()
+        .flatten()
+        .map(|alert| {
+            AlertRule::parse(
+                &alert.rule,
+                alert.command.clone(),
+                alert.desktop_notification.unwrap_or(false),
+            )
+        })
+        .collect()
+}
+
+fn get_temp_thresholds(
+    config: &Config,
+) -> HashMap<String, data_harvester::temperature::TempThresholds> {
+    config
+        .temp_thresholds
+        .iter()
+        .flatten()
+        .map(|(sensor_name, thresholds)| {
+            (
+                sensor_name.clone(),
+                data_harvester::temperature::TempThresholds {
+                    warning: thresholds.warning,
+                    critical: thresholds.critical,
+                },
+            )
+        })
+        .collect()
+}
+
+/// Maps the key of every action to the action's default key.  Empty if nothing is remapped, as
+/// keys are then used as is.
+fn get_keybinds(config: &Config) -> error::Result<HashMap<char, char>> {
+    let keybinds = match &config.keybinds {
+        Some(keybinds) if !keybinds.is_empty() => keybinds,
+        _ => return Ok(HashMap::new()),
+    };
+
+    if let Some(action) = keybinds.keys().find(|action| {
+        !DEFAULT_KEYBINDS
+            .iter()
+            .any(|(name, _)| *name == action.as_str())
+    }) {
+        return Err(BottomError::ConfigError(format!(
+            "Invalid keybind action \"{}\".  Please see the documentation for the list of actions.",
+            action
+        )));
+    }
+
+    let mut key_actions: HashMap<char, &str> = HashMap::new();
+    let mut key_remap = HashMap::new();
+    for (action, default_key) in DEFAULT_KEYBINDS.iter() {
+        let key = match keybinds.get(*action) {
+            Some(key) => {
+                let mut key_chars = key.chars();
+                match (key_chars.next(), key_chars.next()) {
+                    (Some(key_char), None) => key_char,
+                    _ => {
+                        return Err(BottomError::ConfigError(format!(
+                            "Invalid key \"{}\" for {}.  Please have each key be one character.",
+                            key, action
+                        )));
+                    }
+                }
+            }
+            None => *default_key,
+        };
+
+        if let Some(other_action) = key_actions.insert(key, action) {
+            return Err(BottomError::ConfigError(format!(
+                "The key \"{}\" is bound to both {} and {}.  Please bind each action to a different key.",
+                key, other_action, action
+            )));
+        }
+        key_remap.insert(key, *default_key);
+    }
+
+    Ok(key_remap)
+}
+
+fn get_widget_use_dot(config: &Config) -> error::Result<HashMap<WidgetPosition, bool>> {
+    let mut widget_use_dot = HashMap::new();
+    for (widget_type, marker) in config.markers.iter().flatten() {
+        let widget = get_layout_widget(widget_type)?;
+        match widget {
+            WidgetPosition::Process
+            | WidgetPosition::Temp
+            | WidgetPosition::Disk
+            | WidgetPosition::Sockets => {
+                return Err(BottomError::ConfigError(format!(
+                    "Invalid marker widget type \"{}\".  Only graph widgets have markers.",
+                    widget_type
+                )));
+            }
+            _ => {}
+        }
+
+        let use_dot = match marker.to_lowercase().as_str() {
+            "braille" => false,
+            "dot" => true,
+            _ => {
+                return Err(BottomError::ConfigError(format!(
+                    "Invalid marker \"{}\" for {}.  Please have the value be one of <braille|dot>",
+                    marker, widget_type
+                )))
+            }
+        };
+        widget_use_dot.insert(widget, use_dot);
+    }
+
+    Ok(widget_use_dot)
+}
+
+fn get_hidden_graph_parts(
+    config: &Config,
+) -> error::Result<HashMap<WidgetPosition, HiddenGraphParts>> {
+    let mut hidden_graph_parts = HashMap::new();
+    for (widget_type, parts) in config.hidden_graph_parts.iter().flatten() {
+        let widget = get_layout_widget(widget_type)?;
+        match widget {
+            WidgetPosition::Process
+            | WidgetPosition::Temp
+            | WidgetPosition::Disk
+            | WidgetPosition::Sockets => {
+                return Err(BottomError::ConfigError(format!(
+                    "Invalid hidden graph parts widget type \"{}\".  Only graph widgets can hide parts.",
+                    widget_type
+                )));
+            }
+            _ => {}
+        }
+
+        let mut hidden_parts = HiddenGraphParts::default();
+        for part in parts {
+            match part.to_lowercase().as_str() {
+                "legend" => hidden_parts.legend = true,
+                "y_labels" => hidden_parts.y_labels = true,
+                "title" => hidden_parts.title = true,
+                _ => {
+                    return Err(BottomError::ConfigError(format!(
+                        "Invalid hidden graph part \"{}\" for {}.  Please have the value be one of <legend|y_labels|title>",
+                        part, widget_type
+                    )))
+                }
+            }
+        }
+        hidden_graph_parts.insert(widget, hidden_parts);
+    }
+
+    Ok(hidden_graph_parts)
+}
+
+fn get_use_ascii(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<bool> {
+    let ascii = if let Some(ascii) = matches.value_of("ASCII") {
+        ascii
+    } else if let Some(ascii) = config.flags.as_ref().and_then(|flags| flags.ascii.as_ref()) {
+        ascii.as_str()
+    } else {
+        "auto"
+    };
+
+    match ascii.to_lowercase().as_str() {
+        "auto" => Ok(is_limited_terminal()),
+        "always" => Ok(true),
+        "never" => Ok(false),
+        _ => Err(BottomError::ConfigError(format!(
+            "Invalid ASCII setting \"{}\".  Please have the value be one of <auto|always|never>",
+            ascii
+        ))),
+    }
+}
+
+/// Guesses whether the terminal can't show unicode or many colours, from its type and the
+/// locale.  This errs towards no, as the fallback looks worse wherever it isn't needed.
+fn is_limited_terminal() -> bool {
+    if cfg!(target_os = "windows") {
+        return false;
+    }
+
+    let term = std::env::var("TERM").unwrap_or_default();
+    if term.is_empty() || term == "dumb" || term == "linux" || term.starts_with("vt") {
+        return true;
+    }
+
+    // The first of these that's set decides the character encoding
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.is_empty());
+    match locale {
+        Some(locale) => {
+            let locale = locale.to_lowercase();
+            !(locale.contains("utf-8") || locale.contains("utf8"))
+        }
+        None => false,
+    }
+}
+
+fn get_use_dot(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("DOT_MARKER") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(dot_marker) = flags.dot_marker {
+            return dot_marker;
+        }
+    }
+    false
+}
+
+fn get_use_left_legend(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("LEFT_LEGEND") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(left_legend) = flags.left_legend {
+            return left_legend;
+        }
+    }
+
+    false
+}
+
+fn get_use_current_cpu_total(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("USE_CURR_USAGE") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(current_usage) = flags.current_usage {
+            return current_usage;
+        }
+    }
+
+    false
+}
+
+fn get_show_cpu_frequency(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("CPU_FREQUENCY") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(cpu_frequency) = flags.cpu_frequency {
+            return cpu_frequency;
+        }
+    }
+
+    false
+}
+
+fn get_show_cpu_temp(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("CPU_TEMP") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(cpu_temp) = flags.cpu_temp {
+            return cpu_temp;
+        }
+    }
+
+    false
+}
+
+fn get_show_cpu_min_max(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("CPU_MIN_MAX") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(cpu_min_max) = flags.cpu_min_max {
+            return cpu_min_max;
+        }
+    }
+
+    false
+}
+
+fn get_subtract_arc(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("SUBTRACT_ARC") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(subtract_arc) = flags.subtract_arc {
+            return subtract_arc;
+        }
+    }
+
+    false
+}
+
+fn get_mem_as_value(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("MEM_AS_VALUE") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(mem_as_value) = flags.mem_as_value {
+            return mem_as_value;
+        }
+    }
+
+    false
+}
+
+fn get_show_sockets(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("SOCKETS") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(sockets) = flags.sockets {
+            return sockets;
+        }
+    }
+
+    false
+}
+
+fn get_network_use_bits(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("NETWORK_USE_BITS") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(network_use_bits) = flags.network_use_bits {
+            return network_use_bits;
+        }
+    }
+
+    false
+}
+
+fn get_show_psi(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("PSI") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(psi) = flags.psi {
+            return psi;
+        }
+    }
+
+    false
+}
+
+fn get_show_disk_io_graph(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("DISK_IO_GRAPH") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_io_graph) = flags.disk_io_graph {
+            return disk_io_graph;
+        }
+    }
+
+    false
+}
+
+fn get_show_temp_graph(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("TEMP_GRAPH") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(temp_graph) = flags.temp_graph {
+            return temp_graph;
+        }
+    }
+
+    false
+}
+
+fn get_show_battery(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("BATTERY") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(battery) = flags.battery {
+            return battery;
+        }
+    }
+
+    false
+}
+
+fn get_show_gpu(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("GPU") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(gpu) = flags.gpu {
+            return gpu;
+        }
+    }
+
+    false
+}
+
+fn get_show_disk_temp(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("DISK_TEMP") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_temp) = flags.disk_temp {
+            return disk_temp;
+        }
+    }
+
+    false
+}
+
+fn get_show_disk_inodes(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("DISK_INODES") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(disk_inodes) = flags.disk_inodes {
+            return disk_inodes;
+        }
+    }
+
+    false
+}
+
+fn get_hide_network_disks(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("HIDE_NETWORK_DISKS") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(hide_network_disks) = flags.hide_network_disks {
+            return hide_network_disks;
+        }
+    }
+
+    false
+}
+
+fn get_network_disk_timeout(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<u64> {
+    let network_disk_timeout = if let Some(timeout) = matches.value_of("NETWORK_DISK_TIMEOUT") {
+        timeout.parse::<u64>()?
+    } else if let Some(flags) = &config.flags {
+        if let Some(timeout) = flags.network_disk_timeout {
+            timeout
+        } else {
+            DEFAULT_NETWORK_DISK_TIMEOUT_MILLISECONDS
+        }
+    } else {
+        DEFAULT_NETWORK_DISK_TIMEOUT_MILLISECONDS
+    };
+
+    if network_disk_timeout == 0 {
+        return Err(BottomError::InvalidArg(
+            "Please set your network disk timeout to be at least 1 millisecond.".to_string(),
+        ));
+    }
+
+    Ok(network_disk_timeout)
+}
+
+fn get_ping_hosts(matches: &clap::ArgMatches<'static>, config: &Config) -> Vec<String> {
+    if let Some(ping_hosts) = matches.values_of("PING") {
+        return ping_hosts.map(|host| host.to_string()).collect();
+    } else if let Some(flags) = &config.flags {
+        if let Some(ping_hosts) = &flags.ping_hosts {
+            return ping_hosts.clone();
+        }
+    }
 
-    let current_widget_selected = if use_basic_mode {
-        match default_widget {
-            WidgetPosition::Cpu => WidgetPosition::BasicCpu,
-            WidgetPosition::Network => WidgetPosition::BasicNet,
-            WidgetPosition::Mem => WidgetPosition::BasicMem,
-            _ => default_widget,
+    Vec::new()
+}
+
+fn get_remote_hosts(matches: &clap::ArgMatches<'static>, config: &Config) -> Vec<String> {
+    if let Some(remote_hosts) = matches.values_of("REMOTE") {
+        return remote_hosts.map(|host| host.to_string()).collect();
+    } else if let Some(flags) = &config.flags {
+        if let Some(remote_hosts) = &flags.remote_hosts {
+            return remote_hosts.clone();
         }
-    } else {
-        default_widget
-    };
+    }
 
-    let previous_basic_table_selected = if default_widget.is_widget_table() {
-        default_widget
-    } else {
-        WidgetPosition::Process
-    };
+    Vec::new()
+}
 
-    let app_config_fields = AppConfigFields {
-        update_rate_in_milliseconds: get_update_rate_in_milliseconds(matches, config)?,
-        temperature_type: get_temperature(matches, config)?,
-        show_average_cpu: get_avg_cpu(matches, config),
-        use_dot: get_use_dot(matches, config),
-        left_legend: get_use_left_legend(matches, config),
-        use_current_cpu_total: get_use_current_cpu_total(matches, config),
-        show_disabled_data: get_show_disabled_data(matches, config),
-        use_basic_mode,
-        default_time_value,
-        time_interval: get_time_interval(matches, config)?,
-        hide_time: get_hide_time(matches, config),
-        autohide_time,
-    };
+fn get_remote_command(config: &Config) -> String {
+    if let Some(flags) = &config.flags {
+        if let Some(remote_command) = &flags.remote_command {
+            return remote_command.clone();
+        }
+    }
 
-    let time_now = if autohide_time {
-        Some(Instant::now())
+    DEFAULT_REMOTE_COMMAND.to_string()
+}
+
+fn get_show_process_swap(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("PROCESS_SWAP") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_swap) = flags.process_swap {
+            return process_swap;
+        }
+    }
+
+    false
+}
+
+fn get_show_process_affinity(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("PROCESS_AFFINITY") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(process_affinity) = flags.process_affinity {
+            return process_affinity;
+        }
+    }
+
+    false
+}
+
+fn get_copy_pid_only(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("COPY_PID") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(copy_pid) = flags.copy_pid {
+            return copy_pid;
+        }
+    }
+
+    false
+}
+
+fn get_table_stripes(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("TABLE_STRIPES") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(table_stripes) = flags.table_stripes {
+            return table_stripes;
+        }
+    }
+
+    false
+}
+
+fn get_table_gap(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<u16> {
+    if let Some(table_gap) = matches.value_of("TABLE_GAP") {
+        Ok(table_gap.parse::<u16>()?)
+    } else if let Some(flags) = &config.flags {
+        if let Some(table_gap) = flags.table_gap {
+            Ok(table_gap)
+        } else {
+            Ok(DEFAULT_TABLE_GAP)
+        }
     } else {
-        None
-    };
+        Ok(DEFAULT_TABLE_GAP)
+    }
+}
 
-    Ok(App::builder()
-        .app_config_fields(app_config_fields)
-        .current_widget_selected(current_widget_selected)
-        .previous_basic_table_selected(previous_basic_table_selected)
-        .cpu_state(CpuState::init(default_time_value, time_now))
-        .mem_state(MemState::init(default_time_value, time_now))
-        .net_state(NetState::init(default_time_value, time_now))
-        .build())
+fn get_metrics_log_path(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> Option<std::path::PathBuf> {
+    if let Some(log_metrics) = matches.value_of("LOG_METRICS") {
+        return Some(std::path::PathBuf::from(log_metrics));
+    } else if let Some(flags) = &config.flags {
+        if let Some(log_metrics) = &flags.log_metrics {
+            return Some(std::path::PathBuf::from(log_metrics));
+        }
+    }
+
+    None
 }
 
-fn get_update_rate_in_milliseconds(
+fn get_metrics_log_max_size_bytes(
     matches: &clap::ArgMatches<'static>, config: &Config,
 ) -> error::Result<u64> {
-    let update_rate_in_milliseconds = if let Some(update_rate) = matches.value_of("RATE_MILLIS") {
-        update_rate.parse::<u128>()?
+    let max_size_mb = if let Some(max_size) = matches.value_of("LOG_METRICS_MAX_SIZE") {
+        max_size.parse::<u64>()?
     } else if let Some(flags) = &config.flags {
-        if let Some(rate) = flags.rate {
-            rate as u128
+        if let Some(max_size) = flags.log_metrics_max_size {
+            max_size
         } else {
-            DEFAULT_REFRESH_RATE_IN_MILLISECONDS as u128
+            DEFAULT_METRICS_LOG_MAX_SIZE_MB
         }
     } else {
-        DEFAULT_REFRESH_RATE_IN_MILLISECONDS as u128
+        DEFAULT_METRICS_LOG_MAX_SIZE_MB
     };
 
-    if update_rate_in_milliseconds < 250 {
-        return Err(BottomError::InvalidArg(
-            "Please set your update rate to be at least 250 milliseconds.".to_string(),
-        ));
-    } else if update_rate_in_milliseconds as u128 > std::u64::MAX as u128 {
+    if max_size_mb == 0 {
         return Err(BottomError::InvalidArg(
-            "Please set your update rate to be at most unsigned INT_MAX.".to_string(),
+            "Please set your metrics log max size to be at least 1 megabyte.".to_string(),
         ));
     }
 
-    Ok(update_rate_in_milliseconds as u64)
+    Ok(max_size_mb * 1_000_000)
 }
 
-fn get_temperature(
+fn get_prometheus_port(
     matches: &clap::ArgMatches<'static>, config: &Config,
-) -> error::Result<data_harvester::temperature::TemperatureType> {
-    if matches.is_present("FAHRENHEIT") {
-        return Ok(data_harvester::temperature::TemperatureType::Fahrenheit);
-    } else if matches.is_present("KELVIN") {
-        return Ok(data_harvester::temperature::TemperatureType::Kelvin);
-    } else if matches.is_present("CELSIUS") {
-        return Ok(data_harvester::temperature::TemperatureType::Celsius);
+) -> error::Result<Option<u16>> {
+    if let Some(port) = matches.value_of("PROMETHEUS_PORT") {
+        return Ok(Some(port.parse::<u16>()?));
     } else if let Some(flags) = &config.flags {
-        if let Some(temp_type) = &flags.temperature_type {
-            // Give lowest priority to config.
-            return match temp_type.as_str() {
-                "fahrenheit" | "f" => Ok(data_harvester::temperature::TemperatureType::Fahrenheit),
-                "kelvin" | "k" => Ok(data_harvester::temperature::TemperatureType::Kelvin),
-                "celsius" | "c" => Ok(data_harvester::temperature::TemperatureType::Celsius),
-                _ => Err(BottomError::ConfigError(
-                    "Invalid temperature type.  Please have the value be of the form \
-						 <kelvin|k|celsius|c|fahrenheit|f>"
-                        .to_string(),
-                )),
-            };
+        if let Some(port) = flags.prometheus_port {
+            return Ok(Some(port));
         }
     }
-    Ok(data_harvester::temperature::TemperatureType::Celsius)
+
+    Ok(None)
 }
 
-fn get_avg_cpu(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
-    if matches.is_present("AVG_CPU") {
-        return true;
+fn get_prometheus_address(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<std::net::IpAddr> {
+    let address = if let Some(address) = matches.value_of("PROMETHEUS_ADDRESS") {
+        address
+    } else if let Some(address) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.prometheus_address.as_ref())
+    {
+        address
+    } else {
+        DEFAULT_PROMETHEUS_ADDRESS
+    };
+
+    address.parse::<std::net::IpAddr>().map_err(|_| {
+        BottomError::InvalidArg(format!(
+            "\"{}\" isn't an IP address to serve Prometheus metrics on.",
+            address
+        ))
+    })
+}
+
+fn get_api_port(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<Option<u16>> {
+    if let Some(port) = matches.value_of("API_PORT") {
+        return Ok(Some(port.parse::<u16>()?));
     } else if let Some(flags) = &config.flags {
-        if let Some(avg_cpu) = flags.avg_cpu {
-            return avg_cpu;
+        if let Some(port) = flags.api_port {
+            return Ok(Some(port));
         }
     }
 
-    false
+    Ok(None)
 }
 
-fn get_use_dot(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
-    if matches.is_present("DOT_MARKER") {
+fn get_line_protocol_target(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<Option<LineProtocolTarget>> {
+    let token = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.line_protocol_token.clone());
+    if let Some(url) = matches.value_of("LINE_PROTOCOL") {
+        return Ok(Some(LineProtocolTarget::parse(url, token)?));
+    } else if let Some(flags) = &config.flags {
+        if let Some(url) = &flags.line_protocol {
+            return Ok(Some(LineProtocolTarget::parse(url, token)?));
+        }
+    }
+
+    Ok(None)
+}
+
+fn get_line_protocol_interval(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<std::time::Duration> {
+    let interval_seconds = if let Some(interval) = matches.value_of("LINE_PROTOCOL_INTERVAL") {
+        interval.parse::<u64>()?
+    } else if let Some(flags) = &config.flags {
+        if let Some(interval) = flags.line_protocol_interval {
+            interval
+        } else {
+            DEFAULT_LINE_PROTOCOL_INTERVAL_SECONDS
+        }
+    } else {
+        DEFAULT_LINE_PROTOCOL_INTERVAL_SECONDS
+    };
+
+    if interval_seconds == 0 {
+        return Err(BottomError::InvalidArg(
+            "Please set your line protocol interval to be at least 1 second.".to_string(),
+        ));
+    }
+
+    Ok(std::time::Duration::from_secs(interval_seconds))
+}
+
+fn get_vary_series_markers(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("SERIES_MARKERS") {
         return true;
     } else if let Some(flags) = &config.flags {
-        if let Some(dot_marker) = flags.dot_marker {
-            return dot_marker;
+        if let Some(series_markers) = flags.series_markers {
+            return series_markers;
         }
     }
+
     false
 }
 
-fn get_use_left_legend(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
-    if matches.is_present("LEFT_LEGEND") {
+fn get_show_header(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("HEADER") {
         return true;
     } else if let Some(flags) = &config.flags {
-        if let Some(left_legend) = flags.left_legend {
-            return left_legend;
+        if let Some(header) = flags.header {
+            return header;
         }
     }
 
     false
 }
 
-fn get_use_current_cpu_total(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
-    if matches.is_present("USE_CURR_USAGE") {
+fn parse_sort_key(sort_key: &str) -> Option<data_harvester::processes::ProcessSorting> {
+    use data_harvester::processes::ProcessSorting;
+
+    match sort_key.to_lowercase().as_str() {
+        "cpu" => Some(ProcessSorting::CPU),
+        "mem" | "memory" => Some(ProcessSorting::MEM),
+        "swap" => Some(ProcessSorting::SWAP),
+        "pid" => Some(ProcessSorting::PID),
+        "name" => Some(ProcessSorting::NAME),
+        _ => None,
+    }
+}
+
+fn get_default_sort(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<data_harvester::processes::ProcessSorting> {
+    let default_sort = if let Some(default_sort) = matches.value_of("DEFAULT_SORT") {
+        default_sort
+    } else if let Some(default_sort) = config
+        .flags
+        .as_ref()
+        .and_then(|flags| flags.default_sort.as_ref())
+    {
+        default_sort.as_str()
+    } else {
+        return Ok(data_harvester::processes::ProcessSorting::CPU);
+    };
+
+    parse_sort_key(default_sort).ok_or_else(|| {
+        BottomError::ConfigError(format!(
+            "Invalid default sort \"{}\".  Please have the value be one of \
+			 <cpu|mem|swap|pid|name>",
+            default_sort
+        ))
+    })
+}
+
+fn get_secondary_sort_keys(
+    config: &Config,
+) -> error::Result<Vec<data_harvester::processes::ProcessSorting>> {
+    use data_harvester::processes::ProcessSorting;
+
+    if let Some(flags) = &config.flags {
+        if let Some(secondary_sort) = &flags.secondary_sort {
+            return secondary_sort
+                .iter()
+                .map(|sort_key| {
+                    parse_sort_key(sort_key).ok_or_else(|| {
+                        BottomError::ConfigError(format!(
+                            "Invalid secondary sort key \"{}\".  Please have each value be one of \
+							 <cpu|mem|swap|pid|name>",
+                            sort_key
+                        ))
+                    })
+                })
+                .collect();
+        }
+    }
+
+    Ok(vec![ProcessSorting::NAME, ProcessSorting::PID])
+}
+
+fn get_group_ignore_case(config: &Config) -> bool {
+    if let Some(flags) = &config.flags {
+        if let Some(group_ignore_case) = flags.group_ignore_case {
+            return group_ignore_case;
+        }
+    }
+
+    // Windows doesn't care about case for executables, so default to matching that.
+    cfg!(target_os = "windows")
+}
+
+fn get_group_by_command(config: &Config) -> error::Result<bool> {
+    if let Some(flags) = &config.flags {
+        if let Some(group_by) = &flags.group_by {
+            return match group_by.as_str() {
+                "basename" | "name" => Ok(false),
+                "command" | "cmd" => Ok(true),
+                _ => Err(BottomError::ConfigError(
+                    "Invalid grouping type.  Please have the value be of the form \
+						 <basename|name|command|cmd>"
+                        .to_string(),
+                )),
+            };
+        }
+    }
+
+    Ok(false)
+}
+
+fn get_persist_search_history(config: &Config) -> bool {
+    if let Some(flags) = &config.flags {
+        if let Some(persist_search_history) = flags.persist_search_history {
+            return persist_search_history;
+        }
+    }
+
+    false
+}
+
+fn get_persist_state(config: &Config) -> bool {
+    if let Some(flags) = &config.flags {
+        if let Some(persist_state) = flags.persist_state {
+            return persist_state;
+        }
+    }
+
+    false
+}
+
+/// Gets the widgets taken out of the layout with the hide flags.
+fn get_hidden_widgets(matches: &clap::ArgMatches<'static>, config: &Config) -> Vec<WidgetPosition> {
+    let flags = config.flags.as_ref();
+    let hide_options = [
+        (
+            "HIDE_CPU",
+            flags.and_then(|flags| flags.hide_cpu),
+            WidgetPosition::Cpu,
+        ),
+        (
+            "HIDE_MEM",
+            flags.and_then(|flags| flags.hide_mem),
+            WidgetPosition::Mem,
+        ),
+        (
+            "HIDE_NET",
+            flags.and_then(|flags| flags.hide_net),
+            WidgetPosition::Network,
+        ),
+        (
+            "HIDE_TEMPS",
+            flags.and_then(|flags| flags.hide_temps),
+            WidgetPosition::Temp,
+        ),
+        (
+            "HIDE_DISKS",
+            flags.and_then(|flags| flags.hide_disks),
+            WidgetPosition::Disk,
+        ),
+        (
+            "HIDE_PROCS",
+            flags.and_then(|flags| flags.hide_procs),
+            WidgetPosition::Process,
+        ),
+    ];
+
+    hide_options
+        .iter()
+        .filter(|(arg_name, is_hidden, _)| matches.is_present(arg_name) || *is_hidden == Some(true))
+        .map(|(_, _, widget)| *widget)
+        .collect()
+}
+
+fn get_hide_kernel_threads(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("HIDE_KERNEL_THREADS") {
         return true;
     } else if let Some(flags) = &config.flags {
-        if let Some(current_usage) = flags.current_usage {
-            return current_usage;
+        if let Some(hide_kernel_threads) = flags.hide_kernel_threads {
+            return hide_kernel_threads;
         }
     }
 
@@ -240,8 +1586,35 @@ fn get_use_basic_mode(matches: &clap::ArgMatches<'static>, config: &Config) -> b
     false
 }
 
+fn get_retention_time(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<u64> {
+    let retention_time = if let Some(retention) = matches.value_of("RETENTION") {
+        retention.parse::<u128>()?
+    } else if let Some(flags) = &config.flags {
+        if let Some(retention) = flags.retention {
+            retention as u128
+        } else {
+            STALE_MAX_MILLISECONDS as u128
+        }
+    } else {
+        STALE_MAX_MILLISECONDS as u128
+    };
+
+    if retention_time < 30000 {
+        return Err(BottomError::InvalidArg(
+            "Please set your retention to be at least 30000 milliseconds.".to_string(),
+        ));
+    } else if retention_time > MAX_RETENTION_MILLISECONDS as u128 {
+        return Err(BottomError::InvalidArg(format!(
+            "Please set your retention to be at most {} milliseconds.",
+            MAX_RETENTION_MILLISECONDS
+        )));
+    }
+
+    Ok(retention_time as u64)
+}
+
 fn get_default_time_value(
-    matches: &clap::ArgMatches<'static>, config: &Config,
+    matches: &clap::ArgMatches<'static>, config: &Config, retention_time: u64,
 ) -> error::Result<u64> {
     let default_time = if let Some(default_time_value) = matches.value_of("DEFAULT_TIME_VALUE") {
         default_time_value.parse::<u128>()?
@@ -259,16 +1632,38 @@ fn get_default_time_value(
         return Err(BottomError::InvalidArg(
             "Please set your default value to be at least 30000 milliseconds.".to_string(),
         ));
-    } else if default_time as u128 > STALE_MAX_MILLISECONDS as u128 {
+    } else if default_time as u128 > retention_time as u128 {
         return Err(BottomError::InvalidArg(format!(
             "Please set your default value to be at most {} milliseconds.",
-            STALE_MAX_MILLISECONDS
+            retention_time
         )));
     }
 
     Ok(default_time as u64)
 }
 
+/// A graph's own default time value, falling back to the global one if unset.
+fn get_widget_default_time_value(
+    widget_default_time_value: Option<u64>, default_time_value: u64, retention_time: u64,
+) -> error::Result<u64> {
+    match widget_default_time_value {
+        Some(widget_default_time_value) if widget_default_time_value < 30000 => {
+            Err(BottomError::ConfigError(
+                "Please set your graph default values to be at least 30000 milliseconds."
+                    .to_string(),
+            ))
+        }
+        Some(widget_default_time_value) if widget_default_time_value > retention_time => {
+            Err(BottomError::ConfigError(format!(
+                "Please set your graph default values to be at most {} milliseconds.",
+                retention_time
+            )))
+        }
+        Some(widget_default_time_value) => Ok(widget_default_time_value),
+        None => Ok(default_time_value),
+    }
+}
+
 fn get_time_interval(matches: &clap::ArgMatches<'static>, config: &Config) -> error::Result<u64> {
     let time_interval = if let Some(time_interval) = matches.value_of("TIME_DELTA") {
         time_interval.parse::<u128>()?
@@ -375,32 +1770,75 @@ fn get_autohide_time(matches: &clap::ArgMatches<'static>, config: &Config) -> bo
     false
 }
 
-fn get_default_widget(matches: &clap::ArgMatches<'static>, config: &Config) -> WidgetPosition {
+pub fn get_colour_scheme(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<ColourScheme> {
+    let theme = if let Some(theme) = matches.value_of("THEME") {
+        theme
+    } else if let Some(theme) = config.flags.as_ref().and_then(|flags| flags.theme.as_ref()) {
+        theme.as_str()
+    } else {
+        return Ok(ColourScheme::Default);
+    };
+
+    match theme.to_lowercase().as_str() {
+        "default" => Ok(ColourScheme::Default),
+        "gruvbox" => Ok(ColourScheme::Gruvbox),
+        "nord" => Ok(ColourScheme::Nord),
+        "light" => Ok(ColourScheme::Light),
+        "colorblind" => Ok(ColourScheme::ColourBlind),
+        "colorblind_light" => Ok(ColourScheme::ColourBlindLight),
+        _ => Err(BottomError::ConfigError(format!(
+            "Invalid theme \"{}\".  Please have the value be one of <default|gruvbox|nord|light|colorblind|colorblind_light>",
+            theme
+        ))),
+    }
+}
+
+fn get_expanded(matches: &clap::ArgMatches<'static>, config: &Config) -> bool {
+    if matches.is_present("EXPANDED") {
+        return true;
+    } else if let Some(flags) = &config.flags {
+        if let Some(expanded) = flags.expanded {
+            return expanded;
+        }
+    }
+
+    false
+}
+
+fn get_default_widget(
+    matches: &clap::ArgMatches<'static>, config: &Config,
+) -> error::Result<WidgetPosition> {
     if matches.is_present("CPU_WIDGET") {
-        return WidgetPosition::Cpu;
+        return Ok(WidgetPosition::Cpu);
     } else if matches.is_present("MEM_WIDGET") {
-        return WidgetPosition::Mem;
+        return Ok(WidgetPosition::Mem);
     } else if matches.is_present("DISK_WIDGET") {
-        return WidgetPosition::Disk;
+        return Ok(WidgetPosition::Disk);
     } else if matches.is_present("TEMP_WIDGET") {
-        return WidgetPosition::Temp;
+        return Ok(WidgetPosition::Temp);
     } else if matches.is_present("NET_WIDGET") {
-        return WidgetPosition::Network;
+        return Ok(WidgetPosition::Network);
     } else if matches.is_present("PROC_WIDGET") {
-        return WidgetPosition::Process;
+        return Ok(WidgetPosition::Process);
     } else if let Some(flags) = &config.flags {
         if let Some(default_widget) = &flags.default_widget {
             return match default_widget.as_str() {
-                "cpu_default" => WidgetPosition::Cpu,
-                "memory_default" => WidgetPosition::Mem,
-                "processes_default" => WidgetPosition::Process,
-                "network_default" => WidgetPosition::Network,
-                "temperature_default" => WidgetPosition::Temp,
-                "disk_default" => WidgetPosition::Disk,
-                _ => WidgetPosition::Process,
+                "cpu_default" => Ok(WidgetPosition::Cpu),
+                "memory_default" => Ok(WidgetPosition::Mem),
+                "process_default" | "processes_default" => Ok(WidgetPosition::Process),
+                "network_default" => Ok(WidgetPosition::Network),
+                "temperature_default" => Ok(WidgetPosition::Temp),
+                "disk_default" => Ok(WidgetPosition::Disk),
+                _ => Err(BottomError::ConfigError(format!(
+                    "Invalid default widget \"{}\".  Please have the value be one of \
+					 <cpu_default|memory_default|disk_default|temperature_default|network_default|process_default>",
+                    default_widget
+                ))),
             };
         }
     }
 
-    WidgetPosition::Process
+    Ok(WidgetPosition::Process)
 }
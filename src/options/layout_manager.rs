@@ -1,3 +1,142 @@
 use serde::Deserialize;
-use toml::Value;
 
+use crate::{
+    app::{
+        layout::{BottomLayout, LayoutNode},
+        WidgetPosition,
+    },
+    utils::error::{self, BottomError},
+};
+
+/// A row or column of a custom layout, holding either a widget or more columns or rows.
+#[derive(Deserialize)]
+pub struct ConfigLayoutNode {
+    pub ratio: Option<u32>,
+    #[serde(rename = "type")]
+    pub widget_type: Option<String>,
+    pub child: Option<Vec<ConfigLayoutNode>>,
+}
+
+/// A layout under a `[[layout]]` table, which can be switched to while running.
+#[derive(Deserialize)]
+pub struct ConfigLayout {
+    pub name: String,
+    pub row: Option<Vec<ConfigLayoutNode>>,
+}
+
+/// Builds the layout from the `[[row]]` tables of the config file, if there are any.
+pub fn get_custom_layout(
+    rows: &Option<Vec<ConfigLayoutNode>>,
+) -> error::Result<Option<BottomLayout>> {
+    if let Some(rows) = rows {
+        if rows.is_empty() {
+            return Ok(None);
+        }
+
+        return Ok(Some(get_layout(rows)?));
+    }
+
+    Ok(None)
+}
+
+/// Builds the named layouts from the `[[layout]]` tables of the config file, in order.
+pub fn get_named_layouts(
+    layouts: &Option<Vec<ConfigLayout>>,
+) -> error::Result<Vec<(String, BottomLayout)>> {
+    let mut named_layouts: Vec<(String, BottomLayout)> = Vec::new();
+    if let Some(layouts) = layouts {
+        for layout in layouts {
+            if named_layouts.iter().any(|(name, _)| *name == layout.name) {
+                return Err(BottomError::ConfigError(format!(
+                    "Invalid layout name \"{}\".  Please give each layout a different name.",
+                    layout.name
+                )));
+            }
+
+            match &layout.row {
+                Some(rows) if !rows.is_empty() => {
+                    named_layouts.push((layout.name.clone(), get_layout(rows)?));
+                }
+                _ => {
+                    return Err(BottomError::ConfigError(format!(
+                        "Invalid layout \"{}\".  Please give each layout at least one row.",
+                        layout.name
+                    )));
+                }
+            }
+        }
+    }
+
+    Ok(named_layouts)
+}
+
+fn get_layout(rows: &[ConfigLayoutNode]) -> error::Result<BottomLayout> {
+    let mut used_widgets = Vec::new();
+    Ok(BottomLayout {
+        rows: rows
+            .iter()
+            .map(|row| get_layout_node(row, &mut used_widgets))
+            .collect::<error::Result<_>>()?,
+    })
+}
+
+fn get_layout_node(
+    node: &ConfigLayoutNode, used_widgets: &mut Vec<WidgetPosition>,
+) -> error::Result<LayoutNode> {
+    let ratio = node.ratio.unwrap_or(1);
+    if ratio == 0 {
+        return Err(BottomError::ConfigError(
+            "Invalid layout ratio.  Please have each ratio be at least 1.".to_string(),
+        ));
+    }
+
+    match (&node.widget_type, &node.child) {
+        (Some(widget_type), None) => {
+            let widget = get_layout_widget(widget_type)?;
+            if used_widgets.contains(&widget) {
+                return Err(BottomError::ConfigError(format!(
+                    "Invalid layout.  The \"{}\" widget appears more than once.",
+                    widget_type
+                )));
+            }
+            used_widgets.push(widget);
+
+            Ok(LayoutNode::widget(ratio, widget))
+        }
+        (None, Some(children)) if !children.is_empty() => Ok(LayoutNode::split(
+            ratio,
+            children
+                .iter()
+                .map(|child| get_layout_node(child, used_widgets))
+                .collect::<error::Result<_>>()?,
+        )),
+        _ => Err(BottomError::ConfigError(
+            "Invalid layout.  Please have each row or column hold either a widget type or \
+			 at least one child, but not both."
+                .to_string(),
+        )),
+    }
+}
+
+pub fn get_layout_widget(widget_type: &str) -> error::Result<WidgetPosition> {
+    match widget_type.to_lowercase().as_str() {
+        "cpu" => Ok(WidgetPosition::Cpu),
+        "mem" | "memory" => Ok(WidgetPosition::Mem),
+        "net" | "network" => Ok(WidgetPosition::Network),
+        "proc" | "process" | "processes" => Ok(WidgetPosition::Process),
+        "temp" | "temperature" => Ok(WidgetPosition::Temp),
+        "disk" => Ok(WidgetPosition::Disk),
+        "temp_graph" => Ok(WidgetPosition::TempGraph),
+        "disk_io" => Ok(WidgetPosition::DiskIo),
+        "battery" => Ok(WidgetPosition::Battery),
+        "gpu" => Ok(WidgetPosition::Gpu),
+        "psi" => Ok(WidgetPosition::Psi),
+        "ping" => Ok(WidgetPosition::Ping),
+        "sockets" => Ok(WidgetPosition::Sockets),
+        _ => Err(BottomError::ConfigError(format!(
+            "Invalid layout widget type \"{}\".  Please have each type be one of \
+			 <cpu|mem|net|proc|temp|disk|temp_graph|disk_io|battery|gpu|psi|ping|sockets>",
+            widget_type
+        ))),
+    }
+}
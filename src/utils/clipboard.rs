@@ -0,0 +1,135 @@
+//! Copying text to the system clipboard.  This hands the text to the platform's clipboard tool,
+//! and falls back to the OSC 52 escape sequence when there isn't one that works, or when running
+//! over SSH, where the remote machine's clipboard is of no use and the terminal has to do it.
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use crate::utils::error::{self, BottomError};
+
+const BASE64_CHARS: &[u8] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// How the text was handed over.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ClipboardMethod {
+    /// A clipboard tool took it, so it's known to be copied.
+    Command,
+    /// It was sent to the terminal, which may or may not support OSC 52, so there's no telling
+    /// whether it actually got copied.
+    Osc52,
+}
+
+/// Copies the text to the clipboard.
+pub fn copy_to_clipboard(text: &str) -> error::Result<ClipboardMethod> {
+    if !is_ssh_session() {
+        for (command, args) in get_clipboard_commands() {
+            if run_clipboard_command(command, args, text).is_ok() {
+                return Ok(ClipboardMethod::Command);
+            }
+        }
+    }
+
+    copy_with_osc52(text)?;
+    Ok(ClipboardMethod::Osc52)
+}
+
+fn is_ssh_session() -> bool {
+    std::env::var_os("SSH_TTY").is_some() || std::env::var_os("SSH_CONNECTION").is_some()
+}
+
+fn get_clipboard_commands() -> Vec<(&'static str, &'static [&'static str])> {
+    if cfg!(target_os = "macos") {
+        vec![("pbcopy", &[])]
+    } else if cfg!(target_os = "windows") {
+        vec![("clip", &[])]
+    } else {
+        let mut commands: Vec<(&'static str, &'static [&'static str])> = Vec::new();
+        if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+            commands.push(("wl-copy", &[]));
+        }
+        if std::env::var_os("DISPLAY").is_some() {
+            commands.push(("xclip", &["-selection", "clipboard"]));
+            commands.push(("xsel", &["--clipboard", "--input"]));
+        }
+        commands
+    }
+}
+
+fn run_clipboard_command(command: &str, args: &[&str], text: &str) -> error::Result<()> {
+    // Output is thrown away so it can't end up drawn over the interface
+    let mut child = Command::new(command)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+
+    if let Some(stdin) = &mut child.stdin {
+        stdin.write_all(text.as_bytes())?;
+    }
+    // Closes stdin so the command knows the text has ended
+    child.stdin = None;
+
+    if child.wait()?.success() {
+        Ok(())
+    } else {
+        Err(BottomError::GenericError(format!(
+            "{} failed to copy to the clipboard",
+            command
+        )))
+    }
+}
+
+/// Asks the terminal to set its clipboard, which works through SSH on most modern terminals.
+fn copy_with_osc52(text: &str) -> error::Result<()> {
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", encode_base64(text.as_bytes()))?;
+    stdout.flush()?;
+
+    Ok(())
+}
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut encoded = String::with_capacity((bytes.len() + 2) / 3 * 4);
+    for chunk in bytes.chunks(3) {
+        let group = (u32::from(chunk[0]) << 16)
+            | (u32::from(*chunk.get(1).unwrap_or(&0)) << 8)
+            | u32::from(*chunk.get(2).unwrap_or(&0));
+        for index in 0..4 {
+            if index <= chunk.len() {
+                encoded.push(BASE64_CHARS[((group >> (18 - 6 * index)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
+            }
+        }
+    }
+
+    encoded
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_base64() {
+        // The test vectors from RFC 4648, section 10
+        assert_eq!(encode_base64(b""), "");
+        assert_eq!(encode_base64(b"f"), "Zg==");
+        assert_eq!(encode_base64(b"fo"), "Zm8=");
+        assert_eq!(encode_base64(b"foo"), "Zm9v");
+        assert_eq!(encode_base64(b"foob"), "Zm9vYg==");
+        assert_eq!(encode_base64(b"fooba"), "Zm9vYmE=");
+        assert_eq!(encode_base64(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn test_encode_base64_all_bits() {
+        assert_eq!(encode_base64(&[0x00, 0x00, 0x00]), "AAAA");
+        assert_eq!(encode_base64(&[0xff, 0xff, 0xff]), "////");
+        assert_eq!(encode_base64(&[0xfb, 0xef, 0xbe]), "++++");
+        assert_eq!(encode_base64("PID\tNAME".as_bytes()), "UElECU5BTUU=");
+    }
+}
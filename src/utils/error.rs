@@ -51,6 +51,12 @@ impl std::fmt::Display for BottomError {
     }
 }
 
+impl From<serde_json::Error> for BottomError {
+    fn from(err: serde_json::Error) -> Self {
+        BottomError::ConversionError(err.to_string())
+    }
+}
+
 impl From<std::io::Error> for BottomError {
     fn from(err: std::io::Error) -> Self {
         BottomError::InvalidIO(err.to_string())
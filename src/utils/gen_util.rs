@@ -60,6 +60,18 @@ pub fn get_simple_byte_values(bytes: u64, spacing: bool) -> (f64, String) {
     }
 }
 
+/// Returns a tuple containing the value and the unit.  In units of 1000.
+/// This only supports up to a terabit.
+pub fn get_simple_bit_values(bits: u64) -> (f64, String) {
+    match bits {
+        b if b < 1000 => (bits as f64, "b".to_string()),
+        b if b < 1_000_000 => (bits as f64 / 1000.0, "Kb".to_string()),
+        b if b < 1_000_000_000 => (bits as f64 / 1_000_000.0, "Mb".to_string()),
+        b if b < 1_000_000_000_000 => (bits as f64 / 1_000_000_000.0, "Gb".to_string()),
+        _ => (bits as f64 / 1_000_000_000_000.0, "Tb".to_string()),
+    }
+}
+
 /// Gotta get partial ordering?  No problem, here's something to deal with it~
 pub fn get_ordering<T: std::cmp::PartialOrd>(
     a_val: T, b_val: T, reverse_order: bool,
@@ -85,3 +97,28 @@ pub fn get_ordering<T: std::cmp::PartialOrd>(
         None => Ordering::Equal,
     }
 }
+
+/// Returns this machine's hostname, if it can be found.
+#[cfg(unix)]
+pub fn get_hostname() -> Option<String> {
+    let mut buffer = [0_u8; 256];
+    if unsafe { libc::gethostname(buffer.as_mut_ptr() as *mut libc::c_char, buffer.len()) } != 0 {
+        return None;
+    }
+
+    let length = buffer
+        .iter()
+        .position(|byte| *byte == 0)
+        .unwrap_or(buffer.len());
+    Some(String::from_utf8_lossy(&buffer[..length]).into_owned())
+}
+
+#[cfg(windows)]
+pub fn get_hostname() -> Option<String> {
+    std::env::var("COMPUTERNAME").ok()
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn get_hostname() -> Option<String> {
+    None
+}
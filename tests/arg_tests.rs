@@ -60,6 +60,34 @@ fn test_small_default_time() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_large_retention() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--retention")
+        .arg("3600001")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Please set your retention to be at most",
+        ));
+    Ok(())
+}
+
+#[test]
+fn test_default_time_above_retention() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--retention")
+        .arg("60000")
+        .arg("-t")
+        .arg("90000")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Please set your default value to be at most 60000",
+        ));
+    Ok(())
+}
+
 #[test]
 fn test_large_delta_time() -> Result<(), Box<dyn std::error::Error>> {
     Command::new(get_os_binary_loc())
@@ -153,3 +181,292 @@ fn test_conflicting_default_widget() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn test_invalid_network_scale() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--network_scale")
+        .arg("exponential")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid network scale."));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_network_y_max() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--network_y_max")
+        .arg("1 gigabit")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid network y-axis maximum."));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_table_gap() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--table_gap")
+        .arg("wide")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid digit"));
+
+    Ok(())
+}
+
+#[test]
+fn test_zero_metrics_log_max_size() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--log_metrics")
+        .arg(std::env::temp_dir().join("bottom_test_metrics.csv"))
+        .arg("--log_metrics_max_size")
+        .arg("0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Please set your metrics log max size to be at least 1 megabyte.",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_headless_without_outputs() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--headless")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Headless mode needs somewhere to send metrics",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_prometheus_port() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--prometheus_port")
+        .arg("70000")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("number too large"));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_prometheus_address() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--prometheus_port")
+        .arg("9100")
+        .arg("--prometheus_address")
+        .arg("localhost")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "isn't an IP address to serve Prometheus metrics on",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_line_protocol_url() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--line_protocol")
+        .arg("localhost:8086")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "expected it to start with http://, udp://, or tcp://",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_api_port() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--api_port")
+        .arg("http")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("invalid digit"));
+
+    Ok(())
+}
+
+#[test]
+fn test_zero_network_disk_timeout() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--network_disk_timeout")
+        .arg("0")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "Please set your network disk timeout to be at least 1 millisecond.",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_default_sort() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--default_sort")
+        .arg("disk")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid default sort"));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_theme() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--theme")
+        .arg("solarized")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid theme"));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_ascii() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--ascii")
+        .arg("sometimes")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid ASCII setting"));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_alert_rule() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = std::env::temp_dir().join("bottom_test_invalid_alert_rule.toml");
+    std::fs::write(&config_path, "[[alerts]]\nrule = \"cpu_total > lots\"\n")?;
+
+    Command::new(get_os_binary_loc())
+        .arg("-C")
+        .arg(&config_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("the threshold isn't a number"));
+
+    Ok(())
+}
+
+#[test]
+fn test_conflicting_keybinds() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = std::env::temp_dir().join("bottom_test_conflicting_keybinds.toml");
+    std::fs::write(&config_path, "[keybinds]\nsort_cpu = \"m\"\n")?;
+
+    Command::new(get_os_binary_loc())
+        .arg("-C")
+        .arg(&config_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains(
+            "The key \"m\" is bound to both sort_cpu and sort_mem.",
+        ));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_hidden_graph_part() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = std::env::temp_dir().join("bottom_test_invalid_hidden_graph_part.toml");
+    std::fs::write(&config_path, "[hidden_graph_parts]\ncpu = [\"x_labels\"]\n")?;
+
+    Command::new(get_os_binary_loc())
+        .arg("-C")
+        .arg(&config_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid hidden graph part"));
+
+    Ok(())
+}
+
+#[test]
+fn test_dump() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--dump")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("\"processes\""));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_hidden_widgets() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .args(&[
+            "--hide_cpu",
+            "--hide_mem",
+            "--hide_net",
+            "--hide_temps",
+            "--hide_disks",
+            "--hide_procs",
+        ])
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid hidden widgets"));
+
+    Ok(())
+}
+
+#[test]
+fn test_print_config() -> Result<(), Box<dyn std::error::Error>> {
+    Command::new(get_os_binary_loc())
+        .arg("--print_config")
+        .assert()
+        .success()
+        .stdout(predicate::str::contains("[flags]"));
+
+    Ok(())
+}
+
+#[test]
+fn test_generate_config_existing_file() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = std::env::temp_dir().join("bottom_test_generate_config_existing.toml");
+    std::fs::write(&config_path, "")?;
+
+    Command::new(get_os_binary_loc())
+        .arg("--generate_config")
+        .arg("-C")
+        .arg(&config_path)
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("already exists"));
+
+    Ok(())
+}
+
+#[test]
+fn test_invalid_profile() -> Result<(), Box<dyn std::error::Error>> {
+    let config_path = std::env::temp_dir().join("bottom_test_invalid_profile.toml");
+    std::fs::write(&config_path, "[profiles.laptop.flags]\nbasic = true\n")?;
+
+    Command::new(get_os_binary_loc())
+        .arg("-C")
+        .arg(&config_path)
+        .arg("--profile")
+        .arg("server")
+        .assert()
+        .failure()
+        .stderr(predicate::str::contains("Invalid profile"));
+
+    Ok(())
+}